@@ -0,0 +1,366 @@
+//! Cache responses that rarely change.
+
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+use url::Url;
+
+/// The default maximum number of in-memory entries.
+pub const DEFAULT_MAX_CACHE_ENTRIES: usize = 1024;
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Incremented on every use, so entries can be ordered by their last use.
+    tick: u64,
+
+    /// Response bodies, keyed by url.
+    map: HashMap<String, Entry>,
+
+    /// Urls by the tick of their last use, least recent first.
+    order: BTreeMap<u64, String>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    created: SystemTime,
+    tick: u64,
+    body: Vec<u8>,
+}
+
+/// Makes temporary file names unique within this process.
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// An in-memory, and optionally on-disk, cache of API responses.
+///
+/// A [Client](crate::Client) with a cache uses it for the landing page
+/// (`/`), `/conformance`, and the pages of `/collections`, which rarely
+/// change. Entries
+/// older than the cache's time-to-live are fetched again.
+///
+/// Expired in-memory entries are dropped whenever a new entry is stored, and
+/// once there are more than [Cache::max_entries], the least recently used are
+/// evicted.
+/// Clones of a cache share their in-memory entries.
+///
+/// # Examples
+///
+/// ```
+/// use stac_api::{Cache, Client};
+/// use std::time::Duration;
+///
+/// let cache = Cache::new(Duration::from_secs(60 * 60)).directory("/tmp/stac-cache");
+/// let client = Client::new("https://planetarycomputer.microsoft.com/api/stac/v1")
+///     .unwrap()
+///     .cache(cache);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cache {
+    ttl: Duration,
+    directory: Option<PathBuf>,
+    max_entries: usize,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl Cache {
+    /// Creates a new in-memory cache whose entries live for `ttl`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Cache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Cache::new(Duration::from_secs(60));
+    /// ```
+    pub fn new(ttl: Duration) -> Cache {
+        Cache {
+            ttl,
+            directory: None,
+            max_entries: DEFAULT_MAX_CACHE_ENTRIES,
+            entries: Arc::new(Mutex::new(Entries::default())),
+        }
+    }
+
+    /// Also stores entries as files in this directory.
+    ///
+    /// Files are named by the SHA-256 hash of the url. The directory is
+    /// created if it doesn't exist. Entries on disk outlive
+    /// the process, so repeated invocations of a program can share them.
+    /// Each file is written to a temporary file and then renamed, so readers
+    /// never see a partially-written entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Cache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Cache::new(Duration::from_secs(60)).directory("/tmp/stac-cache");
+    /// ```
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Cache {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Sets the maximum number of in-memory entries.
+    ///
+    /// Defaults to [DEFAULT_MAX_CACHE_ENTRIES]. Files on disk aren't counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Cache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Cache::new(Duration::from_secs(60)).max_entries(16);
+    /// ```
+    pub fn max_entries(mut self, max_entries: usize) -> Cache {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Removes all in-memory entries.
+    ///
+    /// Files on disk are left in place, but are ignored once they expire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Cache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Cache::new(Duration::from_secs(60));
+    /// cache.clear();
+    /// ```
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    pub(crate) async fn get(&self, url: &Url) -> Option<Vec<u8>> {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(entry) = entries.map.get(url.as_str()) {
+                if self.is_fresh(entry.created) {
+                    let body = entry.body.clone();
+                    entries.touch(url.as_str());
+                    return Some(body);
+                }
+            }
+        }
+        let path = self.path(url)?;
+        let created = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+        if !self.is_fresh(created) {
+            return None;
+        }
+        let body = tokio::fs::read(&path).await.ok()?;
+        self.entries.lock().unwrap().insert(
+            url.to_string(),
+            created,
+            body.clone(),
+            self.max_entries,
+        );
+        Some(body)
+    }
+
+    pub(crate) async fn put(&self, url: &Url, body: Vec<u8>) {
+        if let Some(path) = self.path(url) {
+            if let Some(directory) = path.parent() {
+                if let Err(err) = tokio::fs::create_dir_all(directory).await {
+                    tracing::warn!("could not create cache directory: {err}");
+                }
+            }
+            let temp_path = path.with_extension(format!(
+                "{}.{}.tmp",
+                std::process::id(),
+                TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            let result = match tokio::fs::write(&temp_path, &body).await {
+                Ok(()) => tokio::fs::rename(&temp_path, &path).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                tracing::warn!("could not write cache file {}: {err}", path.display());
+                let _ = tokio::fs::remove_file(&temp_path).await;
+            }
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.retain(|_, entry| self.is_fresh(entry.created));
+        let Entries { map, order, .. } = &mut *entries;
+        order.retain(|_, url| map.contains_key(url));
+        entries.insert(url.to_string(), SystemTime::now(), body, self.max_entries);
+    }
+
+    /// Removes an entry, e.g. because its body couldn't be decoded.
+    pub(crate) async fn remove(&self, url: &Url) {
+        self.entries.lock().unwrap().remove(url.as_str());
+        if let Some(path) = self.path(url) {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    }
+
+    fn is_fresh(&self, created: SystemTime) -> bool {
+        created
+            .elapsed()
+            .map(|elapsed| elapsed < self.ttl)
+            .unwrap_or_default()
+    }
+
+    fn path(&self, url: &Url) -> Option<PathBuf> {
+        let file_name = Sha256::digest(url.as_str()).iter().fold(
+            String::with_capacity(64),
+            |mut file_name, byte| {
+                let _ = write!(file_name, "{byte:02x}");
+                file_name
+            },
+        );
+        self.directory
+            .as_ref()
+            .map(|directory| directory.join(file_name).with_extension("json"))
+    }
+}
+
+impl Entries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch(&mut self, url: &str) {
+        let tick = self.next_tick();
+        if let Some(entry) = self.map.get_mut(url) {
+            let _ = self.order.remove(&entry.tick);
+            entry.tick = tick;
+            let _ = self.order.insert(tick, url.to_string());
+        }
+    }
+
+    fn insert(&mut self, url: String, created: SystemTime, body: Vec<u8>, max_entries: usize) {
+        self.remove(&url);
+        let tick = self.next_tick();
+        let _ = self.order.insert(tick, url.clone());
+        let _ = self.map.insert(
+            url,
+            Entry {
+                created,
+                tick,
+                body,
+            },
+        );
+        while self.map.len() > max_entries {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            let _ = self.map.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, url: &str) {
+        if let Some(entry) = self.map.remove(url) {
+            let _ = self.order.remove(&entry.tick);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+    use std::time::Duration;
+    use url::Url;
+
+    #[tokio::test]
+    async fn memory() {
+        let url = Url::parse("http://stac.test/conformance").unwrap();
+        let cache = Cache::new(Duration::from_secs(60));
+        assert!(cache.get(&url).await.is_none());
+        cache.put(&url, b"{}".to_vec()).await;
+        assert_eq!(cache.get(&url).await.unwrap(), b"{}");
+        cache.clear();
+        assert!(cache.get(&url).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn expired() {
+        let url = Url::parse("http://stac.test/conformance").unwrap();
+        let cache = Cache::new(Duration::ZERO);
+        cache.put(&url, b"{}".to_vec()).await;
+        assert!(cache.get(&url).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn directory() {
+        let url = Url::parse("http://stac.test/collections").unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = Cache::new(Duration::from_secs(60)).directory(directory.path());
+        cache.put(&url, b"{}".to_vec()).await;
+        let cache = Cache::new(Duration::from_secs(60)).directory(directory.path());
+        assert_eq!(cache.get(&url).await.unwrap(), b"{}");
+    }
+
+    #[tokio::test]
+    async fn evict() {
+        let cache = Cache::new(Duration::from_secs(60)).max_entries(2);
+        for i in 0..3 {
+            let url = Url::parse(&format!("http://stac.test/{i}")).unwrap();
+            cache.put(&url, b"{}".to_vec()).await;
+        }
+        assert_eq!(cache.entries.lock().unwrap().map.len(), 2);
+        let cache = Cache::new(Duration::ZERO);
+        for i in 0..3 {
+            let url = Url::parse(&format!("http://stac.test/{i}")).unwrap();
+            cache.put(&url, b"{}".to_vec()).await;
+        }
+        assert_eq!(cache.entries.lock().unwrap().map.len(), 1);
+        assert_eq!(cache.entries.lock().unwrap().order.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn evict_least_recently_used() {
+        let urls: Vec<_> = (0..3)
+            .map(|i| Url::parse(&format!("http://stac.test/{i}")).unwrap())
+            .collect();
+        let cache = Cache::new(Duration::from_secs(60)).max_entries(2);
+        cache.put(&urls[0], b"0".to_vec()).await;
+        cache.put(&urls[1], b"1".to_vec()).await;
+        assert_eq!(cache.get(&urls[0]).await.unwrap(), b"0");
+        cache.put(&urls[2], b"2".to_vec()).await;
+        assert_eq!(cache.get(&urls[0]).await.unwrap(), b"0");
+        assert!(cache.get(&urls[1]).await.is_none());
+        assert_eq!(cache.get(&urls[2]).await.unwrap(), b"2");
+    }
+
+    #[tokio::test]
+    async fn directory_no_temp_files() {
+        let url = Url::parse("http://stac.test/collections").unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = Cache::new(Duration::from_secs(60)).directory(directory.path());
+        cache.put(&url, b"{}".to_vec()).await;
+        cache.put(&url, b"[]".to_vec()).await;
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+        cache.remove(&url).await;
+        assert!(cache.get(&url).await.is_none());
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn directory_similar_urls() {
+        let a = Url::parse("http://stac.test/a-b/collections").unwrap();
+        let b = Url::parse("http://stac.test/a_b/collections").unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = Cache::new(Duration::from_secs(60)).directory(directory.path());
+        cache.put(&a, b"a".to_vec()).await;
+        cache.put(&b, b"b".to_vec()).await;
+        let cache = Cache::new(Duration::from_secs(60)).directory(directory.path());
+        assert_eq!(cache.get(&a).await.unwrap(), b"a");
+        assert_eq!(cache.get(&b).await.unwrap(), b"b");
+    }
+}
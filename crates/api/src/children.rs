@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use stac::{Container, Href, Link};
+use stac_derive::{Links, SelfHref};
+
+/// Object containing an array of child catalogs and collections, and an array of links.
+///
+/// Part of the [children extension](https://github.com/stac-api-extensions/children).
+#[derive(Debug, Serialize, Deserialize, SelfHref, Links)]
+pub struct Children {
+    /// The child [stac::Catalog] and [stac::Collection] objects.
+    pub children: Vec<Container>,
+
+    /// The [stac::Link] relations.
+    pub links: Vec<Link>,
+
+    /// Additional fields.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
+
+    #[serde(skip)]
+    self_href: Option<Href>,
+}
+
+impl From<Vec<Container>> for Children {
+    fn from(children: Vec<Container>) -> Children {
+        Children {
+            children,
+            links: Vec::new(),
+            additional_fields: Map::new(),
+            self_href: None,
+        }
+    }
+}
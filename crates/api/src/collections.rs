@@ -12,21 +12,201 @@ pub struct Collections {
     /// The [stac::Link] relations.
     pub links: Vec<Link>,
 
+    /// The number of collections that meet the selection parameters, possibly estimated.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "numberMatched")]
+    pub number_matched: Option<u64>,
+
+    /// The number of collections in the collections array.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "numberReturned")]
+    pub number_returned: Option<u64>,
+
     /// Additional fields.
     #[serde(flatten)]
     pub additional_fields: Map<String, Value>,
 
+    /// Optional pagination information for the next page.
+    ///
+    /// This is not part of the specification, but can be used to hold arbitrary
+    /// pagination information (tokens) to later be turned into links.
+    #[serde(skip)]
+    pub next: Option<Map<String, Value>>,
+
+    /// Optional pagination information for the previous page.
+    ///
+    /// This is not part of the specification, but can be used to hold arbitrary
+    /// pagination information (tokens) to later be turned into links.
+    #[serde(skip)]
+    pub prev: Option<Map<String, Value>>,
+
     #[serde(skip)]
     self_href: Option<Href>,
 }
 
+/// GET parameters for the collections endpoint.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetCollections {
+    /// The maximum number of results to return (page size).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// An opaque pagination token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Comma-separated [free-text](https://github.com/stac-api-extensions/freetext-search) search terms.
+    ///
+    /// A collection matches if any of the terms are found in its id, title,
+    /// description, keywords, or string summaries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+
+    /// Comma-separated keywords.
+    ///
+    /// A collection matches if it has any of these keywords.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+}
+
+impl GetCollections {
+    /// Returns true if this collection matches the `q` and `keywords` parameters.
+    ///
+    /// Matching is case-insensitive. The `limit` and `token` parameters are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    /// use stac_api::GetCollections;
+    ///
+    /// let mut collection = Collection::new("an-id", "Sentinel-2 imagery");
+    /// collection.keywords = Some(vec!["optical".to_string()]);
+    /// let get_collections = GetCollections {
+    ///     q: Some("sentinel,landsat".to_string()),
+    ///     keywords: Some("Optical".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(get_collections.matches(&collection));
+    /// ```
+    pub fn matches(&self, collection: &Collection) -> bool {
+        self.q_matches(collection) && self.keywords_match(collection)
+    }
+
+    fn q_matches(&self, collection: &Collection) -> bool {
+        let Some(q) = self.q.as_deref() else {
+            return true;
+        };
+        let mut text = vec![collection.id.as_str(), collection.description.as_str()];
+        text.extend(collection.title.as_deref());
+        text.extend(collection.keywords.iter().flatten().map(String::as_str));
+        for value in collection.summaries.iter().flat_map(Map::values) {
+            match value {
+                Value::String(s) => text.push(s),
+                Value::Array(values) => text.extend(values.iter().filter_map(Value::as_str)),
+                _ => {}
+            }
+        }
+        let text: Vec<_> = text.into_iter().map(str::to_lowercase).collect();
+        split(q).any(|term| text.iter().any(|text| text.contains(&term)))
+    }
+
+    fn keywords_match(&self, collection: &Collection) -> bool {
+        let Some(keywords) = self.keywords.as_deref() else {
+            return true;
+        };
+        split(keywords).any(|keyword| {
+            collection
+                .keywords
+                .iter()
+                .flatten()
+                .any(|k| k.to_lowercase() == keyword)
+        })
+    }
+}
+
+fn split(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split(',')
+        .map(|term| term.trim().to_lowercase())
+        .filter(|term| !term.is_empty())
+}
+
 impl From<Vec<Collection>> for Collections {
     fn from(collections: Vec<Collection>) -> Collections {
         Collections {
             collections,
             links: Vec::new(),
+            number_matched: None,
+            number_returned: None,
             additional_fields: Map::new(),
+            next: None,
+            prev: None,
             self_href: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Collections, GetCollections};
+    use serde_json::json;
+    use stac::Collection;
+
+    #[test]
+    fn numbers() {
+        let mut collections = Collections::from(vec![Collection::new("an-id", "a description")]);
+        collections.number_matched = Some(42);
+        collections.number_returned = Some(1);
+        let value = serde_json::to_value(collections).unwrap();
+        assert_eq!(value["numberMatched"], 42);
+        assert_eq!(value["numberReturned"], 1);
+
+        let collections: Collections = serde_json::from_value(value).unwrap();
+        assert_eq!(collections.number_matched, Some(42));
+        assert!(!collections.additional_fields.contains_key("numberMatched"));
+    }
+
+    #[test]
+    fn get_collections() {
+        let get_collections: GetCollections =
+            serde_urlencoded::from_str("limit=10&token=20").unwrap();
+        assert_eq!(get_collections.limit, Some(10));
+        assert_eq!(get_collections.token.as_deref(), Some("20"));
+        assert_eq!(
+            serde_json::to_value(GetCollections::default()).unwrap(),
+            json!({})
+        );
+        let get_collections: GetCollections =
+            serde_urlencoded::from_str("q=sentinel,landsat&keywords=optical").unwrap();
+        assert_eq!(get_collections.q.as_deref(), Some("sentinel,landsat"));
+        assert_eq!(get_collections.keywords.as_deref(), Some("optical"));
+    }
+
+    #[test]
+    fn matches() {
+        let mut collection = Collection::new("an-id", "A description");
+        collection.title = Some("Sentinel-2".to_string());
+        collection.keywords = Some(vec!["Optical".to_string()]);
+        let mut summaries = serde_json::Map::new();
+        let _ = summaries.insert(
+            "platform".to_string(),
+            json!(["sentinel-2a", "sentinel-2b"]),
+        );
+        collection.summaries = Some(summaries);
+
+        let matches = |q: Option<&str>, keywords: Option<&str>| {
+            GetCollections {
+                q: q.map(String::from),
+                keywords: keywords.map(String::from),
+                ..Default::default()
+            }
+            .matches(&collection)
+        };
+        assert!(matches(None, None));
+        assert!(matches(Some("SENTINEL"), None));
+        assert!(matches(Some("landsat, 2b"), None));
+        assert!(matches(Some("description"), None));
+        assert!(!matches(Some("landsat"), None));
+        assert!(matches(None, Some("optical,sar")));
+        assert!(!matches(None, Some("sar")));
+        assert!(!matches(Some("sentinel"), Some("sar")));
+    }
+}
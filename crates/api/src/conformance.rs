@@ -19,6 +19,27 @@ pub const GEOJSON_URI: &str = "http://www.opengis.net/spec/ogcapi-features-1/1.0
 /// The item search conformance uri.
 pub const ITEM_SEARCH_URI: &str = "https://api.stacspec.org/v1.0.0/item-search";
 
+/// The fields conformance uri.
+pub const FIELDS_URI: &str = "https://api.stacspec.org/v1.0.0/item-search#fields";
+
+/// The sort conformance uri.
+pub const SORT_URI: &str = "https://api.stacspec.org/v1.0.0/item-search#sort";
+
+/// The children conformance uri.
+pub const CHILDREN_URI: &str = "https://api.stacspec.org/v1.0.0-rc.2/children";
+
+/// The transaction conformance uris.
+pub const TRANSACTION_URIS: [&str; 2] = [
+    "https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction",
+    "http://www.opengis.net/spec/ogcapi-features-4/1.0/conf/simpletx",
+];
+
+/// The collection search conformance uris.
+pub const COLLECTION_SEARCH_URIS: [&str; 2] = [
+    "https://api.stacspec.org/v1.0.0-rc.1/collection-search",
+    "http://www.opengis.net/spec/ogcapi-common-2/1.0/conf/simple-query",
+];
+
 /// The filter conformance uris.
 pub const FILTER_URIS: [&str; 5] = [
     "http://www.opengis.net/spec/ogcapi-features-3/1.0/conf/filter",
@@ -100,6 +121,79 @@ impl Conformance {
             .extend(FILTER_URIS.iter().map(|s| s.to_string()));
         self
     }
+
+    /// Adds [fields](https://github.com/stac-api-extensions/fields) conformance
+    /// class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Conformance;
+    /// let conformance = Conformance::new().fields();
+    /// ```
+    pub fn fields(mut self) -> Conformance {
+        self.conforms_to.push(FIELDS_URI.to_string());
+        self
+    }
+
+    /// Adds [sort](https://github.com/stac-api-extensions/sort) conformance
+    /// class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Conformance;
+    /// let conformance = Conformance::new().sort();
+    /// ```
+    pub fn sort(mut self) -> Conformance {
+        self.conforms_to.push(SORT_URI.to_string());
+        self
+    }
+
+    /// Adds [children](https://github.com/stac-api-extensions/children)
+    /// conformance class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Conformance;
+    /// let conformance = Conformance::new().children();
+    /// ```
+    pub fn children(mut self) -> Conformance {
+        self.conforms_to.push(CHILDREN_URI.to_string());
+        self
+    }
+
+    /// Adds [transaction](https://github.com/stac-api-extensions/transaction)
+    /// conformance classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Conformance;
+    /// let conformance = Conformance::new().transaction();
+    /// ```
+    pub fn transaction(mut self) -> Conformance {
+        self.conforms_to
+            .extend(TRANSACTION_URIS.iter().map(|s| s.to_string()));
+        self
+    }
+
+    /// Adds [collection
+    /// search](https://github.com/stac-api-extensions/collection-search)
+    /// conformance classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Conformance;
+    /// let conformance = Conformance::new().collection_search();
+    /// ```
+    pub fn collection_search(mut self) -> Conformance {
+        self.conforms_to
+            .extend(COLLECTION_SEARCH_URIS.iter().map(|s| s.to_string()));
+        self
+    }
 }
 
 impl Default for Conformance {
@@ -37,6 +37,10 @@ pub enum Error {
     #[error("feature not enabled: {0}")]
     FeatureNotEnabled(&'static str),
 
+    /// Invalid well-known text (WKT).
+    #[error("invalid wkt: {0}")]
+    InvalidWkt(String),
+
     /// Invalid bounding box.
     #[error("invalid bbox ({0:?}): {1}")]
     InvalidBbox(Vec<f64>, &'static str),
@@ -79,6 +83,11 @@ pub enum Error {
     #[cfg(feature = "client")]
     Reqwest(#[from] reqwest::Error),
 
+    /// A filter's coordinates couldn't be reprojected from its filter-crs.
+    #[error("reprojection error: {0}")]
+    #[cfg(feature = "proj")]
+    Reprojection(String),
+
     /// A search has both bbox and intersects.
     #[error("search has bbox and intersects")]
     SearchHasBboxAndIntersects(Box<Search>),
@@ -110,4 +119,8 @@ pub enum Error {
     /// This functionality is not yet implemented.
     #[error("this functionality is not yet implemented: {0}")]
     Unimplemented(&'static str),
+
+    /// The filter-crs isn't supported.
+    #[error("unsupported filter-crs: {0}")]
+    UnsupportedFilterCrs(String),
 }
@@ -1,4 +1,6 @@
+use crate::Item;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::{
     convert::Infallible,
     fmt::{Display, Formatter},
@@ -24,6 +26,115 @@ pub struct Fields {
     pub exclude: Vec<String>,
 }
 
+/// The fields returned when fields are requested but none are included.
+pub const DEFAULT_FIELDS: [&str; 10] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "id",
+    "geometry",
+    "bbox",
+    "links",
+    "assets",
+    "collection",
+    "properties.datetime",
+];
+
+/// Fields that are never excluded.
+const MANDATORY_FIELDS: [&str; 3] = ["type", "stac_version", "id"];
+
+impl Fields {
+    /// Applies these fields to an item.
+    ///
+    /// Follows the [fields
+    /// extension](https://github.com/stac-api-extensions/fields#includeexclude-semantics)
+    /// semantics:
+    ///
+    /// - If there's nothing to include or exclude, returns the [DEFAULT_FIELDS]
+    /// - If there's only excludes, returns everything except the excludes
+    /// - Otherwise, returns the [DEFAULT_FIELDS] plus the includes, minus any
+    ///   excludes that aren't also included
+    ///
+    /// Fields are dotted paths, e.g. `properties.eo:cloud_cover`. The `type`,
+    /// `stac_version`, and `id` fields are never excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::{Fields, Item};
+    /// use serde_json::json;
+    ///
+    /// let item: Item = serde_json::from_value(json!({
+    ///     "type": "Feature",
+    ///     "id": "an-id",
+    ///     "geometry": null,
+    ///     "properties": {"datetime": "2024-01-01T00:00:00Z", "eo:cloud_cover": 42},
+    /// })).unwrap();
+    /// let fields: Fields = "-geometry,properties.eo:cloud_cover".parse().unwrap();
+    /// let item = fields.apply(item);
+    /// assert!(!item.contains_key("geometry"));
+    /// assert_eq!(item["properties"]["eo:cloud_cover"], 42);
+    /// ```
+    pub fn apply(&self, item: Item) -> Item {
+        let mut item = if self.include.is_empty() && !self.exclude.is_empty() {
+            item
+        } else {
+            let mut output = Map::new();
+            for path in DEFAULT_FIELDS
+                .iter()
+                .copied()
+                .chain(self.include.iter().map(String::as_str))
+            {
+                copy_path(&item, &mut output, path);
+            }
+            output
+        };
+        for path in &self.exclude {
+            if !self.include.contains(path) && !MANDATORY_FIELDS.contains(&path.as_str()) {
+                remove_path(&mut item, path);
+            }
+        }
+        item
+    }
+}
+
+fn copy_path(source: &Map<String, Value>, destination: &mut Map<String, Value>, path: &str) {
+    let (key, rest) = match path.split_once('.') {
+        Some((key, rest)) => (key, Some(rest)),
+        None => (path, None),
+    };
+    let Some(value) = source.get(key) else {
+        return;
+    };
+    match (rest, value) {
+        (None, value) => {
+            let _ = destination.insert(key.to_string(), value.clone());
+        }
+        (Some(rest), Value::Object(source)) => {
+            let destination = destination
+                .entry(key)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(destination) = destination {
+                copy_path(source, destination, rest);
+            }
+        }
+        (Some(_), _) => {}
+    }
+}
+
+fn remove_path(map: &mut Map<String, Value>, path: &str) {
+    match path.split_once('.') {
+        Some((key, rest)) => {
+            if let Some(Value::Object(map)) = map.get_mut(key) {
+                remove_path(map, rest);
+            }
+        }
+        None => {
+            let _ = map.remove(path);
+        }
+    }
+}
+
 impl FromStr for Fields {
     type Err = Infallible;
 
@@ -43,6 +154,13 @@ impl FromStr for Fields {
     }
 }
 
+impl From<&str> for Fields {
+    fn from(s: &str) -> Fields {
+        let Ok(fields) = s.parse();
+        fields
+    }
+}
+
 impl Display for Fields {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut fields = Vec::new();
@@ -59,6 +177,69 @@ impl Display for Fields {
 #[cfg(test)]
 mod tests {
     use super::Fields;
+    use crate::Item;
+    use serde_json::json;
+
+    fn item() -> Item {
+        serde_json::from_value(json!({
+            "type": "Feature",
+            "stac_version": "1.1.0",
+            "id": "an-id",
+            "geometry": null,
+            "bbox": [1, 2, 3, 4],
+            "properties": {
+                "datetime": "2024-01-01T00:00:00Z",
+                "eo:cloud_cover": 42,
+                "platform": "landsat-8",
+            },
+            "links": [],
+            "assets": {},
+            "collection": "a-collection",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_empty() {
+        let item = Fields::default().apply(item());
+        assert_eq!(
+            item["properties"],
+            json!({"datetime": "2024-01-01T00:00:00Z"})
+        );
+        assert!(item.contains_key("geometry"));
+    }
+
+    #[test]
+    fn apply_include() {
+        let item = Fields::from("properties.platform").apply(item());
+        assert_eq!(
+            item["properties"],
+            json!({"datetime": "2024-01-01T00:00:00Z", "platform": "landsat-8"})
+        );
+    }
+
+    #[test]
+    fn apply_exclude() {
+        let item = Fields::from("-properties.platform,-id,-bbox").apply(item());
+        assert_eq!(item["properties"]["eo:cloud_cover"], 42);
+        assert!(!item["properties"]
+            .as_object()
+            .unwrap()
+            .contains_key("platform"));
+        assert!(!item.contains_key("bbox"));
+        assert_eq!(item["id"], "an-id");
+    }
+
+    #[test]
+    fn apply_include_and_exclude() {
+        let item = Fields::from("properties,-properties.platform,-geometry").apply(item());
+        assert_eq!(item["properties"]["eo:cloud_cover"], 42);
+        assert!(!item["properties"]
+            .as_object()
+            .unwrap()
+            .contains_key("platform"));
+        assert!(!item.contains_key("geometry"));
+    }
 
     #[test]
     fn empty() {
@@ -1,8 +1,20 @@
-use crate::Result;
+use crate::{Error, Result};
 use cql2::Expr;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::{convert::Infallible, str::FromStr};
+use std::{convert::Infallible, f64::consts::PI, str::FromStr};
+
+/// The default filter-crs, WGS 84 longitude and latitude.
+pub const CRS84: &str = "http://www.opengis.net/def/crs/OGC/1.3/CRS84";
+
+/// WGS 84 latitude and longitude, with the axes in that order.
+pub const EPSG_4326: &str = "http://www.opengis.net/def/crs/EPSG/0/4326";
+
+/// Web Mercator.
+pub const EPSG_3857: &str = "http://www.opengis.net/def/crs/EPSG/0/3857";
+
+/// The Web Mercator sphere's radius, in meters.
+const EARTH_RADIUS: f64 = 6_378_137.0;
 
 /// The language of the filter expression.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -43,6 +55,154 @@ impl Filter {
     }
 }
 
+/// A transform of a coordinate to [CRS84].
+type Transform = Box<dyn Fn(f64, f64) -> Result<(f64, f64)>>;
+
+impl Filter {
+    /// Converts this filter's geometries and bboxes from `crs` to [CRS84].
+    ///
+    /// [CRS84] (the default filter-crs), [EPSG_4326], and [EPSG_3857] are
+    /// always supported, either as their URIs or as `CRS84`, `EPSG:4326`, and
+    /// `EPSG:3857`. [EPSG_4326] coordinates are latitude first, so their axes
+    /// are swapped. With the `proj` feature, any other EPSG code (e.g.
+    /// `EPSG:32613` or `http://www.opengis.net/def/crs/EPSG/0/32613`) is
+    /// reprojected with [proj4rs](https://docs.rs/proj4rs), with easting (or
+    /// longitude) first. Other CRSs return [Error::UnsupportedFilterCrs].
+    ///
+    /// The filter is converted to cql2-json unless `crs` is already [CRS84].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Filter;
+    /// use serde_json::json;
+    ///
+    /// let filter = Filter::Cql2Text("S_INTERSECTS(geometry, POINT(41 -105))".to_string());
+    /// let Filter::Cql2Json(json) = filter.into_crs84("EPSG:4326").unwrap() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(json["args"][1]["coordinates"], json!([-105.0, 41.0]));
+    /// Filter::default().into_crs84("not-a-crs").unwrap_err();
+    /// ```
+    pub fn into_crs84(self, crs: &str) -> Result<Filter> {
+        let transform: Transform = match crs {
+            CRS84 | "CRS84" | "OGC:CRS84" => return Ok(self),
+            EPSG_4326 | "EPSG:4326" => Box::new(|lat, lon| Ok((lon, lat))),
+            EPSG_3857 | "EPSG:3857" => Box::new(|x, y| {
+                Ok((
+                    (x / EARTH_RADIUS).to_degrees(),
+                    (2.0 * (y / EARTH_RADIUS).exp().atan() - PI / 2.0).to_degrees(),
+                ))
+            }),
+            _ => reprojection(crs)?,
+        };
+        match self.into_cql2_json()? {
+            Filter::Cql2Json(mut json) => {
+                for value in json.values_mut() {
+                    transform_value(value, &transform)?;
+                }
+                Ok(Filter::Cql2Json(json))
+            }
+            Filter::Cql2Text(_) => unreachable!("the filter was converted to cql2-json"),
+        }
+    }
+}
+
+/// Returns the EPSG code of a CRS, either as `EPSG:<code>` or as its URI.
+#[cfg_attr(not(feature = "proj"), allow(dead_code))]
+fn epsg_code(crs: &str) -> Option<u16> {
+    crs.strip_prefix("EPSG:")
+        .or_else(|| crs.strip_prefix("http://www.opengis.net/def/crs/EPSG/0/"))
+        .and_then(|code| code.parse().ok())
+}
+
+/// Returns a transform from an EPSG CRS to [CRS84].
+#[cfg(feature = "proj")]
+fn reprojection(crs: &str) -> Result<Transform> {
+    use proj4rs::{proj::Proj, transform::transform};
+
+    let unsupported = || Error::UnsupportedFilterCrs(crs.to_string());
+    let from = epsg_code(crs)
+        .and_then(|code| Proj::from_epsg_code(code).ok())
+        .ok_or_else(unsupported)?;
+    let to = Proj::from_epsg_code(4326).map_err(|_| unsupported())?;
+    Ok(Box::new(move |x, y| {
+        // proj4rs uses radians for geographic coordinates
+        let mut point = if from.is_latlong() {
+            (x.to_radians(), y.to_radians(), 0.0)
+        } else {
+            (x, y, 0.0)
+        };
+        transform(&from, &to, &mut point).map_err(|err| Error::Reprojection(err.to_string()))?;
+        Ok((point.0.to_degrees(), point.1.to_degrees()))
+    }))
+}
+
+#[cfg(not(feature = "proj"))]
+fn reprojection(crs: &str) -> Result<Transform> {
+    Err(Error::UnsupportedFilterCrs(crs.to_string()))
+}
+
+/// Transforms any geometry or bbox literals in a cql2-json value.
+fn transform_value(value: &mut Value, transform: &Transform) -> Result<()> {
+    match value {
+        Value::Object(object) => {
+            if object.contains_key("type") {
+                if let Some(coordinates) = object.get_mut("coordinates") {
+                    return transform_coordinates(coordinates, transform);
+                }
+            }
+            if let Some(Value::Array(bbox)) = object.get_mut("bbox") {
+                return transform_bbox(bbox, transform);
+            }
+            for value in object.values_mut() {
+                transform_value(value, transform)?;
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                transform_value(value, transform)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn transform_coordinates(coordinates: &mut Value, transform: &Transform) -> Result<()> {
+    if let Value::Array(array) = coordinates {
+        if let (Some(x), Some(y)) = (
+            array.first().and_then(Value::as_f64),
+            array.get(1).and_then(Value::as_f64),
+        ) {
+            let (x, y) = transform(x, y)?;
+            array[0] = x.into();
+            array[1] = y.into();
+        } else {
+            for coordinates in array {
+                transform_coordinates(coordinates, transform)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transform_bbox(bbox: &mut [Value], transform: &Transform) -> Result<()> {
+    let (min, max) = match bbox.len() {
+        4 => ((0, 1), (2, 3)),
+        6 => ((0, 1), (3, 4)),
+        _ => return Ok(()),
+    };
+    for (x, y) in [min, max] {
+        if let (Some(a), Some(b)) = (bbox[x].as_f64(), bbox[y].as_f64()) {
+            let (a, b) = transform(a, b)?;
+            bbox[x] = a.into();
+            bbox[y] = b.into();
+        }
+    }
+    Ok(())
+}
+
 impl Default for Filter {
     fn default() -> Self {
         Filter::Cql2Json(Default::default())
@@ -56,6 +216,24 @@ impl FromStr for Filter {
     }
 }
 
+impl From<&str> for Filter {
+    fn from(s: &str) -> Filter {
+        Filter::Cql2Text(s.to_string())
+    }
+}
+
+impl From<String> for Filter {
+    fn from(s: String) -> Filter {
+        Filter::Cql2Text(s)
+    }
+}
+
+impl From<Map<String, Value>> for Filter {
+    fn from(map: Map<String, Value>) -> Filter {
+        Filter::Cql2Json(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Filter;
@@ -85,6 +263,61 @@ mod tests {
         assert!(value.get("filter").is_some());
     }
 
+    #[test]
+    fn into_crs84() {
+        let filter = Filter::Cql2Json(
+            json!({
+                "op": "s_intersects",
+                "args": [
+                    {"property": "geometry"},
+                    {"bbox": [0.0, 0.0, 20037508.342789244, 20037508.342789244]}
+                ]
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        );
+        let Filter::Cql2Json(json) = filter.into_crs84("EPSG:3857").unwrap() else {
+            panic!("should be cql2-json");
+        };
+        let bbox = json["args"][1]["bbox"].as_array().unwrap();
+        assert!((bbox[2].as_f64().unwrap() - 180.0).abs() < 1e-9);
+        assert!((bbox[3].as_f64().unwrap() - 85.0511287798066).abs() < 1e-9);
+        assert_eq!(json["args"][0], json!({"property": "geometry"}));
+
+        let filter = Filter::Cql2Text("eo:cloud_cover < 10".to_string());
+        assert_eq!(filter.clone().into_crs84(super::CRS84).unwrap(), filter);
+        let _ = filter.into_crs84("not-a-crs").unwrap_err();
+    }
+
+    #[test]
+    fn epsg_code() {
+        assert_eq!(super::epsg_code("EPSG:32613"), Some(32613));
+        assert_eq!(
+            super::epsg_code("http://www.opengis.net/def/crs/EPSG/0/32613"),
+            Some(32613)
+        );
+        assert!(super::epsg_code("CRS84").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn into_crs84_utm() {
+        let filter = Filter::Cql2Text("S_INTERSECTS(geometry, POINT(500000 4500000))".to_string());
+        let Filter::Cql2Json(json) = filter.into_crs84("EPSG:32613").unwrap() else {
+            panic!("should be cql2-json");
+        };
+        let coordinates = json["args"][1]["coordinates"].as_array().unwrap();
+        assert!((coordinates[0].as_f64().unwrap() - -105.0).abs() < 1e-6);
+        assert!((coordinates[1].as_f64().unwrap() - 40.65).abs() < 1e-2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "proj"))]
+    fn into_crs84_without_proj() {
+        let _ = Filter::default().into_crs84("EPSG:32613").unwrap_err();
+    }
+
     #[test]
     fn text() {
         let filter = Filter::Cql2Text(
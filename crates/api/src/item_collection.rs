@@ -33,7 +33,7 @@ where
 /// extension](https://github.com/stac-api-extensions/fields) is used, it might
 /// not be. Defined by the [itemcollection
 /// fragment](https://github.com/radiantearth/stac-api-spec/blob/main/fragments/itemcollection/README.md).
-#[derive(Debug, Serialize, Deserialize, Default, Links, SelfHref)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, Links, SelfHref)]
 pub struct ItemCollection {
     #[serde(
         default = "item_collection_type",
@@ -101,7 +101,7 @@ pub struct ItemCollection {
 /// The search-related metadata for the [ItemCollection].
 ///
 /// Part of the [context extension](https://github.com/stac-api-extensions/context).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Context {
     /// The count of results returned by this response. Equal to the cardinality
     /// of features array.
@@ -146,6 +146,61 @@ impl ItemCollection {
             self_href: None,
         })
     }
+
+    /// Returns the number of items that matched the search, possibly estimated.
+    ///
+    /// Uses `numberMatched` if set, falling back to the `matched` field of the
+    /// [context extension](https://github.com/stac-api-extensions/context).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::ItemCollection;
+    ///
+    /// let mut item_collection = ItemCollection::new(Vec::new()).unwrap();
+    /// assert_eq!(item_collection.matched(), None);
+    /// item_collection.number_matched = Some(42);
+    /// assert_eq!(item_collection.matched(), Some(42));
+    /// ```
+    pub fn matched(&self) -> Option<u64> {
+        self.number_matched
+            .or_else(|| self.context.as_ref().and_then(|context| context.matched))
+    }
+
+    /// Returns the number of items returned in this page.
+    ///
+    /// Uses `numberReturned` if set, falling back to the `returned` field of
+    /// the [context extension](https://github.com/stac-api-extensions/context).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::ItemCollection;
+    ///
+    /// let item_collection = ItemCollection::new(Vec::new()).unwrap();
+    /// assert_eq!(item_collection.returned(), Some(0));
+    /// ```
+    pub fn returned(&self) -> Option<u64> {
+        self.number_returned
+            .or_else(|| self.context.as_ref().map(|context| context.returned))
+    }
+
+    /// Returns the maximum number of items this page was limited to, if known.
+    ///
+    /// Only the [context extension](https://github.com/stac-api-extensions/context)
+    /// reports the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::ItemCollection;
+    ///
+    /// let item_collection = ItemCollection::new(Vec::new()).unwrap();
+    /// assert_eq!(item_collection.limit(), None);
+    /// ```
+    pub fn limit(&self) -> Option<u64> {
+        self.context.as_ref().and_then(|context| context.limit)
+    }
 }
 
 impl From<Vec<Item>> for ItemCollection {
@@ -156,3 +211,38 @@ impl From<Vec<Item>> for ItemCollection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ItemCollection;
+    use serde_json::json;
+
+    #[test]
+    fn numbers_from_context() {
+        let item_collection: ItemCollection = serde_json::from_value(json!({
+            "type": "FeatureCollection",
+            "features": [],
+            "links": [],
+            "context": {"returned": 0, "limit": 10, "matched": 42}
+        }))
+        .unwrap();
+        assert_eq!(item_collection.matched(), Some(42));
+        assert_eq!(item_collection.returned(), Some(0));
+        assert_eq!(item_collection.limit(), Some(10));
+    }
+
+    #[test]
+    fn numbers() {
+        let item_collection: ItemCollection = serde_json::from_value(json!({
+            "type": "FeatureCollection",
+            "features": [],
+            "links": [],
+            "numberMatched": 42,
+            "numberReturned": 0
+        }))
+        .unwrap();
+        assert_eq!(item_collection.matched(), Some(42));
+        assert_eq!(item_collection.returned(), Some(0));
+        assert_eq!(item_collection.limit(), None);
+    }
+}
@@ -140,6 +140,35 @@ impl Items {
         Ok(self)
     }
 
+    /// Converts this filter's geometries to [CRS84](crate::CRS84), and clears `filter_crs`.
+    ///
+    /// Returns an error if `filter_crs` isn't supported, even if there isn't
+    /// a filter. See [Filter::into_crs84] for the supported CRSs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Items;
+    ///
+    /// let items = Items {
+    ///     filter_crs: Some("EPSG:4326".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let items = items.into_crs84_filter().unwrap();
+    /// assert!(items.filter_crs.is_none());
+    /// ```
+    pub fn into_crs84_filter(mut self) -> Result<Items> {
+        if let Some(crs) = self.filter_crs.take() {
+            match self.filter.take() {
+                Some(filter) => self.filter = Some(filter.into_crs84(&crs)?),
+                None => {
+                    let _ = Filter::default().into_crs84(&crs)?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
     /// Returns true if this items structure matches the given item.
     ///
     /// # Examples
@@ -63,6 +63,9 @@
     warnings
 )]
 
+#[cfg(feature = "client")]
+mod cache;
+mod children;
 #[cfg(feature = "client")]
 pub mod client;
 mod collections;
@@ -72,6 +75,7 @@ mod fields;
 mod filter;
 mod item_collection;
 mod items;
+pub mod lint;
 #[cfg(feature = "python")]
 pub mod python;
 mod root;
@@ -80,15 +84,19 @@ mod sort;
 mod url_builder;
 
 #[cfg(feature = "client")]
-pub use client::{BlockingClient, Client};
-pub use collections::Collections;
+pub use cache::{Cache, DEFAULT_MAX_CACHE_ENTRIES};
+pub use children::Children;
+#[cfg(feature = "client")]
+pub use client::{BlockingClient, Client, Exchange};
+pub use collections::{Collections, GetCollections};
 pub use conformance::{
-    Conformance, COLLECTIONS_URI, CORE_URI, FEATURES_URI, FILTER_URIS, GEOJSON_URI,
-    ITEM_SEARCH_URI, OGC_API_FEATURES_URI,
+    Conformance, CHILDREN_URI, COLLECTIONS_URI, COLLECTION_SEARCH_URIS, CORE_URI, FEATURES_URI,
+    FIELDS_URI, FILTER_URIS, GEOJSON_URI, ITEM_SEARCH_URI, OGC_API_FEATURES_URI, SORT_URI,
+    TRANSACTION_URIS,
 };
 pub use error::Error;
-pub use fields::Fields;
-pub use filter::Filter;
+pub use fields::{Fields, DEFAULT_FIELDS};
+pub use filter::{Filter, CRS84, EPSG_3857, EPSG_4326};
 pub use item_collection::{Context, ItemCollection};
 pub use items::{GetItems, Items};
 pub use root::Root;
@@ -125,7 +133,7 @@ use tracing as _;
 #[cfg(test)]
 use {geojson as _, tokio_test as _};
 #[cfg(all(not(feature = "client"), test))]
-use {mockito as _, tokio as _};
+use {mockito as _, tempfile as _, tokio as _};
 
 // From https://github.com/rust-lang/cargo/issues/383#issuecomment-720873790,
 // may they be forever blessed.
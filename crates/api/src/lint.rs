@@ -0,0 +1,187 @@
+//! Check that API responses have the links that clients rely on.
+//!
+//! STAC API responses must have some links, e.g. a landing page's `self`,
+//! `root`, and `conformance` links, and every link should be absolute, since
+//! clients follow them from wherever they got the response. These checks
+//! report [MissingLink](Rule::MissingLink) and
+//! [RelativeLink](Rule::RelativeLink) problems through [stac::Lint], so they
+//! work for servers testing their own responses and for users checking a
+//! third-party API.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{Catalog, Lint};
+//! use stac_api::{Conformance, Root};
+//!
+//! let root = Root {
+//!     catalog: Catalog::new("an-id", "a description"),
+//!     conformance: Conformance::new(),
+//! };
+//! let problems = root.lint();
+//! assert_eq!(problems.len(), 3); // no self, root, or conformance links
+//! ```
+
+use crate::{Collections, Root, COLLECTIONS_URI, FEATURES_URI, ITEM_SEARCH_URI};
+use stac::{
+    lint::{Problem, Rule},
+    Collection, Link, Lint,
+};
+
+/// The links required on every collection in an API.
+const COLLECTION_LINKS: [(&str, Option<&str>); 4] = [
+    ("self", None),
+    ("root", None),
+    ("parent", None),
+    ("items", None),
+];
+
+impl Lint for Root {
+    fn lint(&self) -> Vec<Problem> {
+        let conforms_to = |uri: &str| {
+            self.conformance
+                .conforms_to
+                .iter()
+                .any(|conformance| conformance == uri)
+        };
+        let mut required = vec![("self", None), ("root", None), ("conformance", None)];
+        if conforms_to(COLLECTIONS_URI) || conforms_to(FEATURES_URI) {
+            required.push(("data", None));
+        }
+        if conforms_to(ITEM_SEARCH_URI) {
+            required.push(("search", Some("GET")));
+            required.push(("search", Some("POST")));
+        }
+        links(&self.catalog.id, &self.catalog.links, &required)
+    }
+}
+
+impl Lint for Collections {
+    fn lint(&self) -> Vec<Problem> {
+        let mut problems = links(
+            "collections",
+            &self.links,
+            &[("self", None), ("root", None)],
+        );
+        for collection in &self.collections {
+            problems.extend(collection_links(collection));
+        }
+        problems
+    }
+}
+
+/// Checks that a collection from an API has `self`, `root`, `parent`, and
+/// `items` links, and that all of its links are absolute.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Collection, Link};
+///
+/// let mut collection = Collection::new("an-id", "a description");
+/// collection.links = vec![
+///     Link::self_("http://stac-api.test/collections/an-id"),
+///     Link::root("http://stac-api.test"),
+///     Link::parent("http://stac-api.test"),
+///     Link::new("http://stac-api.test/collections/an-id/items", "items"),
+/// ];
+/// assert!(stac_api::lint::collection_links(&collection).is_empty());
+/// ```
+pub fn collection_links(collection: &Collection) -> Vec<Problem> {
+    links(&collection.id, &collection.links, &COLLECTION_LINKS)
+}
+
+/// Checks that links include each required `(rel, method)` pair and are all absolute.
+///
+/// A method of `None` matches any link with that rel. A link without a
+/// method matches `GET`.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Link, lint::Rule};
+///
+/// let links = vec![Link::new("/search", "search").method("POST")];
+/// let problems = stac_api::lint::links(
+///     "an-id",
+///     &links,
+///     &[("search", Some("GET")), ("search", Some("POST"))],
+/// );
+/// assert_eq!(problems[0].rule, Rule::MissingLink);
+/// assert_eq!(problems[1].rule, Rule::RelativeLink);
+/// ```
+pub fn links(id: &str, links: &[Link], required: &[(&str, Option<&str>)]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (rel, method) in required {
+        if !links.iter().any(|link| {
+            link.rel == *rel
+                && method.is_none_or(|method| {
+                    link.method
+                        .as_deref()
+                        .unwrap_or("GET")
+                        .eq_ignore_ascii_case(method)
+                })
+        }) {
+            let message = match method {
+                Some(method) => format!("missing {method} {rel} link"),
+                None => format!("missing {rel} link"),
+            };
+            problems.push(Problem {
+                rule: Rule::MissingLink,
+                id: id.to_string(),
+                message,
+            });
+        }
+    }
+    for link in links {
+        if !link.href.is_url() {
+            problems.push(Problem {
+                rule: Rule::RelativeLink,
+                id: id.to_string(),
+                message: format!("{} link has a relative href: {}", link.rel, link.href),
+            });
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Collections, Conformance, Root};
+    use stac::{lint::Rule, Catalog, Collection, Link, Lint};
+
+    #[test]
+    fn root() {
+        let mut catalog = Catalog::new("an-id", "a description");
+        catalog.links = vec![
+            Link::self_("http://stac-api.test/"),
+            Link::root("http://stac-api.test/"),
+            Link::new("http://stac-api.test/conformance", "conformance"),
+            Link::new("http://stac-api.test/search", "search").method("GET"),
+        ];
+        let mut root = Root {
+            catalog,
+            conformance: Conformance::new(),
+        };
+        assert!(root.lint().is_empty());
+
+        root.conformance = root.conformance.item_search();
+        let problems = root.lint();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].message, "missing POST search link");
+    }
+
+    #[test]
+    fn collections() {
+        let mut collection = Collection::new("an-id", "a description");
+        collection.links = vec![Link::self_("collections/an-id")];
+        let collections = Collections::from(vec![collection]);
+        let problems = collections.lint();
+        assert!(problems
+            .iter()
+            .any(|problem| problem.id == "collections" && problem.rule == Rule::MissingLink));
+        assert!(problems
+            .iter()
+            .any(|problem| problem.id == "an-id" && problem.rule == Rule::RelativeLink));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Error, Fields, GetItems, Items, Result, Sortby};
+use crate::{Error, Fields, Filter, GetItems, Items, Result, Sortby};
 use geojson::Geometry;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -82,6 +82,29 @@ impl Search {
         self
     }
 
+    /// Sets the intersects of this search from well-known text (WKT).
+    ///
+    /// Requires the `geo` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    ///
+    /// let search = Search::new().intersects_wkt("POINT(-105.1 41.1)").unwrap();
+    /// assert!(search.intersects.is_some());
+    /// Search::new().intersects_wkt("not wkt").unwrap_err();
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn intersects_wkt(self, wkt: &str) -> Result<Search> {
+        use geozero::{wkt::Wkt, ToGeo};
+
+        let geometry = Wkt(wkt)
+            .to_geo()
+            .map_err(|err| Error::InvalidWkt(err.to_string()))?;
+        Ok(self.intersects(Geometry::new((&geometry).into())))
+    }
+
     /// Sets the collections of this search.
     pub fn collections(mut self, collections: Vec<String>) -> Search {
         self.collections = collections;
@@ -113,11 +136,76 @@ impl Search {
     }
 
     /// Sets the fields of this search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    ///
+    /// let search = Search::new().fields("id,-geometry".into());
+    /// let fields = search.items.fields.unwrap();
+    /// assert_eq!(fields.include, vec!["id"]);
+    /// assert_eq!(fields.exclude, vec!["geometry"]);
+    /// ```
     pub fn fields(mut self, fields: Fields) -> Search {
         self.items.fields = Some(fields);
         self
     }
 
+    /// Sets the filter of this search.
+    ///
+    /// Strings are treated as cql2-text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::{Filter, Search};
+    ///
+    /// let search = Search::new().filter("eo:cloud_cover < 10");
+    /// assert_eq!(
+    ///     search.items.filter.unwrap(),
+    ///     Filter::Cql2Text("eo:cloud_cover < 10".to_string())
+    /// );
+    /// ```
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Search {
+        self.items.filter = Some(filter.into());
+        self
+    }
+
+    /// Sets the query of this search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use serde_json::json;
+    ///
+    /// let query = json!({"eo:cloud_cover": {"lt": 10}}).as_object().unwrap().clone();
+    /// let search = Search::new().query(query);
+    /// ```
+    pub fn query(mut self, query: Map<String, Value>) -> Search {
+        self.items.query = Some(query);
+        self
+    }
+
+    /// Sets the paging token of this search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    ///
+    /// let search = Search::new().token("next:an-id");
+    /// assert_eq!(search.items.additional_fields["token"], "next:an-id");
+    /// ```
+    pub fn token(mut self, token: impl ToString) -> Search {
+        let _ = self
+            .items
+            .additional_fields
+            .insert("token".to_string(), token.to_string().into());
+        self
+    }
+
     /// Returns an error if this search is invalid, e.g. if both bbox and intersects are specified.
     ///
     /// Returns the search unchanged if it is valid.
@@ -133,6 +221,12 @@ impl Search {
     /// search = search.valid().unwrap();
     /// search.intersects = Some(Geometry::new(Value::Point(vec![0.0, 0.0])));
     /// search.valid().unwrap_err();
+    ///
+    /// Search::new()
+    ///     .bbox(stac::Bbox::new(-180.0, -90.0, 180.0, 80.0))
+    ///     .intersects(Geometry::new(Value::Point(vec![0.0, 0.0])))
+    ///     .valid()
+    ///     .unwrap_err();
     /// ```
     pub fn valid(mut self) -> Result<Search> {
         self.items = self.items.valid()?;
@@ -252,6 +346,14 @@ impl Search {
         self.items = self.items.into_cql2_json()?;
         Ok(self)
     }
+
+    /// Converts this search's filter to [CRS84](crate::CRS84), and clears `filter_crs`.
+    ///
+    /// See [Items::into_crs84_filter].
+    pub fn into_crs84_filter(mut self) -> Result<Search> {
+        self.items = self.items.into_crs84_filter()?;
+        Ok(self)
+    }
 }
 
 impl TryFrom<Search> for GetSearch {
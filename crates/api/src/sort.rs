@@ -1,5 +1,9 @@
+use crate::Item;
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    cmp::Ordering,
     convert::Infallible,
     fmt::{Display, Formatter, Result},
     str::FromStr,
@@ -57,6 +61,225 @@ impl Sortby {
             direction: Direction::Descending,
         }
     }
+
+    /// Compares two items by this sortby's field and direction.
+    ///
+    /// The field is a dotted path into the item, e.g. `properties.datetime`.
+    /// Paths that aren't found at the top level of the item are looked up in
+    /// its properties, so `datetime` and `properties.datetime` are equivalent.
+    /// Numbers are compared numerically. If both values are strings that parse
+    /// as RFC 3339 datetimes they're compared chronologically, otherwise
+    /// strings are compared lexicographically. Missing values sort after
+    /// present ones when ascending.
+    ///
+    /// Because a string column can mix datetimes and other strings, this isn't
+    /// a total order over many items. Use [Sortby::sort_items] to sort a list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::{Item, Sortby};
+    /// use std::cmp::Ordering;
+    ///
+    /// let a: Item = serde_json::from_str(r#"{"properties": {"eo:cloud_cover": 10}}"#).unwrap();
+    /// let b: Item = serde_json::from_str(r#"{"properties": {"eo:cloud_cover": 5}}"#).unwrap();
+    /// assert_eq!(Sortby::asc("eo:cloud_cover").compare(&a, &b), Ordering::Greater);
+    /// assert_eq!(Sortby::desc("properties.eo:cloud_cover").compare(&a, &b), Ordering::Less);
+    /// ```
+    pub fn compare(&self, a: &Item, b: &Item) -> Ordering {
+        let a = self.lookup(a);
+        let b = self.lookup(b);
+        let datetimes = [a, b].into_iter().all(is_datetime_or_not_string);
+        self.direct(Key::new(a, datetimes).cmp(&Key::new(b, datetimes)))
+    }
+
+    /// Sorts items by one or more sortbys, in order of precedence.
+    ///
+    /// Values are compared as in [Sortby::compare], except that strings are
+    /// compared chronologically only if every string in that field is a
+    /// datetime. Each field is compared by a single kind of key, so this is a
+    /// total order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::{Item, Sortby};
+    ///
+    /// let mut items: Vec<Item> = ["2023-01-01T00:00:00Z", "not-a-datetime", "2022-01-01T00:00:00Z"]
+    ///     .into_iter()
+    ///     .map(|datetime| serde_json::from_value(serde_json::json!({"properties": {"datetime": datetime}})).unwrap())
+    ///     .collect();
+    /// Sortby::sort_items(&[Sortby::asc("datetime")], &mut items);
+    /// assert_eq!(items[2]["properties"]["datetime"], "not-a-datetime");
+    /// ```
+    pub fn sort_items(sortby: &[Sortby], items: &mut Vec<Item>) {
+        Sortby::sort_items_by(sortby, items, |item| item);
+    }
+
+    /// Sorts values by the items that `item` returns for them, as in [Sortby::sort_items].
+    ///
+    /// This lets callers sort e.g. references or `(key, value)` pairs without
+    /// building a full [Item] for every value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::{Item, Sortby};
+    ///
+    /// let mut pairs: Vec<(Item, &str)> = [("b", "second"), ("a", "first")]
+    ///     .into_iter()
+    ///     .map(|(id, value)| (serde_json::from_value(serde_json::json!({"id": id})).unwrap(), value))
+    ///     .collect();
+    /// Sortby::sort_items_by(&[Sortby::asc("id")], &mut pairs, |(item, _)| item);
+    /// assert_eq!(pairs[0].1, "first");
+    /// ```
+    pub fn sort_items_by<T>(sortby: &[Sortby], values: &mut Vec<T>, item: impl Fn(&T) -> &Item) {
+        if sortby.is_empty() {
+            return;
+        }
+        let columns: Vec<bool> = sortby
+            .iter()
+            .map(|sortby| {
+                values
+                    .iter()
+                    .all(|value| is_datetime_or_not_string(sortby.lookup(item(value))))
+            })
+            .collect();
+        let mut keyed: Vec<(Vec<Key>, T)> = std::mem::take(values)
+            .into_iter()
+            .map(|value| {
+                let keys = sortby
+                    .iter()
+                    .zip(&columns)
+                    .map(|(sortby, &datetimes)| Key::new(sortby.lookup(item(&value)), datetimes))
+                    .collect();
+                (keys, value)
+            })
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| {
+            sortby
+                .iter()
+                .zip(a.iter().zip(b))
+                .map(|(sortby, (a, b))| sortby.direct(a.cmp(b)))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        values.extend(keyed.into_iter().map(|(_, value)| value));
+    }
+
+    fn direct(&self, ordering: Ordering) -> Ordering {
+        match self.direction {
+            Direction::Ascending => ordering,
+            Direction::Descending => ordering.reverse(),
+        }
+    }
+
+    fn lookup<'a>(&self, item: &'a Item) -> Option<&'a Value> {
+        lookup(item, &self.field).or_else(|| {
+            item.get("properties")
+                .and_then(|properties| properties.as_object())
+                .and_then(|properties| lookup(properties, &self.field))
+        })
+    }
+}
+
+fn lookup<'a>(map: &'a serde_json::Map<String, Value>, path: &str) -> Option<&'a Value> {
+    match path.split_once('.') {
+        Some((key, rest)) => match map.get(key) {
+            Some(Value::Object(map)) => lookup(map, rest),
+            _ => map.get(path),
+        },
+        None => map.get(path),
+    }
+    .filter(|value| !value.is_null())
+}
+
+/// A value's sort key.
+///
+/// Keys of different kinds are ordered by kind, and missing values sort last.
+#[derive(Debug)]
+enum Key {
+    Bool(bool),
+    Number(f64),
+    Datetime(DateTime<FixedOffset>),
+    String(String),
+    Other(u8),
+    Missing,
+}
+
+impl Key {
+    fn new(value: Option<&Value>, datetimes: bool) -> Key {
+        match value {
+            None => Key::Missing,
+            Some(Value::Bool(b)) => Key::Bool(*b),
+            Some(Value::Number(n)) => n.as_f64().map(Key::Number).unwrap_or(Key::Other(2)),
+            Some(Value::String(s)) => match parse_datetime(s).filter(|_| datetimes) {
+                Some(datetime) => Key::Datetime(datetime),
+                None => Key::String(s.clone()),
+            },
+            Some(value) => Key::Other(rank(value)),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Key::Bool(_) => 1,
+            Key::Number(_) => 2,
+            Key::Datetime(_) | Key::String(_) => 3,
+            Key::Other(rank) => *rank,
+            Key::Missing => u8::MAX,
+        }
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        match (self, other) {
+            (Key::Bool(a), Key::Bool(b)) => a.cmp(b),
+            (Key::Number(a), Key::Number(b)) => a.total_cmp(b),
+            (Key::Datetime(a), Key::Datetime(b)) => a.cmp(b),
+            (Key::String(a), Key::String(b)) => a.cmp(b),
+            (Key::Datetime(_), Key::String(_)) => Ordering::Less,
+            (Key::String(_), Key::Datetime(_)) => Ordering::Greater,
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Key) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Key {}
+
+fn is_datetime_or_not_string(value: Option<&Value>) -> bool {
+    match value {
+        Some(Value::String(s)) => parse_datetime(s).is_some(),
+        _ => true,
+    }
+}
+
+fn parse_datetime(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok()
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
 }
 
 impl FromStr for Sortby {
@@ -85,7 +308,13 @@ impl Display for Sortby {
 #[cfg(test)]
 mod tests {
     use super::Sortby;
+    use crate::Item;
     use serde_json::json;
+    use std::cmp::Ordering;
+
+    fn item(value: serde_json::Value) -> Item {
+        serde_json::from_value(value).unwrap()
+    }
 
     #[test]
     fn optional_plus() {
@@ -111,4 +340,111 @@ mod tests {
             serde_json::to_value(Sortby::desc("foo")).unwrap()
         );
     }
+
+    #[test]
+    fn compare_numbers() {
+        let a = item(json!({"properties": {"gsd": 10}}));
+        let b = item(json!({"properties": {"gsd": 2.5}}));
+        assert_eq!(Sortby::asc("gsd").compare(&a, &b), Ordering::Greater);
+        assert_eq!(Sortby::desc("gsd").compare(&a, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_datetimes() {
+        let a = item(json!({"properties": {"datetime": "2023-01-01T00:00:00+01:00"}}));
+        let b = item(json!({"properties": {"datetime": "2022-12-31T23:30:00Z"}}));
+        assert_eq!(
+            Sortby::asc("properties.datetime").compare(&a, &b),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn compare_strings() {
+        let a = item(json!({"id": "a"}));
+        let b = item(json!({"id": "b"}));
+        assert_eq!(Sortby::asc("id").compare(&a, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_nested() {
+        let a = item(json!({"properties": {"foo": {"bar": 1}}}));
+        let b = item(json!({"properties": {"foo": {"bar": 2}}}));
+        assert_eq!(
+            Sortby::asc("properties.foo.bar").compare(&a, &b),
+            Ordering::Less
+        );
+        assert_eq!(Sortby::asc("foo.bar").compare(&a, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn sort_mixed_datetimes_and_strings() {
+        let mut items: Vec<Item> = [
+            json!("2023-01-01T00:00:00+01:00"),
+            json!("b"),
+            json!("2022-12-31T23:30:00Z"),
+            json!("2022-06-01T00:00:00Z"),
+            json!("a"),
+            json!(null),
+            json!(42),
+        ]
+        .into_iter()
+        .map(|value| item(json!({"properties": {"value": value}})))
+        .collect();
+        Sortby::sort_items(&[Sortby::asc("value")], &mut items);
+        let values: Vec<_> = items
+            .iter()
+            .map(|item| item["properties"]["value"].clone())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                json!(42),
+                json!("2022-06-01T00:00:00Z"),
+                json!("2022-12-31T23:30:00Z"),
+                json!("2023-01-01T00:00:00+01:00"),
+                json!("a"),
+                json!("b"),
+                json!(null),
+            ]
+        );
+
+        let mut items: Vec<Item> = (0..100)
+            .map(|i| {
+                let value = if i % 3 == 0 {
+                    json!(format!("20{:02}-01-01T00:00:00Z", i))
+                } else {
+                    json!(format!("{:02}", 100 - i))
+                };
+                item(json!({"properties": {"value": value}}))
+            })
+            .collect();
+        Sortby::sort_items(&[Sortby::desc("value")], &mut items);
+        for pair in items.windows(2) {
+            assert!(
+                pair[0]["properties"]["value"].as_str() >= pair[1]["properties"]["value"].as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn sort_datetimes() {
+        let mut items: Vec<Item> = ["2023-01-01T00:00:00+01:00", "2022-12-31T23:30:00Z"]
+            .into_iter()
+            .map(|datetime| item(json!({"properties": {"datetime": datetime}})))
+            .collect();
+        Sortby::sort_items(&[Sortby::asc("datetime")], &mut items);
+        assert_eq!(
+            items[0]["properties"]["datetime"],
+            "2023-01-01T00:00:00+01:00"
+        );
+    }
+
+    #[test]
+    fn compare_missing() {
+        let a = item(json!({"properties": {}}));
+        let b = item(json!({"properties": {"gsd": 1}}));
+        assert_eq!(Sortby::asc("gsd").compare(&a, &b), Ordering::Greater);
+        assert_eq!(Sortby::desc("gsd").compare(&a, &b), Ordering::Less);
+    }
 }
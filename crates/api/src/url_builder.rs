@@ -3,6 +3,10 @@ use url::{ParseError, Url};
 
 /// Builds urls on a root url.
 ///
+/// Trailing slashes on the root are normalized, and path segments (e.g.
+/// collection and item ids) are percent-encoded, so ids with `/` or `?` make
+/// valid urls.
+///
 /// # Examples
 ///
 /// ```
@@ -17,41 +21,139 @@ use url::{ParseError, Url};
 pub struct UrlBuilder {
     root: Url,
     collections: Url,
-    collections_with_slash: Url,
     conformance: Url,
     service_desc: Url,
     search: Url,
+    trailing_slash: bool,
+    query: Vec<(String, String)>,
 }
 
 impl UrlBuilder {
     /// Creates a new url builder.
     ///
+    /// Any query parameters on the url are added to every generated url.
+    ///
     /// # Examples
     ///
     /// ```
     /// # use stac_api::UrlBuilder;
     /// let url_builder = UrlBuilder::new("http://stac-api.test").unwrap();
+    /// let url_builder = UrlBuilder::new("http://stac-api.test/api/v1//?token=abc").unwrap();
+    /// assert_eq!(
+    ///     url_builder.search().as_str(),
+    ///     "http://stac-api.test/api/v1/search?token=abc"
+    /// );
     /// ```
     pub fn new(url: &str) -> Result<UrlBuilder, ParseError> {
-        let root: Url = if url.ends_with('/') {
-            url.parse()?
-        } else {
-            format!("{}/", url).parse()?
-        };
-        Ok(UrlBuilder {
-            collections: root.join("collections")?,
-            collections_with_slash: root.join("collections/")?,
-            conformance: root.join("conformance")?,
-            service_desc: root.join("api")?,
-            search: root.join("search")?,
+        let mut root: Url = url.parse()?;
+        if root.cannot_be_a_base() {
+            return Err(ParseError::RelativeUrlWithCannotBeABaseBase);
+        }
+        let query = root.query_pairs().into_owned().collect();
+        root.set_query(None);
+        let path = format!("{}/", root.path().trim_end_matches('/'));
+        root.set_path(&path);
+        let mut url_builder = UrlBuilder {
+            collections: root.clone(),
+            conformance: root.clone(),
+            service_desc: root.clone(),
+            search: root.clone(),
             root,
-        })
+            trailing_slash: false,
+            query,
+        };
+        url_builder.build();
+        Ok(url_builder)
+    }
+
+    /// Sets whether generated urls (other than the root) end with a slash.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test")
+    ///     .unwrap()
+    ///     .trailing_slash(true);
+    /// assert_eq!(url_builder.collections().as_str(), "http://stac-api.test/collections/");
+    /// ```
+    pub fn trailing_slash(mut self, trailing_slash: bool) -> UrlBuilder {
+        self.trailing_slash = trailing_slash;
+        self.build();
+        self
+    }
+
+    /// Adds a query parameter to every generated url, e.g. a provider's access token.
+    ///
+    /// If the parameter is already set, its value is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test")
+    ///     .unwrap()
+    ///     .query("token", "abc");
+    /// assert_eq!(
+    ///     url_builder.item("a-collection", "an-item").unwrap().as_str(),
+    ///     "http://stac-api.test/collections/a-collection/items/an-item?token=abc"
+    /// );
+    ///
+    /// let url_builder = UrlBuilder::new("http://stac-api.test?token=abc")
+    ///     .unwrap()
+    ///     .query("token", "def");
+    /// assert_eq!(url_builder.search().as_str(), "http://stac-api.test/search?token=def");
+    /// ```
+    pub fn query(mut self, key: impl ToString, value: impl ToString) -> UrlBuilder {
+        let key = key.to_string();
+        let value = value.to_string();
+        if let Some((_, existing)) = self.query.iter_mut().find(|(k, _)| *k == key) {
+            *existing = value;
+        } else {
+            self.query.push((key, value));
+        }
+        self.build();
+        self
+    }
+
+    /// Sets a url's query, merging in this builder's query parameters.
+    ///
+    /// Parameters that are already in `query` take precedence over the builder's.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test?token=abc&limit=10").unwrap();
+    /// let mut url = url_builder.search().clone();
+    /// url_builder.merge_query(&mut url, "limit=42&token=");
+    /// assert_eq!(url.as_str(), "http://stac-api.test/search?limit=42&token=");
+    ///
+    /// url_builder.merge_query(&mut url, "page=2");
+    /// assert_eq!(
+    ///     url.as_str(),
+    ///     "http://stac-api.test/search?page=2&token=abc&limit=10"
+    /// );
+    /// ```
+    pub fn merge_query(&self, url: &mut Url, query: &str) {
+        url.set_query(None);
+        let pairs: Vec<(String, String)> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        let extra = self
+            .query
+            .iter()
+            .filter(|(key, _)| pairs.iter().all(|(k, _)| k != key));
+        let mut pairs = pairs.iter().chain(extra).peekable();
+        if pairs.peek().is_some() {
+            let _ = url.query_pairs_mut().extend_pairs(pairs);
+        }
     }
 
     /// Returns the root url.
     ///
     /// The root url always has a trailing slash, even if the builder was
-    /// created without one.
+    /// created without one, and includes the builder's query parameters.
     ///
     /// # Examples
     ///
@@ -92,7 +194,7 @@ impl UrlBuilder {
     /// );
     /// ```
     pub fn collection(&self, id: &str) -> Result<Url, ParseError> {
-        self.collections_with_slash.join(id)
+        Ok(self.url(&["collections", id]))
     }
 
     /// Returns an items url.
@@ -108,10 +210,10 @@ impl UrlBuilder {
     /// );
     /// ```
     pub fn items(&self, id: &str) -> Result<Url, ParseError> {
-        self.collections_with_slash.join(&format!("{}/items", id))
+        Ok(self.url(&["collections", id, "items"]))
     }
 
-    /// Returns a item url.
+    /// Returns an item url.
     ///
     /// # Examples
     ///
@@ -122,10 +224,13 @@ impl UrlBuilder {
     ///     url_builder.item("a-collection", "an-item").unwrap().as_str(),
     ///     "http://stac-api.test/collections/a-collection/items/an-item"
     /// );
+    /// assert_eq!(
+    ///     url_builder.item("a-collection", "an/item").unwrap().as_str(),
+    ///     "http://stac-api.test/collections/a-collection/items/an%2Fitem"
+    /// );
     /// ```
     pub fn item(&self, collection_id: &str, id: &str) -> Result<Url, ParseError> {
-        self.collections_with_slash
-            .join(&format!("{}/items/{}", collection_id, id))
+        Ok(self.url(&["collections", collection_id, "items", id]))
     }
 
     /// Returns the conformance url.
@@ -160,6 +265,54 @@ impl UrlBuilder {
         &self.service_desc
     }
 
+    /// Returns the service-doc url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test").unwrap();
+    /// assert_eq!(
+    ///     url_builder.service_doc().as_str(),
+    ///     "http://stac-api.test/api.html"
+    /// );
+    /// ```
+    pub fn service_doc(&self) -> Url {
+        self.url(&["api.html"])
+    }
+
+    /// Returns the children url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test").unwrap();
+    /// assert_eq!(
+    ///     url_builder.children().as_str(),
+    ///     "http://stac-api.test/children"
+    /// );
+    /// ```
+    pub fn children(&self) -> Url {
+        self.url(&["children"])
+    }
+
+    /// Returns the queryables url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac_api::UrlBuilder;
+    /// let url_builder = UrlBuilder::new("http://stac-api.test").unwrap();
+    /// assert_eq!(
+    ///     url_builder.queryables().as_str(),
+    ///     "http://stac-api.test/queryables"
+    /// );
+    /// ```
+    pub fn queryables(&self) -> Url {
+        self.url(&["queryables"])
+    }
+
     /// Returns the search url.
     ///
     /// # Examples
@@ -175,6 +328,28 @@ impl UrlBuilder {
     pub fn search(&self) -> &Url {
         &self.search
     }
+
+    fn build(&mut self) {
+        let mut root = self.root.clone();
+        self.merge_query(&mut root, "");
+        self.root = root;
+        self.collections = self.url(&["collections"]);
+        self.conformance = self.url(&["conformance"]);
+        self.service_desc = self.url(&["api"]);
+        self.search = self.url(&["search"]);
+    }
+
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.root.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            let _ = path.pop_if_empty().extend(segments);
+            if self.trailing_slash && !segments.is_empty() {
+                let _ = path.push("");
+            }
+        }
+        self.merge_query(&mut url, "");
+        url
+    }
 }
 
 impl FromStr for UrlBuilder {
@@ -184,3 +359,45 @@ impl FromStr for UrlBuilder {
         UrlBuilder::new(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UrlBuilder;
+
+    #[test]
+    fn normalize_trailing_slashes() {
+        for root in [
+            "http://stac-api.test/api/v1",
+            "http://stac-api.test/api/v1/",
+            "http://stac-api.test/api/v1///",
+        ] {
+            let url_builder = UrlBuilder::new(root).unwrap();
+            assert_eq!(url_builder.root().as_str(), "http://stac-api.test/api/v1/");
+            assert_eq!(
+                url_builder.collections().as_str(),
+                "http://stac-api.test/api/v1/collections"
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_slash_and_query() {
+        let url_builder = UrlBuilder::new("http://stac-api.test?token=abc")
+            .unwrap()
+            .trailing_slash(true)
+            .query("provider", "a provider");
+        assert_eq!(
+            url_builder.root().as_str(),
+            "http://stac-api.test/?token=abc&provider=a+provider"
+        );
+        assert_eq!(
+            url_builder.items("a-collection").unwrap().as_str(),
+            "http://stac-api.test/collections/a-collection/items/?token=abc&provider=a+provider"
+        );
+    }
+
+    #[test]
+    fn cannot_be_a_base() {
+        let _ = UrlBuilder::new("mailto:stac@stac-api.test").unwrap_err();
+    }
+}
@@ -0,0 +1,388 @@
+use serde::Serialize;
+use stac::Format;
+use thiserror::Error;
+
+/// Crate-specific error enum.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The command can't be run with `--dry-run`.
+    #[error("{0} does not support --dry-run")]
+    DryRunNotSupported(&'static str),
+
+    /// Some functionality requires a certain optional feature to be enabled.
+    #[error("stacrs is not compiled with {0} support")]
+    FeatureNotEnabled(&'static str),
+
+    /// One or more harvest jobs failed.
+    #[error("{0} harvest job(s) failed")]
+    HarvestFailed(usize),
+
+    /// A harvest configuration file is invalid.
+    #[error("invalid harvest config: {0}")]
+    InvalidHarvestConfig(String),
+
+    /// A harvest or search was interrupted, and its progress was saved to a checkpoint file.
+    #[error("interrupted, resume with --resume {}", .0.display())]
+    Interrupted(std::path::PathBuf),
+
+    /// A collection or item id can't be used as a file or directory name.
+    #[error("can't export id as a file name: {0}")]
+    InvalidExportId(String),
+
+    /// An invalid `--basic-auth` value, which should be `USER:PASSWORD`.
+    #[error("invalid basic auth, expected USER:PASSWORD")]
+    InvalidBasicAuth,
+
+    /// A `stacrs split` output template doesn't contain `{}`.
+    #[error("output template must contain {{}}: {0}")]
+    InvalidOutputTemplate(String),
+
+    /// An invalid `key=value` pair.
+    #[error("invalid key=value: {0}")]
+    InvalidKeyValue(String),
+
+    /// The output format can't be used for this command.
+    #[error("invalid output format: {0}")]
+    InvalidOutputFormat(Format),
+
+    /// [std::io::Error]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// An item link did not point to an item.
+    #[error("item link was not an item: {0}")]
+    ItemLinkNotAnItem(String),
+
+    /// An item does not have a collection.
+    #[error("item without a collection: {0}")]
+    ItemWithoutCollection(String),
+
+    /// Items were provided without collections, and we weren't asked to create them.
+    #[error("items don't have a collection and `create_collections` is false")]
+    ItemsWithoutCollections,
+
+    /// `stacrs repl` was run from inside a `stacrs repl` session.
+    #[error("can't start a repl from inside a repl")]
+    NestedRepl,
+
+    /// [object_store::Error]
+    #[error(transparent)]
+    ObjectStore(#[from] object_store::Error),
+
+    /// [pgstac::Error]
+    #[cfg(feature = "pgstac")]
+    #[error(transparent)]
+    Pgstac(#[from] pgstac::Error),
+
+    /// `--resume` was used with a search that can't be checkpointed.
+    #[error(
+        "--resume is only supported when searching a STAC API into a local stac-geoparquet file"
+    )]
+    ResumeNotSupported,
+
+    /// [serde_json::Error]
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
+    /// Two `stacrs split` keys would be written to the same href.
+    #[error("split key {0} would overwrite {1}")]
+    SplitKeyCollision(String, String),
+
+    /// [stac::Error]
+    #[error(transparent)]
+    Stac(#[from] stac::Error),
+
+    /// [stac_api::Error]
+    #[error(transparent)]
+    StacApi(#[from] stac_api::Error),
+
+    /// [stac_duckdb::Error]
+    #[error(transparent)]
+    StacDuckdb(#[from] stac_duckdb::Error),
+
+    /// [stac_server::Error]
+    #[error(transparent)]
+    StacServer(#[from] stac_server::Error),
+
+    /// [tokio::task::JoinError]
+    #[error(transparent)]
+    TokioJoin(#[from] tokio::task::JoinError),
+
+    /// [tokio_postgres::Error]
+    #[cfg(feature = "pgstac")]
+    #[error(transparent)]
+    TokioPostgres(#[from] tokio_postgres::Error),
+
+    /// [toml::de::Error]
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+
+    /// We don't know how to load this value into a server.
+    #[error("don't know how to load value: {0}")]
+    UnloadableValue(String),
+
+    /// One or more validation errors were found.
+    #[error("one or more validation errors")]
+    Validation,
+}
+
+/// The category of an [Error], used to pick the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    /// Anything that doesn't fit in another category.
+    Other,
+
+    /// Bad arguments or input values provided by the user.
+    Usage,
+
+    /// Reading or writing data failed.
+    Io,
+
+    /// The STAC value is invalid.
+    Validation,
+
+    /// A search failed.
+    Search,
+
+    /// A server backend failed.
+    Backend,
+}
+
+/// How errors are printed to standard error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// A human-readable message.
+    #[default]
+    Text,
+
+    /// A JSON object with the error kind, exit code, and message.
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonError {
+    kind: ErrorKind,
+    code: i32,
+    message: String,
+}
+
+impl Error {
+    /// Returns this error's kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_cli::{Error, ErrorKind};
+    ///
+    /// assert_eq!(Error::Validation.kind(), ErrorKind::Validation);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::DryRunNotSupported(_)
+            | Error::FeatureNotEnabled(_)
+            | Error::InvalidBasicAuth
+            | Error::InvalidExportId(_)
+            | Error::InvalidHarvestConfig(_)
+            | Error::InvalidKeyValue(_)
+            | Error::InvalidOutputTemplate(_)
+            | Error::InvalidOutputFormat(_)
+            | Error::ItemLinkNotAnItem(_)
+            | Error::ItemWithoutCollection(_)
+            | Error::ItemsWithoutCollections
+            | Error::NestedRepl
+            | Error::ResumeNotSupported
+            | Error::SplitKeyCollision(_, _)
+            | Error::Toml(_)
+            | Error::UnloadableValue(_) => ErrorKind::Usage,
+            Error::Io(_) | Error::ObjectStore(_) | Error::SerdeJson(_) => ErrorKind::Io,
+            Error::Stac(error) => stac_error_kind(error),
+            Error::StacApi(error) => stac_api_error_kind(error),
+            Error::StacDuckdb(error) => stac_duckdb_error_kind(error),
+            Error::StacServer(_) => ErrorKind::Backend,
+            #[cfg(feature = "pgstac")]
+            Error::Pgstac(error) if error.is_data_error() => ErrorKind::Search,
+            #[cfg(feature = "pgstac")]
+            Error::Pgstac(_) => ErrorKind::Backend,
+            #[cfg(feature = "pgstac")]
+            Error::TokioPostgres(_) => ErrorKind::Backend,
+            Error::Validation => ErrorKind::Validation,
+            Error::HarvestFailed(_) | Error::Interrupted(_) | Error::TokioJoin(_) => {
+                ErrorKind::Other
+            }
+        }
+    }
+
+    /// Returns the process exit code for this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_cli::Error;
+    ///
+    /// assert_eq!(Error::Validation.code(), 4);
+    /// ```
+    pub fn code(&self) -> i32 {
+        self.kind().code()
+    }
+
+    /// Prints this error to standard error in the given format.
+    pub fn report(&self, format: ErrorFormat) {
+        match format {
+            ErrorFormat::Text => eprintln!("ERROR: {}", self),
+            ErrorFormat::Json => {
+                let json = JsonError {
+                    kind: self.kind(),
+                    code: self.code(),
+                    message: self.to_string(),
+                };
+                match serde_json::to_string(&json) {
+                    Ok(s) => eprintln!("{}", s),
+                    Err(_) => eprintln!("ERROR: {}", self),
+                }
+            }
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Returns the process exit code for this kind of error.
+    ///
+    /// Code `2` matches what [clap] uses for argument parsing errors.
+    pub fn code(&self) -> i32 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::Io => 3,
+            ErrorKind::Validation => 4,
+            ErrorKind::Search => 5,
+            ErrorKind::Backend => 6,
+        }
+    }
+}
+
+fn stac_error_kind(error: &stac::Error) -> ErrorKind {
+    use stac::Error;
+    match error {
+        Error::Validation(_) | Error::JsonschemaValidation(_) => ErrorKind::Validation,
+        Error::Io(_)
+        | Error::ObjectStore(_)
+        | Error::ObjectStorePath(_)
+        | Error::Reqwest(_)
+        | Error::SerdeJson(_)
+        | Error::Json(_)
+        | Error::Parquet(_) => ErrorKind::Io,
+        Error::FeatureNotEnabled(_)
+        | Error::UnsupportedFormat(_)
+        | Error::UnsupportedMigration(_, _)
+        | Error::InvalidOrder(_)
+        | Error::InvalidRewrite(_)
+        | Error::InvalidSplit(_)
+        | Error::Regex(_)
+        | Error::UrlParse(_) => ErrorKind::Usage,
+        _ => ErrorKind::Other,
+    }
+}
+
+fn stac_api_error_kind(error: &stac_api::Error) -> ErrorKind {
+    use stac_api::Error;
+    match error {
+        Error::Stac(error) => stac_error_kind(error),
+        // Errors with a status came back from the API, anything else (e.g.
+        // a refused connection or a timeout) never got a response.
+        Error::Reqwest(error) if error.status().is_none() => ErrorKind::Io,
+        Error::Io(_) | Error::SerdeJson(_) => ErrorKind::Io,
+        Error::ChronoParse(_)
+        | Error::Cql2(_)
+        | Error::EmptyDatetimeInterval
+        | Error::FeatureNotEnabled(_)
+        | Error::InvalidBbox(_, _)
+        | Error::InvalidWkt(_)
+        | Error::ParseFloatError(_)
+        | Error::ParseIntError(_)
+        | Error::SearchHasBboxAndIntersects(_)
+        | Error::StartIsAfterEnd(_, _)
+        | Error::UnsupportedFilterCrs(_)
+        | Error::UrlParse(_) => ErrorKind::Usage,
+        _ => ErrorKind::Search,
+    }
+}
+
+fn stac_duckdb_error_kind(error: &stac_duckdb::Error) -> ErrorKind {
+    use stac_duckdb::Error;
+    match error {
+        Error::Stac(error) => stac_error_kind(error),
+        Error::StacApi(error) => stac_api_error_kind(error),
+        Error::DuckDB(_) if error.is_io() => ErrorKind::Io,
+        Error::InvalidPageToken(_)
+        | Error::InvalidSample(_)
+        | Error::UnknownColumn(_)
+        | Error::Unimplemented(_) => ErrorKind::Usage,
+        _ => ErrorKind::Search,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ErrorKind};
+
+    #[test]
+    fn codes_are_distinct() {
+        let kinds = [
+            ErrorKind::Other,
+            ErrorKind::Usage,
+            ErrorKind::Io,
+            ErrorKind::Validation,
+            ErrorKind::Search,
+            ErrorKind::Backend,
+        ];
+        for (i, a) in kinds.iter().enumerate() {
+            for b in &kinds[i + 1..] {
+                assert_ne!(a.code(), b.code());
+            }
+        }
+    }
+
+    #[test]
+    fn stac_validation() {
+        let error = Error::from(stac::Error::Validation(Vec::new()));
+        assert_eq!(error.kind(), ErrorKind::Validation);
+    }
+
+    #[test]
+    fn stac_api_io() {
+        let error = Error::from(stac_api::Error::from(std::io::Error::other("an error")));
+        assert_eq!(error.kind(), ErrorKind::Io);
+        let error = Error::from(stac_api::Error::from(stac::Error::from(
+            std::io::Error::other("an error"),
+        )));
+        assert_eq!(error.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn stac_duckdb_kinds() {
+        let error = Error::from(stac_duckdb::Error::from(stac_api::Error::from(
+            std::io::Error::other("an error"),
+        )));
+        assert_eq!(error.kind(), ErrorKind::Io);
+        let error = Error::from(stac_duckdb::Error::InvalidPageToken("a-token".to_string()));
+        assert_eq!(error.kind(), ErrorKind::Usage);
+        let error = Error::from(stac_duckdb::Error::UnknownCollection(
+            "a-collection".to_string(),
+        ));
+        assert_eq!(error.kind(), ErrorKind::Search);
+    }
+
+    #[test]
+    #[cfg(feature = "pgstac")]
+    fn pgstac_kinds() {
+        let error = Error::from(pgstac::Error::InvalidToken {
+            token: "a-token".to_string(),
+            version: "0.9.0".to_string(),
+        });
+        assert_eq!(error.kind(), ErrorKind::Search);
+        let error = Error::from(pgstac::Error::InvalidVersion("not-a-version".to_string()));
+        assert_eq!(error.kind(), ErrorKind::Backend);
+    }
+}
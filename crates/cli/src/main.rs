@@ -4,11 +4,12 @@ use stac_cli::Stacrs;
 #[tokio::main]
 async fn main() {
     let args = Stacrs::parse();
+    let error_format = args.error_format();
     std::process::exit(match args.run().await {
         Ok(()) => 0,
         Err(err) => {
-            eprintln!("ERROR: {}", err);
-            1 // TODO make this more meaningful
+            err.report(error_format);
+            err.code()
         }
     })
 }
@@ -0,0 +1,194 @@
+use crate::{Command, Error, Result, Stacrs};
+use clap::Parser;
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const PROMPT: &str = "stacrs> ";
+
+const HELP: &str = "Enter any stacrs command without the leading `stacrs`, e.g. `search data/items.parquet --max-items 1`.
+Global options passed to `stacrs repl`, e.g. `--opt`, are used by every command unless the command sets its own.
+Type `help <command>` for a command's options, and `exit` or `quit` (or Ctrl-D) to leave.";
+
+/// An API client's href and the `--api-cache-ttl` and `--api-cache-dir` it was created with.
+type ApiClientKey = (String, Option<u64>, Option<PathBuf>);
+
+/// Clients that are kept warm between the commands of a `stacrs repl` session.
+///
+/// Creating a DuckDB client loads its extensions, and creating an API client
+/// builds a new connection pool, both of which are slow compared to a small
+/// query.
+#[derive(Debug, Default)]
+pub(crate) struct Session {
+    duckdb: OnceLock<stac_duckdb::Client>,
+    api_clients: Mutex<HashMap<ApiClientKey, stac_api::Client>>,
+}
+
+impl Session {
+    /// Returns the session's DuckDB client, creating it on first use.
+    pub(crate) fn duckdb(&self) -> Result<&stac_duckdb::Client> {
+        if let Some(client) = self.duckdb.get() {
+            return Ok(client);
+        }
+        let client = stac_duckdb::Client::new()?;
+        Ok(self.duckdb.get_or_init(|| client))
+    }
+
+    /// Returns the session's API client for an href, creating it with the command's cache on first use.
+    ///
+    /// Clients are keyed by the cache options too, so a command with a
+    /// different `--api-cache-ttl` or `--api-cache-dir` gets its own client.
+    pub(crate) fn api_client(&self, href: &str, stacrs: &Stacrs) -> Result<stac_api::Client> {
+        let key = (
+            href.to_string(),
+            stacrs.api_cache_ttl,
+            stacrs.api_cache_dir.clone(),
+        );
+        let mut api_clients = self.api_clients.lock().unwrap();
+        if let Some(client) = api_clients.get(&key) {
+            Ok(client.clone())
+        } else {
+            let client = crate::new_api_client(href, stacrs.api_cache())?;
+            let _ = api_clients.insert(key, client.clone());
+            Ok(client)
+        }
+    }
+}
+
+impl Stacrs {
+    /// Uses the `stacrs repl` global options for every option that this command didn't set itself.
+    ///
+    /// Flags like `--dry-run` can't be unset by a command, so they're on if either sets them.
+    fn inherit(&mut self, stacrs: &Stacrs) {
+        self.input_format = self.input_format.or(stacrs.input_format);
+        if self.options.is_empty() {
+            self.options = stacrs.options.clone();
+        }
+        self.output_format = self.output_format.or(stacrs.output_format);
+        self.compact_json = self.compact_json.or(stacrs.compact_json);
+        self.parquet_compression = self.parquet_compression.or(stacrs.parquet_compression);
+        self.error_format = self.error_format.or(stacrs.error_format);
+        self.dry_run |= stacrs.dry_run;
+        self.skip_invalid |= stacrs.skip_invalid;
+        if self.api_cache_ttl.is_none() && self.api_cache_dir.is_none() {
+            self.api_cache_ttl = stacrs.api_cache_ttl;
+            self.api_cache_dir = stacrs.api_cache_dir.clone();
+        }
+        self.precision = self.precision.or(stacrs.precision);
+        self.simplify = self.simplify.or(stacrs.simplify);
+    }
+}
+
+/// Reads commands from standard input and runs them until `exit`, `quit`, or the end of input.
+///
+/// Errors are reported and don't end the session.
+pub(crate) async fn run(stacrs: &Stacrs) -> Result<()> {
+    let session = Arc::new(Session::default());
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    loop {
+        eprint!("{PROMPT}");
+        std::io::stderr().flush()?;
+        let Some(line) = lines.next_line().await? else {
+            eprintln!();
+            return Ok(());
+        };
+        let Some(words) = shlex::split(&line) else {
+            eprintln!("ERROR: unmatched quote");
+            continue;
+        };
+        match words.first().map(String::as_str) {
+            None => continue,
+            Some("exit" | "quit") => return Ok(()),
+            Some("help") if words.len() == 1 => {
+                eprintln!("{HELP}");
+                continue;
+            }
+            _ => {}
+        }
+        let mut command =
+            match Stacrs::try_parse_from(std::iter::once("stacrs".into()).chain(words)) {
+                Ok(command) => command,
+                Err(err) => {
+                    let _ = err.print();
+                    continue;
+                }
+            };
+        if matches!(command.command, Command::Repl) {
+            Error::NestedRepl.report(command.error_format());
+            continue;
+        }
+        command.inherit(stacrs);
+        command.session = Some(session.clone());
+        let error_format = command.error_format();
+        if let Err(err) = Box::pin(command.run()).await {
+            err.report(error_format);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Session;
+    use crate::{ErrorFormat, Stacrs};
+    use clap::Parser;
+    use stac::Format;
+
+    #[test]
+    fn api_clients_are_reused() {
+        let session = Session::default();
+        let stacrs = Stacrs::parse_from(["stacrs", "translate"]);
+        let _ = session.api_client("http://stac.test/a", &stacrs).unwrap();
+        let _ = session.api_client("http://stac.test/a", &stacrs).unwrap();
+        let _ = session.api_client("http://stac.test/b", &stacrs).unwrap();
+        assert_eq!(session.api_clients.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn api_clients_are_keyed_by_cache_options() {
+        let session = Session::default();
+        let tempdir = tempfile::tempdir().unwrap();
+        for args in [
+            vec!["stacrs", "translate"],
+            vec!["stacrs", "--api-cache-ttl", "60", "translate"],
+            vec!["stacrs", "--api-cache-ttl", "120", "translate"],
+            vec![
+                "stacrs",
+                "--api-cache-ttl",
+                "120",
+                "--api-cache-dir",
+                tempdir.path().to_str().unwrap(),
+                "translate",
+            ],
+        ] {
+            let stacrs = Stacrs::parse_from(args);
+            let _ = session.api_client("http://stac.test", &stacrs).unwrap();
+        }
+        assert_eq!(session.api_clients.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn inherit_global_options() {
+        let stacrs = Stacrs::parse_from([
+            "stacrs",
+            "--output-format",
+            "ndjson",
+            "--error-format",
+            "json",
+            "--skip-invalid",
+            "repl",
+        ]);
+        let mut command = Stacrs::parse_from(["stacrs", "translate"]);
+        command.inherit(&stacrs);
+        assert_eq!(command.output_format(None), Format::NdJson);
+        assert_eq!(command.error_format(), ErrorFormat::Json);
+        assert!(command.skip_invalid);
+
+        let mut command = Stacrs::parse_from(["stacrs", "--output-format", "json", "translate"]);
+        command.inherit(&stacrs);
+        assert_eq!(command.output_format(None), Format::Json(false));
+    }
+}
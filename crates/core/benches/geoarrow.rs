@@ -0,0 +1,109 @@
+//! Benchmarks arrow conversion and stac-geoparquet reading and writing.
+//!
+//! Items come from [stac::testing::Generator]. The `synthetic` group uses one
+//! million items by default. Set `STAC_BENCH_SYNTHETIC_ITEMS` to use a
+//! different number of items.
+
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use serde_json::json;
+use stac::{
+    testing::{Distribution, Generator, GeometryType},
+    ItemCollection,
+};
+use std::hint::black_box;
+
+/// Generates items that look like a typical optical imagery collection.
+fn items(count: usize) -> ItemCollection {
+    Generator::new(42)
+        .geometry_type(GeometryType::Polygon)
+        .extension("https://stac-extensions.github.io/eo/v1.1.0/schema.json")
+        .property(
+            "eo:cloud_cover",
+            Distribution::Uniform {
+                min: 0.0,
+                max: 100.0,
+            },
+        )
+        .property(
+            "platform",
+            Distribution::Choice(vec![json!("sentinel-2a"), json!("sentinel-2b")]),
+        )
+        .item_collection(count)
+}
+
+fn to_bytes(items: ItemCollection) -> Bytes {
+    let mut buf = Vec::new();
+    stac::geoparquet::into_writer(&mut buf, items).unwrap();
+    buf.into()
+}
+
+fn arrow(c: &mut Criterion) {
+    let items = items(100);
+    let table = stac::geoarrow::to_table(items.clone()).unwrap();
+    let mut group = c.benchmark_group("arrow");
+    let _ = group.bench_function("to_table", |b| {
+        b.iter_batched(
+            || items.clone(),
+            |items| stac::geoarrow::to_table(black_box(items)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    let _ = group.bench_function("from_table", |b| {
+        b.iter_batched(
+            || table.clone(),
+            |table| stac::geoarrow::from_table(black_box(table)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn geoparquet(c: &mut Criterion) {
+    let items = items(100);
+    let bytes = to_bytes(items.clone());
+    let mut group = c.benchmark_group("geoparquet");
+    let _ = group.bench_function("write", |b| {
+        b.iter_batched(
+            || items.clone(),
+            |items| to_bytes(black_box(items)),
+            BatchSize::SmallInput,
+        )
+    });
+    let _ = group.bench_function("read", |b| {
+        b.iter(|| stac::geoparquet::from_reader(black_box(bytes.clone())).unwrap())
+    });
+    group.finish();
+}
+
+fn synthetic(c: &mut Criterion) {
+    let count = std::env::var("STAC_BENCH_SYNTHETIC_ITEMS")
+        .ok()
+        .map(|count| count.parse().unwrap())
+        .unwrap_or(1_000_000);
+    let items = items(count);
+    let bytes = to_bytes(items.clone());
+    let mut group = c.benchmark_group("synthetic");
+    let _ = group.sample_size(10);
+    let _ = group.bench_function("to_table", |b| {
+        b.iter_batched(
+            || items.clone(),
+            |items| stac::geoarrow::to_table(black_box(items)).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+    let _ = group.bench_function("write", |b| {
+        b.iter_batched(
+            || items.clone(),
+            |items| to_bytes(black_box(items)),
+            BatchSize::LargeInput,
+        )
+    });
+    let _ = group.bench_function("read", |b| {
+        b.iter(|| stac::geoparquet::from_reader(black_box(bytes.clone())).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, arrow, geoparquet, synthetic);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! Compares **serde_json** with the JSON deserialization used by this crate.
+//!
+//! Run with `--features simd-json` to benchmark the **simd-json** fast path.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use stac::{FromJson, FromNdjson, Item, ItemCollection, ToNdjson};
+use std::hint::black_box;
+
+fn item(c: &mut Criterion) {
+    let buf = std::fs::read("examples/extended-item.json").unwrap();
+    let mut group = c.benchmark_group("item");
+    let _ = group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_slice::<Item>(black_box(&buf)).unwrap())
+    });
+    let _ = group.bench_function("stac", |b| {
+        b.iter(|| Item::from_json_slice(black_box(&buf)).unwrap())
+    });
+    group.finish();
+}
+
+fn item_collection(c: &mut Criterion) {
+    let buf = std::fs::read("data/two-sentinel-2-items.json").unwrap();
+    let mut group = c.benchmark_group("item_collection");
+    let _ = group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_slice::<ItemCollection>(black_box(&buf)).unwrap())
+    });
+    let _ = group.bench_function("stac", |b| {
+        b.iter(|| ItemCollection::from_json_slice(black_box(&buf)).unwrap())
+    });
+    group.finish();
+}
+
+fn ndjson(c: &mut Criterion) {
+    let item_collection: ItemCollection = stac::read("data/two-sentinel-2-items.json").unwrap();
+    let buf = item_collection.to_ndjson_vec().unwrap();
+    let mut group = c.benchmark_group("ndjson");
+    let _ = group.bench_function("serde_json", |b| {
+        b.iter(|| {
+            black_box(&buf)
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| serde_json::from_slice::<Item>(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    let _ = group.bench_function("stac", |b| {
+        b.iter(|| ItemCollection::from_ndjson_bytes(black_box(buf.clone())).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, item, item_collection, ndjson);
+criterion_main!(benches);
@@ -14,6 +14,12 @@ pub enum Bbox {
 }
 
 impl Bbox {
+    /// The tolerance used when comparing a bbox to coordinates.
+    ///
+    /// Differences this small, e.g. from floating point error when a bbox was
+    /// computed or rounding when it was written, aren't treated as mismatches.
+    pub const TOLERANCE: f64 = 1e-7;
+
     /// Creates a new 2D bbox.
     ///
     /// # Examples
@@ -152,6 +158,52 @@ impl Bbox {
         }
     }
 
+    /// Returns true if this bbox contains a horizontal position, within [Bbox::TOLERANCE].
+    ///
+    /// A bbox whose minimum x is larger than its maximum x crosses the antimeridian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    /// let bbox = Bbox::new(1., 1., 2., 2.);
+    /// assert!(bbox.contains_xy(1.5, 2.00000001));
+    /// assert!(!bbox.contains_xy(1.5, 3.));
+    /// let bbox = Bbox::new(170., -10., -170., 10.);
+    /// assert!(bbox.contains_xy(180., 0.));
+    /// ```
+    pub fn contains_xy(&self, x: f64, y: f64) -> bool {
+        let (xmin, xmax) = (self.xmin() - Bbox::TOLERANCE, self.xmax() + Bbox::TOLERANCE);
+        let x_inside = if self.xmin() <= self.xmax() {
+            xmin <= x && x <= xmax
+        } else {
+            xmin <= x || x <= xmax
+        };
+        x_inside && self.ymin() - Bbox::TOLERANCE <= y && y <= self.ymax() + Bbox::TOLERANCE
+    }
+
+    /// Returns true if this bbox's horizontal extents equal another's, within [Bbox::TOLERANCE].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    /// let bbox = Bbox::new(1., 1., 2., 2.);
+    /// assert!(bbox.horizontal_eq(&Bbox::new(1., 1., 2.00000001, 2.)));
+    /// assert!(bbox.horizontal_eq(&Bbox::ThreeDimensional([1., 1., 0., 2., 2., 10.])));
+    /// assert!(!bbox.horizontal_eq(&Bbox::new(1., 1., 2.1, 2.)));
+    /// ```
+    pub fn horizontal_eq(&self, other: &Bbox) -> bool {
+        [
+            (self.xmin(), other.xmin()),
+            (self.ymin(), other.ymin()),
+            (self.xmax(), other.xmax()),
+            (self.ymax(), other.ymax()),
+        ]
+        .into_iter()
+        .all(|(a, b)| (a - b).abs() <= Bbox::TOLERANCE)
+    }
+
     /// Converts this bbox to a [Geometry](geojson::Geometry).
     ///
     /// # Examples
@@ -240,6 +292,25 @@ mod tests {
     use super::Bbox;
     use geojson::Value;
 
+    #[test]
+    fn contains_xy() {
+        let bbox = Bbox::new(1., 2., 3., 4.);
+        assert!(bbox.contains_xy(1., 2.));
+        assert!(bbox.contains_xy(3. + Bbox::TOLERANCE / 2., 4.));
+        assert!(!bbox.contains_xy(3. + Bbox::TOLERANCE * 2., 4.));
+        let bbox = Bbox::new(170., -10., -170., 10.);
+        assert!(bbox.contains_xy(175., 0.));
+        assert!(bbox.contains_xy(-175., 0.));
+        assert!(!bbox.contains_xy(0., 0.));
+    }
+
+    #[test]
+    fn horizontal_eq() {
+        let bbox = Bbox::new(1., 2., 3., 4.);
+        assert!(bbox.horizontal_eq(&Bbox::new(1. - Bbox::TOLERANCE / 2., 2., 3., 4.)));
+        assert!(!bbox.horizontal_eq(&Bbox::new(1. - Bbox::TOLERANCE * 2., 2., 3., 4.)));
+    }
+
     #[test]
     fn to_geometry() {
         let bbox = Bbox::new(1., 2., 3., 4.);
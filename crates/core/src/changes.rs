@@ -0,0 +1,196 @@
+//! Find the items that changed between two snapshots of a catalog.
+//!
+//! A change feed lets downstream systems sync incrementally instead of
+//! re-reading a whole catalog. Items are matched by their collection and id,
+//! and an item is updated if any part of it, other than its structural links
+//! (e.g. `self` and `root`), differs between the snapshots. Ignoring structural
+//! links means that a relocated catalog isn't reported as entirely updated.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{Item, changes::{self, ChangeKind}};
+//!
+//! let a = Item::new("a");
+//! let b = Item::new("b");
+//! let mut updated = b.clone();
+//! updated.properties.title = Some("a new title".to_string());
+//! let old = vec![a, b];
+//! let new = vec![updated, Item::new("c")];
+//! let changes = changes::diff(old, new, chrono::Utc::now());
+//! let kinds: Vec<_> = changes.iter().map(|change| (change.kind, change.id.as_str())).collect();
+//! assert_eq!(
+//!     kinds,
+//!     [(ChangeKind::Updated, "b"), (ChangeKind::Created, "c"), (ChangeKind::Deleted, "a")]
+//! );
+//! ```
+
+use crate::Item;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// How an item changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// The item is only in the new snapshot.
+    Created,
+
+    /// The item is in both snapshots, but differs.
+    Updated,
+
+    /// The item is only in the old snapshot.
+    Deleted,
+}
+
+/// A single entry in a change feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    /// How the item changed.
+    #[serde(rename = "change")]
+    pub kind: ChangeKind,
+
+    /// The item's collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+
+    /// The item's id.
+    pub id: String,
+
+    /// When the change happened.
+    ///
+    /// For created and updated items, this is the item's `updated` field if
+    /// it's a valid datetime. Otherwise, it's the time passed to [diff].
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Returns the changes between two snapshots of items.
+///
+/// Created and updated items are returned in the order of the new snapshot,
+/// followed by deleted items in the order of the old snapshot. If a snapshot
+/// has more than one item with the same collection and id, the last one is
+/// used.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, changes};
+///
+/// let item = Item::new("an-id");
+/// let changes = changes::diff(vec![item.clone()], vec![item], chrono::Utc::now());
+/// assert!(changes.is_empty());
+/// ```
+pub fn diff(
+    old: impl IntoIterator<Item = Item>,
+    new: impl IntoIterator<Item = Item>,
+    timestamp: DateTime<Utc>,
+) -> Vec<Change> {
+    let mut old_keys = Vec::new();
+    let mut old_items = HashMap::new();
+    for item in old {
+        let key = (item.collection.clone(), item.id.clone());
+        if old_items.insert(key.clone(), item).is_none() {
+            old_keys.push(key);
+        }
+    }
+    let mut new_keys = Vec::new();
+    let mut new_items = HashMap::new();
+    for item in new {
+        let key = (item.collection.clone(), item.id.clone());
+        if new_items.insert(key.clone(), item).is_none() {
+            new_keys.push(key);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for key in new_keys {
+        let item = &new_items[&key];
+        let kind = match old_items.get(&key) {
+            None => ChangeKind::Created,
+            Some(old) if content(old) != content(item) => ChangeKind::Updated,
+            Some(_) => continue,
+        };
+        let timestamp = item
+            .properties
+            .updated
+            .as_deref()
+            .and_then(|updated| DateTime::parse_from_rfc3339(updated).ok())
+            .map(|updated| updated.to_utc())
+            .unwrap_or(timestamp);
+        changes.push(Change::new(kind, key, timestamp));
+    }
+    for key in old_keys {
+        if !new_items.contains_key(&key) {
+            changes.push(Change::new(ChangeKind::Deleted, key, timestamp));
+        }
+    }
+    changes
+}
+
+impl Change {
+    fn new(
+        kind: ChangeKind,
+        (collection, id): (Option<String>, String),
+        timestamp: DateTime<Utc>,
+    ) -> Change {
+        Change {
+            kind,
+            collection,
+            id,
+            timestamp,
+        }
+    }
+}
+
+fn content(item: &Item) -> Option<Value> {
+    let mut item = item.clone();
+    item.links.retain(|link| !link.is_structural());
+    serde_json::to_value(item).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChangeKind;
+    use crate::{Item, Link};
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[test]
+    fn keyed_by_collection() {
+        let old = vec![Item::new("a").collection("one")];
+        let new = vec![Item::new("a").collection("two")];
+        let changes = super::diff(old, new, "2024-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, ChangeKind::Created);
+        assert_eq!(changes[0].collection.as_deref(), Some("two"));
+        assert_eq!(changes[1].kind, ChangeKind::Deleted);
+    }
+
+    #[test]
+    fn ignores_structural_links() {
+        let old = Item::new("a");
+        let mut new = old.clone();
+        new.links.push(Link::self_("https://stac.test/a.json"));
+        assert!(super::diff(vec![old.clone()], vec![new.clone()], Utc::now()).is_empty());
+        new.links
+            .push(Link::new("https://stac.test/license", "license"));
+        assert_eq!(super::diff(vec![old], vec![new], Utc::now()).len(), 1);
+    }
+
+    #[test]
+    fn timestamp_from_updated() {
+        let mut item = Item::new("a");
+        item.properties.updated = Some("2023-06-01T12:00:00Z".to_string());
+        let changes = super::diff(
+            Vec::new(),
+            vec![item],
+            "2024-01-01T00:00:00Z".parse().unwrap(),
+        );
+        assert_eq!(
+            serde_json::to_value(&changes[0]).unwrap(),
+            json!({"change": "created", "id": "a", "timestamp": "2023-06-01T12:00:00Z"})
+        );
+    }
+}
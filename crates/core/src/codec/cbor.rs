@@ -0,0 +1,40 @@
+use super::Codec;
+use crate::Result;
+
+/// [CBOR](https://cbor.io/), the Concise Binary Object Representation.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Format, Item};
+///
+/// let format = Format::infer_from_href("item.cbor").unwrap();
+/// assert_eq!(format, Format::Custom("cbor"));
+/// let bytes = format.into_vec(Item::new("an-id")).unwrap();
+/// let item: Item = format.from_bytes(bytes).unwrap();
+/// assert_eq!(item.id, "an-id");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Cbor;
+
+impl Codec for Cbor {
+    fn name(&self) -> &'static str {
+        "cbor"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["cbor"]
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let value: serde_json::Value = ciborium::from_reader(bytes)?;
+        serde_json::to_vec(&value).map_err(Into::into)
+    }
+
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        let value: serde_json::Value = serde_json::from_slice(&bytes)?;
+        let mut buf = Vec::new();
+        ciborium::into_writer(&value, &mut buf)?;
+        Ok(buf)
+    }
+}
@@ -0,0 +1,360 @@
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use super::Codec;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use crate::Format;
+use crate::Result;
+#[cfg(feature = "gzip")]
+use std::io::Read;
+use std::{
+    fmt::{Debug, Formatter},
+    io::Write,
+};
+
+/// A compression algorithm, used by the built-in compressed codecs and to
+/// stream compressed newline-delimited JSON with [crate::ndjson::Writer].
+///
+/// # Examples
+///
+/// ```
+/// use stac::codec::Compression;
+///
+/// assert_eq!(Compression::default(), Compression::None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression.
+    #[default]
+    None,
+
+    /// Gzip compression.
+    #[cfg(feature = "gzip")]
+    Gzip,
+
+    /// Zstd compression.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+/// A writer that compresses everything written to it.
+pub(crate) enum Encoder<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+/// Gzip-compressed JSON or NDJSON.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Format, Item};
+///
+/// let format = Format::infer_from_href("item.json.gz").unwrap();
+/// assert_eq!(format, Format::Custom("json.gz"));
+/// let bytes = format.into_vec(Item::new("an-id")).unwrap();
+/// let item: Item = format.from_bytes(bytes).unwrap();
+/// ```
+#[cfg(feature = "gzip")]
+#[derive(Debug, Clone, Copy)]
+pub struct Gzip {
+    ndjson: bool,
+}
+
+/// Zstd-compressed JSON or NDJSON.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Format, Item, ItemCollection};
+///
+/// let format = Format::infer_from_href("items.ndjson.zst").unwrap();
+/// assert_eq!(format, Format::Custom("ndjson.zst"));
+/// let item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+/// let bytes = format.into_vec(item_collection).unwrap();
+/// let item_collection: ItemCollection = format.from_bytes(bytes).unwrap();
+/// assert_eq!(item_collection.items.len(), 2);
+/// ```
+#[cfg(feature = "zstd")]
+#[derive(Debug, Clone, Copy)]
+pub struct Zstd {
+    ndjson: bool,
+}
+
+#[cfg(feature = "gzip")]
+impl Gzip {
+    /// Returns a codec for gzip-compressed JSON.
+    pub fn json() -> Gzip {
+        Gzip { ndjson: false }
+    }
+
+    /// Returns a codec for gzip-compressed newline-delimited JSON.
+    pub fn ndjson() -> Gzip {
+        Gzip { ndjson: true }
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Codec for Gzip {
+    fn name(&self) -> &'static str {
+        if self.ndjson {
+            "ndjson.gz"
+        } else {
+            "json.gz"
+        }
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        if self.ndjson {
+            &["ndjson.gz"]
+        } else {
+            &["json.gz", "geojson.gz"]
+        }
+    }
+
+    fn format(&self) -> Format {
+        inner_format(self.ndjson)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        let _ = flate2::read::MultiGzDecoder::new(bytes).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        Compression::Gzip.encode(&bytes)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Zstd {
+    /// Returns a codec for zstd-compressed JSON.
+    pub fn json() -> Zstd {
+        Zstd { ndjson: false }
+    }
+
+    /// Returns a codec for zstd-compressed newline-delimited JSON.
+    pub fn ndjson() -> Zstd {
+        Zstd { ndjson: true }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Codec for Zstd {
+    fn name(&self) -> &'static str {
+        if self.ndjson {
+            "ndjson.zst"
+        } else {
+            "json.zst"
+        }
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        if self.ndjson {
+            &["ndjson.zst"]
+        } else {
+            &["json.zst", "geojson.zst"]
+        }
+    }
+
+    fn format(&self) -> Format {
+        inner_format(self.ndjson)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        zstd::decode_all(bytes).map_err(Into::into)
+    }
+
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        Compression::Zstd.encode(&bytes)
+    }
+}
+
+impl Compression {
+    /// Infers the compression from an href's extension, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::codec::Compression;
+    ///
+    /// assert_eq!(Compression::infer_from_href("items.ndjson"), Compression::None);
+    /// #[cfg(feature = "gzip")]
+    /// assert_eq!(Compression::infer_from_href("items.ndjson.gz"), Compression::Gzip);
+    /// ```
+    pub fn infer_from_href(href: &str) -> Compression {
+        let Some((_, extension)) = href.rsplit_once('.') else {
+            return Compression::None;
+        };
+        #[cfg(feature = "gzip")]
+        if extension.eq_ignore_ascii_case("gz") {
+            return Compression::Gzip;
+        }
+        #[cfg(feature = "zstd")]
+        if extension.eq_ignore_ascii_case("zst") {
+            return Compression::Zstd;
+        }
+        #[cfg(not(any(feature = "gzip", feature = "zstd")))]
+        let _ = extension;
+        Compression::None
+    }
+
+    /// Compresses bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::codec::Compression;
+    ///
+    /// assert_eq!(Compression::None.encode(b"foo").unwrap(), b"foo");
+    /// ```
+    pub fn encode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = Encoder::new(Vec::new(), *self)?;
+        encoder.write_all(bytes)?;
+        encoder.finish()
+    }
+}
+
+impl<W: Write> Encoder<W> {
+    pub(crate) fn new(writer: W, compression: Compression) -> Result<Encoder<W>> {
+        match compression {
+            Compression::None => Ok(Encoder::None(writer)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Encoder::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            ))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Encoder::Zstd(zstd::Encoder::new(writer, 0)?)),
+        }
+    }
+
+    /// Returns the underlying writer, which holds the compressed bytes written so far.
+    #[cfg(feature = "object-store")]
+    pub(crate) fn get_mut(&mut self) -> &mut W {
+        match self {
+            Encoder::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.get_mut(),
+        }
+    }
+
+    /// Finishes compression, flushes, and returns the underlying writer.
+    pub(crate) fn finish(self) -> Result<W> {
+        // Without compression features, `None` is the only variant.
+        #[allow(clippy::infallible_destructuring_match)]
+        let mut writer = match self {
+            Encoder::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write> Debug for Encoder<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoder::None(_) => f.write_str("Encoder::None"),
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(_) => f.write_str("Encoder::Gzip"),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(_) => f.write_str("Encoder::Zstd"),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Encoder::None(writer) => writer.write(buf),
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Encoder::None(writer) => writer.flush(),
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn inner_format(ndjson: bool) -> Format {
+    if ndjson {
+        Format::ndjson()
+    } else {
+        Format::json()
+    }
+}
+
+#[cfg(all(test, any(feature = "gzip", feature = "zstd")))]
+mod tests {
+    use super::Compression;
+    use crate::{Format, Item, ItemCollection};
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip_roundtrip() {
+        let format: Format = "ndjson.gz".parse().unwrap();
+        let item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+        let bytes = format.into_vec(item_collection).unwrap();
+        assert_eq!(&bytes[..2], &[0x1f, 0x8b]);
+        let item_collection: ItemCollection = format.from_bytes(bytes).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip_path() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("item.json.gz");
+        let format = Format::infer_from_href(path.to_str().unwrap()).unwrap();
+        format.write(&path, Item::new("an-id")).unwrap();
+        let item: Item = format.read(path.to_str().unwrap()).unwrap();
+        assert_eq!(item.id, "an-id");
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn zstd_roundtrip() {
+        let format: Format = "ndjson.zst".parse().unwrap();
+        let item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+        let bytes = format.into_vec(item_collection).unwrap();
+        let item_collection: ItemCollection = format.from_bytes(bytes).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[test]
+    fn infer_from_href_ignores_case() {
+        #[cfg(feature = "gzip")]
+        assert_eq!(
+            Compression::infer_from_href("ITEMS.NDJSON.GZ"),
+            Compression::Gzip
+        );
+        #[cfg(feature = "zstd")]
+        assert_eq!(
+            Compression::infer_from_href("items.ndjson.Zst"),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::infer_from_href("items.ndjson"),
+            Compression::None
+        );
+        assert_eq!(Compression::infer_from_href("items"), Compression::None);
+    }
+}
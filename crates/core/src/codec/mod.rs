@@ -0,0 +1,249 @@
+//! Pluggable formats.
+//!
+//! A [Codec] converts between its own bytes and bytes in another format
+//! (JSON by default). Once it is registered with [register], it can be used
+//! everywhere a [Format] is, e.g. [Format::from_bytes] and
+//! [Format::put_opts].
+//!
+//! Some codecs are built in and registered by default:
+//!
+//! - `json.gz` and `ndjson.gz`: gzip-compressed JSON and NDJSON (requires the `gzip` feature)
+//! - `json.zst` and `ndjson.zst`: zstd-compressed JSON and NDJSON (requires the `zstd` feature)
+//! - `cbor`: [CBOR](https://cbor.io/) (requires the `cbor` feature)
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{codec::Codec, Format, Item, Result};
+//!
+//! #[derive(Debug)]
+//! struct Reversed;
+//!
+//! impl Codec for Reversed {
+//!     fn name(&self) -> &'static str {
+//!         "reversed"
+//!     }
+//!
+//!     fn extensions(&self) -> &'static [&'static str] {
+//!         &["nosj"]
+//!     }
+//!
+//!     fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+//!         Ok(bytes.iter().rev().copied().collect())
+//!     }
+//!
+//!     fn encode(&self, json: Vec<u8>) -> Result<Vec<u8>> {
+//!         Ok(json.into_iter().rev().collect())
+//!     }
+//! }
+//!
+//! stac::codec::register(Reversed).unwrap();
+//! let format = Format::infer_from_href("item.nosj").unwrap();
+//! assert_eq!(format, Format::Custom("reversed"));
+//! let bytes = format.into_vec(Item::new("an-id")).unwrap();
+//! let item: Item = format.from_bytes(bytes).unwrap();
+//! assert_eq!(item.id, "an-id");
+//! ```
+
+#[cfg(feature = "cbor")]
+mod cbor;
+mod compression;
+
+#[cfg(feature = "cbor")]
+pub use cbor::Cbor;
+pub use compression::Compression;
+pub(crate) use compression::Encoder;
+#[cfg(feature = "gzip")]
+pub use compression::Gzip;
+#[cfg(feature = "zstd")]
+pub use compression::Zstd;
+
+use crate::{Error, Format, Result};
+use std::{
+    fmt::Debug,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+static CODECS: LazyLock<RwLock<Vec<Arc<dyn Codec>>>> = LazyLock::new(|| {
+    #[allow(unused_mut)]
+    let mut codecs: Vec<Arc<dyn Codec>> = Vec::new();
+    #[cfg(feature = "gzip")]
+    {
+        codecs.push(Arc::new(Gzip::json()));
+        codecs.push(Arc::new(Gzip::ndjson()));
+    }
+    #[cfg(feature = "zstd")]
+    {
+        codecs.push(Arc::new(Zstd::json()));
+        codecs.push(Arc::new(Zstd::ndjson()));
+    }
+    #[cfg(feature = "cbor")]
+    codecs.push(Arc::new(Cbor));
+    RwLock::new(codecs)
+});
+
+/// Converts between a custom format and one of the built-in formats.
+pub trait Codec: Debug + Send + Sync + 'static {
+    /// Returns the name of this format.
+    ///
+    /// This is what's used to parse a [Format](crate::Format) from a string,
+    /// e.g. `stacrs translate --output-format <name>`.
+    fn name(&self) -> &'static str;
+
+    /// Returns the file extensions (without the leading dot) used to infer this format from an href.
+    ///
+    /// Multi-part extensions, e.g. `json.br`, are allowed.
+    fn extensions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the format of the decoded bytes.
+    ///
+    /// Defaults to compact JSON.
+    fn format(&self) -> Format {
+        Format::json()
+    }
+
+    /// Decodes bytes in this format into bytes in [Codec::format].
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>>;
+
+    /// Encodes bytes in [Codec::format] into this format.
+    fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>>;
+}
+
+/// Registers a codec.
+///
+/// If a codec with the same name, ignoring case, is already registered, it is
+/// replaced. Returns an error if the codec's name or one of its single-part
+/// extensions is a built-in format, e.g. `json`, since those can't be taken
+/// over by a codec.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{codec::Codec, Result};
+///
+/// #[derive(Debug)]
+/// struct Json;
+///
+/// impl Codec for Json {
+///     fn name(&self) -> &'static str {
+///         "JSON"
+///     }
+///
+///     fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+///         Ok(bytes.to_vec())
+///     }
+///
+///     fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+///         Ok(bytes)
+///     }
+/// }
+///
+/// stac::codec::register(Json).unwrap_err();
+/// ```
+pub fn register(codec: impl Codec) -> Result<()> {
+    if Format::from_built_in_str(codec.name()).is_some() {
+        return Err(Error::CodecConflict(codec.name().to_string()));
+    }
+    if let Some(extension) = codec.extensions().iter().find(|extension| {
+        !extension.contains('.') && Format::from_built_in_str(extension).is_some()
+    }) {
+        return Err(Error::CodecConflict(extension.to_string()));
+    }
+    let mut codecs = CODECS.write().unwrap_or_else(|err| err.into_inner());
+    codecs.retain(|c| !c.name().eq_ignore_ascii_case(codec.name()));
+    codecs.push(Arc::new(codec));
+    Ok(())
+}
+
+/// Returns the registered codec with the given name, if there is one.
+pub fn get(name: &str) -> Option<Arc<dyn Codec>> {
+    CODECS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .find(|codec| codec.name().eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+/// Returns the registered codec whose extension matches the end of this href.
+///
+/// Like [get], extensions are matched case-insensitively.
+pub(crate) fn infer_from_href(href: &str) -> Option<Arc<dyn Codec>> {
+    let href = href.to_lowercase();
+    CODECS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .find(|codec| {
+            codec.extensions().iter().any(|extension| {
+                href.strip_suffix(extension.to_lowercase().as_str())
+                    .map(|s| s.ends_with('.'))
+                    .unwrap_or_default()
+            })
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Codec;
+    use crate::{Error, Format, Result};
+
+    #[derive(Debug)]
+    struct Named(&'static str, &'static [&'static str]);
+
+    impl Codec for Named {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            self.1
+        }
+
+        fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+            Ok(bytes.to_vec())
+        }
+
+        fn encode(&self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+            Ok(bytes)
+        }
+    }
+
+    #[test]
+    fn replace_ignoring_case() {
+        super::register(Named("codec-test-replace", &["codec-test-replace-a"])).unwrap();
+        super::register(Named("CODEC-TEST-REPLACE", &["codec-test-replace-b"])).unwrap();
+        assert_eq!(
+            super::get("codec-test-replace").unwrap().name(),
+            "CODEC-TEST-REPLACE"
+        );
+        assert!(Format::infer_from_href("item.codec-test-replace-a").is_none());
+        assert_eq!(
+            Format::infer_from_href("item.codec-test-replace-b").unwrap(),
+            Format::Custom("CODEC-TEST-REPLACE")
+        );
+    }
+
+    #[test]
+    fn built_in_conflicts() {
+        for name in ["json", "NDJSON", "geojson-pretty", "geoparquet[snappy]"] {
+            assert!(matches!(
+                super::register(Named(name, &[])).unwrap_err(),
+                Error::CodecConflict(_)
+            ));
+        }
+        assert!(matches!(
+            super::register(Named("codec-test-conflict", &["JSON"])).unwrap_err(),
+            Error::CodecConflict(_)
+        ));
+        assert!(super::get("codec-test-conflict").is_none());
+        assert_eq!(
+            Format::infer_from_href("item.json").unwrap(),
+            Format::json()
+        );
+        super::register(Named("codec-test-multipart", &["multipart.json"])).unwrap();
+    }
+}
@@ -1,12 +1,18 @@
+//! STAC Collections.
+
 use crate::{
-    Asset, Assets, Bbox, Error, Href, Item, ItemAsset, Link, Links, Migrate, Result, SelfHref,
-    Version, STAC_VERSION,
+    Asset, Assets, Bbox, Error, Fields, Href, Item, ItemAsset, Link, Links, Migrate, Result,
+    SelfHref, Version, STAC_VERSION,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Fields, Links, SelfHref};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
 const DEFAULT_LICENSE: &str = "other";
 
@@ -82,6 +88,10 @@ pub struct Collection {
     /// `Collection`'s license(s), either a SPDX [License
     /// identifier](https://spdx.org/licenses/), `"various"` if multiple licenses
     /// apply or `"proprietary"` for all other cases.
+    ///
+    /// As of STAC v1.1.0, this can be an SPDX license expression or `"other"`,
+    /// and `"various"` and `"proprietary"` are deprecated. Use
+    /// [license::is_valid](crate::license::is_valid) to check a value.
     #[serde(default)]
     pub license: String,
 
@@ -143,7 +153,9 @@ pub struct Provider {
 
     /// Roles of the provider.
     ///
-    /// Any of `"licensor"`, `"producer"`, `"processor"`, or `"host"`.
+    /// Any of `"licensor"`, `"producer"`, `"processor"`, or `"host"`. Use
+    /// [Provider::role] and [Provider::has_role] to work with these as
+    /// [Role]s.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<String>>,
 
@@ -156,6 +168,25 @@ pub struct Provider {
     pub additional_fields: Map<String, Value>,
 }
 
+/// The role of a [Provider].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// The organization that is licensing the dataset under the license
+    /// specified in the collection's `license` field.
+    Licensor,
+
+    /// The provider that initially captured and processed the source data,
+    /// e.g. ESA for Sentinel-2 data.
+    Producer,
+
+    /// A provider that processed data to a derived product.
+    Processor,
+
+    /// The provider offering the data on their storage.
+    Host,
+}
+
 /// The object describes the spatio-temporal extents of the [Collection](crate::Collection).
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Extent {
@@ -183,7 +214,263 @@ pub struct TemporalExtent {
     pub interval: Vec<[Option<DateTime<Utc>>; 2]>,
 }
 
+/// Builder for a STAC Collection.
+///
+/// Unlike setting a [Collection]'s public fields directly, the builder checks
+/// the collection's extents when [Builder::build] is called.
+#[derive(Debug)]
+pub struct Builder {
+    id: String,
+    description: String,
+    title: Option<String>,
+    license: Option<String>,
+    keywords: Vec<String>,
+    providers: Vec<Provider>,
+    bboxes: Vec<Bbox>,
+    intervals: Vec<[Option<DateTime<Utc>>; 2]>,
+    extensions: Vec<String>,
+    assets: HashMap<String, Asset>,
+    additional_fields: Map<String, Value>,
+}
+
+impl Builder {
+    /// Creates a new builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description");
+    /// ```
+    pub fn new(id: impl ToString, description: impl ToString) -> Builder {
+        Builder {
+            id: id.to_string(),
+            description: description.to_string(),
+            title: None,
+            license: None,
+            keywords: Vec::new(),
+            providers: Vec::new(),
+            bboxes: Vec::new(),
+            intervals: Vec::new(),
+            extensions: Vec::new(),
+            assets: HashMap::new(),
+            additional_fields: Map::new(),
+        }
+    }
+
+    /// Sets the title.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description").title("A title");
+    /// ```
+    pub fn title(mut self, title: impl ToString) -> Builder {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the license.
+    ///
+    /// Defaults to `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description").license("CC-BY-4.0");
+    /// ```
+    pub fn license(mut self, license: impl ToString) -> Builder {
+        self.license = Some(license.to_string());
+        self
+    }
+
+    /// Adds a keyword.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description").keyword("satellite");
+    /// ```
+    pub fn keyword(mut self, keyword: impl ToString) -> Builder {
+        self.keywords.push(keyword.to_string());
+        self
+    }
+
+    /// Adds a provider.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Builder, Provider};
+    /// let builder = Builder::new("an-id", "a description").provider(Provider::new("a-name"));
+    /// ```
+    pub fn provider(mut self, provider: Provider) -> Builder {
+        self.providers.push(provider);
+        self
+    }
+
+    /// Adds a spatial extent bbox.
+    ///
+    /// If no bboxes are added, the spatial extent is global.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Builder, Bbox};
+    /// let builder = Builder::new("an-id", "a description").bbox(Bbox::new(-106., 41., -105., 42.));
+    /// ```
+    pub fn bbox(mut self, bbox: impl Into<Bbox>) -> Builder {
+        self.bboxes.push(bbox.into());
+        self
+    }
+
+    /// Adds a temporal extent interval.
+    ///
+    /// Use `None` for an open start or end. If no intervals are added, the
+    /// temporal extent is fully open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description")
+    ///     .interval("2024-01-01T00:00:00Z".parse().ok(), None);
+    /// ```
+    pub fn interval(mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Builder {
+        self.intervals.push([start, end]);
+        self
+    }
+
+    /// Adds an extension identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description")
+    ///     .extension("https://stac-extensions.github.io/projection/v2.0.0/schema.json");
+    /// ```
+    pub fn extension(mut self, identifier: impl ToString) -> Builder {
+        let identifier = identifier.to_string();
+        if !self.extensions.contains(&identifier) {
+            self.extensions.push(identifier);
+        }
+        self
+    }
+
+    /// Adds an asset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let builder = Builder::new("an-id", "a description").asset("thumbnail", "thumbnail.png");
+    /// ```
+    pub fn asset(mut self, key: impl ToString, asset: impl Into<Asset>) -> Builder {
+        let _ = self.assets.insert(key.to_string(), asset.into());
+        self
+    }
+
+    /// Sets an additional field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Builder;
+    /// let collection = Builder::new("an-id", "a description")
+    ///     .field("sci:doi", "10.5061/dryad.s2v81.2")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(collection.additional_fields["sci:doi"], "10.5061/dryad.s2v81.2");
+    /// ```
+    pub fn field(mut self, key: impl ToString, value: impl Into<Value>) -> Builder {
+        let _ = self.additional_fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Builds a [Collection] from this builder.
+    ///
+    /// Returns an error if any bbox is invalid, or if any interval's start is
+    /// after its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Builder, Bbox};
+    ///
+    /// let collection = Builder::new("an-id", "a description")
+    ///     .bbox(Bbox::new(-106., 41., -105., 42.))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(collection.extent.spatial.bbox.len(), 1);
+    /// ```
+    pub fn build(self) -> Result<Collection> {
+        for bbox in &self.bboxes {
+            if !bbox.is_valid() {
+                return Err(Error::InvalidBbox((*bbox).into()));
+            }
+        }
+        for [start, end] in &self.intervals {
+            if let (Some(start), Some(end)) = (start, end) {
+                if start > end {
+                    return Err(Error::InvalidDatetime(format!("{}/{}", start, end)));
+                }
+            }
+        }
+        let mut collection = Collection::new(self.id, self.description);
+        collection.title = self.title;
+        if let Some(license) = self.license {
+            collection.license = license;
+        }
+        if !self.keywords.is_empty() {
+            collection.keywords = Some(self.keywords);
+        }
+        if !self.providers.is_empty() {
+            collection.providers = Some(self.providers);
+        }
+        if !self.bboxes.is_empty() {
+            collection.extent.spatial.bbox = self.bboxes;
+        }
+        if !self.intervals.is_empty() {
+            collection.extent.temporal.interval = self.intervals;
+        }
+        collection.extensions = self.extensions;
+        collection.assets = self.assets;
+        collection.additional_fields = self.additional_fields;
+        Ok(collection)
+    }
+}
+
+impl Fields for Builder {
+    fn fields(&self) -> &Map<String, Value> {
+        &self.additional_fields
+    }
+    fn fields_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.additional_fields
+    }
+}
+
 impl Collection {
+    /// Returns a [Builder] for a collection with the given `id` and `description`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    ///
+    /// let collection = Collection::builder("an-id", "a description")
+    ///     .title("A title")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(collection.title.unwrap(), "A title");
+    /// ```
+    pub fn builder(id: impl ToString, description: impl ToString) -> Builder {
+        Builder::new(id, description)
+    }
+
     /// Creates a new `Collection` with the given `id`.
     ///
     /// # Examples
@@ -284,6 +571,80 @@ impl Collection {
         }
     }
 
+    /// Adds a provider to this collection.
+    ///
+    /// If the collection already has a provider with the same name, the new
+    /// provider's roles are added to the existing provider's instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Role, Collection, Provider};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.add_provider(Provider::new("a-name").role(Role::Producer));
+    /// collection.add_provider(Provider::new("a-name").role(Role::Host));
+    /// let providers = collection.providers.unwrap();
+    /// assert_eq!(providers.len(), 1);
+    /// assert!(providers[0].has_role(Role::Host));
+    /// ```
+    pub fn add_provider(&mut self, provider: Provider) {
+        let providers = self.providers.get_or_insert_with(Vec::new);
+        if let Some(existing) = providers.iter_mut().find(|p| p.name == provider.name) {
+            for role in provider.roles.into_iter().flatten() {
+                let roles = existing.roles.get_or_insert_with(Vec::new);
+                if !roles.contains(&role) {
+                    roles.push(role);
+                }
+            }
+        } else {
+            providers.push(provider);
+        }
+    }
+
+    /// Returns an iterator over this collection's providers with the given role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Role, Collection, Provider};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.add_provider(Provider::new("a-name").role(Role::Licensor));
+    /// assert_eq!(collection.providers_with_role(Role::Licensor).count(), 1);
+    /// assert_eq!(collection.providers_with_role(Role::Host).count(), 0);
+    /// ```
+    pub fn providers_with_role(&self, role: Role) -> impl Iterator<Item = &Provider> {
+        self.providers
+            .iter()
+            .flatten()
+            .filter(move |provider| provider.has_role(role))
+    }
+
+    /// Adds a `license` link pointing to the SPDX page for this collection's license.
+    ///
+    /// Does nothing if this collection already has a `license` link, or if
+    /// the license isn't a single SPDX identifier (see [crate::license::url]).
+    /// Returns a reference to the `license` link, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.license = "MIT".to_string();
+    /// let link = collection.add_license_link().unwrap();
+    /// assert_eq!(link.href, "https://spdx.org/licenses/MIT.html");
+    /// ```
+    pub fn add_license_link(&mut self) -> Option<&Link> {
+        if !self.links.iter().any(Link::is_license) {
+            let url = crate::license::url(&self.license)?;
+            self.links.push(Link::license(url));
+        }
+        self.links.iter().find(|link| link.is_license())
+    }
+
     /// Adds an item to this collection.
     ///
     /// This method does a couple of things:
@@ -311,6 +672,60 @@ impl Collection {
         self.update_extents(item);
         self.maybe_add_item_link(item)
     }
+
+    /// Moves an item asset definition into this collection's assets, with the given href.
+    ///
+    /// Returns false if there's no item asset with that key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, ItemAsset};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// let item_asset: ItemAsset = serde_json::from_value(serde_json::json!({
+    ///     "title": "Thumbnail",
+    ///     "type": "image/png",
+    /// })).unwrap();
+    /// collection.item_assets.insert("thumbnail".to_string(), item_asset);
+    /// assert!(collection.promote_item_asset("thumbnail", "./thumbnail.png").unwrap());
+    /// assert_eq!(collection.assets["thumbnail"].href, "./thumbnail.png");
+    /// assert!(collection.item_assets.is_empty());
+    /// ```
+    pub fn promote_item_asset(&mut self, key: &str, href: impl ToString) -> Result<bool> {
+        if let Some(item_asset) = self.item_assets.remove(key) {
+            let asset = item_asset.into_asset(href)?;
+            let _ = self.assets.insert(key.to_string(), asset);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Moves one of this collection's assets into its item asset definitions, dropping its href.
+    ///
+    /// Returns false if there's no asset with that key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Collection};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.assets.insert("data".to_string(), Asset::new("./data.tif"));
+    /// assert!(collection.demote_asset("data").unwrap());
+    /// assert!(collection.item_assets.contains_key("data"));
+    /// assert!(collection.assets.is_empty());
+    /// ```
+    pub fn demote_asset(&mut self, key: &str) -> Result<bool> {
+        if let Some(asset) = self.assets.remove(key) {
+            let item_asset = ItemAsset::from_asset(asset)?;
+            let _ = self.item_assets.insert(key.to_string(), item_asset);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
 }
 
 impl Provider {
@@ -332,6 +747,100 @@ impl Provider {
             additional_fields: Map::new(),
         }
     }
+
+    /// Adds a role to this provider, if it doesn't already have it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Role, Provider};
+    /// let provider = Provider::new("a-name").role(Role::Producer).role(Role::Processor);
+    /// assert_eq!(provider.roles.unwrap(), ["producer", "processor"]);
+    /// ```
+    pub fn role(mut self, role: Role) -> Provider {
+        if !self.has_role(role) {
+            self.roles
+                .get_or_insert_with(Vec::new)
+                .push(role.to_string());
+        }
+        self
+    }
+
+    /// Returns true if this provider has the given role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{collection::Role, Provider};
+    /// let provider = Provider::new("a-name").role(Role::Host);
+    /// assert!(provider.has_role(Role::Host));
+    /// assert!(!provider.has_role(Role::Licensor));
+    /// ```
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles
+            .iter()
+            .flatten()
+            .any(|r| r.as_str() == role.as_str())
+    }
+
+    /// Returns the roles of this provider that aren't one of the [Role]s
+    /// defined by the STAC specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Provider;
+    /// let mut provider = Provider::new("a-name");
+    /// provider.roles = Some(vec!["host".to_string(), "hoster".to_string()]);
+    /// assert_eq!(provider.unknown_roles(), ["hoster"]);
+    /// ```
+    pub fn unknown_roles(&self) -> Vec<&str> {
+        self.roles
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|role| role.parse::<Role>().is_err())
+            .collect()
+    }
+}
+
+impl Role {
+    /// Returns this role as a string, e.g. `licensor`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::collection::Role;
+    /// assert_eq!(Role::Licensor.as_str(), "licensor");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Licensor => "licensor",
+            Role::Producer => "producer",
+            Role::Processor => "processor",
+            Role::Host => "host",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Role> {
+        match s {
+            "licensor" => Ok(Role::Licensor),
+            "producer" => Ok(Role::Producer),
+            "processor" => Ok(Role::Processor),
+            "host" => Ok(Role::Host),
+            _ => Err(Error::UnknownProviderRole(s.to_string())),
+        }
+    }
 }
 
 impl Default for SpatialExtent {
@@ -474,10 +983,25 @@ mod tests {
             let link = collection.link("item").unwrap();
             assert!(link.href.to_string().ends_with("simple-item.json"));
         }
+
+        #[test]
+        fn add_license_link() {
+            let mut collection = Collection::new("an-id", "a description");
+            assert!(collection.add_license_link().is_none());
+            collection.license = "CC-BY-4.0".to_string();
+            let _ = collection.add_license_link().unwrap();
+            let _ = collection.add_license_link().unwrap();
+            assert_eq!(collection.links.len(), 1);
+            assert_eq!(
+                collection.links[0].href,
+                "https://spdx.org/licenses/CC-BY-4.0.html"
+            );
+        }
     }
 
     mod provider {
         use super::Provider;
+        use crate::collection::Role;
 
         #[test]
         fn new() {
@@ -497,6 +1021,52 @@ mod tests {
             assert!(value.get("roles").is_none());
             assert!(value.get("url").is_none());
         }
+
+        #[test]
+        fn roles() {
+            let provider = Provider::new("a-name").role(Role::Host).role(Role::Host);
+            assert_eq!(provider.roles.as_ref().unwrap(), &["host"]);
+            assert!(provider.unknown_roles().is_empty());
+            assert_eq!("licensor".parse::<Role>().unwrap(), Role::Licensor);
+            let _ = "owner".parse::<Role>().unwrap_err();
+        }
+    }
+
+    mod builder {
+        use super::Collection;
+        use crate::Bbox;
+
+        #[test]
+        fn defaults() {
+            let collection = Collection::builder("an-id", "a description")
+                .build()
+                .unwrap();
+            assert_eq!(collection, Collection::new("an-id", "a description"));
+        }
+
+        #[test]
+        fn extents() {
+            let start = "2024-01-01T00:00:00Z".parse().unwrap();
+            let end = "2024-01-02T00:00:00Z".parse().unwrap();
+            let collection = Collection::builder("an-id", "a description")
+                .bbox(Bbox::new(1., 2., 3., 4.))
+                .interval(Some(start), Some(end))
+                .build()
+                .unwrap();
+            assert_eq!(collection.extent.spatial.bbox, [Bbox::new(1., 2., 3., 4.)]);
+            assert_eq!(
+                collection.extent.temporal.interval,
+                [[Some(start), Some(end)]]
+            );
+            let _ = Collection::builder("an-id", "a description")
+                .interval(Some(end), Some(start))
+                .build()
+                .unwrap_err();
+            let _ = Collection::builder("an-id", "a description")
+                .bbox(Bbox::new(1., 4., 3., 2.))
+                .build()
+                .unwrap_err();
+        }
     }
 
     mod extent {
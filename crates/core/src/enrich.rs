@@ -0,0 +1,414 @@
+//! Add sizes and content types to assets without downloading them.
+//!
+//! An [Enricher] looks up each asset's size and content type, with a `HEAD`
+//! request for urls or file metadata for local paths, and records them in
+//! the asset's `file:size` and `type` fields:
+//!
+//! ```
+//! use stac::{enrich::Enricher, Asset, Item};
+//!
+//! let mut item = Item::new("an-id");
+//! let _ = item.assets.insert("data".to_string(), Asset::new("examples/simple-item.json"));
+//! # tokio_test::block_on(async {
+//! let count = Enricher::new().concurrency(4).enrich_item(&mut item).await.unwrap();
+//! assert_eq!(count, 1);
+//! assert!(item.assets["data"].additional_fields.contains_key("file:size"));
+//! # });
+//! ```
+
+use crate::{Href, Item, RealizedHref, Result, SelfHref};
+use futures::StreamExt;
+use object_store::ObjectStore;
+use serde_json::Value;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use url::{Position, Url};
+
+/// The schema url of the file extension, which defines `file:size`.
+pub const FILE_EXTENSION_SCHEMA_URL: &str =
+    "https://stac-extensions.github.io/file/v2.1.0/schema.json";
+
+/// The default number of assets that are looked up at the same time.
+pub const DEFAULT_CONCURRENCY: usize = 16;
+
+/// Everything in [FILE_EXTENSION_SCHEMA_URL] up until the version, so any version of the extension matches.
+const FILE_EXTENSION_IDENTIFIER_PREFIX: &str = "https://stac-extensions.github.io/file/";
+
+const FILE_SIZE: &str = "file:size";
+
+/// The size and content type of an object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stat {
+    /// The object's size, in bytes.
+    pub size: u64,
+
+    /// The object's content type, if it's known.
+    ///
+    /// This is only available for objects in object stores or on the web.
+    pub content_type: Option<String>,
+}
+
+/// Adds sizes and content types to assets.
+#[derive(Clone, Debug)]
+pub struct Enricher {
+    options: Vec<(String, String)>,
+    concurrency: usize,
+    overwrite: bool,
+}
+
+impl Enricher {
+    /// Creates a new enricher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::enrich::Enricher;
+    ///
+    /// let enricher = Enricher::new();
+    /// ```
+    pub fn new() -> Enricher {
+        Enricher::default()
+    }
+
+    /// Sets the options used to build object stores, e.g. credentials.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::enrich::Enricher;
+    ///
+    /// let enricher = Enricher::new().options([("aws_region", "us-west-2")]);
+    /// ```
+    pub fn options<I, K, V>(mut self, options: I) -> Enricher
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        self.options = options
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value.into()))
+            .collect();
+        self
+    }
+
+    /// Sets the maximum number of assets that are looked up at the same time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::enrich::Enricher;
+    ///
+    /// let enricher = Enricher::new().concurrency(4);
+    /// ```
+    pub fn concurrency(mut self, concurrency: usize) -> Enricher {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets whether existing sizes and content types are replaced.
+    ///
+    /// By default, only assets that are missing a size or a content type are looked up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::enrich::Enricher;
+    ///
+    /// let enricher = Enricher::new().overwrite(true);
+    /// ```
+    pub fn overwrite(mut self, overwrite: bool) -> Enricher {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Enriches an item's assets, returning the number of assets that were changed.
+    ///
+    /// Relative asset hrefs are resolved against the item's self href. Assets
+    /// that can't be looked up are logged and skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{enrich::Enricher, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// # tokio_test::block_on(async {
+    /// let count = Enricher::new().enrich_item(&mut item).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn enrich_item(&self, item: &mut Item) -> Result<usize> {
+        self.enrich_items(std::slice::from_mut(item)).await
+    }
+
+    /// Enriches the assets of many items, returning the number of assets that were changed.
+    ///
+    /// The concurrency limit applies across all of the items. One object store
+    /// is built for each scheme and bucket (or host), and shared by all of the
+    /// assets in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{enrich::Enricher, Item};
+    ///
+    /// let mut items = vec![Item::new("a"), Item::new("b")];
+    /// # tokio_test::block_on(async {
+    /// let count = Enricher::new().enrich_items(&mut items).await.unwrap();
+    /// assert_eq!(count, 0);
+    /// # });
+    /// ```
+    pub async fn enrich_items(&self, items: &mut [Item]) -> Result<usize> {
+        let mut lookups = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            for (key, asset) in &item.assets {
+                if self.overwrite
+                    || asset.r#type.is_none()
+                    || !asset.additional_fields.contains_key(FILE_SIZE)
+                {
+                    let href = match item.self_href() {
+                        Some(base) => base.join(asset.href.as_str())?,
+                        None => Href::from(asset.href.as_str()),
+                    };
+                    lookups.push((index, key.clone(), href));
+                }
+            }
+        }
+        let mut object_stores: HashMap<String, Arc<dyn ObjectStore>> = HashMap::new();
+        let mut targets = Vec::with_capacity(lookups.len());
+        for (index, key, href) in lookups {
+            let target = match href.clone().realize() {
+                RealizedHref::PathBuf(path) => Target::Path(path),
+                RealizedHref::Url(url) => {
+                    let (scheme, path) = match object_store::ObjectStoreScheme::parse(&url) {
+                        Ok(parsed) => parsed,
+                        Err(err) => {
+                            tracing::warn!("could not look up asset {key} ({href}): {err}");
+                            continue;
+                        }
+                    };
+                    let store_key = format!("{:?}:{}", scheme, &url[..Position::BeforePath]);
+                    let object_store = match object_stores.get(&store_key) {
+                        Some(object_store) => object_store.clone(),
+                        None => match crate::io::parse_url_opts(&url, self.options.clone()).await {
+                            Ok((object_store, _)) => object_stores
+                                .entry(store_key)
+                                .or_insert(Arc::from(object_store))
+                                .clone(),
+                            Err(err) => {
+                                tracing::warn!("could not look up asset {key} ({href}): {err}");
+                                continue;
+                            }
+                        },
+                    };
+                    Target::Object(object_store, path, url)
+                }
+            };
+            targets.push((index, key, href, target));
+        }
+        let stats: Vec<_> = futures::stream::iter(targets)
+            .map(|(index, key, href, target)| async move {
+                let result = match target {
+                    Target::Path(path) => stat_path(&path),
+                    Target::Object(object_store, path, url) => {
+                        stat_object(object_store.as_ref(), &path, &url).await
+                    }
+                };
+                (index, key, href, result)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+        let mut count = 0;
+        for (index, key, href, result) in stats {
+            let stat = match result {
+                Ok(stat) => stat,
+                Err(err) => {
+                    tracing::warn!("could not look up asset {key} ({href}): {err}");
+                    continue;
+                }
+            };
+            let item = &mut items[index];
+            let Some(asset) = item.assets.get_mut(&key) else {
+                continue;
+            };
+            let mut changed = false;
+            if self.overwrite || !asset.additional_fields.contains_key(FILE_SIZE) {
+                let size = Value::from(stat.size);
+                changed |= asset.additional_fields.get(FILE_SIZE) != Some(&size);
+                let _ = asset.additional_fields.insert(FILE_SIZE.to_string(), size);
+            }
+            if let Some(content_type) = stat.content_type {
+                if self.overwrite || asset.r#type.is_none() {
+                    changed |= asset.r#type.as_ref() != Some(&content_type);
+                    asset.r#type = Some(content_type);
+                }
+            }
+            if changed {
+                count += 1;
+            }
+            if asset.additional_fields.contains_key(FILE_SIZE)
+                && !item
+                    .extensions
+                    .iter()
+                    .any(|extension| extension.starts_with(FILE_EXTENSION_IDENTIFIER_PREFIX))
+            {
+                item.extensions.push(FILE_EXTENSION_SCHEMA_URL.to_string());
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl Default for Enricher {
+    fn default() -> Enricher {
+        Enricher {
+            options: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            overwrite: false,
+        }
+    }
+}
+
+/// Looks up the size and content type of an object without downloading it.
+///
+/// Urls are looked up with a `HEAD` request. If the server doesn't support
+/// those, the first byte is requested instead and the size is read from the
+/// `Content-Range` header. Local paths are looked up with their file metadata.
+///
+/// # Examples
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// let options: [(&str, &str); 0] = [];
+/// let stat = stac::enrich::stat_opts("examples/simple-item.json", options).await.unwrap();
+/// assert!(stat.size > 0);
+/// # });
+/// ```
+pub async fn stat_opts<I, K, V>(href: impl Into<Href>, options: I) -> Result<Stat>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    match href.into().realize() {
+        RealizedHref::PathBuf(path) => stat_path(&path),
+        RealizedHref::Url(url) => {
+            let options = options
+                .into_iter()
+                .map(|(key, value)| (key.as_ref().to_string(), value.into()))
+                .collect();
+            let (object_store, path) = crate::io::parse_url_opts(&url, options).await?;
+            stat_object(object_store.as_ref(), &path, &url).await
+        }
+    }
+}
+
+/// Where an asset is looked up.
+enum Target {
+    Path(PathBuf),
+    Object(Arc<dyn ObjectStore>, object_store::path::Path, Url),
+}
+
+fn stat_path(path: &std::path::Path) -> Result<Stat> {
+    Ok(Stat {
+        size: std::fs::metadata(path)?.len(),
+        content_type: None,
+    })
+}
+
+async fn stat_object(
+    object_store: &dyn ObjectStore,
+    path: &object_store::path::Path,
+    url: &Url,
+) -> Result<Stat> {
+    use object_store::{Attribute, GetOptions, GetRange};
+
+    let get_options = GetOptions {
+        head: true,
+        ..Default::default()
+    };
+    let get_result = match object_store.get_opts(path, get_options).await {
+        Ok(get_result) => get_result,
+        Err(err @ object_store::Error::NotFound { .. }) => return Err(err.into()),
+        Err(err) => {
+            tracing::debug!("HEAD {url} failed ({err}), requesting the first byte");
+            let get_options = GetOptions {
+                range: Some(GetRange::Bounded(0..1)),
+                ..Default::default()
+            };
+            object_store.get_opts(path, get_options).await?
+        }
+    };
+    Ok(Stat {
+        size: u64::try_from(get_result.meta.size)?,
+        content_type: get_result
+            .attributes
+            .get(&Attribute::ContentType)
+            .map(|value| value.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Enricher, FILE_EXTENSION_SCHEMA_URL};
+    use crate::{Asset, Item, SelfHref};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn enrich_local_assets() {
+        let tempdir = TempDir::new().unwrap();
+        std::fs::write(tempdir.path().join("data.tif"), [0u8; 42]).unwrap();
+        let mut item = Item::new("an-id");
+        *item.self_href_mut() = Some(tempdir.path().join("item.json").into());
+        let _ = item
+            .assets
+            .insert("data".to_string(), Asset::new("./data.tif"));
+        let _ = item
+            .assets
+            .insert("missing".to_string(), Asset::new("./missing.tif"));
+        let count = Enricher::new().enrich_item(&mut item).await.unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(item.assets["data"].additional_fields["file:size"], 42);
+        assert!(!item.assets["missing"]
+            .additional_fields
+            .contains_key("file:size"));
+        assert!(item
+            .extensions
+            .contains(&FILE_EXTENSION_SCHEMA_URL.to_string()));
+
+        let count = Enricher::new().enrich_item(&mut item).await.unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(item.extensions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn enrich_keeps_file_extension_version() {
+        let tempdir = TempDir::new().unwrap();
+        std::fs::write(tempdir.path().join("data.tif"), [0u8; 42]).unwrap();
+        let mut item = Item::new("an-id");
+        *item.self_href_mut() = Some(tempdir.path().join("item.json").into());
+        item.extensions
+            .push("https://stac-extensions.github.io/file/v2.0.0/schema.json".to_string());
+        let _ = item
+            .assets
+            .insert("data".to_string(), Asset::new("./data.tif"));
+        let count = Enricher::new().enrich_item(&mut item).await.unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            item.extensions,
+            vec!["https://stac-extensions.github.io/file/v2.0.0/schema.json"]
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn stat_url() {
+        let tempdir = TempDir::new().unwrap();
+        let path = tempdir.path().join("data.tif");
+        std::fs::write(&path, [0u8; 42]).unwrap();
+        let url = url::Url::from_file_path(&path).unwrap();
+        let options: [(&str, &str); 0] = [];
+        let stat = super::stat_opts(url, options).await.unwrap();
+        assert_eq!(stat.size, 42);
+    }
+}
@@ -10,10 +10,38 @@ pub enum Error {
     #[cfg(feature = "geoarrow")]
     Arrow(#[from] arrow_schema::ArrowError),
 
+    /// An item's bbox does not match the bounds of its geometry.
+    #[error("bbox {bbox:?} does not match the bounds of the geometry ({expected:?})")]
+    BboxMismatch {
+        /// The item's bbox.
+        bbox: Box<Option<crate::Bbox>>,
+
+        /// The bounds of the item's geometry.
+        expected: Box<Option<crate::Bbox>>,
+    },
+
     /// [chrono::ParseError]
     #[error(transparent)]
     ChronoParse(#[from] chrono::ParseError),
 
+    /// A codec's name or extension is one of the built-in formats.
+    #[error("codec conflicts with a built-in format: {0}")]
+    CodecConflict(String),
+
+    /// Object store credentials couldn't be resolved, e.g. because of a missing AWS profile.
+    #[error("credentials: {0}")]
+    Credentials(String),
+
+    /// [ciborium::de::Error]
+    #[error(transparent)]
+    #[cfg(feature = "cbor")]
+    CiboriumDe(#[from] ciborium::de::Error<std::io::Error>),
+
+    /// [ciborium::ser::Error]
+    #[error(transparent)]
+    #[cfg(feature = "cbor")]
+    CiboriumSer(#[from] ciborium::ser::Error<std::io::Error>),
+
     /// A required feature is not enabled.
     #[error("{0} is not enabled")]
     FeatureNotEnabled(&'static str),
@@ -43,10 +71,34 @@ pub enum Error {
     #[error(transparent)]
     Geojson(#[from] Box<geojson::Error>),
 
+    /// [geozero::error::GeozeroError]
+    #[error(transparent)]
+    #[cfg(feature = "geo")]
+    Geozero(#[from] geozero::error::GeozeroError),
+
     /// [std::io::Error]
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    /// Returned when two stac-geoparquet files with different base hrefs can't be merged.
+    #[cfg(feature = "geoparquet")]
+    #[error("incompatible base hrefs: {0} and {1}")]
+    IncompatibleBaseHrefs(String, String),
+
+    /// Returned when two parquet columns with the same name can't be merged.
+    #[cfg(feature = "geoparquet")]
+    #[error("incompatible types for column {column}: {existing} and {new}")]
+    IncompatibleColumnTypes {
+        /// The column name.
+        column: String,
+
+        /// The column's type in the existing data.
+        existing: String,
+
+        /// The column's type in the new data.
+        new: String,
+    },
+
     /// Returned when a STAC object has the wrong type field.
     #[error("incorrect type: expected={expected}, actual={actual}")]
     IncorrectType {
@@ -62,6 +114,30 @@ pub enum Error {
     #[error("invalid attribute name: {0}")]
     InvalidAttribute(String),
 
+    /// Returned when a field's value can't be deserialized.
+    #[error("invalid value for field {key}: {source}")]
+    InvalidField {
+        /// The field's key, including any prefix.
+        key: String,
+
+        /// The underlying deserialization error.
+        source: serde_json::Error,
+    },
+
+    /// A JSON pointer is malformed, or points at a field that isn't an additional field.
+    #[error("invalid JSON pointer: {0}")]
+    InvalidPointer(String),
+
+    /// An io option has an invalid value.
+    #[error("invalid value for option {key}: {value}")]
+    InvalidOption {
+        /// The option's key.
+        key: &'static str,
+
+        /// The invalid value.
+        value: String,
+    },
+
     /// This vector is not a valid bounding box.
     #[error("invalid bbox: {0:?}")]
     InvalidBbox(Vec<f64>),
@@ -70,6 +146,23 @@ pub enum Error {
     #[error("invalid datetime: {0}")]
     InvalidDatetime(String),
 
+    /// Returned when a [geoparquet Order](crate::geoparquet::Order) can't be parsed.
+    #[error("invalid order, expected datetime or hilbert: {0}")]
+    #[cfg(feature = "geoparquet")]
+    InvalidOrder(String),
+
+    /// Returned when a [SplitBy](crate::split::SplitBy) can't be parsed.
+    #[error("invalid split: {0}")]
+    InvalidSplit(String),
+
+    /// Returned when a [rewrite Rule](crate::rewrite::Rule) can't be parsed.
+    #[error("invalid rewrite rule, expected FROM=TO: {0}")]
+    InvalidRewrite(String),
+
+    /// Returned when JSON can't be deserialized, with where it went wrong.
+    #[error(transparent)]
+    Json(Box<JsonError>),
+
     /// Returned when there is not a required field on a STAC object
     #[error("no \"{0}\" field in the JSON object")]
     MissingField(&'static str),
@@ -101,6 +194,11 @@ pub enum Error {
     #[cfg(feature = "geoparquet")]
     Parquet(#[from] parquet::errors::ParquetError),
 
+    /// [regex::Error]
+    #[error(transparent)]
+    #[cfg(feature = "regex")]
+    Regex(#[from] regex::Error),
+
     /// [reqwest::Error]
     #[cfg(feature = "reqwest")]
     #[error(transparent)]
@@ -114,6 +212,11 @@ pub enum Error {
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 
+    /// [simd_json::Error]
+    #[error(transparent)]
+    #[cfg(feature = "simd-json")]
+    SimdJson(#[from] simd_json::Error),
+
     /// [tokio::task::JoinError]
     #[error(transparent)]
     #[cfg(feature = "object-store")]
@@ -123,6 +226,10 @@ pub enum Error {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// Returned when a [provider Role](crate::collection::Role) can't be parsed.
+    #[error("unknown provider role: {0}")]
+    UnknownProviderRole(String),
+
     /// Returned when the `type` field of a STAC object does not equal `"Feature"`, `"Catalog"`, or `"Collection"`.
     #[error("unknown \"type\": {0}")]
     UnknownType(String),
@@ -151,7 +258,142 @@ pub enum Error {
     /// [jsonschema::ValidationError]
     #[cfg(feature = "validate")]
     #[error(transparent)]
-    JsonschemaValidation(#[from] jsonschema::ValidationError<'static>),
+    JsonschemaValidation(#[from] Box<jsonschema::ValidationError<'static>>),
+}
+
+/// A JSON deserialization error, with the href, JSON pointer, and position of the problem.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Error, FromJson, Item};
+///
+/// let err = Item::from_json_slice(br#"{"type": "Feature", "id": 42}"#).unwrap_err();
+/// let Error::Json(err) = err else { panic!() };
+/// assert_eq!(err.pointer(), "/id");
+/// assert_eq!(err.line(), 1);
+/// ```
+#[derive(Debug)]
+pub struct JsonError {
+    href: Option<String>,
+    pointer: String,
+    line: usize,
+    column: usize,
+    message: String,
+    source: serde_json::Error,
+}
+
+impl JsonError {
+    pub(crate) fn new(source: serde_json::Error, pointer: String) -> JsonError {
+        let (line, column) = (source.line(), source.column());
+        let message = source.to_string();
+        let message = message
+            .strip_suffix(&format!(" at line {line} column {column}"))
+            .unwrap_or(&message)
+            .to_string();
+        JsonError {
+            href: None,
+            pointer,
+            line,
+            column,
+            message,
+            source,
+        }
+    }
+
+    /// Returns the href of the JSON that couldn't be deserialized, if it's known.
+    pub fn href(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+
+    /// Returns the [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901)
+    /// to the value that couldn't be deserialized, e.g. `/properties/datetime`.
+    ///
+    /// The pointer is empty if the problem is with the whole document, e.g. it's truncated.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Returns the one-based line of the problem.
+    ///
+    /// For newline-delimited JSON, this is the line in the whole file.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the one-based column of the problem.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(href) = self.href.as_ref() {
+            write!(f, "{href}:")?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        if !self.pointer.is_empty() {
+            write!(f, " (at {})", self.pointer)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<JsonError> for Error {
+    fn from(err: JsonError) -> Error {
+        Error::Json(Box::new(err))
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for JsonError {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> JsonError {
+        let mut pointer = String::new();
+        for segment in err.path().iter() {
+            pointer.push('/');
+            match segment {
+                serde_path_to_error::Segment::Seq { index } => pointer.push_str(&index.to_string()),
+                serde_path_to_error::Segment::Map { key }
+                | serde_path_to_error::Segment::Enum { variant: key } => {
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                }
+                serde_path_to_error::Segment::Unknown => pointer.push('?'),
+            }
+        }
+        JsonError::new(err.into_inner(), pointer)
+    }
+}
+
+impl Error {
+    /// Sets the href of a JSON error, if it doesn't already have one.
+    pub(crate) fn with_href(self, href: impl ToString) -> Error {
+        match self {
+            Error::Json(mut err) => {
+                if err.href.is_none() {
+                    err.href = Some(href.to_string());
+                }
+                Error::Json(err)
+            }
+            err => err,
+        }
+    }
+
+    /// Sets the line of a JSON error, e.g. for a line of newline-delimited JSON.
+    pub(crate) fn with_line(self, line: usize) -> Error {
+        match self {
+            Error::Json(mut err) => {
+                err.line = line;
+                Error::Json(err)
+            }
+            err => err,
+        }
+    }
 }
 
 /// A validation error
@@ -1,6 +1,6 @@
 use crate::{Error, Result};
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::{json, Map, Value};
+use serde_json::{Map, Value};
 
 /// Trait for structures that have gettable and settable fields.
 ///
@@ -72,6 +72,12 @@ pub trait Fields {
 
     /// Gets values with a prefix.
     ///
+    /// The prefix is stripped from the keys before deserializing, and nested
+    /// objects and arrays (e.g. `raster:bands`) are passed through as-is. If a
+    /// field can't be deserialized, the error is an [Error::InvalidField] with
+    /// the path to the bad value, e.g. `raster:bands[0].nodata`. If a field
+    /// that's required by `D` is missing, the key is just the prefix.
+    ///
     /// # Examples
     ///
     /// ```
@@ -89,7 +95,18 @@ pub trait Fields {
                 let _ = map.insert(key[prefix.len()..].to_string(), value.clone());
             }
         }
-        serde_json::from_value(json!(map)).map_err(Error::from)
+        serde_path_to_error::deserialize(Value::Object(map)).map_err(|err| {
+            let path = err.path().to_string();
+            let key = if path == "." {
+                prefix.trim_end_matches(':').to_string()
+            } else {
+                format!("{}{}", prefix, path)
+            };
+            Error::InvalidField {
+                key,
+                source: err.into_inner(),
+            }
+        })
     }
 
     /// Sets values with a prefix.
@@ -132,3 +149,66 @@ pub trait Fields {
             .retain(|key, _| !(key.starts_with(&prefix) && key.len() > prefix.len()));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Fields;
+    use crate::{Error, Item};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Raster {
+        bands: Vec<Band>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Band {
+        nodata: Option<f64>,
+    }
+
+    #[test]
+    fn fields_with_prefix_nested() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .set_field("raster:bands", json!([{"nodata": 0}, {"nodata": 1}]))
+            .unwrap();
+        let raster: Raster = item.fields_with_prefix("raster").unwrap();
+        assert_eq!(raster.bands.len(), 2);
+    }
+
+    #[test]
+    fn fields_with_prefix_missing_required_key() {
+        let mut item = Item::new("an-id");
+        let _ = item.set_field("raster:other", 42).unwrap();
+        let err = item.fields_with_prefix::<Raster>("raster").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidField { ref key, .. } if key == "raster"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn fields_with_prefix_invalid_key() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .set_field("raster:bands", json!([{"nodata": "not a number"}]))
+            .unwrap();
+        let _ = item.set_field("raster:other", 42).unwrap();
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Optional {
+            bands: Option<Vec<Band>>,
+        }
+        let err = item.fields_with_prefix::<Optional>("raster").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidField { ref key, .. } if key == "raster:bands[0].nodata"),
+            "{:?}",
+            err
+        );
+        assert!(err.to_string().contains("raster:bands[0].nodata"));
+    }
+}
@@ -1,12 +1,19 @@
 use crate::{
+    codec::Codec,
     geoparquet::{Compression, FromGeoparquet, IntoGeoparquet},
-    Error, FromJson, FromNdjson, Href, RealizedHref, Result, SelfHref, ToJson, ToNdjson,
+    partial::{ReadOptions, ReadReport},
+    Error, FromJson, FromNdjson, Href, ItemCollection, RealizedHref, Result, SelfHref, ToJson,
+    ToNdjson, Value,
 };
 use bytes::Bytes;
-use std::{fmt::Display, path::Path, str::FromStr};
+use std::{fmt::Display, path::Path, str::FromStr, sync::Arc};
 
 /// The format of STAC data.
+///
+/// New formats may be added in minor releases, so matches on a format need a
+/// wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Format {
     /// JSON data (the default).
     ///
@@ -18,6 +25,85 @@ pub enum Format {
 
     /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet)
     Geoparquet(Option<Compression>),
+
+    /// A format provided by a registered [Codec].
+    ///
+    /// The value is the codec's name. See [crate::codec] for how to register one.
+    Custom(&'static str),
+}
+
+/// Options for writing STAC values.
+///
+/// Catalogs with overly-precise footprints can be shrunk a lot by rounding
+/// and simplifying their items' geometries on the way out. The options are
+/// applied to items and item collections only.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Format, Item, WriteOptions};
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let options = WriteOptions {
+///     precision: Some(3),
+///     ..Default::default()
+/// };
+/// let bytes = Format::json().into_vec_opts(item, options).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WriteOptions {
+    /// Round item geometry and bbox coordinates to this many decimal places.
+    ///
+    /// See [Item::round_coordinates](crate::Item::round_coordinates).
+    pub precision: Option<u8>,
+
+    /// Simplify item geometries with this tolerance, in coordinate units.
+    ///
+    /// Geometries are simplified before they're rounded. Requires the `geo`
+    /// feature; see [Item::simplify](crate::Item#method.simplify).
+    pub simplify: Option<f64>,
+
+    /// Sort the items in an item collection along a Hilbert curve.
+    ///
+    /// See [sort_by_hilbert](crate::geoparquet::sort_by_hilbert).
+    pub hilbert: bool,
+}
+
+impl WriteOptions {
+    /// Rounds, simplifies, and sorts the items in a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Value, WriteOptions};
+    ///
+    /// let mut value: Value = stac::read("examples/simple-item.json").unwrap();
+    /// let options = WriteOptions {
+    ///     precision: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// options.apply(&mut value).unwrap();
+    /// ```
+    pub fn apply(&self, value: &mut Value) -> Result<()> {
+        if let Some(epsilon) = self.simplify {
+            #[cfg(feature = "geo")]
+            value.simplify(epsilon)?;
+            #[cfg(not(feature = "geo"))]
+            {
+                let _ = epsilon;
+                return Err(Error::FeatureNotEnabled("geo"));
+            }
+        }
+        if let Some(precision) = self.precision {
+            value.round_coordinates(precision);
+        }
+        if self.hilbert {
+            if let Value::ItemCollection(item_collection) = value {
+                crate::geoparquet::sort_by_hilbert(&mut item_collection.items);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Format {
@@ -31,7 +117,91 @@ impl Format {
     /// assert_eq!(Format::Json(false), Format::infer_from_href("item.json").unwrap());
     /// ```
     pub fn infer_from_href(href: &str) -> Option<Format> {
-        href.rsplit_once('.').and_then(|(_, ext)| ext.parse().ok())
+        if let Some(codec) = crate::codec::infer_from_href(href) {
+            Some(Format::Custom(codec.name()))
+        } else {
+            href.rsplit_once('.').and_then(|(_, ext)| ext.parse().ok())
+        }
+    }
+
+    /// Infer the format by looking at the bytes themselves.
+    ///
+    /// Geoparquet is detected by its magic bytes, and JSON is told apart from
+    /// newline-delimited JSON by counting the top-level values. If the `gzip`
+    /// or `zstd` features are enabled, compressed data are decompressed and
+    /// sniffed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Format;
+    ///
+    /// assert_eq!(Format::infer_from_bytes(b"{\"type\": \"Feature\"}").unwrap(), Format::json());
+    /// assert_eq!(Format::infer_from_bytes(b"{}\n{}\n").unwrap(), Format::ndjson());
+    /// assert_eq!(Format::infer_from_bytes(b"PAR1").unwrap(), Format::Geoparquet(None));
+    /// ```
+    pub fn infer_from_bytes(bytes: &[u8]) -> Option<Format> {
+        if bytes.starts_with(b"PAR1") {
+            return Some(Format::Geoparquet(None));
+        }
+        #[cfg(feature = "gzip")]
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            return infer_compressed(
+                bytes,
+                crate::codec::Gzip::json(),
+                crate::codec::Gzip::ndjson(),
+            );
+        }
+        #[cfg(feature = "zstd")]
+        if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            return infer_compressed(
+                bytes,
+                crate::codec::Zstd::json(),
+                crate::codec::Zstd::ndjson(),
+            );
+        }
+        let mut values =
+            serde_json::Deserializer::from_slice(bytes).into_iter::<serde::de::IgnoredAny>();
+        match (values.next(), values.next()) {
+            (Some(Ok(_)), None) => Some(Format::json()),
+            (Some(Ok(_)), Some(_)) => Some(Format::ndjson()),
+            _ => None,
+        }
+    }
+
+    /// Infer the format from a media type, e.g. from an HTTP `Content-Type` header.
+    ///
+    /// Parameters (e.g. `; charset=utf-8`) are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Format;
+    ///
+    /// assert_eq!(Format::infer_from_content_type("application/geo+json").unwrap(), Format::json());
+    /// assert_eq!(Format::infer_from_content_type("application/x-ndjson; charset=utf-8").unwrap(), Format::ndjson());
+    /// assert!(Format::infer_from_content_type("text/html").is_none());
+    /// ```
+    pub fn infer_from_content_type(content_type: &str) -> Option<Format> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match media_type.as_str() {
+            "application/json" | "application/geo+json" => Some(Format::json()),
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => {
+                Some(Format::ndjson())
+            }
+            crate::mime::APPLICATION_PARQUET | "application/x-parquet" => {
+                Some(Format::Geoparquet(None))
+            }
+            "application/cbor" => {
+                crate::codec::get("cbor").map(|codec| Format::Custom(codec.name()))
+            }
+            _ => None,
+        }
     }
 
     /// Returns true if this is a geoparquet href.
@@ -48,7 +218,6 @@ impl Format {
     ///
     /// let item: Item = Format::json().read("examples/simple-item.json").unwrap();
     /// ```
-    #[allow(unused_variables)]
     pub fn read<T: SelfHref + FromJson + FromNdjson + FromGeoparquet>(
         &self,
         href: impl Into<Href>,
@@ -56,15 +225,8 @@ impl Format {
         let mut href = href.into();
         let mut value: T = match href.clone().realize() {
             RealizedHref::Url(url) => {
-                #[cfg(feature = "reqwest")]
-                {
-                    let bytes = reqwest::blocking::get(url)?.bytes()?;
-                    self.from_bytes(bytes)?
-                }
-                #[cfg(not(feature = "reqwest"))]
-                {
-                    return Err(Error::FeatureNotEnabled("reqwest"));
-                }
+                let (_, bytes) = crate::io::get_blocking(&url, None)?;
+                self.from_bytes(bytes)?
             }
             RealizedHref::PathBuf(path) => {
                 let path = path.canonicalize()?;
@@ -95,6 +257,13 @@ impl Format {
             Format::Json(_) => T::from_json_path(&path),
             Format::NdJson => T::from_ndjson_path(&path),
             Format::Geoparquet(_) => T::from_geoparquet_path(&path),
+            Format::Custom(_) => std::fs::read(&path)
+                .map_err(Error::from)
+                .and_then(|bytes| self.from_bytes(bytes))
+                .map(|mut value: T| {
+                    *value.self_href_mut() = Some(path.as_path().into());
+                    value
+                }),
         }
         .map_err(|err| {
             if let Error::Io(err) = err {
@@ -103,7 +272,7 @@ impl Format {
                     path: path.to_string_lossy().into_owned(),
                 }
             } else {
-                err
+                err.with_href(path.display())
             }
         })
     }
@@ -128,6 +297,46 @@ impl Format {
             Format::Json(_) => T::from_json_slice(&bytes.into()),
             Format::NdJson => T::from_ndjson_bytes(bytes),
             Format::Geoparquet(_) => T::from_geoparquet_bytes(bytes),
+            Format::Custom(_) => {
+                let codec = self.codec()?;
+                codec.format().from_bytes(codec.decode(&bytes.into())?)
+            }
+        }
+    }
+
+    /// Reads items from some bytes, optionally skipping records that aren't valid items.
+    ///
+    /// Only newline-delimited JSON and stac-geoparquet can skip invalid
+    /// records. Other formats are read as a whole, so any invalid item is an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{partial::ReadOptions, Format};
+    ///
+    /// let bytes = std::fs::read("data/items.ndjson").unwrap();
+    /// let report = Format::ndjson()
+    ///     .from_bytes_opts(bytes, ReadOptions { skip_invalid: true })
+    ///     .unwrap();
+    /// assert_eq!(report.item_collection.items.len(), 2);
+    /// assert!(report.skipped.is_empty());
+    /// ```
+    pub fn from_bytes_opts(
+        &self,
+        bytes: impl Into<Bytes>,
+        options: ReadOptions,
+    ) -> Result<ReadReport> {
+        match self {
+            Format::NdJson => crate::ndjson::read_items(bytes.into(), options),
+            #[cfg(feature = "geoparquet")]
+            Format::Geoparquet(_) => crate::geoparquet::read_items(bytes.into(), options),
+            _ => self
+                .from_bytes::<ItemCollection>(bytes)
+                .map(|item_collection| ReadReport {
+                    item_collection,
+                    skipped: Vec::new(),
+                }),
         }
     }
 
@@ -156,11 +365,8 @@ impl Format {
         let href = href.into();
         match href.realize() {
             RealizedHref::Url(url) => {
-                use object_store::ObjectStore;
-
-                let (object_store, path) = object_store::parse_url_opts(&url, options)?;
-                let get_result = object_store.get(&path).await?;
-                let mut value: T = self.from_bytes(get_result.bytes().await?)?;
+                let (_, bytes) = crate::io::get_url_opts(&url, options).await?;
+                let mut value: T = self.from_bytes(bytes).map_err(|err| err.with_href(&url))?;
                 *value.self_href_mut() = Some(Href::Url(url));
                 Ok(value)
             }
@@ -170,6 +376,9 @@ impl Format {
 
     /// Writes a STAC value to the provided path.
     ///
+    /// The value is written to a temporary file next to `path`, which is then
+    /// renamed, so an interrupted write never leaves a partial file behind.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -182,11 +391,60 @@ impl Format {
         path: impl AsRef<Path>,
         value: T,
     ) -> Result<()> {
-        match self {
+        crate::io::write_atomic(path.as_ref(), |path| match self {
             Format::Json(pretty) => value.to_json_path(path, *pretty),
             Format::NdJson => value.to_ndjson_path(path),
             Format::Geoparquet(compression) => value.into_geoparquet_path(path, *compression),
-        }
+            Format::Custom(_) => std::fs::write(path, self.into_vec(value)?).map_err(Error::from),
+        })
+    }
+
+    /// Writes a STAC value to the provided path, rounding and simplifying its items first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac::{Format, Item, WriteOptions};
+    ///
+    /// let options = WriteOptions {
+    ///     precision: Some(6),
+    ///     ..Default::default()
+    /// };
+    /// Format::json().write_opts("an-id.json", Item::new("an-id"), options).unwrap();
+    /// ```
+    pub fn write_opts(
+        &self,
+        path: impl AsRef<Path>,
+        value: impl Into<Value>,
+        options: WriteOptions,
+    ) -> Result<()> {
+        let mut value = value.into();
+        options.apply(&mut value)?;
+        self.write(path, value)
+    }
+
+    /// Converts a STAC object into some bytes, rounding and simplifying its items first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Format, Item, WriteOptions};
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_geometry(Geometry::new(Value::Point(vec![-105.123456, 41.987654]))).unwrap();
+    /// let options = WriteOptions {
+    ///     precision: Some(2),
+    ///     ..Default::default()
+    /// };
+    /// let bytes = Format::json().into_vec_opts(item, options).unwrap();
+    /// let item: Item = serde_json::from_slice(&bytes).unwrap();
+    /// assert_eq!(item.geometry.unwrap().value, Value::Point(vec![-105.12, 41.99]));
+    /// ```
+    pub fn into_vec_opts(&self, value: impl Into<Value>, options: WriteOptions) -> Result<Vec<u8>> {
+        let mut value = value.into();
+        options.apply(&mut value)?;
+        self.into_vec(value)
     }
 
     /// Converts a STAC object into some bytes.
@@ -204,6 +462,10 @@ impl Format {
             Format::Json(pretty) => value.to_json_vec(*pretty),
             Format::NdJson => value.to_ndjson_vec(),
             Format::Geoparquet(compression) => value.into_geoparquet_vec(*compression),
+            Format::Custom(_) => {
+                let codec = self.codec()?;
+                codec.encode(codec.format().into_vec(value)?)
+            }
         }
     }
 
@@ -235,16 +497,14 @@ impl Format {
         K: AsRef<str>,
         V: Into<String>,
     {
-        let href = href.to_string();
-        if let Ok(url) = url::Url::parse(&href) {
-            use object_store::ObjectStore;
-
-            let (object_store, path) = object_store::parse_url_opts(&url, options)?;
-            let bytes = self.into_vec(value)?;
-            let put_result = object_store.put(&path, bytes.into()).await?;
-            Ok(Some(put_result))
-        } else {
-            self.write(href, value).map(|_| None)
+        match Href::from(href.to_string()) {
+            Href::Url(url) => {
+                let bytes = self.into_vec(value)?;
+                crate::io::put_url_opts(&url, bytes, options)
+                    .await
+                    .map(Some)
+            }
+            Href::String(path) => self.write(path, value).map(|_| None),
         }
     }
 
@@ -263,6 +523,25 @@ impl Format {
     pub fn geoparquet() -> Format {
         Format::Geoparquet(None)
     }
+
+    fn codec(&self) -> Result<Arc<dyn Codec>> {
+        match self {
+            Format::Custom(name) => {
+                crate::codec::get(name).ok_or_else(|| Error::UnsupportedFormat(name.to_string()))
+            }
+            _ => Err(Error::UnsupportedFormat(self.to_string())),
+        }
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn infer_compressed(bytes: &[u8], json: impl Codec, ndjson: impl Codec) -> Option<Format> {
+    let decoded = json.decode(bytes).ok()?;
+    match Format::infer_from_bytes(&decoded)? {
+        Format::Json(_) => Some(Format::Custom(json.name())),
+        Format::NdJson => Some(Format::Custom(ndjson.name())),
+        _ => None,
+    }
 }
 
 impl Default for Format {
@@ -289,6 +568,7 @@ impl Display for Format {
                     f.write_str("geoparquet")
                 }
             }
+            Self::Custom(name) => f.write_str(name),
         }
     }
 }
@@ -296,34 +576,52 @@ impl Display for Format {
 impl FromStr for Format {
     type Err = Error;
 
-    #[cfg_attr(not(feature = "geoparquet"), allow(unused_variables))]
     fn from_str(s: &str) -> Result<Format> {
-        match s.to_ascii_lowercase().as_str() {
-            "json" | "geojson" => Ok(Self::Json(false)),
-            "json-pretty" | "geojson-pretty" => Ok(Self::Json(true)),
-            "ndjson" => Ok(Self::NdJson),
-            _ => {
-                if s.starts_with("parquet") || s.starts_with("geoparquet") {
-                    if let Some((_, compression)) = s.split_once('[') {
-                        if let Some(stop) = compression.find(']') {
-                            #[cfg(feature = "geoparquet")]
-                            {
-                                Ok(Self::Geoparquet(Some(compression[..stop].parse()?)))
-                            }
-                            #[cfg(not(feature = "geoparquet"))]
-                            {
-                                Ok(Self::Geoparquet(Some(Compression)))
-                            }
-                        } else {
-                            Err(Error::UnsupportedFormat(s.to_string()))
+        if let Some(format) = Format::from_built_in_str(s) {
+            format
+        } else if let Some(codec) = crate::codec::get(s) {
+            Ok(Self::Custom(codec.name()))
+        } else {
+            Err(Error::UnsupportedFormat(s.to_string()))
+        }
+    }
+}
+
+impl Format {
+    /// Parses the name of a built-in format, or returns `None` if it isn't one.
+    ///
+    /// Like format names in general, built-in names are case-insensitive.
+    #[cfg_attr(not(feature = "geoparquet"), allow(unused_variables))]
+    pub(crate) fn from_built_in_str(s: &str) -> Option<Result<Format>> {
+        let lowercase = s.to_ascii_lowercase();
+        match lowercase.as_str() {
+            "json" | "geojson" => Some(Ok(Self::Json(false))),
+            "json-pretty" | "geojson-pretty" => Some(Ok(Self::Json(true))),
+            "ndjson" => Some(Ok(Self::NdJson)),
+            name if name.starts_with("parquet") || name.starts_with("geoparquet") => {
+                if let Some((_, compression)) = s.split_once('[') {
+                    if let Some(stop) = compression.find(']') {
+                        #[cfg(feature = "geoparquet")]
+                        {
+                            Some(
+                                compression[..stop]
+                                    .parse()
+                                    .map(|compression| Self::Geoparquet(Some(compression)))
+                                    .map_err(Error::from),
+                            )
+                        }
+                        #[cfg(not(feature = "geoparquet"))]
+                        {
+                            Some(Ok(Self::Geoparquet(Some(Compression))))
                         }
                     } else {
-                        Ok(Self::Geoparquet(None))
+                        Some(Err(Error::UnsupportedFormat(s.to_string())))
                     }
                 } else {
-                    Err(Error::UnsupportedFormat(s.to_string()))
+                    Some(Ok(Self::Geoparquet(None)))
                 }
             }
+            _ => None,
         }
     }
 }
@@ -331,7 +629,7 @@ impl FromStr for Format {
 #[cfg(test)]
 mod tests {
     use super::Format;
-    use crate::geoparquet::Compression;
+    use crate::{codec::Codec, geoparquet::Compression, Item, Result};
 
     #[test]
     fn parse_geoparquet() {
@@ -362,4 +660,80 @@ mod tests {
             Format::infer_from_href("out.parquet").unwrap()
         );
     }
+
+    #[derive(Debug)]
+    struct Identity;
+
+    impl Codec for Identity {
+        fn name(&self) -> &'static str {
+            "identity"
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            &["identity.json"]
+        }
+
+        fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+            Ok(bytes.to_vec())
+        }
+
+        fn encode(&self, json: Vec<u8>) -> Result<Vec<u8>> {
+            Ok(json)
+        }
+    }
+
+    #[test]
+    fn custom() {
+        crate::codec::register(Identity).unwrap();
+        let format: Format = "identity".parse().unwrap();
+        assert_eq!(format, Format::Custom("identity"));
+        assert_eq!(format.to_string(), "identity");
+        assert_eq!(
+            Format::infer_from_href("item.identity.json").unwrap(),
+            format
+        );
+        assert_eq!(
+            Format::infer_from_href("ITEM.IDENTITY.JSON").unwrap(),
+            format
+        );
+        assert_eq!(
+            Format::infer_from_href("item.json").unwrap(),
+            Format::json()
+        );
+        let item: Item = format.read("examples/simple-item.json").unwrap();
+        assert_eq!(item.id, "20201211_223832_CS2");
+    }
+
+    #[test]
+    fn unregistered() {
+        let _ = "not-a-codec".parse::<Format>().unwrap_err();
+        let _ = Format::Custom("not-a-codec")
+            .into_vec(Item::new("an-id"))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn write_options_hilbert() {
+        use crate::{Bbox, ItemCollection, Value, WriteOptions};
+
+        let items: Vec<_> = [None, Some(Bbox::new(139.7, 35.7, 139.7, 35.7))]
+            .into_iter()
+            .enumerate()
+            .map(|(i, bbox)| {
+                let mut item = Item::new(i.to_string());
+                item.bbox = bbox;
+                item
+            })
+            .collect();
+        let mut value = Value::ItemCollection(ItemCollection::from(items));
+        let options = WriteOptions {
+            hilbert: true,
+            ..Default::default()
+        };
+        options.apply(&mut value).unwrap();
+        let Value::ItemCollection(item_collection) = value else {
+            panic!("should be an item collection");
+        };
+        assert_eq!(item_collection.items[0].id, "1");
+    }
 }
@@ -4,11 +4,15 @@
 
 pub mod json;
 
-use crate::{Error, ItemCollection, Result};
+use crate::{
+    item::FlattenOptions,
+    partial::{ReadOptions, ReadReport},
+    Error, Href, Item, ItemCollection, Result, SelfHref,
+};
 use arrow_json::ReaderBuilder;
-use arrow_schema::{DataType, Field, SchemaBuilder, TimeUnit};
+use arrow_schema::{DataType, Field, Fields, Schema, SchemaBuilder, TimeUnit};
 use geo_types::Geometry;
-use geoarrow::{array::GeometryBuilder, table::Table};
+use geoarrow::{array::GeometryBuilder, table::Table, NativeArray};
 use serde_json::{json, Value};
 use std::sync::Arc;
 
@@ -23,11 +27,46 @@ const DATETIME_COLUMNS: [&str; 8] = [
     "unpublished",
 ];
 
+/// The default number of items in each record batch of a [Table] created by [to_table].
+const CHUNK_SIZE: usize = 65_536;
+
+/// The name of the optional column that holds each item's self href.
+///
+/// The name is prefixed so it can't collide with an item property. See
+/// [TableBuilder::self_href].
+pub const SELF_HREF_COLUMN: &str = "stac:self_href";
+
+/// Builds a [Table] from items.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{geoarrow::TableBuilder, Item};
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let table = TableBuilder::new(vec![item]).self_href(true).build().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TableBuilder {
+    item_collection: ItemCollection,
+    self_href: bool,
+    flatten: FlattenOptions,
+    precision: Option<u8>,
+    #[cfg(feature = "geo")]
+    simplify: Option<f64>,
+    chunk_size: usize,
+}
+
 /// Converts an [ItemCollection] to a [Table].
 ///
 /// Any invalid attributes in the items (e.g. top-level attributes that conflict
 /// with STAC spec attributes) will be dropped with a warning.
 ///
+/// Items are decoded into record batches of up to 65,536 rows, and only one
+/// batch of rows is held in memory at a time. If the `rayon` feature is
+/// enabled, the schema is inferred from slices of the items in parallel, and
+/// each batch's items are flattened and serialized in parallel.
+///
 /// # Examples
 ///
 /// ```
@@ -38,82 +77,430 @@ const DATETIME_COLUMNS: [&str; 8] = [
 /// let table = stac::geoarrow::to_table(item_collection).unwrap();
 /// ```
 pub fn to_table(item_collection: impl Into<ItemCollection>) -> Result<Table> {
-    let item_collection = item_collection.into();
-    let mut values = Vec::with_capacity(item_collection.items.len());
-    let mut builder = GeometryBuilder::new();
-    for mut item in item_collection.items {
-        builder.push_geometry(
-            item.geometry
-                .take()
-                .and_then(|geometry| Geometry::try_from(geometry).ok())
-                .as_ref(),
-        )?;
-        let flat_item = item.into_flat_item(true)?;
-        let mut value = serde_json::to_value(flat_item)?;
-        {
-            let value = value
+    TableBuilder::new(item_collection).build()
+}
+
+impl TableBuilder {
+    /// Creates a new table builder for these items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item};
+    ///
+    /// let builder = TableBuilder::new(vec![Item::new("an-id")]);
+    /// ```
+    pub fn new(item_collection: impl Into<ItemCollection>) -> TableBuilder {
+        TableBuilder {
+            item_collection: item_collection.into(),
+            self_href: false,
+            flatten: FlattenOptions::default(),
+            precision: None,
+            #[cfg(feature = "geo")]
+            simplify: None,
+            chunk_size: CHUNK_SIZE,
+        }
+    }
+
+    /// Sets whether each item's self href is stored in a [SELF_HREF_COLUMN] column.
+    ///
+    /// Self hrefs aren't part of the STAC data, so they're dropped by
+    /// default. Keeping them preserves where each item came from through
+    /// conversions, and [from_table] restores them. Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item, SelfHref};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let table = TableBuilder::new(vec![item.clone()]).self_href(true).build().unwrap();
+    /// let item_collection = stac::geoarrow::from_table(table).unwrap();
+    /// assert_eq!(item_collection.items[0].self_href(), item.self_href());
+    /// ```
+    pub fn self_href(mut self, self_href: bool) -> TableBuilder {
+        self.self_href = self_href;
+        self
+    }
+
+    /// Sets how items are flattened into rows.
+    ///
+    /// By default, properties that collide with top-level fields and
+    /// top-level foreign members are dropped with a warning. Read the table
+    /// back with [from_table_with] and the same options to get the original
+    /// items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, item::{Collision, FlattenOptions}, Item};
+    ///
+    /// let options = FlattenOptions {
+    ///     collision: Collision::Prefix,
+    ///     separator: None,
+    /// };
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let table = TableBuilder::new(vec![item])
+    ///     .flatten(options)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn flatten(mut self, options: FlattenOptions) -> TableBuilder {
+        self.flatten = options;
+        self
+    }
+
+    /// Rounds each item's geometry and bbox coordinates to this many decimal places.
+    ///
+    /// See [Item::round_coordinates].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let table = TableBuilder::new(vec![item]).precision(3).build().unwrap();
+    /// ```
+    pub fn precision(mut self, precision: u8) -> TableBuilder {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Simplifies each item's geometry with this tolerance, in coordinate units.
+    ///
+    /// Geometries are simplified before their coordinates are rounded. Only
+    /// available with the `geo` feature, see [Item::simplify].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let table = TableBuilder::new(vec![item]).simplify(0.001).build().unwrap();
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn simplify(mut self, epsilon: f64) -> TableBuilder {
+        self.simplify = Some(epsilon);
+        self
+    }
+
+    /// Sets the maximum number of items in each record batch.
+    ///
+    /// Items are converted to rows one batch at a time, so smaller batches use
+    /// less memory. Defaults to 65,536.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let items = vec![item.clone(), item.clone(), item];
+    /// let table = TableBuilder::new(items).chunk_size(2).build().unwrap();
+    /// assert_eq!(table.batches().len(), 2);
+    /// ```
+    pub fn chunk_size(mut self, chunk_size: usize) -> TableBuilder {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Builds the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoarrow::TableBuilder, Item};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let table = TableBuilder::new(vec![item]).build().unwrap();
+    /// ```
+    pub fn build(mut self) -> Result<Table> {
+        #[cfg(feature = "geo")]
+        if let Some(epsilon) = self.simplify {
+            self.item_collection.simplify(epsilon)?;
+        }
+        if let Some(precision) = self.precision {
+            self.item_collection.round_coordinates(precision);
+        }
+        build(
+            self.item_collection,
+            self.self_href,
+            self.flatten,
+            self.chunk_size,
+        )
+    }
+}
+
+fn build(
+    item_collection: ItemCollection,
+    self_href: bool,
+    flatten: FlattenOptions,
+    chunk_size: usize,
+) -> Result<Table> {
+    if item_collection.items.is_empty() {
+        return Err(Error::NoItems);
+    }
+    let row = |item: Item| -> Result<_> {
+        let href = item.self_href().filter(|_| self_href).map(Href::to_string);
+        let (geometry, mut value) = to_row(item, flatten)?;
+        if let Some(href) = href {
+            let _ = value
                 .as_object_mut()
-                .expect("a flat item should serialize to an object");
-            let _ = value.remove("geometry");
-            if let Some(bbox) = value.remove("bbox") {
-                let bbox = bbox
-                    .as_array()
-                    .expect("STAC items should always have a list as their bbox");
-                if bbox.len() == 4 {
-                    let _ = value.insert("bbox".into(), json!({
-                        "xmin": bbox[0].as_number().expect("all bbox values should be a number"),
-                        "ymin": bbox[1].as_number().expect("all bbox values should be a number"),
-                        "xmax": bbox[2].as_number().expect("all bbox values should be a number"),
-                        "ymax": bbox[3].as_number().expect("all bbox values should be a number"),
-                    }));
-                } else if bbox.len() == 6 {
-                    let _ = value.insert("bbox".into(), json!({
-                        "xmin": bbox[0].as_number().expect("all bbox values should be a number"),
-                        "ymin": bbox[1].as_number().expect("all bbox values should be a number"),
-                        "zmin": bbox[2].as_number().expect("all bbox values should be a number"),
-                        "xmax": bbox[3].as_number().expect("all bbox values should be a number"),
-                        "ymax": bbox[4].as_number().expect("all bbox values should be a number"),
-                        "zmax": bbox[5].as_number().expect("all bbox values should be a number"),
-                    }));
-                } else {
-                    return Err(Error::InvalidBbox(
-                        bbox.iter().filter_map(|v| v.as_f64()).collect(),
-                    ));
-                }
+                .expect("a row should be an object")
+                .insert(SELF_HREF_COLUMN.to_string(), href.into());
+        }
+        Ok((geometry, value))
+    };
+    // The schema has to cover every item before anything is decoded, so infer
+    // a schema for each slice of items, one row at a time, and merge them.
+    let infer = |items: &[Item]| -> Result<Schema> {
+        let mut error = None;
+        let values = items.iter().map_while(|item| match row(item.clone()) {
+            Ok((_, value)) => Some(Ok(value)),
+            Err(err) => {
+                error = Some(err);
+                None
             }
+        });
+        let schema = arrow_json::reader::infer_json_schema_from_iterator(values)?;
+        if let Some(err) = error {
+            Err(err)
+        } else {
+            Ok(schema)
         }
-        values.push(value);
+    };
+    #[cfg(feature = "rayon")]
+    let schemas = {
+        use rayon::prelude::*;
+        let items = &item_collection.items;
+        items
+            .par_chunks(items.len().div_ceil(rayon::current_num_threads()))
+            .map(infer)
+            .collect::<Result<Vec<_>>>()?
+    };
+    #[cfg(not(feature = "rayon"))]
+    let schemas = vec![infer(&item_collection.items)?];
+    let mut schemas = schemas.into_iter();
+    let mut schema = schemas.next().expect("there should be at least one item");
+    for other in schemas {
+        schema = merge_schemas(schema, other)?;
     }
-    let schema = arrow_json::reader::infer_json_schema_from_iterator(values.iter().map(Ok))?;
     let mut schema_builder = SchemaBuilder::new();
     for field in schema.fields().iter() {
-        if DATETIME_COLUMNS.contains(&field.name().as_str()) {
-            schema_builder.push(Field::new(
+        match field.name().as_str() {
+            name if DATETIME_COLUMNS.contains(&name) => schema_builder.push(Field::new(
                 field.name(),
                 DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
                 field.is_nullable(),
-            ));
-        } else {
-            schema_builder.push(field.clone());
+            )),
+            "links" => schema_builder.push(links_field(field)),
+            "assets" => schema_builder.push(assets_field(field)),
+            _ => schema_builder.push(field.clone()),
         }
     }
     let metadata = schema.metadata;
     let schema = Arc::new(schema_builder.finish().with_metadata(metadata));
     let mut decoder = ReaderBuilder::new(schema.clone()).build_decoder()?;
-    decoder.serialize(&values)?;
-    let batch = decoder.flush()?.ok_or(Error::NoItems)?;
-    let array = builder.finish();
+    let mut batches = Vec::new();
+    let mut arrays = Vec::new();
+    let mut items = item_collection.items.into_iter();
+    loop {
+        let chunk: Vec<Item> = items.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        #[cfg(feature = "rayon")]
+        let rows = {
+            use rayon::prelude::*;
+            chunk.into_par_iter().map(row).collect::<Result<Vec<_>>>()?
+        };
+        #[cfg(not(feature = "rayon"))]
+        let rows = chunk.into_iter().map(row).collect::<Result<Vec<_>>>()?;
+        let (geometries, values): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+        decoder.serialize(&values)?;
+        batches.push(decoder.flush()?.ok_or(Error::NoItems)?);
+        let mut builder = GeometryBuilder::new();
+        for geometry in geometries {
+            builder.push_geometry(geometry.as_ref())?;
+        }
+        arrays.push(builder.finish());
+    }
+    let chunks: Vec<&dyn NativeArray> = arrays
+        .iter()
+        .map(|array| -> &dyn NativeArray { array })
+        .collect();
     Table::from_arrow_and_geometry(
-        vec![batch],
+        batches,
         schema,
-        geoarrow::chunked_array::ChunkedNativeArrayDyn::from_geoarrow_chunks(&[&array])?
-            .into_inner(),
+        geoarrow::chunked_array::ChunkedNativeArrayDyn::from_geoarrow_chunks(&chunks)?.into_inner(),
     )
     .map_err(Error::from)
 }
 
+/// Merges two inferred schemas, keeping fields in the order they first appear.
+fn merge_schemas(a: Schema, b: Schema) -> Result<Schema> {
+    Ok(Schema::new(merge_fields(a.fields(), b.fields())?))
+}
+
+fn merge_fields(a: &Fields, b: &Fields) -> Result<Fields> {
+    let mut fields: Vec<Field> = a.iter().map(|field| field.as_ref().clone()).collect();
+    for field in b.iter() {
+        if let Some(existing) = fields.iter_mut().find(|f| f.name() == field.name()) {
+            let data_type = merge_data_types(existing.data_type(), field.data_type())?;
+            *existing = Field::new(field.name(), data_type, true);
+        } else {
+            fields.push(field.as_ref().clone());
+        }
+    }
+    Ok(fields.into())
+}
+
+/// Merges two inferred data types with the same coercion rules as
+/// [arrow_json::reader::infer_json_schema_from_iterator].
+fn merge_data_types(a: &DataType, b: &DataType) -> Result<DataType> {
+    let list_of = |data_type| DataType::List(Arc::new(Field::new("item", data_type, true)));
+    Ok(match (a, b) {
+        (DataType::Null, other) | (other, DataType::Null) => other.clone(),
+        (DataType::Struct(a), DataType::Struct(b)) => DataType::Struct(merge_fields(a, b)?),
+        (DataType::List(a), DataType::List(b)) => {
+            list_of(merge_data_types(a.data_type(), b.data_type())?)
+        }
+        (DataType::Struct(_), _) | (_, DataType::Struct(_)) => {
+            return Err(arrow_schema::ArrowError::JsonError(format!(
+                "incompatible types found during schema inference: {a} and {b}"
+            ))
+            .into())
+        }
+        (DataType::List(item), scalar) | (scalar, DataType::List(item)) => {
+            list_of(merge_data_types(item.data_type(), scalar)?)
+        }
+        (a, b) if a == b => a.clone(),
+        (DataType::Int64 | DataType::Float64, DataType::Int64 | DataType::Float64) => {
+            DataType::Float64
+        }
+        _ => DataType::Utf8,
+    })
+}
+
+/// Returns a `links` field with the stac-geoparquet list-of-structs layout.
+///
+/// Every link struct starts with the same fields, in the same order, no matter
+/// which fields the items actually use, so files written from different items
+/// have compatible schemas. Any other inferred link fields come after.
+fn links_field(field: &Field) -> Field {
+    let inferred = match field.data_type() {
+        DataType::List(item) => Some(item.data_type()),
+        _ => None,
+    };
+    let standard = ["href", "rel", "type", "title"]
+        .into_iter()
+        .map(|name| Field::new(name, DataType::Utf8, true))
+        .collect();
+    let item = Field::new("item", standard_struct(standard, inferred), true);
+    Field::new(field.name(), DataType::List(Arc::new(item)), true)
+}
+
+/// Returns an `assets` field with the stac-geoparquet struct-of-structs layout.
+///
+/// Like [links_field], every asset struct starts with the same fields.
+fn assets_field(field: &Field) -> Field {
+    let DataType::Struct(assets) = field.data_type() else {
+        return field.clone();
+    };
+    let assets: Vec<Field> = assets
+        .iter()
+        .map(|asset| {
+            let mut standard: Vec<Field> = ["href", "type", "title", "description"]
+                .into_iter()
+                .map(|name| Field::new(name, DataType::Utf8, true))
+                .collect();
+            standard.push(Field::new(
+                "roles",
+                DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+                true,
+            ));
+            Field::new(
+                asset.name(),
+                standard_struct(standard, Some(asset.data_type())),
+                true,
+            )
+        })
+        .collect();
+    Field::new(
+        field.name(),
+        DataType::Struct(assets.into()),
+        field.is_nullable(),
+    )
+}
+
+/// Returns a struct with the standard fields, followed by any other inferred fields.
+fn standard_struct(mut fields: Vec<Field>, inferred: Option<&DataType>) -> DataType {
+    if let Some(DataType::Struct(inferred)) = inferred {
+        for field in inferred.iter() {
+            if !fields.iter().any(|f| f.name() == field.name()) {
+                fields.push(field.as_ref().clone());
+            }
+        }
+    }
+    DataType::Struct(fields.into())
+}
+
+/// Converts an item into its geometry and a flat JSON value, ready for decoding.
+fn to_row(mut item: Item, flatten: FlattenOptions) -> Result<(Option<Geometry>, Value)> {
+    let geometry = item
+        .geometry
+        .take()
+        .and_then(|geometry| Geometry::try_from(geometry).ok());
+    let flat_item = item.into_flat_item_opts(flatten)?;
+    let mut value = serde_json::to_value(flat_item)?;
+    {
+        let value = value
+            .as_object_mut()
+            .expect("a flat item should serialize to an object");
+        let _ = value.remove("geometry");
+        if let Some(bbox) = value.remove("bbox") {
+            let bbox = bbox
+                .as_array()
+                .expect("STAC items should always have a list as their bbox");
+            if bbox.len() == 4 {
+                let _ = value.insert(
+                    "bbox".into(),
+                    json!({
+                        "xmin": bbox[0].as_number().expect("all bbox values should be a number"),
+                        "ymin": bbox[1].as_number().expect("all bbox values should be a number"),
+                        "xmax": bbox[2].as_number().expect("all bbox values should be a number"),
+                        "ymax": bbox[3].as_number().expect("all bbox values should be a number"),
+                    }),
+                );
+            } else if bbox.len() == 6 {
+                let _ = value.insert(
+                    "bbox".into(),
+                    json!({
+                        "xmin": bbox[0].as_number().expect("all bbox values should be a number"),
+                        "ymin": bbox[1].as_number().expect("all bbox values should be a number"),
+                        "zmin": bbox[2].as_number().expect("all bbox values should be a number"),
+                        "xmax": bbox[3].as_number().expect("all bbox values should be a number"),
+                        "ymax": bbox[4].as_number().expect("all bbox values should be a number"),
+                        "zmax": bbox[5].as_number().expect("all bbox values should be a number"),
+                    }),
+                );
+            } else {
+                return Err(Error::InvalidBbox(
+                    bbox.iter().filter_map(|v| v.as_f64()).collect(),
+                ));
+            }
+        }
+    }
+    Ok((geometry, value))
+}
+
 /// Converts a [Table] to an [ItemCollection].
 ///
+/// If the table has a [SELF_HREF_COLUMN] column, it's used to set each item's
+/// self href.
+///
 /// # Examples
 ///
 /// ```
@@ -132,11 +519,63 @@ pub fn to_table(item_collection: impl Into<ItemCollection>) -> Result<Table> {
 /// # }
 /// ```
 pub fn from_table(table: Table) -> Result<ItemCollection> {
-    json::from_table(table)?
-        .into_iter()
-        .map(|item| serde_json::from_value(Value::Object(item)).map_err(Error::from))
-        .collect::<Result<Vec<_>>>()
-        .map(ItemCollection::from)
+    from_table_opts(table, ReadOptions::default(), FlattenOptions::default())
+        .map(|report| report.item_collection)
+}
+
+/// Converts a [Table] that was built with [TableBuilder::flatten] to an [ItemCollection].
+///
+/// # Examples
+///
+/// ```
+/// use stac::{geoarrow::TableBuilder, item::{Collision, FlattenOptions}, Item};
+///
+/// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let _ = item.additional_fields.insert("foo".into(), "bar".into());
+/// let options = FlattenOptions {
+///     collision: Collision::Prefix,
+///     separator: None,
+/// };
+/// let table = TableBuilder::new(vec![item]).flatten(options).build().unwrap();
+/// let item_collection = stac::geoarrow::from_table_with(table, options).unwrap();
+/// assert_eq!(item_collection.items[0].additional_fields["foo"], "bar");
+/// ```
+pub fn from_table_with(table: Table, flatten: FlattenOptions) -> Result<ItemCollection> {
+    from_table_opts(table, ReadOptions::default(), flatten).map(|report| report.item_collection)
+}
+
+/// Converts a [Table] to items, skipping rows that aren't valid items if asked.
+pub(crate) fn from_table_opts(
+    table: Table,
+    options: ReadOptions,
+    flatten: FlattenOptions,
+) -> Result<ReadReport> {
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for (i, mut item) in json::from_table(table)?.into_iter().enumerate() {
+        let href = item
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .and_then(|properties| properties.remove(SELF_HREF_COLUMN));
+        if flatten != FlattenOptions::default() {
+            if let Some(Value::Object(flat)) = item.remove("properties") {
+                let (properties, additional_fields) = flatten.unflatten(flat)?;
+                item.extend(additional_fields);
+                let _ = item.insert("properties".to_string(), Value::Object(properties));
+            }
+        }
+        let result = serde_json::from_value::<Item>(Value::Object(item)).map_err(Error::from);
+        if let Some(mut item) = options.handle(i + 1, || None, result, &mut skipped)? {
+            if let Some(Value::String(href)) = href {
+                *item.self_href_mut() = Some(href.into());
+            }
+            items.push(item);
+        }
+    }
+    Ok(ReadReport {
+        item_collection: items.into(),
+        skipped,
+    })
 }
 
 // We only run tests when the geoparquet feature is enabled so that we don't
@@ -172,6 +611,229 @@ mod tests {
         let _ = super::from_table(table).unwrap();
     }
 
+    #[test]
+    fn standard_links_and_assets() {
+        use crate::{Asset, Link};
+        use arrow_schema::DataType;
+
+        let mut a = Item::new("a");
+        a.geometry = Some(geojson::Geometry::new(geojson::Value::Point(vec![
+            1.0, 2.0,
+        ])));
+        a.links.push(Link::new("http://stac-rs.test/a", "related"));
+        let _ = a.assets.insert("data".to_string(), Asset::new("a.tif"));
+        let mut b = Item::new("b");
+        b.geometry = Some(geojson::Geometry::new(geojson::Value::Point(vec![
+            1.0, 2.0,
+        ])));
+        b.links.push(
+            Link::new("http://stac-rs.test/b", "related")
+                .title("B".to_string())
+                .json(),
+        );
+        let mut asset = Asset::new("b.tif");
+        asset.roles = vec!["data".to_string()];
+        let _ = b.assets.insert("data".to_string(), asset);
+
+        let names = |data_type: &DataType| match data_type {
+            DataType::Struct(fields) => fields
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect::<Vec<_>>(),
+            _ => panic!("not a struct: {data_type:?}"),
+        };
+        for item in [a.clone(), b] {
+            let table = super::to_table(vec![item]).unwrap();
+            let schema = table.schema();
+            let DataType::List(link) = schema.field_with_name("links").unwrap().data_type() else {
+                panic!("links should be a list");
+            };
+            assert_eq!(names(link.data_type()), ["href", "rel", "type", "title"]);
+            let DataType::Struct(assets) = schema.field_with_name("assets").unwrap().data_type()
+            else {
+                panic!("assets should be a struct");
+            };
+            assert_eq!(
+                names(assets[0].data_type()),
+                ["href", "type", "title", "description", "roles"]
+            );
+        }
+
+        let table = super::to_table(vec![a.clone()]).unwrap();
+        let item_collection = super::from_table(table).unwrap();
+        assert_eq!(item_collection.items[0].links, a.links);
+        assert_eq!(item_collection.items[0].assets, a.assets);
+    }
+
+    #[test]
+    fn roundtrip_flatten() {
+        use crate::item::{Collision, FlattenOptions};
+
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("collection".to_string(), "a property".into());
+        let _ = item
+            .additional_fields
+            .insert("foo".to_string(), "bar".into());
+        let options = FlattenOptions {
+            collision: Collision::Prefix,
+            separator: Some('.'),
+        };
+        let table = super::TableBuilder::new(vec![item])
+            .flatten(options)
+            .build()
+            .unwrap();
+        let item_collection = super::from_table_with(table, options).unwrap();
+        let item = &item_collection.items[0];
+        assert_eq!(
+            item.properties.additional_fields["collection"],
+            "a property"
+        );
+        assert_eq!(item.additional_fields["foo"], "bar");
+    }
+
+    #[test]
+    fn to_table_chunks() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let items: Vec<_> = (0..5)
+            .map(|i| {
+                let mut item = item.clone();
+                item.id = format!("item-{i}");
+                item
+            })
+            .collect();
+        let table = super::TableBuilder::new(items)
+            .chunk_size(2)
+            .build()
+            .unwrap();
+        assert_eq!(table.batches().len(), 3);
+        assert_eq!(table.len(), 5);
+        let item_collection = super::from_table(table).unwrap();
+        assert_eq!(item_collection.items[4].id, "item-4");
+    }
+
+    #[test]
+    fn merge_data_types() {
+        use arrow_schema::{DataType, Field};
+        use std::sync::Arc;
+
+        let list = |data_type| DataType::List(Arc::new(Field::new("item", data_type, true)));
+        let merge = |a: &DataType, b: &DataType| super::merge_data_types(a, b).unwrap();
+        assert_eq!(
+            merge(&DataType::Int64, &DataType::Float64),
+            DataType::Float64
+        );
+        assert_eq!(merge(&DataType::Null, &DataType::Int64), DataType::Int64);
+        assert_eq!(merge(&DataType::Boolean, &DataType::Int64), DataType::Utf8);
+        assert_eq!(
+            merge(&list(DataType::Int64), &DataType::Float64),
+            list(DataType::Float64)
+        );
+        let a = DataType::Struct(vec![Field::new("a", DataType::Int64, true)].into());
+        let b = DataType::Struct(vec![Field::new("b", DataType::Utf8, true)].into());
+        assert_eq!(
+            merge(&a, &b),
+            DataType::Struct(
+                vec![
+                    Field::new("a", DataType::Int64, true),
+                    Field::new("b", DataType::Utf8, true)
+                ]
+                .into()
+            )
+        );
+        let _ = super::merge_data_types(&a, &DataType::Utf8).unwrap_err();
+    }
+
+    #[test]
+    fn to_table_merges_schemas() {
+        let items: Vec<_> = (0..100)
+            .map(|i| {
+                let mut item = Item::new(format!("item-{i}"));
+                item.geometry = Some(geojson::Geometry::new(geojson::Value::Point(vec![
+                    1.0, 2.0,
+                ])));
+                if i < 50 {
+                    let _ = item
+                        .properties
+                        .additional_fields
+                        .insert("foo".into(), i.into());
+                } else {
+                    let _ = item
+                        .properties
+                        .additional_fields
+                        .insert("foo".into(), (i as f64 + 0.5).into());
+                    let _ = item
+                        .properties
+                        .additional_fields
+                        .insert("bar".into(), "baz".into());
+                }
+                item
+            })
+            .collect();
+        let table = super::to_table(items).unwrap();
+        let schema = table.schema();
+        assert_eq!(
+            schema.field_with_name("foo").unwrap().data_type(),
+            &arrow_schema::DataType::Float64
+        );
+        assert!(schema.field_with_name("bar").is_ok());
+        let item_collection = super::from_table(table).unwrap();
+        assert_eq!(
+            item_collection.items[99].properties.additional_fields["foo"],
+            99.5
+        );
+    }
+
+    #[test]
+    fn to_table_empty() {
+        let _ = super::to_table(Vec::<Item>::new()).unwrap_err();
+    }
+
+    #[test]
+    fn roundtrip_self_href() {
+        use crate::SelfHref;
+
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let table = super::to_table(vec![item.clone()]).unwrap();
+        let item_collection = super::from_table(table).unwrap();
+        assert!(item_collection.items[0].self_href().is_none());
+
+        let mut no_href = Item::new("no-href");
+        no_href.bbox = item.bbox;
+        no_href.geometry = item.geometry.clone();
+        let table = super::TableBuilder::new(vec![item.clone(), no_href])
+            .self_href(true)
+            .build()
+            .unwrap();
+        let item_collection = super::from_table(table).unwrap();
+        assert_eq!(item_collection.items[0].self_href(), item.self_href());
+        assert!(item_collection.items[1].self_href().is_none());
+        assert!(!item_collection.items[0]
+            .properties
+            .additional_fields
+            .contains_key(super::SELF_HREF_COLUMN));
+    }
+
+    #[test]
+    fn self_href_property() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("self_href".to_string(), "a-value".into());
+        let table = super::TableBuilder::new(vec![item])
+            .self_href(true)
+            .build()
+            .unwrap();
+        let item_collection = super::from_table(table).unwrap();
+        assert_eq!(
+            item_collection.items[0].properties.additional_fields["self_href"],
+            "a-value"
+        );
+    }
+
     #[test]
     fn roundtrip_with_missing_asset() {
         let items: ItemCollection = crate::read("data/two-sentinel-2-items.json").unwrap();
@@ -0,0 +1,535 @@
+use super::{Metadata, METADATA_KEY, VERSION};
+use crate::{Error, ItemCollection, Result};
+use arrow_array::{
+    new_null_array, Array, ArrayRef, LargeListArray, ListArray, RecordBatch, StructArray,
+};
+use arrow_schema::{DataType, Field, FieldRef, Fields, Schema, SchemaRef, TimeUnit};
+use bytes::Bytes;
+use parquet::{
+    arrow::{
+        arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder},
+        ArrowWriter,
+    },
+    file::{metadata::KeyValue, properties::WriterProperties, reader::ChunkReader},
+};
+use serde_json::Value;
+use std::{collections::HashMap, io::Write, sync::Arc};
+
+/// A reader of record batches, and the key-value metadata of a parquet file.
+struct Parquet {
+    schema: SchemaRef,
+    batches: ParquetRecordBatchReader,
+    key_values: HashMap<String, String>,
+}
+
+/// Writes the items in an existing stac-geoparquet file plus new items to a
+/// [std::io::Write] as [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet).
+///
+/// The existing rows are copied one record batch at a time, without
+/// converting them to items or reading the whole file into memory. The schemas are merged: columns that only exist on one side become
+/// nullable, and columns with different types are widened (e.g. integers and
+/// floats become 64-bit floats, and timestamps use the finer unit). Struct
+/// and list columns are merged field-by-field. The `geo` metadata and any
+/// collections in the stac-geoparquet metadata are merged as well.
+///
+/// If the existing file has a [Metadata::base_href], relative asset hrefs in
+/// the new items are resolved against it when the file is read.
+///
+/// Returns an error if a column's types can't be reconciled.
+///
+/// # Examples
+///
+/// ```
+/// use std::{fs::File, io::Cursor};
+/// use stac::Item;
+///
+/// let file = File::open("data/extended-item.parquet").unwrap();
+/// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+/// item.properties.additional_fields.insert("a-new-property".into(), 42.into());
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::append(file, &mut cursor, vec![item]).unwrap();
+/// ```
+pub fn append<R, W>(reader: R, writer: W, item_collection: impl Into<ItemCollection>) -> Result<()>
+where
+    R: ChunkReader + 'static,
+    W: Write + Send,
+{
+    let existing = read(reader)?;
+    // New rows are flattened, rounded, and simplified like the existing ones,
+    // their relative hrefs share the existing base href, and their self hrefs
+    // are kept if the existing ones were.
+    let mut metadata = Metadata::new([]);
+    if let Some(existing) = existing
+        .key_values
+        .get(METADATA_KEY)
+        .map(|value| serde_json::from_str::<Metadata>(value))
+        .transpose()?
+    {
+        metadata.flatten = existing.flatten;
+        metadata.base_href = existing.base_href;
+        metadata.self_href = existing.self_href;
+        metadata.precision = existing.precision;
+        metadata.simplify = existing.simplify;
+    }
+    let mut buf = Vec::new();
+    super::into_writer_with_metadata(&mut buf, item_collection, &metadata)?;
+    let new = read(Bytes::from(buf))?;
+
+    let schema = Arc::new(Schema::new(merge_fields(
+        existing.schema.fields(),
+        new.schema.fields(),
+    )?));
+    let mut key_values = Vec::new();
+    if let Some(geo) =
+        merge_geo_metadata(existing.key_values.get("geo"), new.key_values.get("geo"))?
+    {
+        key_values.push(KeyValue::new("geo".to_string(), geo));
+    }
+    key_values.push(KeyValue::new(
+        METADATA_KEY.to_string(),
+        merge_stac_metadata(
+            existing.key_values.get(METADATA_KEY),
+            new.key_values.get(METADATA_KEY),
+        )?,
+    ));
+    let writer_properties = WriterProperties::builder()
+        .set_key_value_metadata(Some(key_values))
+        .build();
+    let mut writer = ArrowWriter::try_new(writer, schema.clone(), Some(writer_properties))?;
+    for batch in existing.batches.chain(new.batches) {
+        writer.write(&harmonize_batch(batch?, &schema)?)?;
+    }
+    let _ = writer.close()?;
+    Ok(())
+}
+
+fn read<R>(reader: R) -> Result<Parquet>
+where
+    R: ChunkReader + 'static,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let key_values = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .into_iter()
+        .flatten()
+        .filter_map(|key_value| {
+            key_value
+                .value
+                .clone()
+                .map(|value| (key_value.key.clone(), value))
+        })
+        .collect();
+    let schema = builder.schema().clone();
+    let batches = builder.build()?;
+    Ok(Parquet {
+        schema,
+        batches,
+        key_values,
+    })
+}
+
+/// Merges two lists of fields by name, keeping the order of `a` and then any
+/// new fields from `b`.
+pub(super) fn merge_fields(a: &Fields, b: &Fields) -> Result<Vec<Field>> {
+    let mut fields = Vec::with_capacity(a.len() + b.len());
+    for field in a.iter() {
+        if let Some((_, other)) = b.find(field.name()) {
+            fields.push(merge_field(field, other)?);
+        } else {
+            fields.push(field.as_ref().clone().with_nullable(true));
+        }
+    }
+    for field in b.iter() {
+        if a.find(field.name()).is_none() {
+            fields.push(field.as_ref().clone().with_nullable(true));
+        }
+    }
+    Ok(fields)
+}
+
+fn merge_field(a: &Field, b: &Field) -> Result<Field> {
+    let data_type =
+        widen(a.data_type(), b.data_type()).ok_or_else(|| Error::IncompatibleColumnTypes {
+            column: a.name().to_string(),
+            existing: a.data_type().to_string(),
+            new: b.data_type().to_string(),
+        })?;
+    Ok(Field::new(
+        a.name(),
+        data_type,
+        a.is_nullable() || b.is_nullable(),
+    ))
+}
+
+/// Returns a type that can hold values of both types, if there is one.
+fn widen(a: &DataType, b: &DataType) -> Option<DataType> {
+    use DataType::*;
+
+    if a == b {
+        return Some(a.clone());
+    }
+    match (a, b) {
+        (Null, other) | (other, Null) => Some(other.clone()),
+        (a, b) if a.is_integer() && b.is_integer() => Some(Int64),
+        (a, b) if a.is_numeric() && b.is_numeric() => Some(Float64),
+        (Utf8 | LargeUtf8, Utf8 | LargeUtf8) => Some(LargeUtf8),
+        (Binary | LargeBinary, Binary | LargeBinary) => Some(LargeBinary),
+        (Timestamp(a_unit, a_tz), Timestamp(b_unit, b_tz)) if a_tz == b_tz => {
+            Some(Timestamp(finer(a_unit, b_unit), a_tz.clone()))
+        }
+        (Struct(a), Struct(b)) => merge_fields(a, b).ok().map(|fields| Struct(fields.into())),
+        (List(a), List(b)) => merge_field(a, b).ok().map(|field| List(Arc::new(field))),
+        (List(a) | LargeList(a), List(b) | LargeList(b)) => merge_field(a, b)
+            .ok()
+            .map(|field| LargeList(Arc::new(field))),
+        _ => None,
+    }
+}
+
+fn finer(a: &TimeUnit, b: &TimeUnit) -> TimeUnit {
+    fn rank(unit: &TimeUnit) -> u8 {
+        match unit {
+            TimeUnit::Second => 0,
+            TimeUnit::Millisecond => 1,
+            TimeUnit::Microsecond => 2,
+            TimeUnit::Nanosecond => 3,
+        }
+    }
+    if rank(a) >= rank(b) {
+        *a
+    } else {
+        *b
+    }
+}
+
+pub(super) fn harmonize_batch(batch: RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) => harmonize(column, field.data_type()),
+            None => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(schema.clone(), columns).map_err(Error::from)
+}
+
+/// Converts an array to the data type, matching struct fields by name.
+///
+/// [arrow_cast::cast] matches struct fields by position, so structs (and lists
+/// of structs) are rebuilt here.
+fn harmonize(array: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    if array.data_type() == data_type {
+        return Ok(array.clone());
+    }
+    match data_type {
+        DataType::Struct(fields) => {
+            let Some(array) = array.as_any().downcast_ref::<StructArray>() else {
+                return arrow_cast::cast(array, data_type).map_err(Error::from);
+            };
+            let columns = fields
+                .iter()
+                .map(|field| match array.column_by_name(field.name()) {
+                    Some(column) => harmonize(column, field.data_type()),
+                    None => Ok(new_null_array(field.data_type(), array.len())),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Arc::new(StructArray::try_new(
+                fields.clone(),
+                columns,
+                array.nulls().cloned(),
+            )?))
+        }
+        DataType::List(field) | DataType::LargeList(field) => {
+            harmonize_list(array, field, data_type)
+        }
+        _ => arrow_cast::cast(array, data_type).map_err(Error::from),
+    }
+}
+
+fn harmonize_list(array: &ArrayRef, field: &FieldRef, data_type: &DataType) -> Result<ArrayRef> {
+    // Widen the offsets first, then fix up the values.
+    let array = if array.data_type().equals_datatype(data_type) {
+        array.clone()
+    } else {
+        let offsets_only = match (array.data_type(), data_type) {
+            (DataType::List(from), DataType::LargeList(_)) => DataType::LargeList(from.clone()),
+            _ => array.data_type().clone(),
+        };
+        arrow_cast::cast(array, &offsets_only)?
+    };
+    if let Some(list) = array.as_any().downcast_ref::<ListArray>() {
+        let values = harmonize(list.values(), field.data_type())?;
+        Ok(Arc::new(ListArray::try_new(
+            field.clone(),
+            list.offsets().clone(),
+            values,
+            list.nulls().cloned(),
+        )?))
+    } else if let Some(list) = array.as_any().downcast_ref::<LargeListArray>() {
+        let values = harmonize(list.values(), field.data_type())?;
+        Ok(Arc::new(LargeListArray::try_new(
+            field.clone(),
+            list.offsets().clone(),
+            values,
+            list.nulls().cloned(),
+        )?))
+    } else {
+        arrow_cast::cast(&array, data_type).map_err(Error::from)
+    }
+}
+
+/// Merges the geometry types and bboxes of two `geo` metadata values.
+pub(super) fn merge_geo_metadata(a: Option<&String>, b: Option<&String>) -> Result<Option<String>> {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(geo), None) | (None, Some(geo)) => return Ok(Some(geo.clone())),
+        (None, None) => return Ok(None),
+    };
+    let mut a: Value = serde_json::from_str(a)?;
+    let b: Value = serde_json::from_str(b)?;
+    let primary_column = a
+        .get("primary_column")
+        .and_then(|v| v.as_str())
+        .unwrap_or("geometry")
+        .to_string();
+    if let (Some(a), Some(b)) = (
+        a.pointer_mut(&format!("/columns/{}", primary_column)),
+        b.pointer(&format!("/columns/{}", primary_column)),
+    ) {
+        let geometry_types = match (
+            a.get("geometry_types").and_then(|v| v.as_array()),
+            b.get("geometry_types").and_then(|v| v.as_array()),
+        ) {
+            (Some(a), Some(b)) if !a.is_empty() && !b.is_empty() => {
+                let mut geometry_types = a.clone();
+                for geometry_type in b {
+                    if !geometry_types.contains(geometry_type) {
+                        geometry_types.push(geometry_type.clone());
+                    }
+                }
+                geometry_types
+            }
+            _ => Vec::new(),
+        };
+        a["geometry_types"] = geometry_types.into();
+        let bbox = match (
+            a.get("bbox").and_then(as_bbox),
+            b.get("bbox").and_then(as_bbox),
+        ) {
+            (Some(a), Some(b)) if a.len() == b.len() => {
+                let half = a.len() / 2;
+                Some(
+                    (0..a.len())
+                        .map(|i| {
+                            if i < half {
+                                a[i].min(b[i])
+                            } else {
+                                a[i].max(b[i])
+                            }
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }
+            _ => None,
+        };
+        if let Some(a) = a.as_object_mut() {
+            if let Some(bbox) = bbox {
+                let _ = a.insert("bbox".to_string(), bbox.into());
+            } else {
+                let _ = a.remove("bbox");
+            }
+        }
+    }
+    Ok(Some(serde_json::to_string(&a)?))
+}
+
+fn as_bbox(value: &Value) -> Option<Vec<f64>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|v| v.as_f64())
+        .collect::<Option<Vec<_>>>()
+}
+
+/// Merges the collections of two stac-geoparquet metadata values.
+///
+/// Returns an error if both values have a base href and they differ, because
+/// the relative hrefs of one side would be resolved against the wrong base.
+fn merge_stac_metadata(a: Option<&String>, b: Option<&String>) -> Result<String> {
+    let mut metadata = Metadata {
+        version: VERSION.to_string(),
+        collections: Default::default(),
+        base_href: None,
+        flatten: None,
+        self_href: false,
+        precision: None,
+        simplify: None,
+        // Appended rows follow the existing ones, so the file isn't in Hilbert order anymore.
+        hilbert: false,
+    };
+    for value in [a, b].into_iter().flatten() {
+        let other: Metadata = serde_json::from_str(value)?;
+        metadata.collections.extend(other.collections);
+        match (&metadata.base_href, other.base_href) {
+            (Some(a), Some(b)) if *a != b => {
+                return Err(Error::IncompatibleBaseHrefs(a.clone(), b));
+            }
+            (None, base_href) => metadata.base_href = base_href,
+            _ => {}
+        }
+        if metadata.flatten.is_none() {
+            metadata.flatten = other.flatten;
+        }
+        metadata.self_href |= other.self_href;
+        if metadata.precision.is_none() {
+            metadata.precision = other.precision;
+        }
+        if metadata.simplify.is_none() {
+            metadata.simplify = other.simplify;
+        }
+    }
+    serde_json::to_string(&metadata).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geoparquet::Metadata, Asset, Collection, Item};
+    use arrow_schema::{DataType, Field, Fields, TimeUnit};
+    use bytes::Bytes;
+    use std::{fs::File, io::Cursor};
+
+    fn item(id: &str) -> Item {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        item.id = id.to_string();
+        item
+    }
+
+    #[test]
+    fn append() {
+        let file = File::open("data/extended-item.parquet").unwrap();
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("a-new-property".into(), 42.into());
+        let mut cursor = Cursor::new(Vec::new());
+        super::append(file, &mut cursor, vec![item]).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let item_collection = crate::geoparquet::from_reader(bytes.clone()).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        assert_eq!(
+            item_collection.items[1].properties.additional_fields["a-new-property"],
+            42
+        );
+        assert!(!item_collection.items[0]
+            .properties
+            .additional_fields
+            .contains_key("a-new-property"));
+        assert!(crate::geoparquet::metadata_from_reader(bytes)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn append_twice() {
+        let mut cursor = Cursor::new(Vec::new());
+        crate::geoparquet::into_writer_with_collections(
+            &mut cursor,
+            vec![item("a")],
+            [Collection::new("a-collection", "A collection")],
+        )
+        .unwrap();
+        let mut second = Cursor::new(Vec::new());
+        super::append(
+            Bytes::from(cursor.into_inner()),
+            &mut second,
+            vec![item("b")],
+        )
+        .unwrap();
+        let mut third = Cursor::new(Vec::new());
+        super::append(
+            Bytes::from(second.into_inner()),
+            &mut third,
+            vec![item("c")],
+        )
+        .unwrap();
+        let bytes = Bytes::from(third.into_inner());
+        let metadata = crate::geoparquet::metadata_from_reader(bytes.clone())
+            .unwrap()
+            .unwrap();
+        assert!(metadata.collections.contains_key("a-collection"));
+        let item_collection = crate::geoparquet::from_reader(bytes).unwrap();
+        let ids: Vec<_> = item_collection
+            .items
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn append_base_href() {
+        let mut cursor = Cursor::new(Vec::new());
+        crate::geoparquet::into_writer_with_metadata(
+            &mut cursor,
+            vec![item("a")],
+            &Metadata::new([]).base_href("s3://bucket/items.parquet"),
+        )
+        .unwrap();
+        let mut b = item("b");
+        let _ = b.assets.insert("data".to_string(), Asset::new("./b.tif"));
+        let mut appended = Cursor::new(Vec::new());
+        super::append(Bytes::from(cursor.into_inner()), &mut appended, vec![b]).unwrap();
+        let bytes = Bytes::from(appended.into_inner());
+        let metadata = crate::geoparquet::metadata_from_reader(bytes)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.base_href.unwrap(), "s3://bucket/items.parquet");
+    }
+
+    #[test]
+    fn merge_stac_metadata_base_hrefs() {
+        let a =
+            serde_json::to_string(&Metadata::new([]).base_href("s3://a/items.parquet")).unwrap();
+        let b =
+            serde_json::to_string(&Metadata::new([]).base_href("s3://b/items.parquet")).unwrap();
+        let _ = super::merge_stac_metadata(Some(&a), Some(&b)).unwrap_err();
+        let _ = super::merge_stac_metadata(Some(&a), Some(&a)).unwrap();
+        let _ = super::merge_stac_metadata(Some(&a), None).unwrap();
+    }
+
+    #[test]
+    fn widen() {
+        assert_eq!(
+            super::widen(&DataType::Int32, &DataType::Int64),
+            Some(DataType::Int64)
+        );
+        assert_eq!(
+            super::widen(&DataType::Int64, &DataType::Float64),
+            Some(DataType::Float64)
+        );
+        assert_eq!(
+            super::widen(
+                &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+            ),
+            Some(DataType::Timestamp(
+                TimeUnit::Microsecond,
+                Some("UTC".into())
+            ))
+        );
+        assert_eq!(super::widen(&DataType::Utf8, &DataType::Int64), None);
+        let a = DataType::Struct(Fields::from(vec![Field::new("a", DataType::Int64, false)]));
+        let b = DataType::Struct(Fields::from(vec![Field::new("b", DataType::Utf8, false)]));
+        assert_eq!(
+            super::widen(&a, &b),
+            Some(DataType::Struct(Fields::from(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Utf8, true),
+            ])))
+        );
+    }
+}
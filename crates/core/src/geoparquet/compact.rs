@@ -0,0 +1,689 @@
+use super::{
+    append::{harmonize_batch, merge_fields, merge_geo_metadata},
+    feature::{writer_properties_builder, BBOX_FIELDS},
+    hilbert::center,
+    Metadata,
+};
+use crate::{item::FlattenOptions, Bbox, Collection, Error, Result};
+use arrow_array::{
+    cast::AsArray,
+    types::{Float64Type, Int64Type},
+    Array, ArrayRef, Float64Array, Int64Array, RecordBatch,
+};
+use arrow_schema::{DataType, Fields, Schema, SchemaRef, TimeUnit};
+use bytes::Bytes;
+use parquet::{
+    arrow::{
+        arrow_reader::{ParquetRecordBatchReaderBuilder, RowSelection},
+        ArrowWriter, ProjectionMask,
+    },
+    basic::Compression,
+    file::{
+        metadata::KeyValue,
+        reader::{ChunkReader, Length},
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The default maximum number of items in each compacted file.
+pub const DEFAULT_MAX_ITEMS_PER_FILE: usize = 1_000_000;
+
+/// The default number of rows in each parquet row group.
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 100_000;
+
+/// Whether a row has no sort value, and its sort value.
+type SortKey = (bool, i128);
+
+/// The order of items in compacted files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Sort by datetime (or start datetime), so each row group covers a narrow
+    /// time range. Items without a datetime sort last.
+    #[default]
+    Datetime,
+
+    /// Sort along a Hilbert curve, so each row group covers a small area. See
+    /// [sort_by_hilbert](super::sort_by_hilbert).
+    Hilbert,
+}
+
+/// Rewrites many small stac-geoparquet files into fewer, larger ones.
+///
+/// Items are sorted by [Order], so each row group covers a narrow range of
+/// times or a small area and readers like DuckDB can skip row groups when
+/// filtering. The collections in
+/// every input's stac-geoparquet [Metadata](super::Metadata) are written to
+/// every output.
+///
+/// Rows are copied as record batches, without converting them to items. Only
+/// the sort key of each row is held in memory: when writing, the rows of each
+/// output row group are read from the inputs, so memory use is bounded by the
+/// row group size rather than the number of items. Schemas are merged like
+/// [append](super::append) does.
+///
+/// # Examples
+///
+/// ```
+/// use stac::geoparquet::Compactor;
+///
+/// let bytes = std::fs::read("data/extended-item.parquet").unwrap();
+/// let mut compactor = Compactor::new().max_items_per_file(10).row_group_size(5);
+/// compactor.add_bytes(bytes.clone()).unwrap();
+/// compactor.add_bytes(bytes).unwrap();
+/// let count = compactor.write(|_| Ok(std::io::sink())).unwrap();
+/// assert_eq!(count, 1);
+/// ```
+#[derive(Debug)]
+pub struct Compactor {
+    max_items_per_file: usize,
+    row_group_size: usize,
+    compression: Option<Compression>,
+    order: Order,
+    inputs: Vec<Input>,
+    collections: BTreeMap<String, Collection>,
+    flatten: Option<FlattenOptions>,
+    self_href: bool,
+}
+
+/// A stac-geoparquet file added to a [Compactor].
+#[derive(Debug)]
+struct Input {
+    source: Source,
+    schema: SchemaRef,
+    geo: Option<String>,
+    num_rows: usize,
+}
+
+/// Where an input is read from.
+#[derive(Clone, Debug)]
+enum Source {
+    Bytes(Bytes),
+    File(Arc<File>),
+}
+
+impl Compactor {
+    /// Creates a new compactor with the default file and row group sizes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Compactor;
+    /// let compactor = Compactor::new();
+    /// ```
+    pub fn new() -> Compactor {
+        Compactor {
+            max_items_per_file: DEFAULT_MAX_ITEMS_PER_FILE,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            compression: None,
+            order: Order::default(),
+            inputs: Vec::new(),
+            collections: BTreeMap::new(),
+            flatten: None,
+            self_href: false,
+        }
+    }
+
+    /// Sets the maximum number of items in each output file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Compactor;
+    /// let compactor = Compactor::new().max_items_per_file(500_000);
+    /// ```
+    pub fn max_items_per_file(mut self, max_items_per_file: usize) -> Compactor {
+        self.max_items_per_file = max_items_per_file.max(1);
+        self
+    }
+
+    /// Sets the maximum number of rows in each parquet row group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Compactor;
+    /// let compactor = Compactor::new().row_group_size(50_000);
+    /// ```
+    pub fn row_group_size(mut self, row_group_size: usize) -> Compactor {
+        self.row_group_size = row_group_size.max(1);
+        self
+    }
+
+    /// Sets the parquet compression of the output files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::{Compactor, Compression};
+    /// let compactor = Compactor::new().compression(Compression::SNAPPY);
+    /// ```
+    pub fn compression(mut self, compression: Compression) -> Compactor {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the order of the items in the output files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::{Compactor, Order};
+    /// let compactor = Compactor::new().order(Order::Hilbert);
+    /// ```
+    pub fn order(mut self, order: Order) -> Compactor {
+        self.order = order;
+        self
+    }
+
+    /// Adds the items and collections from a stac-geoparquet file.
+    ///
+    /// If more than one file has a collection with the same id, the last one
+    /// wins. If the file has a [Metadata::base_href], or its items were
+    /// flattened differently than the first file's, it's rewritten in memory
+    /// so its relative asset hrefs are absolute and its rows are flattened
+    /// like the others, since the outputs don't have a base href.
+    pub fn add_bytes(&mut self, bytes: impl Into<Bytes>) -> Result<()> {
+        self.add(Source::Bytes(bytes.into()))
+    }
+
+    /// Adds the items and collections from a stac-geoparquet file on disk.
+    ///
+    /// Unlike [Compactor::add_bytes], the file isn't read into memory: rows
+    /// are read from it when the outputs are written, so it must not change
+    /// until then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Compactor;
+    ///
+    /// let mut compactor = Compactor::new();
+    /// compactor.add_path("data/extended-item.parquet").unwrap();
+    /// assert_eq!(compactor.len(), 1);
+    /// ```
+    pub fn add_path(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.add(Source::File(Arc::new(File::open(path)?)))
+    }
+
+    fn add(&mut self, source: Source) -> Result<()> {
+        let metadata = super::metadata_from_reader(source.clone())?;
+        let mut base_href = None;
+        let mut flatten = FlattenOptions::default();
+        let mut self_href = false;
+        if let Some(metadata) = metadata {
+            self.collections.extend(metadata.collections);
+            base_href = metadata.base_href;
+            flatten = metadata.flatten.unwrap_or_default();
+            self_href = metadata.self_href;
+        }
+        self.self_href |= self_href;
+        let expected = *self.flatten.get_or_insert(flatten);
+        let source = if base_href.is_some() || flatten != expected {
+            let mut item_collection = super::from_reader(source)?;
+            if let Some(base_href) = base_href {
+                item_collection.make_asset_hrefs_absolute(&base_href.into())?;
+            }
+            let mut buf = Vec::new();
+            super::into_writer_with_metadata(
+                &mut buf,
+                item_collection,
+                &Metadata::new([]).flatten(expected).self_href(self_href),
+            )?;
+            Source::Bytes(buf.into())
+        } else {
+            source
+        };
+        let builder = ParquetRecordBatchReaderBuilder::try_new(source.clone())?;
+        let file_metadata = builder.metadata().file_metadata();
+        let geo = file_metadata
+            .key_value_metadata()
+            .into_iter()
+            .flatten()
+            .find(|key_value| key_value.key == "geo")
+            .and_then(|key_value| key_value.value.clone());
+        let num_rows = usize::try_from(file_metadata.num_rows())?;
+        self.inputs.push(Input {
+            schema: builder.schema().clone(),
+            source,
+            geo,
+            num_rows,
+        });
+        Ok(())
+    }
+
+    /// Returns the number of items that have been added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Compactor;
+    /// assert_eq!(Compactor::new().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inputs.iter().map(|input| input.num_rows).sum()
+    }
+
+    /// Returns true if no items have been added.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sorts the items and writes them out.
+    ///
+    /// `make_writer` is called with the index of each output file, starting at
+    /// zero. Returns the number of files written.
+    pub fn write<W, F>(self, mut make_writer: F) -> Result<usize>
+    where
+        W: Write + Send,
+        F: FnMut(usize) -> Result<W>,
+    {
+        let mut rows = Vec::with_capacity(self.len());
+        for (index, input) in self.inputs.iter().enumerate() {
+            let keys = input.sort_keys(self.order)?;
+            rows.extend(
+                keys.into_iter()
+                    .enumerate()
+                    .map(|(row, key)| (key, index, row)),
+            );
+        }
+        // The sort is stable, so items with the same key stay in input order.
+        rows.sort_by_key(|(key, _, _)| *key);
+
+        let mut fields = Vec::new();
+        let mut geo = None;
+        for input in &self.inputs {
+            fields = merge_fields(&Fields::from(fields), input.schema.fields())?;
+            geo = merge_geo_metadata(geo.as_ref(), input.geo.as_ref())?;
+        }
+        let schema = Arc::new(Schema::new(fields));
+        let mut metadata = Metadata::new(self.collections.into_values());
+        metadata.flatten = self.flatten;
+        metadata.self_href = self.self_href;
+        metadata.hilbert = self.order == Order::Hilbert;
+        let mut builder =
+            writer_properties_builder(&metadata)?.set_max_row_group_size(self.row_group_size);
+        if let Some(compression) = self.compression {
+            builder = builder.set_compression(compression);
+        }
+        let writer_properties = builder.build();
+
+        let mut count = 0;
+        for file_rows in rows.chunks(self.max_items_per_file) {
+            let mut writer = ArrowWriter::try_new(
+                make_writer(count)?,
+                schema.clone(),
+                Some(writer_properties.clone()),
+            )?;
+            if let Some(geo) = &geo {
+                writer.append_key_value_metadata(KeyValue::new("geo".to_string(), geo.clone()));
+            }
+            for group in file_rows.chunks(self.row_group_size) {
+                writer.write(&read_rows(&self.inputs, group, &schema)?)?;
+                writer.flush()?;
+            }
+            let _ = writer.close()?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl Input {
+    /// Returns the sort key of every row, reading only the columns the order needs.
+    fn sort_keys(&self, order: Order) -> Result<Vec<SortKey>> {
+        let columns: &[&str] = match order {
+            Order::Datetime => &["datetime", "start_datetime"],
+            Order::Hilbert => &["bbox"],
+        };
+        let builder = ParquetRecordBatchReaderBuilder::try_new(self.source.clone())?;
+        let roots: Vec<_> = builder
+            .parquet_schema()
+            .root_schema()
+            .get_fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| columns.contains(&field.name()))
+            .map(|(index, _)| index)
+            .collect();
+        if roots.is_empty() {
+            return Ok(vec![(true, 0); self.num_rows]);
+        }
+        let mask = ProjectionMask::roots(builder.parquet_schema(), roots);
+        let mut keys = Vec::with_capacity(self.num_rows);
+        for batch in builder.with_projection(mask).build()? {
+            let batch = batch?;
+            match order {
+                Order::Datetime => datetime_keys(&batch, &mut keys)?,
+                Order::Hilbert => hilbert_keys(&batch, &mut keys)?,
+            }
+        }
+        Ok(keys)
+    }
+}
+
+/// Reads rows from the inputs, in the order they're given.
+fn read_rows(
+    inputs: &[Input],
+    rows: &[(SortKey, usize, usize)],
+    schema: &SchemaRef,
+) -> Result<RecordBatch> {
+    let mut selected: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (_, input, row) in rows {
+        selected.entry(*input).or_default().push(*row);
+    }
+    let mut batches = Vec::with_capacity(selected.len());
+    let mut positions = HashMap::with_capacity(selected.len());
+    for (index, mut input_rows) in selected {
+        input_rows.sort_unstable();
+        let input = &inputs[index];
+        let selection = RowSelection::from_consecutive_ranges(
+            input_rows.iter().map(|&row| row..row + 1),
+            input.num_rows,
+        );
+        let reader = ParquetRecordBatchReaderBuilder::try_new(input.source.clone())?
+            .with_row_selection(selection)
+            .with_batch_size(input_rows.len())
+            .build()?;
+        let mut parts = Vec::new();
+        for batch in reader {
+            parts.push(harmonize_batch(batch?, schema)?);
+        }
+        let _ = positions.insert(index, (batches.len(), input_rows));
+        batches.push(arrow_select::concat::concat_batches(schema, &parts)?);
+    }
+    let indices: Vec<_> = rows
+        .iter()
+        .map(|(_, input, row)| {
+            let (batch, input_rows) = &positions[input];
+            let position = input_rows
+                .binary_search(row)
+                .expect("every row was selected");
+            (*batch, position)
+        })
+        .collect();
+    let columns = (0..schema.fields().len())
+        .map(|column| {
+            let arrays: Vec<_> = batches
+                .iter()
+                .map(|batch| batch.column(column).as_ref())
+                .collect();
+            arrow_select::interleave::interleave(&arrays, &indices)
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    RecordBatch::try_new(schema.clone(), columns).map_err(Error::from)
+}
+
+/// Pushes the datetime (or start datetime) of each row, in microseconds.
+fn datetime_keys(batch: &RecordBatch, keys: &mut Vec<SortKey>) -> Result<()> {
+    let datetime = batch.column_by_name("datetime").map(micros).transpose()?;
+    let start_datetime = batch
+        .column_by_name("start_datetime")
+        .map(micros)
+        .transpose()?;
+    for row in 0..batch.num_rows() {
+        let value = [&datetime, &start_datetime]
+            .into_iter()
+            .flatten()
+            .find(|array| array.is_valid(row))
+            .map(|array| array.value(row));
+        keys.push((value.is_none(), value.map(i128::from).unwrap_or_default()));
+    }
+    Ok(())
+}
+
+fn micros(array: &ArrayRef) -> Result<Int64Array> {
+    let timezone = match array.data_type() {
+        DataType::Timestamp(_, timezone) => timezone.clone(),
+        _ => Some("UTC".into()),
+    };
+    let array = arrow_cast::cast(array, &DataType::Timestamp(TimeUnit::Microsecond, timezone))?;
+    let array = arrow_cast::cast(&array, &DataType::Int64)?;
+    Ok(array.as_primitive::<Int64Type>().clone())
+}
+
+/// Pushes the [hilbert_index](super::hilbert_index) of the center of each row's bbox.
+fn hilbert_keys(batch: &RecordBatch, keys: &mut Vec<SortKey>) -> Result<()> {
+    let bbox = batch
+        .column_by_name("bbox")
+        .and_then(|column| column.as_struct_opt());
+    let fields = BBOX_FIELDS
+        .iter()
+        .map(|name| {
+            bbox.and_then(|bbox| bbox.column_by_name(name))
+                .map(|column| arrow_cast::cast(column, &DataType::Float64))
+                .transpose()
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let fields: Option<Vec<&Float64Array>> = fields
+        .iter()
+        .map(|field| {
+            field
+                .as_ref()
+                .map(|field| field.as_primitive::<Float64Type>())
+        })
+        .collect();
+    for row in 0..batch.num_rows() {
+        let key = match (&bbox, &fields) {
+            (Some(bbox), Some(fields))
+                if bbox.is_valid(row) && fields.iter().all(|field| field.is_valid(row)) =>
+            {
+                let (x, y) = center(Bbox::TwoDimensional([
+                    fields[0].value(row),
+                    fields[1].value(row),
+                    fields[2].value(row),
+                    fields[3].value(row),
+                ]));
+                (false, i128::from(super::hilbert_index(x, y)))
+            }
+            _ => (true, 0),
+        };
+        keys.push(key);
+    }
+    Ok(())
+}
+
+impl Length for Source {
+    fn len(&self) -> u64 {
+        match self {
+            Source::Bytes(bytes) => Length::len(bytes),
+            Source::File(file) => Length::len(file.as_ref()),
+        }
+    }
+}
+
+impl ChunkReader for Source {
+    type T = Box<dyn Read + Send>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        Ok(match self {
+            Source::Bytes(bytes) => Box::new(bytes.get_read(start)?),
+            Source::File(file) => Box::new(file.as_ref().get_read(start)?),
+        })
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        match self {
+            Source::Bytes(bytes) => bytes.get_bytes(start, length),
+            Source::File(file) => file.as_ref().get_bytes(start, length),
+        }
+    }
+}
+
+impl FromStr for Order {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Order> {
+        match s {
+            "datetime" => Ok(Order::Datetime),
+            "hilbert" => Ok(Order::Hilbert),
+            _ => Err(Error::InvalidOrder(s.to_string())),
+        }
+    }
+}
+
+impl Default for Compactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compactor, Order};
+    use crate::{
+        geoparquet::{self, Metadata},
+        Asset, Bbox, Collection, Item,
+    };
+    use bytes::Bytes;
+    use std::io::Cursor;
+
+    fn parquet(items: Vec<Item>, collection: Collection) -> Bytes {
+        let mut cursor = Cursor::new(Vec::new());
+        geoparquet::into_writer_with_collections(&mut cursor, items, [collection]).unwrap();
+        cursor.into_inner().into()
+    }
+
+    fn item(id: &str) -> Item {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        item.id = id.to_string();
+        item
+    }
+
+    #[test]
+    fn sorted_and_chunked() {
+        let mut a = item("a").collection("one");
+        a.properties.datetime = Some("2023-03-01T00:00:00Z".parse().unwrap());
+        let mut b = item("b").collection("two");
+        b.properties.datetime = Some("2023-01-01T00:00:00Z".parse().unwrap());
+        let mut c = item("c").collection("one");
+        c.properties.datetime = Some("2023-02-01T00:00:00Z".parse().unwrap());
+
+        let mut compactor = Compactor::new().max_items_per_file(2).row_group_size(1);
+        compactor
+            .add_bytes(parquet(vec![a, c], Collection::new("one", "first")))
+            .unwrap();
+        compactor
+            .add_bytes(parquet(vec![b], Collection::new("two", "second")))
+            .unwrap();
+        assert_eq!(compactor.len(), 3);
+
+        let mut outputs = [Vec::new(), Vec::new()];
+        let mut iter = outputs.iter_mut();
+        let count = compactor
+            .write(|_| Ok(Cursor::new(iter.next().unwrap())))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let first = Bytes::from(outputs[0].clone());
+        let ids: Vec<_> = geoparquet::from_reader(first.clone())
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        assert_eq!(ids, ["b", "c"]);
+        let metadata = geoparquet::metadata_from_reader(first).unwrap().unwrap();
+        assert!(metadata.collections.contains_key("one"));
+        assert!(metadata.collections.contains_key("two"));
+    }
+
+    #[test]
+    fn base_hrefs() {
+        let mut compactor = Compactor::new();
+        for (id, base_href) in [("a", "s3://a/items.parquet"), ("b", "s3://b/items.parquet")] {
+            let mut item = item(id);
+            let _ = item
+                .assets
+                .insert("data".to_string(), Asset::new(format!("./{id}.tif")));
+            let mut cursor = Cursor::new(Vec::new());
+            geoparquet::into_writer_with_metadata(
+                &mut cursor,
+                vec![item],
+                &Metadata::new([]).base_href(base_href),
+            )
+            .unwrap();
+            compactor.add_bytes(cursor.into_inner()).unwrap();
+        }
+        let mut output = Vec::new();
+        let mut outputs = std::iter::once(&mut output);
+        let _ = compactor
+            .write(|_| Ok(Cursor::new(outputs.next().unwrap())))
+            .unwrap();
+        let bytes = Bytes::from(output);
+        let metadata = geoparquet::metadata_from_reader(bytes.clone())
+            .unwrap()
+            .unwrap();
+        assert!(metadata.base_href.is_none());
+        let hrefs: Vec<_> = geoparquet::from_reader(bytes)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.assets["data"].href.clone())
+            .collect();
+        assert_eq!(hrefs, ["s3://a/a.tif", "s3://b/b.tif"]);
+    }
+
+    #[test]
+    fn hilbert() {
+        let no_bbox = |id| {
+            let mut item = item(id);
+            item.bbox = None;
+            item
+        };
+        let mut compactor = Compactor::new().order(Order::Hilbert);
+        let items = [("tokyo", 139.7, 35.7), ("boulder", -105.3, 40.0)]
+            .into_iter()
+            .map(|(id, x, y)| {
+                let mut item = item(id);
+                item.bbox = Some(Bbox::TwoDimensional([x, y, x + 0.1, y + 0.1]));
+                item
+            })
+            .chain([no_bbox("no-bbox")])
+            .collect();
+        compactor
+            .add_bytes(parquet(items, Collection::new("one", "first")))
+            .unwrap();
+        compactor
+            .add_bytes(parquet(
+                vec![no_bbox("a")],
+                Collection::new("two", "second"),
+            ))
+            .unwrap();
+        let mut output = Vec::new();
+        let mut outputs = std::iter::once(&mut output);
+        let _ = compactor
+            .write(|_| Ok(Cursor::new(outputs.next().unwrap())))
+            .unwrap();
+        let output = Bytes::from(output);
+        assert!(
+            geoparquet::metadata_from_reader(output.clone())
+                .unwrap()
+                .unwrap()
+                .hilbert
+        );
+        let ids: Vec<_> = geoparquet::from_reader(output)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        if geoparquet::hilbert_index(139.75, 35.75) < geoparquet::hilbert_index(-105.25, 40.05) {
+            assert_eq!(ids, ["tokyo", "boulder", "no-bbox", "a"]);
+        } else {
+            assert_eq!(ids, ["boulder", "tokyo", "no-bbox", "a"]);
+        }
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!("hilbert".parse::<Order>().unwrap(), Order::Hilbert);
+        let _ = "random".parse::<Order>().unwrap_err();
+    }
+}
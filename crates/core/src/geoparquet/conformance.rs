@@ -0,0 +1,351 @@
+use super::{Metadata, METADATA_KEY};
+use crate::Result;
+use arrow_schema::{DataType, Schema};
+use parquet::{arrow::arrow_reader::ParquetRecordBatchReaderBuilder, file::reader::ChunkReader};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+const REQUIRED_COLUMNS: [&str; 7] = [
+    "type",
+    "stac_version",
+    "id",
+    "geometry",
+    "bbox",
+    "links",
+    "assets",
+];
+const STRING_COLUMNS: [&str; 4] = ["type", "stac_version", "id", "collection"];
+const DATETIME_COLUMNS: [&str; 5] = [
+    "datetime",
+    "start_datetime",
+    "end_datetime",
+    "created",
+    "updated",
+];
+const BBOX_FIELDS: [&str; 6] = ["xmin", "ymin", "zmin", "xmax", "ymax", "zmax"];
+
+/// A way in which a parquet file does not conform to the
+/// [stac-geoparquet specification](https://github.com/stac-utils/stac-geoparquet/blob/main/spec/stac-geoparquet-spec.md).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// The column with the problem, or `None` if the problem is with the
+    /// file's metadata.
+    pub column: Option<String>,
+
+    /// A description of the problem.
+    pub message: String,
+}
+
+/// Checks a parquet file's structure against the stac-geoparquet specification.
+///
+/// Checks the required columns, the geometry encoding metadata, the datetime
+/// column types, the bbox struct layout, and the stac-geoparquet version
+/// metadata. Returns an empty vector if no problems were found. Only the
+/// file's metadata is read, not its rows.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use bytes::Bytes;
+/// use stac::Item;
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::into_writer(&mut cursor, vec![item]).unwrap();
+/// let problems = stac::geoparquet::validate_reader(Bytes::from(cursor.into_inner())).unwrap();
+/// assert!(problems.is_empty());
+/// ```
+pub fn validate_reader<R>(reader: R) -> Result<Vec<Problem>>
+where
+    R: ChunkReader + 'static,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let key_values = builder.metadata().file_metadata().key_value_metadata();
+    let get = |key: &str| {
+        key_values
+            .and_then(|key_values| key_values.iter().find(|key_value| key_value.key == key))
+            .and_then(|key_value| key_value.value.as_deref())
+    };
+    let mut problems = Vec::new();
+    validate_schema(builder.schema(), &mut problems);
+    validate_geo_metadata(get("geo"), &mut problems);
+    validate_stac_metadata(get(METADATA_KEY), &mut problems);
+    Ok(problems)
+}
+
+fn validate_schema(schema: &Schema, problems: &mut Vec<Problem>) {
+    let data_type = |name: &str| {
+        schema
+            .field_with_name(name)
+            .ok()
+            .map(|field| field.data_type())
+    };
+    for column in REQUIRED_COLUMNS {
+        if data_type(column).is_none() {
+            problems.push(Problem::column(column, "required column is missing"));
+        }
+    }
+    if data_type("datetime").is_none()
+        && (data_type("start_datetime").is_none() || data_type("end_datetime").is_none())
+    {
+        problems.push(Problem::column(
+            "datetime",
+            "required column is missing, and start_datetime and end_datetime are not both present",
+        ));
+    }
+    for column in STRING_COLUMNS {
+        if let Some(data_type) = data_type(column) {
+            if !is_string(data_type) {
+                problems.push(Problem::column(
+                    column,
+                    format!("should be a string, found {}", data_type),
+                ));
+            }
+        }
+    }
+    for column in DATETIME_COLUMNS {
+        if let Some(data_type) = data_type(column) {
+            if !matches!(data_type, DataType::Timestamp(_, Some(_))) {
+                problems.push(Problem::column(
+                    column,
+                    format!(
+                        "should be a timestamp with a UTC time zone, found {}",
+                        data_type
+                    ),
+                ));
+            }
+        }
+    }
+    if let Some(data_type) = data_type("geometry") {
+        if !matches!(
+            data_type,
+            DataType::Binary | DataType::LargeBinary | DataType::BinaryView
+        ) {
+            problems.push(Problem::column(
+                "geometry",
+                format!("should be WKB-encoded binary, found {}", data_type),
+            ));
+        }
+    }
+    if let Some(data_type) = data_type("bbox") {
+        validate_bbox(data_type, problems);
+    }
+    if let Some(data_type) = data_type("stac_extensions") {
+        if !list_value_type(data_type).is_some_and(is_string) {
+            problems.push(Problem::column(
+                "stac_extensions",
+                format!("should be a list of strings, found {}", data_type),
+            ));
+        }
+    }
+    if let Some(data_type) = data_type("links") {
+        if !list_value_type(data_type)
+            .is_some_and(|data_type| matches!(data_type, DataType::Struct(_)))
+        {
+            problems.push(Problem::column(
+                "links",
+                format!("should be a list of structs, found {}", data_type),
+            ));
+        }
+    }
+    if let Some(data_type) = data_type("assets") {
+        if !matches!(data_type, DataType::Struct(_)) {
+            problems.push(Problem::column(
+                "assets",
+                format!("should be a struct, found {}", data_type),
+            ));
+        }
+    }
+}
+
+fn validate_bbox(data_type: &DataType, problems: &mut Vec<Problem>) {
+    let DataType::Struct(fields) = data_type else {
+        problems.push(Problem::column(
+            "bbox",
+            format!(
+                "should be a struct of xmin, ymin, xmax, and ymax, found {}",
+                data_type
+            ),
+        ));
+        return;
+    };
+    let names: Vec<_> = fields.iter().map(|field| field.name().as_str()).collect();
+    for name in ["xmin", "ymin", "xmax", "ymax"] {
+        if !names.contains(&name) {
+            problems.push(Problem::column("bbox", format!("missing {} field", name)));
+        }
+    }
+    if names.contains(&"zmin") != names.contains(&"zmax") {
+        problems.push(Problem::column(
+            "bbox",
+            "should have both or neither of zmin and zmax",
+        ));
+    }
+    for field in fields.iter() {
+        if !BBOX_FIELDS.contains(&field.name().as_str()) {
+            problems.push(Problem::column(
+                "bbox",
+                format!("unexpected {} field", field.name()),
+            ));
+        } else if !matches!(field.data_type(), DataType::Float32 | DataType::Float64) {
+            problems.push(Problem::column(
+                "bbox",
+                format!(
+                    "{} field should be a float, found {}",
+                    field.name(),
+                    field.data_type()
+                ),
+            ));
+        }
+    }
+}
+
+fn validate_geo_metadata(geo: Option<&str>, problems: &mut Vec<Problem>) {
+    let Some(geo) = geo else {
+        problems.push(Problem::file("missing geo metadata"));
+        return;
+    };
+    let geo: Value = match serde_json::from_str(geo) {
+        Ok(geo) => geo,
+        Err(err) => {
+            problems.push(Problem::file(format!("invalid geo metadata: {}", err)));
+            return;
+        }
+    };
+    let Some(primary_column) = geo.get("primary_column").and_then(|v| v.as_str()) else {
+        problems.push(Problem::file("geo metadata is missing primary_column"));
+        return;
+    };
+    if primary_column != "geometry" {
+        problems.push(Problem::file(format!(
+            "geo metadata primary_column should be geometry, found {}",
+            primary_column
+        )));
+    }
+    match geo
+        .get("columns")
+        .and_then(|columns| columns.get(primary_column))
+        .and_then(|column| column.get("encoding"))
+        .and_then(|encoding| encoding.as_str())
+    {
+        Some(encoding) if encoding.eq_ignore_ascii_case("wkb") => {}
+        Some(encoding) => problems.push(Problem::column(
+            primary_column,
+            format!("geo metadata encoding should be WKB, found {}", encoding),
+        )),
+        None => problems.push(Problem::column(
+            primary_column,
+            "geo metadata is missing the column's encoding",
+        )),
+    }
+}
+
+fn validate_stac_metadata(metadata: Option<&str>, problems: &mut Vec<Problem>) {
+    let Some(metadata) = metadata else {
+        problems.push(Problem::file(format!("missing {} metadata", METADATA_KEY)));
+        return;
+    };
+    if let Err(err) = serde_json::from_str::<Metadata>(metadata) {
+        problems.push(Problem::file(format!(
+            "invalid {} metadata: {}",
+            METADATA_KEY, err
+        )));
+    }
+}
+
+fn list_value_type(data_type: &DataType) -> Option<&DataType> {
+    match data_type {
+        DataType::List(field) | DataType::LargeList(field) => Some(field.data_type()),
+        _ => None,
+    }
+}
+
+fn is_string(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => true,
+        DataType::Dictionary(_, value_type) => is_string(value_type),
+        _ => false,
+    }
+}
+
+impl Problem {
+    fn column(column: impl ToString, message: impl ToString) -> Problem {
+        Problem {
+            column: Some(column.to_string()),
+            message: message.to_string(),
+        }
+    }
+
+    fn file(message: impl ToString) -> Problem {
+        Problem {
+            column: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(column) = &self.column {
+            write!(f, "{}: {}", column, self.message)
+        } else {
+            f.write_str(&self.message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Problem;
+    use crate::Item;
+    use bytes::Bytes;
+    use std::{fs::File, io::Cursor};
+
+    #[test]
+    fn valid() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut cursor = Cursor::new(Vec::new());
+        crate::geoparquet::into_writer(&mut cursor, vec![item]).unwrap();
+        let problems = super::validate_reader(Bytes::from(cursor.into_inner())).unwrap();
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[test]
+    fn missing_version_metadata() {
+        let file = File::open("data/extended-item.parquet").unwrap();
+        let problems = super::validate_reader(file).unwrap();
+        assert!(problems.contains(&Problem::file("missing stac-geoparquet metadata")));
+    }
+
+    #[test]
+    fn bbox() {
+        use arrow_schema::{DataType, Field, Fields};
+
+        let mut problems = Vec::new();
+        super::validate_bbox(
+            &DataType::Struct(Fields::from(vec![
+                Field::new("xmin", DataType::Float64, false),
+                Field::new("ymin", DataType::Utf8, false),
+                Field::new("xmax", DataType::Float64, false),
+                Field::new("zmin", DataType::Float64, false),
+            ])),
+            &mut problems,
+        );
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().all(|p| p.column.as_deref() == Some("bbox")));
+    }
+
+    #[test]
+    fn not_wkb() {
+        let mut problems = Vec::new();
+        super::validate_geo_metadata(
+            Some(
+                r#"{"primary_column": "geometry", "columns": {"geometry": {"encoding": "point"}}}"#,
+            ),
+            &mut problems,
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].column.as_deref(), Some("geometry"));
+    }
+}
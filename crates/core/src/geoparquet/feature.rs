@@ -1,13 +1,30 @@
-use super::{FromGeoparquet, IntoGeoparquet};
-use crate::{Error, Item, ItemCollection, Result, Value};
+use super::{FromGeoparquet, IntoGeoparquet, Metadata, METADATA_KEY};
+use crate::{
+    geoarrow::TableBuilder,
+    item::FlattenOptions,
+    partial::{ReadOptions, ReadReport},
+    Collection, Error, Item, ItemCollection, Result, Value,
+};
+use arrow_array::RecordBatchReader;
+use arrow_schema::Schema;
 use bytes::Bytes;
-use geoarrow::io::parquet::{GeoParquetRecordBatchReaderBuilder, GeoParquetWriterOptions};
+use geoarrow::{
+    io::parquet::{GeoParquetRecordBatchReaderBuilder, GeoParquetWriterOptions},
+    table::Table,
+};
 use parquet::{
     basic::Compression,
-    file::{properties::WriterProperties, reader::ChunkReader},
+    file::{
+        metadata::KeyValue,
+        properties::{WriterProperties, WriterPropertiesBuilder},
+        reader::{ChunkReader, FileReader, SerializedFileReader},
+    },
 };
 use std::{fs::File, io::Write, path::Path};
 
+/// The fields of the `bbox` struct column.
+pub(super) const BBOX_FIELDS: [&str; 4] = ["xmin", "ymin", "xmax", "ymax"];
+
 /// Reads a [ItemCollection] from a [ChunkReader] as
 /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet).
 ///
@@ -24,8 +41,83 @@ where
     R: ChunkReader + 'static,
 {
     let reader = GeoParquetRecordBatchReaderBuilder::try_new(reader)?.build()?;
+    let flatten = flatten_options(&reader.schema())?;
     let table = reader.read_table()?;
-    crate::geoarrow::from_table(table).map_err(Error::from)
+    crate::geoarrow::from_table_with(table, flatten)
+}
+
+/// Reads items from a [ChunkReader] as
+/// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet), one
+/// record batch at a time.
+///
+/// Unlike [from_reader], only one batch of items is in memory at once.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let file = File::open("data/extended-item.parquet").unwrap();
+/// for item_collection in stac::geoparquet::batches_from_reader(file).unwrap() {
+///     let item_collection = item_collection.unwrap();
+/// }
+/// ```
+pub fn batches_from_reader<R>(reader: R) -> Result<impl Iterator<Item = Result<ItemCollection>>>
+where
+    R: ChunkReader + 'static,
+{
+    let reader = GeoParquetRecordBatchReaderBuilder::try_new(reader)?.build()?;
+    let schema = reader.schema();
+    let flatten = flatten_options(&schema)?;
+    Ok(reader.map(move |batch| {
+        let table = Table::try_new(vec![batch?], schema.clone())?;
+        crate::geoarrow::from_table_with(table, flatten)
+    }))
+}
+
+/// Returns the [FlattenOptions] recorded in the stac-geoparquet metadata of a
+/// reader's schema, or the defaults.
+fn flatten_options(schema: &Schema) -> Result<FlattenOptions> {
+    let metadata: Option<Metadata> = schema
+        .metadata()
+        .get(METADATA_KEY)
+        .map(|value| serde_json::from_str(value))
+        .transpose()?;
+    Ok(metadata
+        .and_then(|metadata| metadata.flatten)
+        .unwrap_or_default())
+}
+
+/// Reads the stac-geoparquet [Metadata] from a [ChunkReader].
+///
+/// Returns `None` if the file doesn't have any stac-geoparquet metadata.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let file = File::open("data/extended-item.parquet").unwrap();
+/// let metadata = stac::geoparquet::metadata_from_reader(file).unwrap();
+/// ```
+pub fn metadata_from_reader<R>(reader: R) -> Result<Option<Metadata>>
+where
+    R: ChunkReader + 'static,
+{
+    let reader = SerializedFileReader::new(reader)?;
+    reader
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|key_values| {
+            key_values
+                .iter()
+                .find(|key_value| key_value.key == METADATA_KEY)
+        })
+        .and_then(|key_value| key_value.value.as_deref())
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(Error::from)
 }
 
 /// Writes a [ItemCollection] to a [std::io::Write] as
@@ -48,7 +140,7 @@ pub fn into_writer<W>(writer: W, item_collection: impl Into<ItemCollection>) ->
 where
     W: Write + Send,
 {
-    into_writer_with_options(writer, item_collection, &Default::default())
+    into_writer_with_collections(writer, item_collection, [])
 }
 
 /// Writes a [ItemCollection] to a [std::io::Write] as
@@ -74,16 +166,102 @@ where
     W: Write + Send,
 {
     let mut options = GeoParquetWriterOptions::default();
-    let writer_properties = WriterProperties::builder()
+    let writer_properties = writer_properties_builder(&Metadata::new([]))?
         .set_compression(compression)
         .build();
     options.writer_properties = Some(writer_properties);
     into_writer_with_options(writer, item_collection, &options)
 }
 
+/// Writes a [ItemCollection] to a [std::io::Write] as
+/// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet), with the
+/// collections embedded in the file's metadata.
+///
+/// Read the collections back with [metadata_from_reader].
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use stac::{Collection, Item};
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let collection: Collection = stac::read("examples/collection.json").unwrap();
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::into_writer_with_collections(&mut cursor, vec![item], [collection]).unwrap();
+/// ```
+pub fn into_writer_with_collections<W>(
+    writer: W,
+    item_collection: impl Into<ItemCollection>,
+    collections: impl IntoIterator<Item = Collection>,
+) -> Result<()>
+where
+    W: Write + Send,
+{
+    into_writer_with_metadata(writer, item_collection, &Metadata::new(collections))
+}
+
+/// Writes a [ItemCollection] to a [std::io::Write] as
+/// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet) with the
+/// provided stac-geoparquet [Metadata].
+///
+/// Use this to record a [Metadata::base_href] for items with relative asset
+/// hrefs. Items are flattened with the metadata's [Metadata::flatten]
+/// options, which readers use to restore them, their self hrefs are kept if
+/// [Metadata::self_href] is set, their geometries are simplified and
+/// rounded if [Metadata::simplify] and [Metadata::precision] are set, and
+/// they're sorted along a Hilbert curve if [Metadata::hilbert] is set.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use stac::{geoparquet::Metadata, Item};
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let metadata = Metadata::new([]).base_href("examples/simple-item.json");
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::into_writer_with_metadata(&mut cursor, vec![item], &metadata).unwrap();
+/// ```
+pub fn into_writer_with_metadata<W>(
+    writer: W,
+    item_collection: impl Into<ItemCollection>,
+    metadata: &Metadata,
+) -> Result<()>
+where
+    W: Write + Send,
+{
+    let options = GeoParquetWriterOptions {
+        writer_properties: Some(writer_properties_builder(metadata)?.build()),
+        ..Default::default()
+    };
+    let mut item_collection = item_collection.into();
+    if metadata.hilbert {
+        super::sort_by_hilbert(&mut item_collection.items);
+    }
+    let mut builder = TableBuilder::new(item_collection)
+        .flatten(metadata.flatten.unwrap_or_default())
+        .self_href(metadata.self_href);
+    if let Some(precision) = metadata.precision {
+        builder = builder.precision(precision);
+    }
+    #[cfg(feature = "geo")]
+    if let Some(epsilon) = metadata.simplify {
+        builder = builder.simplify(epsilon);
+    }
+    #[cfg(not(feature = "geo"))]
+    if metadata.simplify.is_some() {
+        return Err(Error::FeatureNotEnabled("geo"));
+    }
+    write_table(writer, builder.build()?, &options)
+}
+
 /// Writes a [ItemCollection] to a [std::io::Write] as
 /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet) with the provided options.
 ///
+/// Unlike the other writers, this function doesn't add any stac-geoparquet
+/// [Metadata]: set it in the options' writer properties if you need it.
+///
 /// # Examples
 ///
 /// ```
@@ -103,20 +281,64 @@ pub fn into_writer_with_options<W>(
 where
     W: Write + Send,
 {
-    let table = crate::geoarrow::to_table(item_collection)?;
+    write_table(writer, crate::geoarrow::to_table(item_collection)?, options)
+}
+
+fn write_table<W>(writer: W, table: Table, options: &GeoParquetWriterOptions) -> Result<()>
+where
+    W: Write + Send,
+{
     geoarrow::io::parquet::write_geoparquet(table.into_record_batch_reader(), writer, options)
         .map_err(Error::from)
 }
 
+/// Returns a writer properties builder with the stac-geoparquet [Metadata].
+pub(super) fn writer_properties_builder(metadata: &Metadata) -> Result<WriterPropertiesBuilder> {
+    let metadata = serde_json::to_string(metadata)?;
+    Ok(
+        WriterProperties::builder().set_key_value_metadata(Some(vec![KeyValue::new(
+            METADATA_KEY.to_string(),
+            metadata,
+        )])),
+    )
+}
+
+/// Makes relative asset hrefs absolute if the metadata has a base href.
+fn apply_base_href(item_collection: &mut ItemCollection, metadata: Option<Metadata>) -> Result<()> {
+    if let Some(base_href) = metadata.and_then(|metadata| metadata.base_href) {
+        item_collection.make_asset_hrefs_absolute(&base_href.into())?;
+    }
+    Ok(())
+}
+
+/// Reads items from stac-geoparquet bytes, skipping invalid rows if asked.
+pub(crate) fn read_items(bytes: Bytes, options: ReadOptions) -> Result<ReadReport> {
+    let metadata = metadata_from_reader(bytes.clone())?;
+    let flatten = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.flatten)
+        .unwrap_or_default();
+    let reader = GeoParquetRecordBatchReaderBuilder::try_new(bytes)?.build()?;
+    let mut report = crate::geoarrow::from_table_opts(reader.read_table()?, options, flatten)?;
+    apply_base_href(&mut report.item_collection, metadata)?;
+    Ok(report)
+}
+
 impl FromGeoparquet for ItemCollection {
     fn from_geoparquet_path(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let file = File::open(path)?;
-        from_reader(file)
+        let metadata = metadata_from_reader(file.try_clone()?)?;
+        let mut item_collection = from_reader(file)?;
+        apply_base_href(&mut item_collection, metadata)?;
+        Ok(item_collection)
     }
 
     fn from_geoparquet_bytes(bytes: impl Into<Bytes>) -> Result<Self> {
-        let item_collection = from_reader(bytes.into())?;
+        let bytes = bytes.into();
+        let metadata = metadata_from_reader(bytes.clone())?;
+        let mut item_collection = from_reader(bytes)?;
+        apply_base_href(&mut item_collection, metadata)?;
         Ok(item_collection)
     }
 }
@@ -182,7 +404,7 @@ impl IntoGeoparquet for serde_json::Value {
 
 #[cfg(test)]
 mod tests {
-    use crate::{FromGeoparquet, Item, ItemCollection, SelfHref, Value};
+    use crate::{Collection, FromGeoparquet, Item, ItemCollection, SelfHref, Value};
     use bytes::Bytes;
     use std::{
         fs::File,
@@ -215,6 +437,187 @@ mod tests {
         assert_eq!(item_collection.items[0], item);
     }
 
+    #[test]
+    fn bbox_statistics() {
+        use parquet::file::{
+            reader::{FileReader, SerializedFileReader},
+            statistics::Statistics,
+        };
+
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let bbox = item.bbox.unwrap();
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![item]).unwrap();
+        let reader = SerializedFileReader::new(Bytes::from(cursor.into_inner())).unwrap();
+        let row_group = reader.metadata().row_group(0);
+        let expected = [bbox.xmin(), bbox.ymin(), bbox.xmax(), bbox.ymax()];
+        for (field, expected) in super::BBOX_FIELDS.into_iter().zip(expected) {
+            let column = row_group
+                .columns()
+                .iter()
+                .find(|column| column.column_path().string() == format!("bbox.{}", field))
+                .unwrap();
+            let Some(Statistics::Double(statistics)) = column.statistics() else {
+                panic!("bbox.{field} should have double statistics");
+            };
+            assert_eq!(statistics.min_opt(), Some(&expected));
+            assert_eq!(statistics.max_opt(), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn roundtrip_collections() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut collection: Collection = crate::read("examples/collection.json").unwrap();
+        *collection.self_href_mut() = None;
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_collections(&mut cursor, vec![item], [collection.clone()]).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let metadata = super::metadata_from_reader(bytes.clone()).unwrap().unwrap();
+        assert_eq!(metadata.version, crate::geoparquet::VERSION);
+        assert_eq!(metadata.collections[&collection.id], collection);
+        assert_eq!(super::from_reader(bytes).unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn base_href() {
+        use crate::{geoparquet::Metadata, Asset};
+
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let _ = item
+            .assets
+            .insert("data".to_string(), Asset::new("./data/an-id.tif"));
+        let _ = item
+            .assets
+            .insert("absolute".to_string(), Asset::new("/data/an-id.tif"));
+        let metadata = Metadata::new([]).base_href("s3://bucket/items.json");
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_metadata(&mut cursor, vec![item], &metadata).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        assert_eq!(
+            super::from_reader(bytes.clone()).unwrap().items[0].assets["data"].href,
+            "./data/an-id.tif"
+        );
+        let item_collection = ItemCollection::from_geoparquet_bytes(bytes).unwrap();
+        let assets = &item_collection.items[0].assets;
+        assert_eq!(assets["data"].href, "s3://bucket/data/an-id.tif");
+        assert_eq!(assets["absolute"].href, "/data/an-id.tif");
+    }
+
+    #[test]
+    fn roundtrip_self_href() {
+        use crate::{geoparquet::Metadata, SelfHref};
+
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_metadata(
+            &mut cursor,
+            vec![item.clone()],
+            &Metadata::new([]).self_href(true),
+        )
+        .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        assert!(
+            super::metadata_from_reader(bytes.clone())
+                .unwrap()
+                .unwrap()
+                .self_href
+        );
+        let item_collection = super::from_reader(bytes).unwrap();
+        assert_eq!(item_collection.items[0].self_href(), item.self_href());
+    }
+
+    #[test]
+    fn precision() {
+        use crate::geoparquet::Metadata;
+
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_metadata(&mut cursor, vec![item], &Metadata::new([]).precision(1))
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        assert_eq!(
+            super::metadata_from_reader(bytes.clone())
+                .unwrap()
+                .unwrap()
+                .precision,
+            Some(1)
+        );
+        let item_collection = super::from_reader(bytes).unwrap();
+        let bbox = item_collection.items[0].bbox.unwrap();
+        assert_eq!(bbox.xmin(), 172.9);
+        assert_eq!(bbox.ymin(), 1.3);
+    }
+
+    #[test]
+    fn hilbert() {
+        use crate::{geoparquet::Metadata, Bbox};
+
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let items: Vec<_> = [
+            ("no-bbox", None),
+            ("tokyo", Some(Bbox::new(139.7, 35.7, 139.7, 35.7))),
+            ("boulder", Some(Bbox::new(-105.3, 40.0, -105.3, 40.0))),
+        ]
+        .into_iter()
+        .map(|(id, bbox)| {
+            let mut item = item.clone();
+            item.id = id.to_string();
+            item.bbox = bbox;
+            item
+        })
+        .collect();
+        let mut expected = items.clone();
+        crate::geoparquet::sort_by_hilbert(&mut expected);
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_metadata(&mut cursor, items, &Metadata::new([]).hilbert(true))
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        assert!(
+            super::metadata_from_reader(bytes.clone())
+                .unwrap()
+                .unwrap()
+                .hilbert
+        );
+        let ids: Vec<_> = super::from_reader(bytes)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        let expected: Vec<_> = expected.into_iter().map(|item| item.id).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(ids[2], "no-bbox");
+    }
+
+    #[test]
+    fn flatten_options() {
+        use crate::{
+            geoparquet::Metadata,
+            item::{Collision, FlattenOptions},
+        };
+
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let _ = item.additional_fields.insert("foo".into(), "bar".into());
+        let metadata = Metadata::new([]).flatten(FlattenOptions {
+            collision: Collision::Prefix,
+            separator: None,
+        });
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer_with_metadata(&mut cursor, vec![item], &metadata).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let item_collection = super::from_reader(bytes.clone()).unwrap();
+        assert_eq!(item_collection.items[0].additional_fields["foo"], "bar");
+        let item_collection = ItemCollection::from_geoparquet_bytes(bytes).unwrap();
+        assert_eq!(item_collection.items[0].additional_fields["foo"], "bar");
+    }
+
+    #[test]
+    fn metadata_missing() {
+        let file = File::open("data/extended-item.parquet").unwrap();
+        assert!(super::metadata_from_reader(file).unwrap().is_none());
+    }
+
     #[test]
     fn read() {
         let _ = ItemCollection::from_geoparquet_path("data/extended-item.parquet");
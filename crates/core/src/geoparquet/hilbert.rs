@@ -0,0 +1,125 @@
+use crate::{Bbox, Item};
+
+/// The number of bits used for each axis of the [hilbert_index].
+const ORDER: u32 = 16;
+
+/// Returns the position of a longitude and latitude along a Hilbert curve.
+///
+/// Points that are close together usually have close indices, so writing
+/// items in index order keeps each parquet row group's bbox small, which lets
+/// readers skip row groups when filtering on bbox. Coordinates are clamped to
+/// the valid longitude and latitude ranges.
+///
+/// # Examples
+///
+/// ```
+/// use stac::geoparquet::hilbert_index;
+///
+/// let a = hilbert_index(-105.1, 40.1);
+/// let b = hilbert_index(-105.2, 40.2);
+/// let c = hilbert_index(139.7, 35.7);
+/// assert!(a.abs_diff(b) < a.abs_diff(c));
+/// ```
+pub fn hilbert_index(longitude: f64, latitude: f64) -> u64 {
+    let n = 1u32 << ORDER;
+    let scale = |value: f64, min: f64, max: f64| {
+        let value = ((value.clamp(min, max) - min) / (max - min) * f64::from(n)) as u32;
+        value.min(n - 1)
+    };
+    let mut x = scale(longitude, -180., 180.);
+    let mut y = scale(latitude, -90., 90.);
+    let mut d = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        d += u64::from(s) * u64::from(s) * u64::from((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    d
+}
+
+/// Sorts items by the [hilbert_index] of the center of their bbox.
+///
+/// Items without a bbox are sorted last, in their original order.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Bbox, Item};
+///
+/// let mut items: Vec<_> = [(139.7, 35.7), (-105.1, 40.1), (139.8, 35.6)]
+///     .into_iter()
+///     .enumerate()
+///     .map(|(i, (x, y))| {
+///         let mut item = Item::new(i.to_string());
+///         item.bbox = Some(Bbox::new(x, y, x, y));
+///         item
+///     })
+///     .collect();
+/// stac::geoparquet::sort_by_hilbert(&mut items);
+/// assert_eq!(items[0].id, "1");
+/// ```
+pub fn sort_by_hilbert(items: &mut [Item]) {
+    items.sort_by_cached_key(|item| match item.bbox {
+        Some(bbox) => {
+            let (x, y) = center(bbox);
+            (false, hilbert_index(x, y))
+        }
+        None => (true, 0),
+    });
+}
+
+pub(super) fn center(bbox: Bbox) -> (f64, f64) {
+    let (xmin, xmax) = (bbox.xmin(), bbox.xmax());
+    let x = if xmin > xmax {
+        // The bbox crosses the antimeridian.
+        let x = (xmin + xmax + 360.) / 2.;
+        if x > 180. {
+            x - 360.
+        } else {
+            x
+        }
+    } else {
+        (xmin + xmax) / 2.
+    };
+    (x, (bbox.ymin() + bbox.ymax()) / 2.)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bbox, Item};
+
+    #[test]
+    fn corners() {
+        assert_eq!(super::hilbert_index(-180., -90.), 0);
+        assert_eq!(super::hilbert_index(180., -90.), (1 << 32) - 1);
+        assert_eq!(super::hilbert_index(-1000., -1000.), 0);
+    }
+
+    #[test]
+    fn antimeridian() {
+        let (x, _) = super::center(Bbox::new(170., 0., -170., 10.));
+        assert_eq!(x, 180.);
+        let (x, _) = super::center(Bbox::new(175., 0., -165., 10.));
+        assert_eq!(x, -175.);
+    }
+
+    #[test]
+    fn without_bbox_last() {
+        let mut a = Item::new("a");
+        a.bbox = None;
+        let mut b = Item::new("b");
+        b.bbox = Some(Bbox::new(0., 0., 1., 1.));
+        let mut items = vec![a, b];
+        super::sort_by_hilbert(&mut items);
+        assert_eq!(items[0].id, "b");
+    }
+}
@@ -1,26 +1,232 @@
 //! Read data from and write data to [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet/blob/main/spec/stac-geoparquet-spec.md).
 
-use crate::Result;
+use crate::{item::FlattenOptions, Collection, Result};
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Write},
     path::Path,
 };
 
+#[cfg(feature = "geoparquet")]
+mod append;
+#[cfg(feature = "geoparquet")]
+mod compact;
+#[cfg(feature = "geoparquet")]
+mod conformance;
 #[cfg(feature = "geoparquet")]
 mod feature;
+mod hilbert;
 #[cfg(not(feature = "geoparquet"))]
 mod no_feature;
 
 use bytes::Bytes;
+#[cfg(feature = "geoparquet")]
+pub(crate) use feature::read_items;
+pub use hilbert::{hilbert_index, sort_by_hilbert};
 #[cfg(not(feature = "geoparquet"))]
 pub use no_feature::Compression;
 #[cfg(feature = "geoparquet")]
 pub use {
-    feature::{from_reader, into_writer, into_writer_with_compression, into_writer_with_options},
+    append::append,
+    compact::{Compactor, Order, DEFAULT_MAX_ITEMS_PER_FILE, DEFAULT_ROW_GROUP_SIZE},
+    conformance::{validate_reader, Problem},
+    feature::{
+        batches_from_reader, from_reader, into_writer, into_writer_with_collections,
+        into_writer_with_compression, into_writer_with_metadata, into_writer_with_options,
+        metadata_from_reader,
+    },
     parquet::basic::Compression,
 };
 
+/// The key of the [Metadata] in a parquet file's key-value metadata.
+pub const METADATA_KEY: &str = "stac-geoparquet";
+
+/// The stac-geoparquet specification version that this crate writes.
+pub const VERSION: &str = "1.1.0";
+
+/// stac-geoparquet file metadata.
+///
+/// Stored as JSON in a parquet file's key-value metadata under [METADATA_KEY].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// The stac-geoparquet specification version.
+    pub version: String,
+
+    /// The collections of the items in the file, by id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub collections: BTreeMap<String, Collection>,
+
+    /// The href that relative asset hrefs in the file are relative to.
+    ///
+    /// This is usually the href of the data the file was created from. When
+    /// it's set, relative asset hrefs are made absolute on read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_href: Option<String>,
+
+    /// How the items in the file were flattened into rows.
+    ///
+    /// Writers that take [Metadata] flatten items with these options, and
+    /// readers use them to turn the rows back into the original items. If
+    /// they're not set, the default [FlattenOptions] are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flatten: Option<FlattenOptions>,
+
+    /// Whether the file has a [crate::geoarrow::SELF_HREF_COLUMN] column with
+    /// each item's self href.
+    ///
+    /// Writers that take [Metadata] keep the items' self hrefs if this is
+    /// set, and readers restore them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub self_href: bool,
+
+    /// The number of decimal places that geometry and bbox coordinates were rounded to.
+    ///
+    /// Writers that take [Metadata] round coordinates if this is set. See
+    /// [crate::Item::round_coordinates].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u8>,
+
+    /// The tolerance, in coordinate units, that geometries were simplified with.
+    ///
+    /// Writers that take [Metadata] simplify geometries before rounding them
+    /// if this is set, which requires the `geo` feature. See
+    /// [crate::Item::simplify].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simplify: Option<f64>,
+
+    /// Whether the rows are sorted along a Hilbert curve of their bbox centers.
+    ///
+    /// Writers that take [Metadata] sort items with [sort_by_hilbert] if this
+    /// is set, which keeps each row group's bbox small so readers can skip
+    /// row groups when searching by bbox.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hilbert: bool,
+}
+
+impl Metadata {
+    /// Creates new metadata for the current version with the given collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoparquet::Metadata, Collection};
+    ///
+    /// let metadata = Metadata::new([Collection::new("an-id", "a description")]);
+    /// assert!(metadata.collections.contains_key("an-id"));
+    /// ```
+    pub fn new(collections: impl IntoIterator<Item = Collection>) -> Metadata {
+        Metadata {
+            version: VERSION.to_string(),
+            collections: collections
+                .into_iter()
+                .map(|collection| (collection.id.clone(), collection))
+                .collect(),
+            base_href: None,
+            flatten: None,
+            self_href: false,
+            precision: None,
+            simplify: None,
+            hilbert: false,
+        }
+    }
+
+    /// Sets the href that relative asset hrefs are relative to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Metadata;
+    ///
+    /// let metadata = Metadata::new([]).base_href("s3://bucket/items.json");
+    /// assert_eq!(metadata.base_href.unwrap(), "s3://bucket/items.json");
+    /// ```
+    pub fn base_href(mut self, base_href: impl ToString) -> Metadata {
+        self.base_href = Some(base_href.to_string());
+        self
+    }
+
+    /// Sets how items are flattened into rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{geoparquet::Metadata, item::{Collision, FlattenOptions}};
+    ///
+    /// let options = FlattenOptions {
+    ///     collision: Collision::Prefix,
+    ///     separator: None,
+    /// };
+    /// let metadata = Metadata::new([]).flatten(options);
+    /// assert_eq!(metadata.flatten.unwrap(), options);
+    /// ```
+    pub fn flatten(mut self, options: FlattenOptions) -> Metadata {
+        self.flatten = Some(options);
+        self
+    }
+
+    /// Sets whether the items' self hrefs are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Metadata;
+    ///
+    /// let metadata = Metadata::new([]).self_href(true);
+    /// assert!(metadata.self_href);
+    /// ```
+    pub fn self_href(mut self, self_href: bool) -> Metadata {
+        self.self_href = self_href;
+        self
+    }
+
+    /// Sets the number of decimal places to round coordinates to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Metadata;
+    ///
+    /// let metadata = Metadata::new([]).precision(6);
+    /// assert_eq!(metadata.precision, Some(6));
+    /// ```
+    pub fn precision(mut self, precision: u8) -> Metadata {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the tolerance to simplify geometries with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Metadata;
+    ///
+    /// let metadata = Metadata::new([]).simplify(0.0001);
+    /// assert_eq!(metadata.simplify, Some(0.0001));
+    /// ```
+    pub fn simplify(mut self, epsilon: f64) -> Metadata {
+        self.simplify = Some(epsilon);
+        self
+    }
+
+    /// Sets whether items are sorted along a Hilbert curve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::Metadata;
+    ///
+    /// let metadata = Metadata::new([]).hilbert(true);
+    /// assert!(metadata.hilbert);
+    /// ```
+    pub fn hilbert(mut self, hilbert: bool) -> Metadata {
+        self.hilbert = hilbert;
+        self
+    }
+}
+
 /// Create a STAC object from geoparquet data.
 pub trait FromGeoparquet: Sized {
     /// Reads geoparquet data from a file.
@@ -145,9 +351,11 @@ macro_rules! impl_into_geoparquet {
 
 impl_from_geoparquet!(crate::Item);
 impl_from_geoparquet!(crate::Catalog);
-impl_from_geoparquet!(crate::Collection);
+impl_from_geoparquet!(Collection);
 impl_into_geoparquet!(crate::Catalog);
-impl_into_geoparquet!(crate::Collection);
+impl_into_geoparquet!(Collection);
 
+#[cfg(not(feature = "geoparquet"))]
 pub(crate) use impl_from_geoparquet;
+#[cfg(not(feature = "geoparquet"))]
 pub(crate) use impl_into_geoparquet;
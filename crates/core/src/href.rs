@@ -17,7 +17,11 @@ pub enum Href {
 
     /// A string href.
     ///
-    /// This is expected to have `/` delimiters. Windows-style `\` delimiters are not supported.
+    /// This is expected to have `/` delimiters. When an href is created from
+    /// a string on Windows, `\` delimiters are converted to `/`. Absolute
+    /// Windows paths, e.g. `C:\data\catalog.json` or
+    /// `\\server\share\catalog.json`, are converted to `file:` urls on all
+    /// platforms.
     String(String),
 }
 
@@ -75,8 +79,78 @@ pub trait SelfHref {
 }
 
 impl Href {
+    /// Resolves an href against this one, treating this href as the base.
+    ///
+    /// The semantics are the same as [Url::join]:
+    ///
+    /// - if `href` is a url, it is returned as-is
+    /// - if `href` is an absolute path and this href is a url, the path replaces the url's path
+    /// - if `href` is an absolute path and this href is a path, `href` is returned
+    /// - otherwise, `href` is resolved relative to this href's "directory", i.e.
+    ///   everything up to and including the last `/`
+    ///
+    /// The result is [normalized](Href::normalize). Url paths are
+    /// percent-encoded as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Href;
+    ///
+    /// let base = Href::from("/a/b/catalog.json");
+    /// assert_eq!(base.join("./c/item.json").unwrap(), "/a/b/c/item.json");
+    /// assert_eq!(base.join("../item.json").unwrap(), "/a/item.json");
+    ///
+    /// let base = Href::from("http://stac-rs.test/a/catalog.json");
+    /// assert_eq!(base.join("./b c/item.json").unwrap(), "http://stac-rs.test/a/b%20c/item.json");
+    /// assert_eq!(base.join("/item.json").unwrap(), "http://stac-rs.test/item.json");
+    ///
+    /// let base = Href::from("a/catalog.json");
+    /// assert_eq!(base.join("../../item.json").unwrap(), "../item.json");
+    /// ```
+    pub fn join(&self, href: impl Into<Href>) -> Result<Href> {
+        let href = href.into();
+        match (self, href) {
+            (_, Href::Url(url)) => Ok(Href::Url(url)),
+            (Href::Url(base), Href::String(s)) => base.join(&s).map(Href::Url).map_err(Error::from),
+            (Href::String(_), Href::String(s)) if s.starts_with('/') => {
+                Ok(Href::String(normalize_path(&s)))
+            }
+            (Href::String(base), Href::String(s)) => {
+                let directory = base.rfind('/').map(|i| &base[..=i]).unwrap_or_default();
+                Ok(Href::String(normalize_path(&format!("{directory}{s}"))))
+            }
+        }
+    }
+
+    /// Normalizes this href, removing `.` and `..` path segments.
+    ///
+    /// Urls are already normalized when they're parsed, so they're returned
+    /// unchanged. Leading `..` segments of relative paths are kept, a leading
+    /// `./` is preserved, and a relative path that normalizes away entirely
+    /// becomes `.`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Href;
+    ///
+    /// assert_eq!(Href::from("/a/./b/../c.json").normalize(), "/a/c.json");
+    /// assert_eq!(Href::from("./a/../b.json").normalize(), "./b.json");
+    /// assert_eq!(Href::from("../a/b/../c.json").normalize(), "../a/c.json");
+    /// assert_eq!(Href::from("a/..").normalize(), ".");
+    /// ```
+    pub fn normalize(&self) -> Href {
+        match self {
+            Href::Url(url) => Href::Url(url.clone()),
+            Href::String(s) => Href::String(normalize_path(s)),
+        }
+    }
+
     /// Convert this href into an absolute href using the given base.
     ///
+    /// If the base is relative, the result will be too.
+    ///
     /// # Examples
     ///
     /// ```
@@ -87,14 +161,13 @@ impl Href {
     /// ```
     pub fn absolute(&self, base: &Href) -> Result<Href> {
         tracing::debug!("making href={self} absolute with base={base}");
-        match base {
-            Href::Url(url) => url.join(self.as_str()).map(Href::Url).map_err(Error::from),
-            Href::String(s) => Ok(Href::String(make_absolute(self.as_str(), s))),
-        }
+        base.join(self.clone())
     }
 
     /// Convert this href into an relative href using to the given base.
     ///
+    /// Both hrefs are [normalized](Href::normalize) first.
+    ///
     /// # Examples
     ///
     /// ```
@@ -119,10 +192,18 @@ impl Href {
                         .unwrap_or_else(|| self.clone()))
                 }
             },
-            Href::String(s) => Ok(Href::String(make_relative(self.as_str(), s))),
+            Href::String(s) => Ok(Href::String(make_relative(
+                self.normalize().as_str(),
+                &normalize_path(s),
+            ))),
         }
     }
 
+    /// Returns true if this href is a url (including `file:` urls).
+    pub fn is_url(&self) -> bool {
+        matches!(self, Href::Url(_))
+    }
+
     /// Returns true if this href is absolute.
     ///
     /// Urls are always absolute. Strings are absolute if they start with a `/`.
@@ -169,8 +250,12 @@ impl Display for Href {
 
 impl From<&str> for Href {
     fn from(value: &str) -> Self {
-        if let Ok(url) = Url::parse(value) {
+        if let Some(url) = windows_path_to_url(value) {
+            Href::Url(url)
+        } else if let Ok(url) = Url::parse(value) {
             Href::Url(url)
+        } else if cfg!(target_os = "windows") {
+            Href::String(value.replace('\\', "/"))
         } else {
             Href::String(value.to_string())
         }
@@ -179,8 +264,12 @@ impl From<&str> for Href {
 
 impl From<String> for Href {
     fn from(value: String) -> Self {
-        if let Ok(url) = Url::parse(&value) {
+        if let Some(url) = windows_path_to_url(&value) {
             Href::Url(url)
+        } else if let Ok(url) = Url::parse(&value) {
+            Href::Url(url)
+        } else if cfg!(target_os = "windows") {
+            Href::String(value.replace('\\', "/"))
         } else {
             Href::String(value)
         }
@@ -193,7 +282,7 @@ impl From<&Path> for Href {
             if let Ok(url) = Url::from_file_path(value) {
                 Href::Url(url)
             } else {
-                Href::String(value.to_string_lossy().into_owned())
+                Href::from(value.to_string_lossy().as_ref())
             }
         } else {
             Href::String(value.to_string_lossy().into_owned())
@@ -203,15 +292,7 @@ impl From<&Path> for Href {
 
 impl From<PathBuf> for Href {
     fn from(value: PathBuf) -> Self {
-        if cfg!(target_os = "windows") {
-            if let Ok(url) = Url::from_file_path(&value) {
-                Href::Url(url)
-            } else {
-                Href::String(value.to_string_lossy().into_owned())
-            }
-        } else {
-            Href::String(value.to_string_lossy().into_owned())
-        }
+        Href::from(value.as_path())
     }
 }
 
@@ -245,36 +326,75 @@ impl PartialEq<&str> for Href {
     }
 }
 
-fn make_absolute(href: &str, base: &str) -> String {
-    // TODO if we make this interface public, make this an impl Option
-    if href.starts_with('/') {
-        href.to_string()
-    } else {
-        let (base, _) = base.split_at(base.rfind('/').unwrap_or(0));
-        if base.is_empty() {
-            normalize_path(href)
+/// Converts an absolute Windows path to a `file:` url.
+///
+/// Drive paths (`C:\a\b.json` or `C:/a/b.json`) become `file:///C:/a/b.json`,
+/// and UNC paths (`\\server\share\b.json`) become
+/// `file://server/share/b.json`. Unlike [Url::from_file_path], this works on
+/// every platform, so catalogs written on Windows can be read anywhere.
+/// Returns `None` for anything else, including urls with single-letter
+/// schemes like `c://host/a.json`.
+pub(crate) fn windows_path_to_url(s: &str) -> Option<Url> {
+    let bytes = s.as_bytes();
+    if bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/')
+        && !s[2..].starts_with("//")
+    {
+        Url::parse(&format!("file:///{}", s.replace('\\', "/"))).ok()
+    } else if let Some(unc) = s.strip_prefix("\\\\") {
+        let unc = unc.replace('\\', "/");
+        let (server, _) = unc.split_once('/')?;
+        if server.is_empty() || server == "?" || server == "." {
+            // Verbatim and device paths are left to `Url::from_file_path`.
+            None
         } else {
-            normalize_path(&format!("{}/{}", base, href))
+            Url::parse(&format!("file://{unc}")).ok()
         }
+    } else {
+        None
     }
 }
 
 fn normalize_path(path: &str) -> String {
-    let mut parts = if path.starts_with('/') {
-        Vec::new()
-    } else {
-        vec![""]
-    };
-    for part in path.split('/') {
-        match part {
-            "." => {}
+    let is_absolute = path.starts_with('/');
+    let mut parts: Vec<&str> = Vec::new();
+    let mut segments = path.split('/').peekable();
+    if is_absolute {
+        let _ = segments.next();
+    }
+    while let Some(segment) = segments.next() {
+        match segment {
+            "." => {
+                if segments.peek().is_none() {
+                    parts.push("");
+                }
+            }
             ".." => {
-                let _ = parts.pop();
+                if parts.last().map(|part| *part != "..").unwrap_or(false) {
+                    let _ = parts.pop();
+                } else if !is_absolute {
+                    parts.push("..");
+                }
+                if segments.peek().is_none() {
+                    parts.push("");
+                }
             }
+            "" if segments.peek().is_some() => {}
             s => parts.push(s),
         }
     }
-    parts.join("/")
+    let normalized = parts.join("/");
+    if is_absolute {
+        format!("/{normalized}")
+    } else if normalized.is_empty() {
+        ".".to_string()
+    } else if path.starts_with("./") && !normalized.starts_with("..") {
+        format!("./{normalized}")
+    } else {
+        normalized
+    }
 }
 
 fn make_relative(href: &str, base: &str) -> String {
@@ -339,3 +459,109 @@ fn make_relative(href: &str, base: &str) -> String {
 
     relative
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Href;
+
+    #[test]
+    fn join_relative_base() {
+        let base = Href::from("./catalog.json");
+        assert_eq!(base.join("./a/item.json").unwrap(), "./a/item.json");
+        let base = Href::from("catalog.json");
+        assert_eq!(base.join("item.json").unwrap(), "item.json");
+    }
+
+    #[test]
+    fn join_url() {
+        let base = Href::from("/a/catalog.json");
+        assert_eq!(
+            base.join("http://stac-rs.test/item.json").unwrap(),
+            "http://stac-rs.test/item.json"
+        );
+        let base = Href::from("http://stac-rs.test/a/b/");
+        assert_eq!(
+            base.join("../item.json").unwrap(),
+            "http://stac-rs.test/a/item.json"
+        );
+    }
+
+    #[test]
+    fn windows_drive_path() {
+        let href = Href::from("C:\\data\\catalog.json");
+        assert_eq!(href, "file:///C:/data/catalog.json");
+        assert_eq!(
+            href.join("./a b/item.json").unwrap(),
+            "file:///C:/data/a%20b/item.json"
+        );
+        assert_eq!(href.join("/item.json").unwrap(), "file:///C:/item.json");
+        assert_eq!(Href::from("D:/catalog.json"), "file:///D:/catalog.json");
+        assert_eq!(
+            Href::from("file:///C:/data/item.json")
+                .relative(&href)
+                .unwrap(),
+            "item.json"
+        );
+    }
+
+    #[test]
+    fn windows_unc_path() {
+        let href = Href::from("\\\\server\\share\\catalog.json");
+        assert_eq!(href, "file://server/share/catalog.json");
+        assert_eq!(
+            href.join("./a/item.json").unwrap(),
+            "file://server/share/a/item.json"
+        );
+    }
+
+    #[test]
+    fn not_windows_paths() {
+        assert!(super::windows_path_to_url("c:item.json").is_none());
+        assert!(super::windows_path_to_url("s3://bucket/item.json").is_none());
+        assert!(super::windows_path_to_url("\\\\?\\C:\\item.json").is_none());
+        assert_eq!(Href::from("s3://bucket/item.json"), "s3://bucket/item.json");
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn windows_realize() {
+        use super::RealizedHref;
+        use std::path::PathBuf;
+
+        let RealizedHref::PathBuf(path) = Href::from("C:\\data\\catalog.json").realize() else {
+            panic!("should be a path");
+        };
+        assert_eq!(path, PathBuf::from("C:\\data\\catalog.json"));
+        let RealizedHref::PathBuf(path) = Href::from("\\\\server\\share\\catalog.json").realize()
+        else {
+            panic!("should be a path");
+        };
+        assert_eq!(path, PathBuf::from("\\\\server\\share\\catalog.json"));
+        assert_eq!(Href::from("a\\b\\item.json"), "a/b/item.json");
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Href::from("/a/b/..").normalize(), "/a/");
+        assert_eq!(Href::from("/../a.json").normalize(), "/a.json");
+        assert_eq!(Href::from("a//b.json").normalize(), "a/b.json");
+        assert_eq!(Href::from("./../a.json").normalize(), "../a.json");
+    }
+
+    #[test]
+    fn normalize_to_current_directory() {
+        assert_eq!(Href::from("a/..").normalize(), ".");
+        assert_eq!(Href::from("./a/../").normalize(), ".");
+        assert_eq!(Href::from(".").normalize(), ".");
+        assert_eq!(Href::from("/a/..").normalize(), "/");
+    }
+
+    #[test]
+    fn relative_normalizes() {
+        let href = Href::from("/a/./b/../c/item.json");
+        assert_eq!(
+            href.relative(&"/a/d/../catalog.json".into()).unwrap(),
+            "./c/item.json"
+        );
+    }
+}
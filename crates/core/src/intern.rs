@@ -0,0 +1,568 @@
+//! Share strings that repeat across many items.
+//!
+//! Large item collections repeat the same collection ids, extension urls,
+//! asset keys, and media types on every item. An [InternedItemCollection]
+//! stores each of those strings once, as an [Arc<str>] from an [Interner], and
+//! every item points at the shared copy.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{intern::InternedItemCollection, Item};
+//!
+//! let items = (0..3).map(|i| {
+//!     let mut item = Item::new(i.to_string());
+//!     item.collection = Some("a-collection".to_string());
+//!     item
+//! });
+//! let item_collection = InternedItemCollection::from_items(items);
+//! assert_eq!(item_collection.interner().len(), 1);
+//! let item_collection = item_collection.into_item_collection();
+//! assert_eq!(item_collection.items.len(), 3);
+//! ```
+
+use crate::{Asset, Item, ItemCollection, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    sync::Arc,
+};
+
+/// A cache of shared strings.
+///
+/// # Examples
+///
+/// ```
+/// use stac::intern::Interner;
+/// use std::sync::Arc;
+///
+/// let mut interner = Interner::new();
+/// let a = interner.intern("image/tiff");
+/// let b = interner.intern("image/tiff");
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(interner.bytes_saved(), "image/tiff".len());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+    bytes_saved: usize,
+}
+
+/// An [Item] whose repeated strings are shared through an [Interner].
+#[derive(Debug, Clone)]
+pub struct InternedItem {
+    item: Item,
+    collection: Option<Arc<str>>,
+    extensions: Vec<Arc<str>>,
+    assets: Vec<(Arc<str>, InternedAsset)>,
+}
+
+/// An [Asset] whose media type is shared through an [Interner].
+#[derive(Debug, Clone)]
+pub struct InternedAsset {
+    asset: Asset,
+    r#type: Option<Arc<str>>,
+}
+
+/// An [ItemCollection] whose items share their repeated strings.
+#[derive(Debug, Default, Clone)]
+pub struct InternedItemCollection {
+    items: Vec<InternedItem>,
+    interner: Interner,
+}
+
+impl Interner {
+    /// Creates a new, empty interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// assert!(interner.is_empty());
+    /// ```
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Returns the shared copy of a string, adding it if it isn't cached yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let s = interner.intern("a-collection");
+    /// assert_eq!(&*s, "a-collection");
+    /// ```
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(shared) = self.strings.get(s) {
+            self.bytes_saved += s.len();
+            shared.clone()
+        } else {
+            let shared: Arc<str> = Arc::from(s);
+            let _ = self.strings.insert(shared.clone());
+            shared
+        }
+    }
+
+    /// Returns the number of distinct strings in this interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let _ = interner.intern("a");
+    /// let _ = interner.intern("a");
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if this interner doesn't hold any strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::Interner;
+    ///
+    /// assert!(Interner::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns the number of string bytes that were shared instead of copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let _ = interner.intern("abc");
+    /// assert_eq!(interner.bytes_saved(), 0);
+    /// let _ = interner.intern("abc");
+    /// assert_eq!(interner.bytes_saved(), 3);
+    /// ```
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_saved
+    }
+}
+
+impl InternedItem {
+    /// Interns an item's collection id, extension urls, asset keys, and asset media types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Item};
+    ///
+    /// let mut interner = Interner::new();
+    /// let item = InternedItem::new(Item::new("an-id"), &mut interner);
+    /// assert_eq!(item.into_item().id, "an-id");
+    /// ```
+    pub fn new(mut item: Item, interner: &mut Interner) -> InternedItem {
+        let collection = item.collection.take().map(|c| interner.intern(&c));
+        let extensions = std::mem::take(&mut item.extensions)
+            .into_iter()
+            .map(|e| interner.intern(&e))
+            .collect();
+        let assets = std::mem::take(&mut item.assets)
+            .into_iter()
+            .map(|(key, asset)| (interner.intern(&key), InternedAsset::new(asset, interner)))
+            .collect();
+        InternedItem {
+            item,
+            collection,
+            extensions,
+            assets,
+        }
+    }
+
+    /// Returns this item's id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Item};
+    ///
+    /// let item = InternedItem::new(Item::new("an-id"), &mut Interner::new());
+    /// assert_eq!(item.id(), "an-id");
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.item.id
+    }
+
+    /// Returns this item's shared collection id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Item};
+    ///
+    /// let item = InternedItem::new(Item::new("an-id"), &mut Interner::new());
+    /// assert!(item.collection().is_none());
+    /// ```
+    pub fn collection(&self) -> Option<&Arc<str>> {
+        self.collection.as_ref()
+    }
+
+    /// Returns this item's shared extension urls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Item};
+    ///
+    /// let item = InternedItem::new(Item::new("an-id"), &mut Interner::new());
+    /// assert!(item.extensions().is_empty());
+    /// ```
+    pub fn extensions(&self) -> &[Arc<str>] {
+        &self.extensions
+    }
+
+    /// Returns this item's assets, keyed by their shared keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Asset, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let _ = item.assets.insert("data".to_string(), Asset::new("data.tif"));
+    /// let item = InternedItem::new(item, &mut Interner::new());
+    /// assert_eq!(&*item.assets()[0].0, "data");
+    /// ```
+    pub fn assets(&self) -> &[(Arc<str>, InternedAsset)] {
+        &self.assets
+    }
+
+    /// Converts this back into an owned [Item].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedItem, Interner}, Item};
+    ///
+    /// let item = InternedItem::new(Item::new("an-id"), &mut Interner::new());
+    /// let item = item.into_item();
+    /// ```
+    pub fn into_item(self) -> Item {
+        let mut item = self.item;
+        item.collection = self.collection.map(|c| c.to_string());
+        item.extensions = self.extensions.iter().map(|e| e.to_string()).collect();
+        item.assets = self
+            .assets
+            .into_iter()
+            .map(|(key, asset)| (key.to_string(), asset.into_asset()))
+            .collect::<HashMap<_, _>>();
+        item
+    }
+}
+
+impl InternedAsset {
+    /// Interns an asset's media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedAsset, Interner}, Asset};
+    ///
+    /// let asset = InternedAsset::new(Asset::new("data.tif"), &mut Interner::new());
+    /// assert_eq!(asset.href(), "data.tif");
+    /// ```
+    pub fn new(mut asset: Asset, interner: &mut Interner) -> InternedAsset {
+        let r#type = asset.r#type.take().map(|t| interner.intern(&t));
+        InternedAsset { asset, r#type }
+    }
+
+    /// Returns this asset's href.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedAsset, Interner}, Asset};
+    ///
+    /// let asset = InternedAsset::new(Asset::new("data.tif"), &mut Interner::new());
+    /// assert_eq!(asset.href(), "data.tif");
+    /// ```
+    pub fn href(&self) -> &str {
+        &self.asset.href
+    }
+
+    /// Returns this asset's shared media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedAsset, Interner}, Asset};
+    ///
+    /// let mut asset = Asset::new("data.tif");
+    /// asset.r#type = Some("image/tiff".to_string());
+    /// let asset = InternedAsset::new(asset, &mut Interner::new());
+    /// assert_eq!(asset.r#type().map(|t| &**t), Some("image/tiff"));
+    /// ```
+    pub fn r#type(&self) -> Option<&Arc<str>> {
+        self.r#type.as_ref()
+    }
+
+    /// Converts this back into an owned [Asset].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::{InternedAsset, Interner}, Asset};
+    ///
+    /// let asset = InternedAsset::new(Asset::new("data.tif"), &mut Interner::new());
+    /// assert_eq!(asset.into_asset().href, "data.tif");
+    /// ```
+    pub fn into_asset(self) -> Asset {
+        let mut asset = self.asset;
+        asset.r#type = self.r#type.map(|t| t.to_string());
+        asset
+    }
+}
+
+impl InternedItemCollection {
+    /// Creates a new, empty interned item collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    ///
+    /// let item_collection = InternedItemCollection::new();
+    /// assert!(item_collection.is_empty());
+    /// ```
+    pub fn new() -> InternedItemCollection {
+        InternedItemCollection::default()
+    }
+
+    /// Interns items as they're collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::InternedItemCollection, Item};
+    ///
+    /// let item_collection = InternedItemCollection::from_items(vec![Item::new("a")]);
+    /// assert_eq!(item_collection.len(), 1);
+    /// ```
+    pub fn from_items(items: impl IntoIterator<Item = Item>) -> InternedItemCollection {
+        let mut item_collection = InternedItemCollection::new();
+        item_collection.extend(items);
+        item_collection
+    }
+
+    /// Reads and interns items from newline-delimited JSON, one line at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let file = BufReader::new(File::open("data/items.ndjson").unwrap());
+    /// let item_collection = InternedItemCollection::from_ndjson_reader(file).unwrap();
+    /// ```
+    pub fn from_ndjson_reader(reader: impl BufRead) -> Result<InternedItemCollection> {
+        let mut item_collection = InternedItemCollection::new();
+        for item in crate::ndjson::items_from_reader(reader) {
+            item_collection.push(item?);
+        }
+        Ok(item_collection)
+    }
+
+    /// Reads and interns items from
+    /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet), one
+    /// record batch at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let item_collection = InternedItemCollection::from_geoparquet_reader(file).unwrap();
+    /// assert_eq!(item_collection.len(), 1);
+    /// ```
+    #[cfg(feature = "geoparquet")]
+    pub fn from_geoparquet_reader<R>(reader: R) -> Result<InternedItemCollection>
+    where
+        R: parquet::file::reader::ChunkReader + 'static,
+    {
+        let mut item_collection = InternedItemCollection::new();
+        for batch in crate::geoparquet::batches_from_reader(reader)? {
+            item_collection.extend(batch?.items);
+        }
+        Ok(item_collection)
+    }
+
+    /// Interns and adds an item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::InternedItemCollection, Item};
+    ///
+    /// let mut item_collection = InternedItemCollection::new();
+    /// item_collection.push(Item::new("an-id"));
+    /// ```
+    pub fn push(&mut self, item: Item) {
+        self.items.push(InternedItem::new(item, &mut self.interner));
+    }
+
+    /// Returns the interned items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::InternedItemCollection, Item};
+    ///
+    /// let item_collection = InternedItemCollection::from_items(vec![Item::new("a")]);
+    /// assert_eq!(item_collection.items()[0].id(), "a");
+    /// ```
+    pub fn items(&self) -> &[InternedItem] {
+        &self.items
+    }
+
+    /// Returns the number of items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    ///
+    /// assert_eq!(InternedItemCollection::new().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there aren't any items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    ///
+    /// assert!(InternedItemCollection::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the interner that holds the shared strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::intern::InternedItemCollection;
+    ///
+    /// assert!(InternedItemCollection::new().interner().is_empty());
+    /// ```
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Converts this into an [ItemCollection] of owned items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{intern::InternedItemCollection, Item};
+    ///
+    /// let item_collection = InternedItemCollection::from_items(vec![Item::new("a")]);
+    /// let item_collection = item_collection.into_item_collection();
+    /// assert_eq!(item_collection.items[0].id, "a");
+    /// ```
+    pub fn into_item_collection(self) -> ItemCollection {
+        self.items
+            .into_iter()
+            .map(InternedItem::into_item)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl Extend<Item> for InternedItemCollection {
+    fn extend<T: IntoIterator<Item = Item>>(&mut self, iter: T) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl FromIterator<Item> for InternedItemCollection {
+    fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
+        InternedItemCollection::from_items(iter)
+    }
+}
+
+impl From<ItemCollection> for InternedItemCollection {
+    fn from(item_collection: ItemCollection) -> Self {
+        InternedItemCollection::from_items(item_collection.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InternedItemCollection;
+    use crate::{Asset, Item};
+    use std::sync::Arc;
+
+    const EXTENSION: &str = "https://stac-extensions.github.io/eo/v2.0.0/schema.json";
+
+    fn item(i: usize) -> Item {
+        let mut item = Item::new(format!("item-{i}"));
+        item.collection = Some("a-collection".to_string());
+        item.extensions.push(EXTENSION.to_string());
+        let mut asset = Asset::new(format!("item-{i}.tif"));
+        asset.r#type = Some("image/tiff".to_string());
+        let _ = item.assets.insert("data".to_string(), asset);
+        item
+    }
+
+    #[test]
+    fn shares_repeated_strings() {
+        let item_collection = InternedItemCollection::from_items((0..1000).map(item));
+        let interner = item_collection.interner();
+        assert_eq!(interner.len(), 4);
+        let per_item = "a-collection".len() + EXTENSION.len() + "data".len() + "image/tiff".len();
+        assert_eq!(interner.bytes_saved(), 999 * per_item);
+
+        let first = &item_collection.items()[0];
+        let last = &item_collection.items()[999];
+        assert!(Arc::ptr_eq(
+            first.collection().unwrap(),
+            last.collection().unwrap()
+        ));
+        assert!(Arc::ptr_eq(&first.extensions()[0], &last.extensions()[0]));
+        assert!(Arc::ptr_eq(&first.assets()[0].0, &last.assets()[0].0));
+        assert!(Arc::ptr_eq(
+            first.assets()[0].1.r#type().unwrap(),
+            last.assets()[0].1.r#type().unwrap()
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let items: Vec<_> = (0..3).map(item).collect();
+        let item_collection = InternedItemCollection::from_items(items.clone());
+        assert_eq!(item_collection.into_item_collection().items, items);
+    }
+
+    #[test]
+    fn from_ndjson_reader() {
+        let file = std::io::BufReader::new(std::fs::File::open("data/items.ndjson").unwrap());
+        let item_collection = InternedItemCollection::from_ndjson_reader(file).unwrap();
+        assert!(!item_collection.is_empty());
+    }
+}
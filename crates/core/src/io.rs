@@ -19,6 +19,10 @@
 //! }
 //! ```
 //!
+//! If the href doesn't have a recognized extension (e.g. it's an API endpoint),
+//! the format is inferred from the HTTP `Content-Type` header, if there is one,
+//! and then from the data themselves (see [Format::infer_from_bytes]).
+//!
 //! To specify the format, use [Format::read].
 //!
 //! ## Object store
@@ -48,6 +52,54 @@
 //! # }
 //! ```
 //!
+//! ## Credentials
+//!
+//! Besides the object store's own options, AWS credentials can come from a
+//! named profile in the shared credentials and config files
+//! ([AWS_PROFILE_OPTION]), or from assuming a role ([AWS_ROLE_ARN_OPTION]),
+//! optionally with a web identity token
+//! ([AWS_WEB_IDENTITY_TOKEN_FILE_OPTION]). These options require the
+//! `object-store-aws` feature. Credentials are shared by every request made
+//! with the same options, and refreshed before they expire:
+//!
+//! ```no_run
+//! # use stac::Item;
+//! # #[cfg(feature = "object-store-aws")]
+//! # {
+//! # tokio_test::block_on(async {
+//! let item: Item = stac::io::get_opts("s3://bucket/item.json", [("aws_profile", "production")]).await.unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! On Google Cloud, a service account can be impersonated
+//! ([GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION], with the `object-store-gcp`
+//! feature), with credentials from the object store's own options, e.g.
+//! `google_application_credentials`. On Azure, workload identity uses the
+//! object store's `azure_federated_token_file`, `azure_client_id`, and
+//! `azure_tenant_id` options, which must be set together, and
+//! `azure_use_azure_cli` uses the Azure CLI's login.
+//!
+//! ## Caching
+//!
+//! To avoid transferring objects that haven't changed, e.g. when repeatedly
+//! crawling a large static catalog, set the [CACHE_DIRECTORY_OPTION] option
+//! (or use [read_with_cache]). Objects are stored in a local [Cache] with their
+//! `ETag`, and later requests are made conditional with `If-None-Match`:
+//!
+//! ```no_run
+//! # use stac::Item;
+//! # #[cfg(feature = "object-store-http")]
+//! # {
+//! # tokio_test::block_on(async {
+//! let item: Item = stac::io::get_opts(
+//!     "https://stac-rs.test/item.json",
+//!     [(stac::io::CACHE_DIRECTORY_OPTION, "/tmp/stac-cache")],
+//! ).await.unwrap();
+//! # });
+//! # }
+//! ```
+//!
 //! # Writing
 //!
 //! ```no_run
@@ -76,18 +128,62 @@
 //! }
 //! ```
 
+mod cache;
+#[cfg(feature = "object-store")]
+mod credentials;
+#[cfg(feature = "object-store")]
+mod retry;
+
+pub use cache::Cache;
+#[cfg(feature = "object-store")]
+pub use credentials::{
+    AWS_PROFILE_OPTION, AWS_ROLE_ARN_OPTION, AWS_ROLE_SESSION_NAME_OPTION,
+    AWS_WEB_IDENTITY_TOKEN_FILE_OPTION, GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION,
+};
+#[cfg(feature = "object-store")]
+pub use retry::{
+    RetryPolicy, MAX_RETRIES_OPTION, RETRY_BACKOFF_OPTION, RETRY_MAX_BACKOFF_OPTION, TIMEOUT_OPTION,
+};
+
 use crate::{
     geoparquet::{FromGeoparquet, IntoGeoparquet},
     json::{FromJson, ToJson},
     ndjson::{FromNdjson, ToNdjson},
-    Format, Href, Result, SelfHref,
+    Error, Format, Href, RealizedHref, Result, SelfHref,
+};
+use bytes::Bytes;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
-use std::path::Path;
+use url::Url;
+
+/// The option used to set the directory of a [Cache] in [get_opts].
+///
+/// This option is removed before the rest of the options are passed to the object store.
+pub const CACHE_DIRECTORY_OPTION: &str = "stac_cache_dir";
+
+/// The option used to set the part size, in bytes, of multipart uploads in [put_opts].
+///
+/// Objects larger than one part are uploaded in parts. The part size must be
+/// at least [MIN_MULTIPART_PART_SIZE]. This option is removed before the rest
+/// of the options are passed to the object store.
+pub const MULTIPART_PART_SIZE_OPTION: &str = "stac_multipart_part_size";
+
+/// The default part size of multipart uploads, 10 MiB.
+pub const DEFAULT_MULTIPART_PART_SIZE: usize = 10 * 1024 * 1024;
+
+/// The smallest part size of multipart uploads, 5 MiB.
+///
+/// This is the smallest part that S3 accepts, other than the last one.
+pub const MIN_MULTIPART_PART_SIZE: usize = 5 * 1024 * 1024;
 
 /// Reads a STAC value from an href.
 ///
-/// The format will be inferred from the href's extension. If you want to
-/// specify the format, use [Format::read].
+/// The format will be inferred from the href's extension, or if that doesn't
+/// work, from the `Content-Type` header or the data. If you want to specify
+/// the format, use [Format::read].
 ///
 /// # Examples
 ///
@@ -97,9 +193,105 @@ use std::path::Path;
 pub fn read<T: SelfHref + FromJson + FromNdjson + FromGeoparquet>(
     href: impl Into<Href>,
 ) -> Result<T> {
-    let href = href.into();
-    let format = Format::infer_from_href(href.as_str()).unwrap_or_default();
-    format.read(href)
+    read_and_infer(href.into(), None)
+}
+
+/// Reads a STAC value from an href, using a [Cache] for urls.
+///
+/// If the cache has a copy of the object, the request is made with an
+/// `If-None-Match` header, and the cached copy is used if the server responds
+/// with `304 Not Modified`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use stac::{io::Cache, Item};
+///
+/// let cache = Cache::new("/tmp/stac-cache");
+/// let item: Item = stac::io::read_with_cache(
+///     "https://raw.githubusercontent.com/radiantearth/stac-spec/master/examples/simple-item.json",
+///     &cache,
+/// ).unwrap();
+/// ```
+pub fn read_with_cache<T: SelfHref + FromJson + FromNdjson + FromGeoparquet>(
+    href: impl Into<Href>,
+    cache: &Cache,
+) -> Result<T> {
+    read_and_infer(href.into(), Some(cache))
+}
+
+fn read_and_infer<T: SelfHref + FromJson + FromNdjson + FromGeoparquet>(
+    href: Href,
+    cache: Option<&Cache>,
+) -> Result<T> {
+    let format = Format::infer_from_href(href.as_str());
+    match href.realize() {
+        RealizedHref::Url(url) => {
+            let (content_type, bytes) = get_blocking(&url, cache)?;
+            let format = format.unwrap_or_else(|| infer(content_type.as_deref(), &bytes));
+            let mut value: T = format.from_bytes(bytes)?;
+            *value.self_href_mut() = Some(Href::Url(url));
+            Ok(value)
+        }
+        RealizedHref::PathBuf(path) => {
+            if let Some(format) = format {
+                format.read(path)
+            } else {
+                read_path_and_infer(path)
+            }
+        }
+    }
+}
+
+/// Gets the content type and bytes of a url with a blocking request.
+#[allow(unused_variables)]
+pub(crate) fn get_blocking(url: &Url, cache: Option<&Cache>) -> Result<(Option<String>, Bytes)> {
+    #[cfg(feature = "reqwest")]
+    {
+        use reqwest::{
+            header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+            StatusCode,
+        };
+
+        let entry = cache.and_then(|cache| cache.get(url));
+        let mut request = reqwest::blocking::Client::new().get(url.clone());
+        if let Some(entry) = &entry {
+            request = request.header(IF_NONE_MATCH, &entry.etag);
+        }
+        let response = request.send()?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = entry {
+                tracing::debug!("{url} is not modified, using the cached copy");
+                return Ok((entry.content_type, entry.bytes));
+            }
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let content_type = header(CONTENT_TYPE);
+        let etag = header(ETAG);
+        let is_success = response.status().is_success();
+        let bytes = response.bytes()?;
+        if let (Some(cache), Some(etag), true) = (cache, etag, is_success) {
+            cache.put_or_warn(
+                url,
+                &cache::Entry {
+                    etag,
+                    content_type: content_type.clone(),
+                    bytes: bytes.clone(),
+                },
+            );
+        }
+        Ok((content_type, bytes))
+    }
+    #[cfg(not(feature = "reqwest"))]
+    {
+        Err(Error::FeatureNotEnabled("reqwest"))
+    }
 }
 
 /// Gets a value, maybe from an object store.
@@ -149,8 +341,280 @@ where
     V: Into<String>,
 {
     let href = href.into();
-    let format = Format::infer_from_href(href.as_str()).unwrap_or_default();
-    format.get_opts(href, options).await
+    if let Some(format) = Format::infer_from_href(href.as_str()) {
+        return format.get_opts(href, options).await;
+    }
+    match href.realize() {
+        RealizedHref::Url(url) => {
+            let (content_type, bytes) = get_url_opts(&url, options).await?;
+            let mut value: T = infer(content_type.as_deref(), &bytes).from_bytes(bytes)?;
+            *value.self_href_mut() = Some(Href::Url(url));
+            Ok(value)
+        }
+        RealizedHref::PathBuf(path) => read_path_and_infer(path),
+    }
+}
+
+/// Builds the object store for a url.
+///
+/// The retry and credential options are handled here, and the rest are
+/// passed to the object store's builder, as in [object_store::parse_url_opts].
+#[cfg(feature = "object-store")]
+pub(crate) async fn parse_url_opts(
+    url: &Url,
+    mut options: Vec<(String, String)>,
+) -> Result<(Box<dyn object_store::ObjectStore>, object_store::path::Path)> {
+    use object_store::ObjectStoreScheme;
+
+    // Configures a builder with every option that it recognizes.
+    #[cfg(any(
+        feature = "object-store-aws",
+        feature = "object-store-azure",
+        feature = "object-store-gcp",
+        feature = "object-store-http"
+    ))]
+    macro_rules! with_options {
+        ($builder:expr, $options:expr) => {
+            $options
+                .into_iter()
+                .fold($builder, |builder, (key, value)| match key.parse() {
+                    Ok(key) => builder.with_config(key, value),
+                    Err(_) => builder,
+                })
+        };
+    }
+
+    let retry_policy = RetryPolicy::take_from_options(url, &mut options)?;
+    retry_policy.add_timeout_option(&mut options);
+    #[cfg(feature = "object-store-aws")]
+    let credentials = credentials::take_from_options(&mut options).await?;
+    #[cfg(feature = "object-store-gcp")]
+    let google_service_account = credentials::take_google_service_account(&mut options);
+    #[cfg(not(all(feature = "object-store-aws", feature = "object-store-gcp")))]
+    credentials::reject_options(&mut options)?;
+    let (scheme, path) = ObjectStoreScheme::parse(url).map_err(object_store::Error::from)?;
+    let object_store: Box<dyn object_store::ObjectStore> = match scheme {
+        #[cfg(feature = "object-store-aws")]
+        ObjectStoreScheme::AmazonS3 => {
+            let mut builder = with_options!(
+                object_store::aws::AmazonS3Builder::new().with_url(url.to_string()),
+                options
+            )
+            .with_retry(retry_policy.retry_config());
+            if let Some(credentials) = credentials {
+                builder = builder.with_credentials(credentials);
+            }
+            Box::new(builder.build()?)
+        }
+        #[cfg(feature = "object-store-azure")]
+        ObjectStoreScheme::MicrosoftAzure => {
+            credentials::check_azure_options(&options)?;
+            Box::new(
+                with_options!(
+                    object_store::azure::MicrosoftAzureBuilder::new().with_url(url.to_string()),
+                    options
+                )
+                .with_retry(retry_policy.retry_config())
+                .build()?,
+            )
+        }
+        #[cfg(feature = "object-store-gcp")]
+        ObjectStoreScheme::GoogleCloudStorage => {
+            let mut builder = with_options!(
+                object_store::gcp::GoogleCloudStorageBuilder::new().with_url(url.to_string()),
+                options.clone()
+            )
+            .with_retry(retry_policy.retry_config());
+            if let Some(service_account) = google_service_account {
+                let base = builder.clone().build()?.credentials().clone();
+                builder = builder.with_credentials(credentials::impersonate(
+                    service_account,
+                    &options,
+                    base,
+                ));
+            }
+            Box::new(builder.build()?)
+        }
+        #[cfg(feature = "object-store-http")]
+        ObjectStoreScheme::Http => Box::new(
+            with_options!(
+                object_store::http::HttpBuilder::new().with_url(&url[..url::Position::BeforePath]),
+                options
+            )
+            .with_retry(retry_policy.retry_config())
+            .build()?,
+        ),
+        _ => return object_store::parse_url_opts(url, options).map_err(Error::from),
+    };
+    Ok((object_store, path))
+}
+
+/// Gets the content type and bytes of a url from an object store.
+///
+/// If the options include [CACHE_DIRECTORY_OPTION], the request is made
+/// conditional on the `ETag` of any cached copy. Failed requests are retried
+/// with the [RetryPolicy] described by the options.
+#[cfg(feature = "object-store")]
+pub(crate) async fn get_url_opts<I, K, V>(url: &Url, options: I) -> Result<(Option<String>, Bytes)>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    use object_store::{Attribute, GetOptions, ObjectStore};
+
+    let mut cache = None;
+    let mut object_store_options = Vec::new();
+    for (key, value) in options {
+        if key.as_ref() == CACHE_DIRECTORY_OPTION {
+            cache = Some(Cache::new(value.into()));
+        } else {
+            object_store_options.push((key.as_ref().to_string(), value.into()));
+        }
+    }
+    let (object_store, path) = parse_url_opts(url, object_store_options).await?;
+    let entry = match cache.as_ref() {
+        Some(cache) => cache.get_async(url).await,
+        None => None,
+    };
+    let get_options = GetOptions {
+        if_none_match: entry.as_ref().map(|entry| entry.etag.clone()),
+        ..Default::default()
+    };
+    let result: object_store::Result<_> = async {
+        let get_result = object_store.get_opts(&path, get_options).await?;
+        let etag = get_result.meta.e_tag.clone();
+        let content_type = get_result
+            .attributes
+            .get(&Attribute::ContentType)
+            .map(|value| value.to_string());
+        let bytes = get_result.bytes().await?;
+        Ok((etag, content_type, bytes))
+    }
+    .await;
+    match (result, entry) {
+        (Err(object_store::Error::NotModified { .. }), Some(entry)) => {
+            tracing::debug!("{url} is not modified, using the cached copy");
+            Ok((entry.content_type, entry.bytes))
+        }
+        (result, _) => {
+            let (etag, content_type, bytes) = result?;
+            if let (Some(cache), Some(etag)) = (cache, etag) {
+                cache
+                    .put_or_warn_async(
+                        url,
+                        cache::Entry {
+                            etag,
+                            content_type: content_type.clone(),
+                            bytes: bytes.clone(),
+                        },
+                    )
+                    .await;
+            }
+            Ok((content_type, bytes))
+        }
+    }
+}
+
+/// Writes a local file by writing to a temporary file in the same directory, then renaming it.
+///
+/// If writing fails, the temporary file is removed and any existing file at
+/// `path` is left untouched.
+pub(crate) fn write_atomic(path: &Path, write: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("not a file path: {}", path.display()),
+        )
+    })?;
+    let mut temp_file_name = OsString::from(".");
+    temp_file_name.push(file_name);
+    temp_file_name.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp_path = path.with_file_name(temp_file_name);
+    let result =
+        write(&temp_path).and_then(|()| std::fs::rename(&temp_path, path).map_err(Error::from));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Puts bytes to a url in an object store.
+///
+/// Objects larger than the [MULTIPART_PART_SIZE_OPTION] (default
+/// [DEFAULT_MULTIPART_PART_SIZE]) are uploaded in parts, and the upload is
+/// aborted if any part fails, so an interrupted job never leaves a partial
+/// object behind. Single-part puts and each part of a multipart upload are
+/// retried with the [RetryPolicy] described by the options.
+///
+/// The whole object is still held in memory, since it's serialized before
+/// it's put; multipart uploads only limit the size of each request.
+#[cfg(feature = "object-store")]
+pub(crate) async fn put_url_opts<I, K, V>(
+    url: &Url,
+    bytes: impl Into<Bytes>,
+    options: I,
+) -> Result<object_store::PutResult>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    use object_store::{ObjectStore, PutPayload};
+
+    let bytes = bytes.into();
+
+    let mut part_size = DEFAULT_MULTIPART_PART_SIZE;
+    let mut object_store_options = Vec::new();
+    for (key, value) in options {
+        match key.as_ref() {
+            MULTIPART_PART_SIZE_OPTION => {
+                let value = value.into();
+                part_size = value
+                    .parse()
+                    .ok()
+                    .filter(|&part_size| part_size >= MIN_MULTIPART_PART_SIZE)
+                    .ok_or(Error::InvalidOption {
+                        key: MULTIPART_PART_SIZE_OPTION,
+                        value,
+                    })?;
+            }
+            CACHE_DIRECTORY_OPTION => {}
+            key => object_store_options.push((key.to_string(), value.into())),
+        }
+    }
+    let (object_store, path) = parse_url_opts(url, object_store_options).await?;
+    if bytes.len() <= part_size {
+        return object_store
+            .put(&path, PutPayload::from(bytes))
+            .await
+            .map_err(Error::from);
+    }
+    let mut upload = object_store.put_multipart(&path).await?;
+    let mut result = Ok(());
+    for start in (0..bytes.len()).step_by(part_size) {
+        let part = bytes.slice(start..(start + part_size).min(bytes.len()));
+        result = upload.put_part(PutPayload::from(part)).await;
+        if result.is_err() {
+            break;
+        }
+    }
+    let result = match result {
+        Ok(()) => upload.complete().await,
+        Err(err) => Err(err),
+    };
+    if result.is_err() {
+        if let Err(err) = upload.abort().await {
+            tracing::warn!("could not abort multipart upload to {url}: {err}");
+        }
+    }
+    result.map_err(Error::from)
 }
 
 /// Writes a STAC value to a path.
@@ -234,6 +698,66 @@ where
     format.put_opts(href, value, options).await
 }
 
+/// Puts already-serialized bytes, maybe to an object store with options.
+///
+/// Use this to write the same bytes to more than one href without
+/// serializing the value again. Local paths are written atomically, like
+/// [write].
+///
+/// # Examples
+///
+/// ```no_run
+/// use stac::{Format, Item};
+///
+/// let bytes = Format::json().into_vec(Item::new("an-id")).unwrap();
+/// # tokio_test::block_on(async {
+/// let options: [(&str, &str); 0] = [];
+/// stac::io::put_bytes_opts("an-id.json", bytes, options).await.unwrap();
+/// # })
+/// ```
+#[cfg(feature = "object-store")]
+pub async fn put_bytes_opts<I, K, V>(
+    href: impl ToString,
+    bytes: impl Into<Bytes>,
+    options: I,
+) -> Result<Option<object_store::PutResult>>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    match Href::from(href.to_string()) {
+        Href::Url(url) => put_url_opts(&url, bytes, options).await.map(Some),
+        Href::String(path) => {
+            let bytes = bytes.into();
+            write_atomic(Path::new(&path), |path| {
+                std::fs::write(path, &bytes).map_err(Error::from)
+            })
+            .map(|_| None)
+        }
+    }
+}
+
+fn read_path_and_infer<T: SelfHref + FromJson + FromNdjson + FromGeoparquet>(
+    path: PathBuf,
+) -> Result<T> {
+    let path = path.canonicalize()?;
+    let bytes = std::fs::read(&path).map_err(|io| Error::FromPath {
+        io,
+        path: path.to_string_lossy().into_owned(),
+    })?;
+    let mut value: T = infer(None, &bytes).from_bytes(bytes)?;
+    *value.self_href_mut() = Some(path.as_path().into());
+    Ok(value)
+}
+
+fn infer(content_type: Option<&str>, bytes: &[u8]) -> Format {
+    content_type
+        .and_then(Format::infer_from_content_type)
+        .or_else(|| Format::infer_from_bytes(bytes))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
@@ -268,7 +792,7 @@ mod tests {
 
     #[cfg(feature = "reqwest")]
     mod read_with_reqwest {
-        use crate::{Catalog, Collection, Item};
+        use crate::{Catalog, Collection, Item, SelfHref};
 
         read!(
             read_item_from_url,
@@ -285,6 +809,23 @@ mod tests {
             "https://raw.githubusercontent.com/radiantearth/stac-spec/master/examples/collection.json",
             Collection
         );
+
+        #[test]
+        fn read_url_without_extension() {
+            let mut server = mockito::Server::new();
+            let mock = server
+                .mock("GET", "/items/an-id")
+                .with_header("Content-Type", "application/geo+json")
+                .with_body(std::fs::read("examples/simple-item.json").unwrap())
+                .expect(2)
+                .create();
+            let href = format!("{}/items/an-id", server.url());
+            let item: Item = crate::read(href.as_str()).unwrap();
+            assert_eq!(item.self_href().unwrap().as_str(), href);
+            let item: Item = crate::Format::json().read(href.as_str()).unwrap();
+            assert_eq!(item.self_href().unwrap().as_str(), href);
+            mock.assert();
+        }
     }
 
     #[test]
@@ -320,6 +861,60 @@ mod tests {
         let _: Item = super::get(path).await.unwrap();
     }
 
+    #[tokio::test]
+    #[cfg(all(feature = "object-store", not(target_os = "windows")))]
+    async fn get_with_cache() {
+        use super::{cache::Entry, Cache, CACHE_DIRECTORY_OPTION};
+        use url::Url;
+
+        let tempdir = TempDir::new().unwrap();
+        let path = tempdir.path().join("item.json");
+        let _ = std::fs::copy("examples/simple-item.json", &path).unwrap();
+        let url = Url::from_file_path(&path).unwrap();
+        let cache_directory = tempdir.path().join("cache");
+        let options = [(CACHE_DIRECTORY_OPTION, cache_directory.to_str().unwrap())];
+        // Local paths are read directly by `get_opts`, so go through the object store.
+        let (_, bytes) = super::get_url_opts(&url, options).await.unwrap();
+        let item: Item = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(item.id, "20201211_223832_CS2");
+
+        // Swap the cached copy so we can tell that it's used for an unmodified object
+        let cache = Cache::new(&cache_directory);
+        let entry = cache.get(&url).unwrap();
+        let bytes = serde_json::to_vec(&Item::new("from-the-cache")).unwrap();
+        cache
+            .put(
+                &url,
+                &Entry {
+                    bytes: bytes.into(),
+                    ..entry
+                },
+            )
+            .unwrap();
+        let (_, bytes) = super::get_url_opts(&url, options).await.unwrap();
+        let item: Item = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(item.id, "from-the-cache");
+    }
+
+    #[test]
+    fn read_without_extension() {
+        let tempdir = TempDir::new().unwrap();
+        let path = tempdir.path().join("item");
+        let _ = std::fs::copy("examples/simple-item.json", &path).unwrap();
+        let item: Item = super::read(path.to_str().unwrap()).unwrap();
+        assert_eq!(item.id, "20201211_223832_CS2");
+
+        let path = tempdir.path().join("items");
+        crate::Format::ndjson()
+            .write(
+                &path,
+                ItemCollection::from(vec![Item::new("a"), Item::new("b")]),
+            )
+            .unwrap();
+        let item_collection: ItemCollection = super::read(path.to_str().unwrap()).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
     #[test]
     fn write() {
         let tempdir = TempDir::new().unwrap();
@@ -329,6 +924,61 @@ mod tests {
         assert_eq!(item.id, "an-id");
     }
 
+    #[test]
+    fn write_atomic_failure() {
+        let tempdir = TempDir::new().unwrap();
+        let path = tempdir.path().join("item.json");
+        super::write(&path, Item::new("an-id")).unwrap();
+        let _ = super::write_atomic(&path, |path| {
+            std::fs::write(path, "partial")?;
+            Err(crate::Error::NoItems)
+        })
+        .unwrap_err();
+        let item: Item = crate::read(path.as_path()).unwrap();
+        assert_eq!(item.id, "an-id");
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "object-store", not(target_os = "windows")))]
+    async fn put_multipart() {
+        use super::{MIN_MULTIPART_PART_SIZE, MULTIPART_PART_SIZE_OPTION};
+        use url::Url;
+
+        let tempdir = TempDir::new().unwrap();
+        let path = tempdir.path().join("items.json");
+        let url = Url::from_file_path(&path).unwrap();
+        let mut item = Item::new("a");
+        let _ = item.properties.additional_fields.insert(
+            "description".to_string(),
+            "a".repeat(2 * MIN_MULTIPART_PART_SIZE).into(),
+        );
+        let item_collection = ItemCollection::from(vec![item, Item::new("b")]);
+        let _ = super::put_opts(
+            url.as_str(),
+            item_collection,
+            [(
+                MULTIPART_PART_SIZE_OPTION,
+                MIN_MULTIPART_PART_SIZE.to_string(),
+            )],
+        )
+        .await
+        .unwrap();
+        let item_collection: ItemCollection = crate::read(path.as_path()).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+
+        for part_size in ["0", "64"] {
+            let error = super::put_opts(
+                url.as_str(),
+                Item::new("an-id"),
+                [(MULTIPART_PART_SIZE_OPTION, part_size)],
+            )
+            .await
+            .unwrap_err();
+            assert!(matches!(error, crate::Error::InvalidOption { .. }));
+        }
+    }
+
     #[tokio::test]
     #[cfg(feature = "object-store")]
     async fn put() {
@@ -342,4 +992,31 @@ mod tests {
         let item: Item = crate::read(tempdir.path().join("item.json")).unwrap();
         assert_eq!(item.id, "an-id");
     }
+
+    #[tokio::test]
+    #[cfg(feature = "object-store")]
+    async fn put_bytes_opts() {
+        let tempdir = TempDir::new().unwrap();
+        let bytes = bytes::Bytes::from(crate::Format::json().into_vec(Item::new("an-id")).unwrap());
+        let options: [(&str, &str); 0] = [];
+        let path = tempdir.path().join("item.json");
+        let url = format!(
+            "file://{}",
+            tempdir.path().join("url.json").to_string_lossy()
+        );
+        assert!(
+            super::put_bytes_opts(path.to_string_lossy(), bytes.clone(), options)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(super::put_bytes_opts(url, bytes, options)
+            .await
+            .unwrap()
+            .is_some());
+        for file_name in ["item.json", "url.json"] {
+            let item: Item = crate::read(tempdir.path().join(file_name)).unwrap();
+            assert_eq!(item.id, "an-id");
+        }
+    }
 }
@@ -0,0 +1,237 @@
+#![cfg_attr(
+    not(any(feature = "reqwest", feature = "object-store")),
+    allow(dead_code)
+)]
+
+use crate::{Error, Result};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// A local directory of fetched objects, used to make conditional requests.
+///
+/// Each object is stored along with its `ETag`. When an object is fetched
+/// again, the request includes an `If-None-Match` header, and if the server
+/// responds that the object hasn't changed, the stored copy is used instead of
+/// transferring the object again. This makes repeated crawls of large static
+/// catalogs much cheaper.
+///
+/// Objects without an `ETag` aren't stored. Objects are stored in files
+/// named by the SHA-256 hash of their url.
+///
+/// # Examples
+///
+/// ```
+/// use stac::io::Cache;
+///
+/// let cache = Cache::new("/tmp/stac-cache");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cache {
+    directory: PathBuf,
+}
+
+/// An object stored in a [Cache].
+#[derive(Clone, Debug)]
+pub(crate) struct Entry {
+    pub(crate) etag: String,
+    pub(crate) content_type: Option<String>,
+    pub(crate) bytes: Bytes,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Metadata {
+    url: String,
+    etag: String,
+    content_type: Option<String>,
+}
+
+impl Cache {
+    /// Creates a new cache that stores objects in `directory`.
+    ///
+    /// The directory is created when the first object is stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::Cache;
+    ///
+    /// let cache = Cache::new("/tmp/stac-cache");
+    /// assert_eq!(cache.directory().to_str().unwrap(), "/tmp/stac-cache");
+    /// ```
+    pub fn new(directory: impl Into<PathBuf>) -> Cache {
+        Cache {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns this cache's directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::Cache;
+    ///
+    /// let cache = Cache::new("/tmp/stac-cache");
+    /// let directory = cache.directory();
+    /// ```
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub(crate) fn get(&self, url: &Url) -> Option<Entry> {
+        let path = self.path(url);
+        let metadata: Metadata =
+            serde_json::from_slice(&std::fs::read(path.with_extension("json")).ok()?).ok()?;
+        if metadata.url != url.as_str() {
+            return None;
+        }
+        let bytes = std::fs::read(path.with_extension("bin")).ok()?;
+        Some(Entry {
+            etag: metadata.etag,
+            content_type: metadata.content_type,
+            bytes: bytes.into(),
+        })
+    }
+
+    /// Stores an entry.
+    ///
+    /// Both files are written atomically, and the body is written before the
+    /// metadata, so an interrupted write never leaves metadata that points to
+    /// a missing or partial body.
+    pub(crate) fn put(&self, url: &Url, entry: &Entry) -> Result<()> {
+        let path = self.path(url);
+        std::fs::create_dir_all(&self.directory)?;
+        super::write_atomic(&path.with_extension("bin"), |path| {
+            std::fs::write(path, &entry.bytes).map_err(Error::from)
+        })?;
+        let metadata = Metadata {
+            url: url.to_string(),
+            etag: entry.etag.clone(),
+            content_type: entry.content_type.clone(),
+        };
+        let metadata = serde_json::to_vec(&metadata)?;
+        super::write_atomic(&path.with_extension("json"), |path| {
+            std::fs::write(path, &metadata).map_err(Error::from)
+        })
+    }
+
+    /// Stores the entry, logging instead of failing if it can't be written.
+    pub(crate) fn put_or_warn(&self, url: &Url, entry: &Entry) {
+        if let Err(err) = self.put(url, entry) {
+            tracing::warn!("could not cache {url}: {err}");
+        }
+    }
+
+    /// Like [Cache::get], but reads the files on a blocking thread.
+    #[cfg(feature = "object-store")]
+    pub(crate) async fn get_async(&self, url: &Url) -> Option<Entry> {
+        let cache = self.clone();
+        let url = url.clone();
+        tokio::task::spawn_blocking(move || cache.get(&url))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Like [Cache::put_or_warn], but writes the files on a blocking thread.
+    #[cfg(feature = "object-store")]
+    pub(crate) async fn put_or_warn_async(&self, url: &Url, entry: Entry) {
+        let cache = self.clone();
+        let url = url.clone();
+        if let Err(err) = tokio::task::spawn_blocking(move || cache.put_or_warn(&url, &entry)).await
+        {
+            tracing::warn!("could not cache: {err}");
+        }
+    }
+
+    fn path(&self, url: &Url) -> PathBuf {
+        let file_name = Sha256::digest(url.as_str()).iter().fold(
+            String::with_capacity(64),
+            |mut file_name, byte| {
+                let _ = write!(file_name, "{byte:02x}");
+                file_name
+            },
+        );
+        self.directory.join(file_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, Entry};
+    use tempfile::TempDir;
+    use url::Url;
+
+    #[test]
+    fn put_and_get() {
+        let tempdir = TempDir::new().unwrap();
+        let cache = Cache::new(tempdir.path().join("cache"));
+        let url = Url::parse("http://stac-rs.test/catalog.json").unwrap();
+        assert!(cache.get(&url).is_none());
+        cache
+            .put(
+                &url,
+                &Entry {
+                    etag: "\"an-etag\"".to_string(),
+                    content_type: Some("application/json".to_string()),
+                    bytes: "{}".into(),
+                },
+            )
+            .unwrap();
+        let entry = cache.get(&url).unwrap();
+        assert_eq!(entry.etag, "\"an-etag\"");
+        assert_eq!(entry.content_type.as_deref(), Some("application/json"));
+        assert_eq!(entry.bytes, "{}");
+    }
+
+    #[test]
+    fn put_replaces_atomically() {
+        let tempdir = TempDir::new().unwrap();
+        let cache = Cache::new(tempdir.path());
+        let url = Url::parse("http://stac-rs.test/catalog.json").unwrap();
+        for etag in ["\"a\"", "\"b\""] {
+            cache
+                .put(
+                    &url,
+                    &Entry {
+                        etag: etag.to_string(),
+                        content_type: None,
+                        bytes: etag.to_string().into(),
+                    },
+                )
+                .unwrap();
+        }
+        let entry = cache.get(&url).unwrap();
+        assert_eq!(entry.etag, "\"b\"");
+        assert_eq!(entry.bytes, "\"b\"");
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn similar_urls() {
+        let tempdir = TempDir::new().unwrap();
+        let cache = Cache::new(tempdir.path());
+        let a = Url::parse("http://stac-rs.test/a-b.json").unwrap();
+        let b = Url::parse("http://stac-rs.test/a_b.json").unwrap();
+        for (url, etag) in [(&a, "\"a\""), (&b, "\"b\"")] {
+            cache
+                .put(
+                    url,
+                    &Entry {
+                        etag: etag.to_string(),
+                        content_type: None,
+                        bytes: etag.to_string().into(),
+                    },
+                )
+                .unwrap();
+        }
+        assert_eq!(cache.get(&a).unwrap().etag, "\"a\"");
+        assert_eq!(cache.get(&b).unwrap().etag, "\"b\"");
+    }
+}
@@ -0,0 +1,743 @@
+use crate::{Error, Result};
+#[cfg(any(feature = "object-store-aws", feature = "object-store-gcp"))]
+use {
+    async_trait::async_trait,
+    object_store::CredentialProvider,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, OnceLock},
+        time::{Duration, SystemTime},
+    },
+};
+#[cfg(feature = "object-store-aws")]
+use {
+    aws_config::{
+        default_provider::credentials::DefaultCredentialsChain,
+        meta::region::ProvideRegion,
+        profile::{ProfileFileCredentialsProvider, ProfileFileRegionProvider},
+        provider_config::ProviderConfig,
+        sts::AssumeRoleProvider,
+        web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider},
+        Region,
+    },
+    aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider},
+    aws_runtime::env_config::file::EnvConfigFiles,
+    object_store::aws::{AwsCredential, AwsCredentialProvider},
+};
+#[cfg(feature = "object-store-gcp")]
+use {
+    object_store::gcp::{GcpCredential, GcpCredentialProvider},
+    serde::Deserialize,
+};
+
+/// The option used to select a named AWS profile.
+///
+/// The profile is read from the shared credentials and config files (by
+/// default `~/.aws/credentials` and `~/.aws/config`, or the files named by
+/// the `AWS_SHARED_CREDENTIALS_FILE` and `AWS_CONFIG_FILE` environment
+/// variables) by [aws-config](https://docs.rs/aws-config), so a profile can
+/// assume a role with a `source_profile` or a `web_identity_token_file`, or
+/// use a `credential_process` or SSO. The profile's region is used unless
+/// `aws_region` is also set.
+pub const AWS_PROFILE_OPTION: &str = "aws_profile";
+
+/// The option used to set the ARN of an AWS role to assume.
+///
+/// The role is assumed with a web identity token if
+/// [AWS_WEB_IDENTITY_TOKEN_FILE_OPTION] is set, or otherwise with the
+/// credentials of [AWS_PROFILE_OPTION] or the default credentials.
+pub const AWS_ROLE_ARN_OPTION: &str = "aws_role_arn";
+
+/// The option used to set the file containing an OIDC web identity token, e.g. from Kubernetes.
+pub const AWS_WEB_IDENTITY_TOKEN_FILE_OPTION: &str = "aws_web_identity_token_file";
+
+/// The option used to set the session name when assuming an AWS role.
+pub const AWS_ROLE_SESSION_NAME_OPTION: &str = "aws_role_session_name";
+
+/// The option used to set the email of a Google Cloud service account to impersonate.
+///
+/// Short-lived tokens for the service account are generated with the IAM
+/// credentials API, using the credentials from the other `google_` options
+/// or the application default credentials. Those credentials need the
+/// "Service Account Token Creator" role on the service account. This is the
+/// Google Cloud equivalent of [AWS_ROLE_ARN_OPTION].
+pub const GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION: &str = "google_impersonate_service_account";
+
+const AWS_OPTIONS: [&str; 4] = [
+    AWS_PROFILE_OPTION,
+    AWS_ROLE_ARN_OPTION,
+    AWS_WEB_IDENTITY_TOKEN_FILE_OPTION,
+    AWS_ROLE_SESSION_NAME_OPTION,
+];
+
+const GOOGLE_OPTIONS: [&str; 1] = [GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION];
+
+#[cfg(feature = "object-store-aws")]
+const DEFAULT_ROLE_SESSION_NAME: &str = "stac-rs";
+
+#[cfg(feature = "object-store-gcp")]
+const IAM_CREDENTIALS_ENDPOINT: &str = "https://iamcredentials.googleapis.com";
+
+#[cfg(feature = "object-store-gcp")]
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Credentials are refreshed this long before they expire.
+#[cfg(any(feature = "object-store-aws", feature = "object-store-gcp"))]
+const REFRESH_BEFORE_EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+/// The credential providers that have been built, by their options.
+#[cfg(feature = "object-store-aws")]
+type Providers = Mutex<HashMap<Vec<(String, String)>, AwsCredentialProvider>>;
+
+#[cfg(feature = "object-store-aws")]
+static PROVIDERS: OnceLock<Providers> = OnceLock::new();
+
+/// The impersonating credential providers that have been built, by their options.
+#[cfg(feature = "object-store-gcp")]
+type GoogleProviders = Mutex<HashMap<Vec<(String, String)>, GcpCredentialProvider>>;
+
+#[cfg(feature = "object-store-gcp")]
+static GOOGLE_PROVIDERS: OnceLock<GoogleProviders> = OnceLock::new();
+
+/// Removes the options with these keys, returning them sorted by key.
+fn take(options: &mut Vec<(String, String)>, keys: &[&str]) -> Vec<(String, String)> {
+    let mut taken = Vec::new();
+    options.retain(|(key, value)| {
+        if keys.contains(&key.as_str()) {
+            taken.push((key.clone(), value.clone()));
+            false
+        } else {
+            true
+        }
+    });
+    taken.sort();
+    taken
+}
+
+/// Removes the AWS profile and role options and returns a credential provider for them.
+///
+/// Returns `None` if none of these options are set. Providers are shared by
+/// every store built with the same options, so credentials from STS are
+/// fetched once and refreshed before they expire, instead of on every
+/// request. If the profile has a region and `aws_region` isn't set, it's
+/// added to the options.
+#[cfg(feature = "object-store-aws")]
+pub(crate) async fn take_from_options(
+    options: &mut Vec<(String, String)>,
+) -> Result<Option<AwsCredentialProvider>> {
+    take_from_options_with_profile_files(options, None).await
+}
+
+/// Like [take_from_options], but reads profiles from these files instead of
+/// the ones named by the environment.
+#[cfg(feature = "object-store-aws")]
+async fn take_from_options_with_profile_files(
+    options: &mut Vec<(String, String)>,
+    profile_files: Option<EnvConfigFiles>,
+) -> Result<Option<AwsCredentialProvider>> {
+    let taken = take(options, &AWS_OPTIONS);
+    if taken.is_empty() {
+        return Ok(None);
+    }
+    let get = |key: &str| {
+        taken
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    };
+    let profile = get(AWS_PROFILE_OPTION);
+    let mut region = options
+        .iter()
+        .find(|(key, _)| key == "aws_region")
+        .map(|(_, value)| value.clone());
+    if region.is_none() {
+        if let Some(profile) = &profile {
+            let mut builder = ProfileFileRegionProvider::builder().profile_name(profile);
+            if let Some(profile_files) = &profile_files {
+                builder = builder.profile_files(profile_files.clone());
+            }
+            region = builder
+                .build()
+                .region()
+                .await
+                .map(|region| region.to_string());
+            if let Some(region) = &region {
+                options.push(("aws_region".to_string(), region.clone()));
+            }
+        }
+    }
+    let mut key = taken.clone();
+    key.push(("aws_region".to_string(), region.clone().unwrap_or_default()));
+    let providers = PROVIDERS.get_or_init(Default::default);
+    if let Some(provider) = providers.lock().unwrap().get(&key) {
+        return Ok(Some(provider.clone()));
+    }
+    let session_name =
+        get(AWS_ROLE_SESSION_NAME_OPTION).unwrap_or_else(|| DEFAULT_ROLE_SESSION_NAME.to_string());
+    let region = region.map(Region::new);
+    let provider = match (
+        get(AWS_ROLE_ARN_OPTION),
+        get(AWS_WEB_IDENTITY_TOKEN_FILE_OPTION),
+    ) {
+        (Some(role_arn), Some(token_file)) => {
+            let config = ProviderConfig::default().with_region(region);
+            SharedCredentialsProvider::new(
+                WebIdentityTokenCredentialsProvider::builder()
+                    .static_configuration(StaticConfiguration {
+                        web_identity_token_file: token_file.into(),
+                        role_arn,
+                        session_name,
+                    })
+                    .configure(&config)
+                    .build(),
+            )
+        }
+        (None, Some(_)) => {
+            return Err(Error::Credentials(format!(
+                "{AWS_WEB_IDENTITY_TOKEN_FILE_OPTION} is set without {AWS_ROLE_ARN_OPTION}"
+            )));
+        }
+        (role_arn, None) => {
+            let base = match profile {
+                Some(profile) => {
+                    let mut builder =
+                        ProfileFileCredentialsProvider::builder().profile_name(profile);
+                    if let Some(profile_files) = profile_files {
+                        builder = builder.profile_files(profile_files);
+                    }
+                    SharedCredentialsProvider::new(builder.build())
+                }
+                None => {
+                    SharedCredentialsProvider::new(DefaultCredentialsChain::builder().build().await)
+                }
+            };
+            match role_arn {
+                Some(role_arn) => {
+                    let mut builder =
+                        AssumeRoleProvider::builder(role_arn).session_name(session_name);
+                    if let Some(region) = region {
+                        builder = builder.region(region);
+                    }
+                    SharedCredentialsProvider::new(builder.build_from_provider(base).await)
+                }
+                None => base,
+            }
+        }
+    };
+    let provider: AwsCredentialProvider = Arc::new(CachedCredentialProvider::new(provider));
+    Ok(Some(
+        providers
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert(provider)
+            .clone(),
+    ))
+}
+
+/// Returns an error if any AWS or Google Cloud credential options are set
+/// without the feature that's needed to use them.
+#[cfg(not(all(feature = "object-store-aws", feature = "object-store-gcp")))]
+pub(crate) fn reject_options(options: &mut Vec<(String, String)>) -> Result<()> {
+    #[cfg(not(feature = "object-store-aws"))]
+    if !take(options, &AWS_OPTIONS).is_empty() {
+        return Err(Error::FeatureNotEnabled("object-store-aws"));
+    }
+    #[cfg(not(feature = "object-store-gcp"))]
+    if !take(options, &GOOGLE_OPTIONS).is_empty() {
+        return Err(Error::FeatureNotEnabled("object-store-gcp"));
+    }
+    Ok(())
+}
+
+/// Removes the Google Cloud impersonation option, returning the service account.
+#[cfg(feature = "object-store-gcp")]
+pub(crate) fn take_google_service_account(options: &mut Vec<(String, String)>) -> Option<String> {
+    take(options, &GOOGLE_OPTIONS)
+        .pop()
+        .map(|(_, service_account)| service_account)
+}
+
+/// Returns a credential provider that impersonates a Google Cloud service
+/// account with the credentials of `base`.
+///
+/// Providers are shared by every store built with the same options, so
+/// tokens are generated once and refreshed before they expire.
+#[cfg(feature = "object-store-gcp")]
+pub(crate) fn impersonate(
+    service_account: String,
+    options: &[(String, String)],
+    base: GcpCredentialProvider,
+) -> GcpCredentialProvider {
+    let mut key = options.to_vec();
+    key.push((
+        GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION.to_string(),
+        service_account.clone(),
+    ));
+    key.sort();
+    GOOGLE_PROVIDERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert_with(|| {
+            Arc::new(ImpersonatedCredentialProvider::new(
+                service_account,
+                base,
+                IAM_CREDENTIALS_ENDPOINT,
+            ))
+        })
+        .clone()
+}
+
+/// Returns an error if the Azure workload identity options are incomplete.
+///
+/// A federated token file, the Azure equivalent of
+/// [AWS_WEB_IDENTITY_TOKEN_FILE_OPTION], is exchanged for a token of a client
+/// id in a tenant, so both need to be set with it. Otherwise the object store
+/// silently ignores the token file.
+#[cfg(feature = "object-store-azure")]
+pub(crate) fn check_azure_options(options: &[(String, String)]) -> Result<()> {
+    use object_store::azure::AzureConfigKey;
+
+    let has = |wanted: AzureConfigKey| {
+        options
+            .iter()
+            .any(|(key, _)| key.parse::<AzureConfigKey>().ok() == Some(wanted))
+    };
+    if has(AzureConfigKey::FederatedTokenFile) {
+        for (key, name) in [
+            (AzureConfigKey::ClientId, "azure_client_id"),
+            (AzureConfigKey::AuthorityId, "azure_tenant_id"),
+        ] {
+            if !has(key) {
+                return Err(Error::Credentials(format!(
+                    "azure_federated_token_file is set without {name}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An object store credential provider that caches the credentials of an
+/// [aws-config](https://docs.rs/aws-config) provider until they're about to expire.
+#[cfg(feature = "object-store-aws")]
+#[derive(Debug)]
+struct CachedCredentialProvider {
+    provider: SharedCredentialsProvider,
+    cached: tokio::sync::Mutex<Option<(Arc<AwsCredential>, Option<SystemTime>)>>,
+}
+
+#[cfg(feature = "object-store-aws")]
+impl CachedCredentialProvider {
+    fn new(provider: SharedCredentialsProvider) -> CachedCredentialProvider {
+        CachedCredentialProvider {
+            provider,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+}
+
+#[cfg(feature = "object-store-aws")]
+#[async_trait]
+impl CredentialProvider for CachedCredentialProvider {
+    type Credential = AwsCredential;
+
+    async fn get_credential(&self) -> object_store::Result<Arc<AwsCredential>> {
+        let mut cached = self.cached.lock().await;
+        if let Some((credential, expiry)) = cached.as_ref() {
+            let is_fresh = match expiry {
+                Some(expiry) => *expiry > SystemTime::now() + REFRESH_BEFORE_EXPIRY,
+                None => true,
+            };
+            if is_fresh {
+                return Ok(credential.clone());
+            }
+        }
+        let credentials = self.provider.provide_credentials().await.map_err(|err| {
+            object_store::Error::Generic {
+                store: "S3",
+                source: Box::new(err),
+            }
+        })?;
+        let credential = Arc::new(AwsCredential {
+            key_id: credentials.access_key_id().to_string(),
+            secret_key: credentials.secret_access_key().to_string(),
+            token: credentials.session_token().map(String::from),
+        });
+        *cached = Some((credential.clone(), credentials.expiry()));
+        Ok(credential)
+    }
+}
+
+/// An object store credential provider that impersonates a Google Cloud
+/// service account, caching its tokens until they're about to expire.
+#[cfg(feature = "object-store-gcp")]
+#[derive(Debug)]
+struct ImpersonatedCredentialProvider {
+    service_account: String,
+    base: GcpCredentialProvider,
+    endpoint: String,
+    client: reqwest::Client,
+    cached: tokio::sync::Mutex<Option<(Arc<GcpCredential>, SystemTime)>>,
+}
+
+/// The response of the IAM credentials API's `generateAccessToken`.
+#[cfg(feature = "object-store-gcp")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccessToken {
+    access_token: String,
+    expire_time: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "object-store-gcp")]
+impl ImpersonatedCredentialProvider {
+    fn new(
+        service_account: String,
+        base: GcpCredentialProvider,
+        endpoint: impl ToString,
+    ) -> ImpersonatedCredentialProvider {
+        ImpersonatedCredentialProvider {
+            service_account,
+            base,
+            endpoint: endpoint.to_string(),
+            client: reqwest::Client::new(),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+}
+
+#[cfg(feature = "object-store-gcp")]
+#[async_trait]
+impl CredentialProvider for ImpersonatedCredentialProvider {
+    type Credential = GcpCredential;
+
+    async fn get_credential(&self) -> object_store::Result<Arc<GcpCredential>> {
+        let mut cached = self.cached.lock().await;
+        if let Some((credential, expiry)) = cached.as_ref() {
+            if *expiry > SystemTime::now() + REFRESH_BEFORE_EXPIRY {
+                return Ok(credential.clone());
+            }
+        }
+        let generic = |err: reqwest::Error| object_store::Error::Generic {
+            store: "GCS",
+            source: Box::new(err),
+        };
+        let base = self.base.get_credential().await?;
+        let access_token: AccessToken = self
+            .client
+            .post(format!(
+                "{}/v1/projects/-/serviceAccounts/{}:generateAccessToken",
+                self.endpoint, self.service_account
+            ))
+            .bearer_auth(&base.bearer)
+            .json(&serde_json::json!({ "scope": [CLOUD_PLATFORM_SCOPE] }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(generic)?
+            .json()
+            .await
+            .map_err(generic)?;
+        let credential = Arc::new(GcpCredential {
+            bearer: access_token.access_token,
+        });
+        *cached = Some((credential.clone(), access_token.expire_time.into()));
+        Ok(credential)
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "object-store-aws",
+        feature = "object-store-azure",
+        feature = "object-store-gcp"
+    )
+))]
+mod tests {
+    #[cfg(feature = "object-store-aws")]
+    mod aws {
+        use super::super::CachedCredentialProvider;
+        use aws_credential_types::{
+            provider::{future, ProvideCredentials, SharedCredentialsProvider},
+            Credentials,
+        };
+        use aws_runtime::env_config::file::{EnvConfigFileKind, EnvConfigFiles};
+        use object_store::CredentialProvider;
+        use std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            time::{Duration, SystemTime},
+        };
+
+        #[derive(Debug)]
+        struct Counter {
+            count: Arc<AtomicUsize>,
+            expires_in: Duration,
+        }
+
+        impl ProvideCredentials for Counter {
+            fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+            where
+                Self: 'a,
+            {
+                let _ = self.count.fetch_add(1, Ordering::SeqCst);
+                future::ProvideCredentials::ready(Ok(Credentials::new(
+                    "a-key",
+                    "a-secret",
+                    Some("a-token".to_string()),
+                    Some(SystemTime::now() + self.expires_in),
+                    "counter",
+                )))
+            }
+        }
+
+        fn provider(expires_in: Duration) -> (CachedCredentialProvider, Arc<AtomicUsize>) {
+            let count = Arc::new(AtomicUsize::new(0));
+            let provider = CachedCredentialProvider::new(SharedCredentialsProvider::new(Counter {
+                count: count.clone(),
+                expires_in,
+            }));
+            (provider, count)
+        }
+
+        #[tokio::test]
+        async fn caches_credentials() {
+            let (provider, count) = provider(Duration::from_secs(60 * 60));
+            let credential = provider.get_credential().await.unwrap();
+            assert_eq!(credential.key_id, "a-key");
+            assert_eq!(credential.token.as_deref(), Some("a-token"));
+            let _ = provider.get_credential().await.unwrap();
+            assert_eq!(count.load(Ordering::SeqCst), 1);
+        }
+
+        #[tokio::test]
+        async fn refreshes_expiring_credentials() {
+            let (provider, count) = provider(Duration::from_secs(60));
+            let _ = provider.get_credential().await.unwrap();
+            let _ = provider.get_credential().await.unwrap();
+            assert_eq!(count.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test]
+        async fn without_options() {
+            let mut options = vec![("aws_region".to_string(), "eu-west-1".to_string())];
+            assert!(super::super::take_from_options(&mut options)
+                .await
+                .unwrap()
+                .is_none());
+            assert_eq!(options.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn shares_providers() {
+            let options = || {
+                vec![
+                    (
+                        super::super::AWS_PROFILE_OPTION.to_string(),
+                        "stac-rs-shares-providers".to_string(),
+                    ),
+                    ("aws_region".to_string(), "eu-west-1".to_string()),
+                ]
+            };
+            let mut a = options();
+            let a_provider = super::super::take_from_options(&mut a)
+                .await
+                .unwrap()
+                .unwrap();
+            let mut b = options();
+            let b_provider = super::super::take_from_options(&mut b)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(Arc::ptr_eq(&a_provider, &b_provider));
+            assert_eq!(a, vec![("aws_region".to_string(), "eu-west-1".to_string())]);
+        }
+
+        #[tokio::test]
+        async fn token_file_without_role() {
+            let mut options = vec![(
+                super::super::AWS_WEB_IDENTITY_TOKEN_FILE_OPTION.to_string(),
+                "token".to_string(),
+            )];
+            let _ = super::super::take_from_options(&mut options)
+                .await
+                .unwrap_err();
+        }
+
+        #[tokio::test]
+        async fn region_from_profile() {
+            let tempdir = tempfile::tempdir().unwrap();
+            let config = tempdir.path().join("config");
+            std::fs::write(
+                &config,
+                "[profile stac-rs-region-from-profile]\nregion = ap-southeast-2\n",
+            )
+            .unwrap();
+            let profile_files = EnvConfigFiles::builder()
+                .with_file(EnvConfigFileKind::Config, config)
+                .build();
+            let mut options = vec![(
+                super::super::AWS_PROFILE_OPTION.to_string(),
+                "stac-rs-region-from-profile".to_string(),
+            )];
+            assert!(super::super::take_from_options_with_profile_files(
+                &mut options,
+                Some(profile_files)
+            )
+            .await
+            .unwrap()
+            .is_some());
+            assert_eq!(
+                options,
+                vec![("aws_region".to_string(), "ap-southeast-2".to_string())]
+            );
+        }
+    }
+
+    #[cfg(feature = "object-store-azure")]
+    mod azure {
+        fn options(keys: &[&str]) -> Vec<(String, String)> {
+            keys.iter()
+                .map(|key| (key.to_string(), "a-value".to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn federated_token_file() {
+            super::super::check_azure_options(&options(&[
+                "azure_federated_token_file",
+                "azure_client_id",
+                "azure_tenant_id",
+            ]))
+            .unwrap();
+            super::super::check_azure_options(&options(&[
+                "federated_token_file",
+                "client_id",
+                "azure_authority_id",
+            ]))
+            .unwrap();
+            super::super::check_azure_options(&options(&["azure_client_id"])).unwrap();
+        }
+
+        #[test]
+        fn federated_token_file_without_client() {
+            let error = super::super::check_azure_options(&options(&[
+                "azure_federated_token_file",
+                "azure_tenant_id",
+            ]))
+            .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "credentials: azure_federated_token_file is set without azure_client_id"
+            );
+            let _ = super::super::check_azure_options(&options(&[
+                "azure_federated_token_file",
+                "azure_client_id",
+            ]))
+            .unwrap_err();
+        }
+    }
+
+    #[cfg(feature = "object-store-gcp")]
+    mod gcp {
+        use super::super::ImpersonatedCredentialProvider;
+        use chrono::{Duration, Utc};
+        use object_store::{gcp::GcpCredential, CredentialProvider, StaticCredentialProvider};
+        use serde_json::json;
+        use std::sync::Arc;
+
+        const PATH: &str =
+            "/v1/projects/-/serviceAccounts/reader@a-project.iam.gserviceaccount.com:generateAccessToken";
+
+        fn provider(server: &mockito::Server) -> ImpersonatedCredentialProvider {
+            ImpersonatedCredentialProvider::new(
+                "reader@a-project.iam.gserviceaccount.com".to_string(),
+                Arc::new(StaticCredentialProvider::new(GcpCredential {
+                    bearer: "a-base-token".to_string(),
+                })),
+                server.url(),
+            )
+        }
+
+        async fn mock(
+            server: &mut mockito::Server,
+            expires_in: Duration,
+            hits: usize,
+        ) -> mockito::Mock {
+            server
+                .mock("POST", PATH)
+                .match_header("authorization", "Bearer a-base-token")
+                .match_body(mockito::Matcher::PartialJson(json!({
+                    "scope": [super::super::CLOUD_PLATFORM_SCOPE]
+                })))
+                .with_body(
+                    json!({
+                        "accessToken": "an-impersonated-token",
+                        "expireTime": (Utc::now() + expires_in).to_rfc3339(),
+                    })
+                    .to_string(),
+                )
+                .expect(hits)
+                .create_async()
+                .await
+        }
+
+        #[tokio::test]
+        async fn caches_tokens() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = mock(&mut server, Duration::hours(1), 1).await;
+            let provider = provider(&server);
+            let credential = provider.get_credential().await.unwrap();
+            assert_eq!(credential.bearer, "an-impersonated-token");
+            let _ = provider.get_credential().await.unwrap();
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn refreshes_expiring_tokens() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = mock(&mut server, Duration::minutes(1), 2).await;
+            let provider = provider(&server);
+            let _ = provider.get_credential().await.unwrap();
+            let _ = provider.get_credential().await.unwrap();
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn forbidden() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("POST", PATH)
+                .with_status(403)
+                .create_async()
+                .await;
+            let _ = provider(&server).get_credential().await.unwrap_err();
+            mock.assert_async().await;
+        }
+
+        #[test]
+        fn take_service_account() {
+            let mut options = vec![
+                (
+                    super::super::GOOGLE_IMPERSONATE_SERVICE_ACCOUNT_OPTION.to_string(),
+                    "reader@a-project.iam.gserviceaccount.com".to_string(),
+                ),
+                ("google_bucket".to_string(), "a-bucket".to_string()),
+            ];
+            assert_eq!(
+                super::super::take_google_service_account(&mut options).unwrap(),
+                "reader@a-project.iam.gserviceaccount.com"
+            );
+            assert_eq!(
+                options,
+                vec![("google_bucket".to_string(), "a-bucket".to_string())]
+            );
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{Error, Result};
+use std::time::Duration;
+use url::Url;
+
+/// The option used to set the number of times an object store request is retried.
+pub const MAX_RETRIES_OPTION: &str = "stac_max_retries";
+
+/// The option used to set the backoff before the first retry, in milliseconds.
+pub const RETRY_BACKOFF_OPTION: &str = "stac_retry_backoff_ms";
+
+/// The option used to set the longest backoff between retries, in milliseconds.
+pub const RETRY_MAX_BACKOFF_OPTION: &str = "stac_retry_max_backoff_ms";
+
+/// The option used to set the timeout of a single object store request, in milliseconds.
+pub const TIMEOUT_OPTION: &str = "stac_timeout_ms";
+
+/// How object store requests are retried when they fail.
+///
+/// The policy configures the object store's own client, which retries failed
+/// requests with exponential backoff, starting at `initial_backoff` and
+/// doubling up to `max_backoff`. Only transient errors, e.g. server errors,
+/// rate limiting, and dropped connections, are retried: errors that won't go
+/// away by themselves, like denied credentials or a missing object, aren't.
+/// Local files aren't retried.
+///
+/// In [get_opts](crate::io::get_opts) and [put_opts](crate::io::put_opts),
+/// the policy is configured with the [MAX_RETRIES_OPTION],
+/// [RETRY_BACKOFF_OPTION], [RETRY_MAX_BACKOFF_OPTION], and [TIMEOUT_OPTION]
+/// options. To configure a single scheme, add it after a colon, e.g.
+/// `stac_max_retries:s3`. Scheme-specific options take precedence, and all of
+/// these options are removed before the rest are passed to the object store.
+///
+/// # Examples
+///
+/// ```
+/// use stac::io::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::default()
+///     .max_retries(5)
+///     .backoff(Duration::from_millis(200), Duration::from_secs(30))
+///     .timeout(Duration::from_secs(60));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The number of times a request is retried.
+    pub max_retries: usize,
+
+    /// The backoff before the first retry.
+    pub initial_backoff: Duration,
+
+    /// The longest backoff between retries.
+    pub max_backoff: Duration,
+
+    /// The timeout of a single request.
+    pub timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Sets the number of times a request is retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::default().max_retries(0);
+    /// ```
+    pub fn max_retries(mut self, max_retries: usize) -> RetryPolicy {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the backoff before the first retry, and the longest backoff between retries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::default().backoff(Duration::from_millis(50), Duration::from_secs(5));
+    /// ```
+    pub fn backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the timeout of a single request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::default().timeout(Duration::from_secs(30));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> RetryPolicy {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the options that describe this policy for all schemes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::io::RetryPolicy;
+    ///
+    /// let options = RetryPolicy::default().max_retries(5).into_options();
+    /// assert!(options.contains(&("stac_max_retries".to_string(), "5".to_string())));
+    /// ```
+    pub fn into_options(self) -> Vec<(String, String)> {
+        let mut options = vec![
+            (MAX_RETRIES_OPTION.to_string(), self.max_retries.to_string()),
+            (
+                RETRY_BACKOFF_OPTION.to_string(),
+                self.initial_backoff.as_millis().to_string(),
+            ),
+            (
+                RETRY_MAX_BACKOFF_OPTION.to_string(),
+                self.max_backoff.as_millis().to_string(),
+            ),
+        ];
+        if let Some(timeout) = self.timeout {
+            options.push((TIMEOUT_OPTION.to_string(), timeout.as_millis().to_string()));
+        }
+        options
+    }
+
+    /// Removes the retry options from `options` and returns the policy they
+    /// describe for `url`.
+    pub(crate) fn take_from_options(
+        url: &Url,
+        options: &mut Vec<(String, String)>,
+    ) -> Result<RetryPolicy> {
+        let mut general = Vec::new();
+        let mut specific = Vec::new();
+        options.retain(|(key, value)| {
+            let (name, scheme) = match key.split_once(':') {
+                Some((name, scheme)) => (name, Some(scheme)),
+                None => (key.as_str(), None),
+            };
+            let Some(name) = [
+                MAX_RETRIES_OPTION,
+                RETRY_BACKOFF_OPTION,
+                RETRY_MAX_BACKOFF_OPTION,
+                TIMEOUT_OPTION,
+            ]
+            .into_iter()
+            .find(|option| *option == name) else {
+                return true;
+            };
+            match scheme {
+                None => general.push((name, value.clone())),
+                Some(scheme) if scheme == url.scheme() => specific.push((name, value.clone())),
+                Some(_) => {}
+            }
+            false
+        });
+        let mut policy = RetryPolicy::default();
+        for (key, value) in general.into_iter().chain(specific) {
+            let number: u64 = value.parse().map_err(|_| Error::InvalidOption {
+                key,
+                value: value.clone(),
+            })?;
+            match key {
+                MAX_RETRIES_OPTION => policy.max_retries = usize::try_from(number)?,
+                RETRY_BACKOFF_OPTION => policy.initial_backoff = Duration::from_millis(number),
+                RETRY_MAX_BACKOFF_OPTION => policy.max_backoff = Duration::from_millis(number),
+                _ => policy.timeout = Some(Duration::from_millis(number)),
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Returns the object store retry configuration for this policy.
+    #[cfg(any(
+        feature = "object-store-aws",
+        feature = "object-store-azure",
+        feature = "object-store-gcp",
+        feature = "object-store-http"
+    ))]
+    pub(crate) fn retry_config(&self) -> object_store::RetryConfig {
+        object_store::RetryConfig {
+            backoff: object_store::BackoffConfig {
+                init_backoff: self.initial_backoff,
+                max_backoff: self.max_backoff,
+                base: 2.0,
+            },
+            max_retries: self.max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Adds this policy's timeout to the object store client options, unless
+    /// they already set one.
+    pub(crate) fn add_timeout_option(&self, options: &mut Vec<(String, String)>) {
+        if let Some(timeout) = self.timeout {
+            if !options.iter().any(|(key, _)| key == "timeout") {
+                options.push(("timeout".to_string(), format!("{}ms", timeout.as_millis())));
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(15),
+            timeout: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use std::time::Duration;
+    use url::Url;
+
+    #[test]
+    fn options() {
+        let url = Url::parse("s3://bucket/item.json").unwrap();
+        let mut options = vec![
+            ("stac_max_retries:s3".to_string(), "7".to_string()),
+            ("stac_max_retries".to_string(), "2".to_string()),
+            ("stac_timeout_ms:gs".to_string(), "10".to_string()),
+            ("stac_retry_backoff_ms".to_string(), "5".to_string()),
+            ("aws_region".to_string(), "us-west-2".to_string()),
+        ];
+        let policy = RetryPolicy::take_from_options(&url, &mut options).unwrap();
+        assert_eq!(policy.max_retries, 7);
+        assert_eq!(policy.initial_backoff, Duration::from_millis(5));
+        assert_eq!(policy.timeout, None);
+        assert_eq!(
+            options,
+            vec![("aws_region".to_string(), "us-west-2".to_string())]
+        );
+
+        let mut options = vec![("stac_timeout_ms".to_string(), "soon".to_string())];
+        let _ = RetryPolicy::take_from_options(&url, &mut options).unwrap_err();
+    }
+
+    #[test]
+    fn roundtrip_options() {
+        let url = Url::parse("gs://bucket/item.json").unwrap();
+        let policy = RetryPolicy::default()
+            .max_retries(1)
+            .timeout(Duration::from_secs(2));
+        let mut options = policy.into_options();
+        assert_eq!(
+            RetryPolicy::take_from_options(&url, &mut options).unwrap(),
+            policy
+        );
+        assert!(options.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "object-store-aws")]
+    fn retry_config() {
+        let policy = RetryPolicy::default()
+            .max_retries(5)
+            .backoff(Duration::from_secs(1), Duration::from_secs(3));
+        let retry_config = policy.retry_config();
+        assert_eq!(retry_config.max_retries, 5);
+        assert_eq!(retry_config.backoff.init_backoff, Duration::from_secs(1));
+        assert_eq!(retry_config.backoff.max_backoff, Duration::from_secs(3));
+    }
+
+    #[test]
+    fn add_timeout_option() {
+        let policy = RetryPolicy::default().timeout(Duration::from_secs(2));
+        let mut options = Vec::new();
+        policy.add_timeout_option(&mut options);
+        assert_eq!(options, vec![("timeout".to_string(), "2000ms".to_string())]);
+
+        let mut options = vec![("timeout".to_string(), "5s".to_string())];
+        policy.add_timeout_option(&mut options);
+        assert_eq!(options, vec![("timeout".to_string(), "5s".to_string())]);
+    }
+}
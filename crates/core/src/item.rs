@@ -1,13 +1,14 @@
 //! STAC Items.
 
-use crate::{Asset, Assets, Bbox, Error, Fields, Href, Link, Result, Version, STAC_VERSION};
+use crate::{
+    Asset, Assets, Bbox, Error, Fields, Href, Link, Links, Result, SelfHref, Version, STAC_VERSION,
+};
 use chrono::{DateTime, FixedOffset, Utc};
 use geojson::{feature::Id, Feature, Geometry};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Links, Migrate, SelfHref};
 use std::{collections::HashMap, path::Path};
-use url::Url;
 
 const TOP_LEVEL_ATTRIBUTES: [&str; 8] = [
     "type",
@@ -20,6 +21,17 @@ const TOP_LEVEL_ATTRIBUTES: [&str; 8] = [
     "collection",
 ];
 
+/// Properties that are typed fields on [Properties], not additional fields.
+const TYPED_PROPERTIES: [&str; 7] = [
+    "datetime",
+    "start_datetime",
+    "end_datetime",
+    "title",
+    "description",
+    "created",
+    "updated",
+];
+
 const ITEM_TYPE: &str = "Feature";
 
 fn item_type() -> String {
@@ -125,6 +137,9 @@ pub struct Item {
 /// use this "flat" representation.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FlatItem {
+    #[serde(default = "item_type", deserialize_with = "deserialize_item_type")]
+    r#type: String,
+
     #[serde(rename = "stac_version", default = "default_stac_version")]
     version: Version,
 
@@ -168,6 +183,211 @@ pub struct FlatItem {
     pub properties: Map<String, Value>,
 }
 
+impl FlatItem {
+    /// Converts this flat item back into an [Item].
+    ///
+    /// Use the same options that created the flat item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let item = Item::new("an-id");
+    /// let flat_item = item.clone().into_flat_item(true).unwrap();
+    /// assert_eq!(flat_item.into_item(Default::default()).unwrap(), item);
+    /// ```
+    pub fn into_item(self, options: FlattenOptions) -> Result<Item> {
+        let (properties, additional_fields) = options.unflatten(self.properties)?;
+        Ok(Item {
+            r#type: ITEM_TYPE.to_string(),
+            version: self.version,
+            extensions: self.extensions,
+            id: self.id,
+            geometry: self.geometry,
+            bbox: self.bbox,
+            properties: serde_json::from_value(Value::Object(properties))?,
+            links: self.links,
+            assets: self.assets,
+            collection: self.collection,
+            additional_fields,
+            self_href: None,
+        })
+    }
+}
+
+impl TryFrom<FlatItem> for Item {
+    type Error = Error;
+
+    fn try_from(flat_item: FlatItem) -> Result<Item> {
+        flat_item.into_item(FlattenOptions::default())
+    }
+}
+
+/// What to do with fields that don't fit in a [FlatItem] as-is.
+///
+/// These are properties whose names collide with top-level fields (e.g. an
+/// `id` property), properties whose names contain the
+/// [separator](FlattenOptions::separator), and top-level foreign members.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Collision {
+    /// Return an [Error::InvalidAttribute].
+    Error,
+
+    /// Keep the field, prefixed with [PROPERTY_PREFIX] or [FOREIGN_MEMBER_PREFIX].
+    Prefix,
+
+    /// Drop the field with a warning.
+    #[default]
+    Drop,
+}
+
+/// Options for converting between an [Item] and a [FlatItem].
+///
+/// # Examples
+///
+/// ```
+/// use stac::item::{Collision, FlattenOptions};
+///
+/// let options = FlattenOptions {
+///     collision: Collision::Prefix,
+///     separator: Some('.'),
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlattenOptions {
+    /// What to do with fields that don't fit in a flat item as-is.
+    pub collision: Collision,
+
+    /// If set, nested objects in properties are flattened into keys joined
+    /// by this separator, e.g. `{"a": {"b": 1}}` becomes `{"a.b": 1}`.
+    ///
+    /// Objects whose keys can't be joined without ambiguity, e.g. because
+    /// they already contain the separator, are kept whole.
+    pub separator: Option<char>,
+}
+
+/// The prefix for properties that collide with top-level fields, with [Collision::Prefix].
+pub const PROPERTY_PREFIX: &str = "properties.";
+
+/// The prefix for top-level foreign members, with [Collision::Prefix].
+pub const FOREIGN_MEMBER_PREFIX: &str = "item.";
+
+impl FlattenOptions {
+    fn flatten(
+        &self,
+        properties: Map<String, Value>,
+        additional_fields: Map<String, Value>,
+    ) -> Result<Map<String, Value>> {
+        let mut flat = Map::new();
+        for (key, value) in properties {
+            if self.collides(&key) {
+                match self.collision {
+                    Collision::Error => return Err(Error::InvalidAttribute(key)),
+                    Collision::Prefix => {
+                        let _ = flat.insert(format!("{PROPERTY_PREFIX}{key}"), value);
+                    }
+                    Collision::Drop => log::warn!("dropping invalid property: {}", key),
+                }
+            } else {
+                self.insert_flattened(&mut flat, key, value);
+            }
+        }
+        for (key, value) in additional_fields {
+            match self.collision {
+                Collision::Error => return Err(Error::InvalidAttribute(key)),
+                Collision::Prefix => {
+                    let _ = flat.insert(format!("{FOREIGN_MEMBER_PREFIX}{key}"), value);
+                }
+                Collision::Drop => {
+                    log::warn!("dropping out-of-spec top-level attribute: {}", key)
+                }
+            }
+        }
+        Ok(flat)
+    }
+
+    pub(crate) fn unflatten(
+        &self,
+        flat: Map<String, Value>,
+    ) -> Result<(Map<String, Value>, Map<String, Value>)> {
+        let mut properties = Map::new();
+        let mut additional_fields = Map::new();
+        for (key, value) in flat {
+            if self.collision == Collision::Prefix {
+                if let Some(key) = key.strip_prefix(PROPERTY_PREFIX) {
+                    let _ = properties.insert(key.to_string(), value);
+                    continue;
+                }
+                if let Some(key) = key.strip_prefix(FOREIGN_MEMBER_PREFIX) {
+                    let _ = additional_fields.insert(key.to_string(), value);
+                    continue;
+                }
+            }
+            match self.separator {
+                Some(separator) if key.contains(separator) => {
+                    let mut object = &mut properties;
+                    let mut parts = key.split(separator).peekable();
+                    while let Some(part) = parts.next() {
+                        if parts.peek().is_none() {
+                            let _ = object.insert(part.to_string(), value);
+                            break;
+                        }
+                        object = match object
+                            .entry(part)
+                            .or_insert_with(|| Value::Object(Map::new()))
+                        {
+                            Value::Object(object) => object,
+                            _ => return Err(Error::InvalidAttribute(key.to_string())),
+                        };
+                    }
+                }
+                _ => {
+                    let _ = properties.insert(key, value);
+                }
+            }
+        }
+        Ok((properties, additional_fields))
+    }
+
+    /// Returns true if a property can't be stored as-is.
+    fn collides(&self, key: &str) -> bool {
+        self.is_reserved(key)
+            || self
+                .separator
+                .is_some_and(|separator| key.contains(separator))
+    }
+
+    /// Returns true if a key would be mistaken for something other than a property.
+    fn is_reserved(&self, key: &str) -> bool {
+        TOP_LEVEL_ATTRIBUTES.contains(&key)
+            || key == "stac_version"
+            || key == "properties"
+            || (self.collision == Collision::Prefix
+                && (key.starts_with(PROPERTY_PREFIX) || key.starts_with(FOREIGN_MEMBER_PREFIX)))
+    }
+
+    fn insert_flattened(&self, flat: &mut Map<String, Value>, key: String, value: Value) {
+        match (self.separator, value) {
+            (Some(separator), Value::Object(object))
+                if !object.is_empty()
+                    && object.keys().all(|k| {
+                        !k.contains(separator) && !self.is_reserved(&format!("{key}{separator}{k}"))
+                    }) =>
+            {
+                for (k, value) in object {
+                    self.insert_flattened(flat, format!("{key}{separator}{k}"), value);
+                }
+            }
+            (_, value) => {
+                let _ = flat.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Additional metadata fields can be added to the GeoJSON Object Properties.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Properties {
@@ -241,11 +461,22 @@ pub struct Properties {
 }
 
 /// Builder for a STAC Item.
+///
+/// Unlike setting an [Item]'s public fields directly, the builder checks the
+/// item's invariants when [Builder::build] is called.
 #[derive(Debug)]
 pub struct Builder {
     id: String,
     canonicalize_paths: bool,
     assets: HashMap<String, Asset>,
+    geometry: Option<Geometry>,
+    bbox: Option<Bbox>,
+    datetime: Option<DateTime<Utc>>,
+    start_datetime: Option<DateTime<Utc>>,
+    end_datetime: Option<DateTime<Utc>>,
+    collection: Option<String>,
+    extensions: Vec<String>,
+    properties: Map<String, Value>,
 }
 
 impl Builder {
@@ -262,6 +493,14 @@ impl Builder {
             id: id.to_string(),
             canonicalize_paths: true,
             assets: HashMap::new(),
+            geometry: None,
+            bbox: None,
+            datetime: Some(Utc::now()),
+            start_datetime: None,
+            end_datetime: None,
+            collection: None,
+            extensions: Vec::new(),
+            properties: Map::new(),
         }
     }
 
@@ -293,8 +532,135 @@ impl Builder {
         self
     }
 
+    /// Sets the geometry.
+    ///
+    /// If no bbox is set, it is computed from the geometry when the item is
+    /// built, which requires the `geo` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let builder = Builder::new("an-id").geometry(Geometry::new(Value::Point(vec![-105.1, 41.1])));
+    /// ```
+    pub fn geometry(mut self, geometry: impl Into<Geometry>) -> Builder {
+        self.geometry = Some(geometry.into());
+        self
+    }
+
+    /// Sets the bbox.
+    ///
+    /// If a geometry is also set, the bbox must match its bounds (checked with
+    /// the `geo` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{item::Builder, Bbox};
+    /// let builder = Builder::new("an-id").bbox(Bbox::new(-105.1, 41.1, -105.0, 41.2));
+    /// ```
+    pub fn bbox(mut self, bbox: impl Into<Bbox>) -> Builder {
+        self.bbox = Some(bbox.into());
+        self
+    }
+
+    /// Sets the datetime.
+    ///
+    /// Defaults to the time the builder was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// let builder = Builder::new("an-id").datetime("2024-01-01T00:00:00Z".parse().unwrap());
+    /// ```
+    pub fn datetime(mut self, datetime: DateTime<Utc>) -> Builder {
+        self.datetime = Some(datetime);
+        self
+    }
+
+    /// Sets the start and end datetimes, and clears the datetime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// let item = Builder::new("an-id")
+    ///     .datetime_range(
+    ///         "2024-01-01T00:00:00Z".parse().unwrap(),
+    ///         "2024-01-02T00:00:00Z".parse().unwrap(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(item.properties.datetime.is_none());
+    /// ```
+    pub fn datetime_range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Builder {
+        self.datetime = None;
+        self.start_datetime = Some(start);
+        self.end_datetime = Some(end);
+        self
+    }
+
+    /// Sets the collection id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// let builder = Builder::new("an-id").collection("a-collection");
+    /// ```
+    pub fn collection(mut self, collection: impl ToString) -> Builder {
+        self.collection = Some(collection.to_string());
+        self
+    }
+
+    /// Adds an extension identifier.
+    ///
+    /// To set an extension's identifier and fields at the same time, use
+    /// `stac_extensions::WithExtension`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// let builder = Builder::new("an-id")
+    ///     .extension("https://stac-extensions.github.io/projection/v2.0.0/schema.json");
+    /// ```
+    pub fn extension(mut self, identifier: impl ToString) -> Builder {
+        let identifier = identifier.to_string();
+        if !self.extensions.contains(&identifier) {
+            self.extensions.push(identifier);
+        }
+        self
+    }
+
+    /// Sets a property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::item::Builder;
+    /// let item = Builder::new("an-id").property("platform", "landsat-8").build().unwrap();
+    /// assert_eq!(item.properties.additional_fields["platform"], "landsat-8");
+    /// ```
+    pub fn property(mut self, key: impl ToString, value: impl Into<Value>) -> Builder {
+        let _ = self.properties.insert(key.to_string(), value.into());
+        self
+    }
+
     /// Builds an [Item] from this builder.
     ///
+    /// Returns an error if:
+    ///
+    /// - a property conflicts with a top-level item attribute, or with a typed
+    ///   property like `datetime` (use the builder's own setters for those)
+    /// - there's neither a datetime nor a start and end datetime, or the start
+    ///   is after the end
+    /// - the bbox is invalid or doesn't match the geometry
+    /// - there's a geometry without a bbox and the `geo` feature isn't enabled
+    ///
     /// # Examples
     ///
     /// ```
@@ -304,13 +670,57 @@ impl Builder {
     /// assert_eq!(item.assets.len(), 1);
     /// ```
     pub fn build(self) -> Result<Item> {
+        for key in self.properties.keys() {
+            if TOP_LEVEL_ATTRIBUTES.contains(&key.as_str())
+                || TYPED_PROPERTIES.contains(&key.as_str())
+            {
+                return Err(Error::InvalidAttribute(key.to_string()));
+            }
+        }
+        match (self.datetime, self.start_datetime, self.end_datetime) {
+            (_, Some(start), Some(end)) if start > end => {
+                return Err(Error::InvalidDatetime(format!("{}/{}", start, end)));
+            }
+            (None, None, _) | (None, _, None) => {
+                return Err(Error::InvalidDatetime(
+                    "an item needs a datetime, or a start and end datetime".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(bbox) = self.bbox {
+            if !bbox.is_valid() {
+                return Err(Error::InvalidBbox(bbox.into()));
+            }
+        }
         let mut item = Item::new(self.id);
+        item.geometry = self.geometry;
+        item.bbox = self.bbox;
+        if item.geometry.is_some() {
+            #[cfg(feature = "geo")]
+            {
+                if item.bbox.is_some() {
+                    item.validate_bbox()?;
+                } else {
+                    item.sync_bbox();
+                }
+            }
+            #[cfg(not(feature = "geo"))]
+            {
+                if item.bbox.is_none() {
+                    return Err(Error::FeatureNotEnabled("geo"));
+                }
+            }
+        }
+        item.properties.datetime = self.datetime;
+        item.properties.start_datetime = self.start_datetime;
+        item.properties.end_datetime = self.end_datetime;
+        item.properties.additional_fields = self.properties;
+        item.collection = self.collection;
+        item.extensions = self.extensions;
         for (key, mut asset) in self.assets {
-            if Url::parse(&asset.href).is_err() && self.canonicalize_paths {
-                asset.href = Path::new(&asset.href)
-                    .canonicalize()?
-                    .to_string_lossy()
-                    .into_owned();
+            if !Href::from(asset.href.as_str()).is_url() && self.canonicalize_paths {
+                asset.href = Href::from(Path::new(&asset.href).canonicalize()?).to_string();
             }
             let _ = item.assets.insert(key, asset);
         }
@@ -363,6 +773,24 @@ impl Item {
         }
     }
 
+    /// Returns a [Builder] for an item with the given `id`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let item = Item::builder("an-id")
+    ///     .collection("a-collection")
+    ///     .property("platform", "landsat-8")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(item.collection.unwrap(), "a-collection");
+    /// ```
+    pub fn builder(id: impl ToString) -> Builder {
+        Builder::new(id)
+    }
+
     /// Sets this item's collection id in the builder pattern.
     ///
     /// # Examples
@@ -376,24 +804,246 @@ impl Item {
         self
     }
 
-    /// Returns this item's collection link.
+    /// Returns this item's collection link.
+    ///
+    /// This is the first link with a rel="collection".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let link = item.collection_link().unwrap();
+    /// ```
+    pub fn collection_link(&self) -> Option<&Link> {
+        self.links.iter().find(|link| link.is_collection())
+    }
+
+    /// Records that this item was derived from another item.
+    ///
+    /// Adds a `derived_from` link to the source item's href, which is its
+    /// [self href](SelfHref) or, if that isn't set, its `self` link. A source
+    /// that's already linked isn't added again.
+    ///
+    /// Returns [Error::NoHref] if the source item doesn't have an href.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let source: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let mut item = Item::new("derived-item");
+    /// item.add_derived_from(&source).unwrap();
+    /// assert_eq!(item.derived_from_links().count(), 1);
+    /// ```
+    pub fn add_derived_from(&mut self, item: &Item) -> Result<()> {
+        let href = item
+            .self_href()
+            .cloned()
+            .or_else(|| item.self_link().map(|link| link.href.clone()))
+            .ok_or(Error::NoHref)?;
+        if !self.derived_from_links().any(|link| link.href == href) {
+            self.links.push(Link::derived_from(href));
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over this item's `derived_from` links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let item = Item::new("an-id");
+    /// assert_eq!(item.derived_from_links().count(), 0);
+    /// ```
+    pub fn derived_from_links(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| link.is_derived_from())
+    }
+
+    /// Sets this item's geometry.
+    ///
+    /// Also sets this item's bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_geometry(Some(Geometry::new(Value::Point(vec![-105.1, 41.1]))));
+    /// assert_eq!(item.bbox.unwrap(), vec![-105.1, 41.1, -105.1, 41.1].try_into().unwrap());
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn set_geometry(&mut self, geometry: impl Into<Option<Geometry>>) -> Result<()> {
+        let geometry = geometry.into();
+        self.geometry = serde_json::from_value(serde_json::to_value(geometry)?)?;
+        self.sync_bbox();
+        Ok(())
+    }
+
+    /// Recomputes this item's bounding box from its geometry.
+    ///
+    /// Use this after modifying [Item::geometry] directly. If the item has no
+    /// geometry, the bounding box is cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.geometry = Some(Geometry::new(Value::Point(vec![-105.1, 41.1])));
+    /// assert!(item.bbox.is_none());
+    /// item.sync_bbox();
+    /// assert_eq!(item.bbox.unwrap(), vec![-105.1, 41.1, -105.1, 41.1].try_into().unwrap());
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn sync_bbox(&mut self) {
+        self.bbox = self.geometry_bounds();
+    }
+
+    /// Returns an error if this item's bbox doesn't match the bounds of its geometry.
+    ///
+    /// Only the horizontal extents of three-dimensional bounding boxes are
+    /// checked, and differences within [Bbox::TOLERANCE] are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Bbox, Item};
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_geometry(Geometry::new(Value::Point(vec![-105.1, 41.1]))).unwrap();
+    /// item.validate_bbox().unwrap();
+    /// item.bbox = Some(Bbox::new(0., 0., 1., 1.));
+    /// item.validate_bbox().unwrap_err();
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn validate_bbox(&self) -> Result<()> {
+        let expected = self.geometry_bounds();
+        let matches = match (self.bbox, expected) {
+            (Some(bbox), Some(expected)) => bbox.horizontal_eq(&expected),
+            (None, None) => true,
+            _ => false,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(Error::BboxMismatch {
+                bbox: Box::new(self.bbox),
+                expected: Box::new(expected),
+            })
+        }
+    }
+
+    #[cfg(feature = "geo")]
+    fn geometry_bounds(&self) -> Option<Bbox> {
+        use geo::BoundingRect;
+
+        self.geometry
+            .as_ref()
+            .and_then(|geometry| geo::Geometry::try_from(geometry).ok())
+            .and_then(|geometry| geometry.bounding_rect())
+            .map(Bbox::from)
+    }
+
+    /// Sets this item's geometry from well-known text (WKT).
+    ///
+    /// Also sets this item's bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_geometry_wkt("POINT(-105.1 41.1)").unwrap();
+    /// assert_eq!(item.bbox.unwrap(), vec![-105.1, 41.1, -105.1, 41.1].try_into().unwrap());
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn set_geometry_wkt(&mut self, wkt: &str) -> Result<()> {
+        use geozero::{wkt::Wkt, ToGeo};
+
+        let geometry = Wkt(wkt).to_geo()?;
+        self.set_geometry(Geometry::new((&geometry).into()))
+    }
+
+    /// Sets this item's geometry from well-known binary (WKB).
+    ///
+    /// Also sets this item's bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// // POINT(1 2)
+    /// let wkb = [
+    ///     1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63, 0, 0, 0, 0, 0, 0, 0, 64,
+    /// ];
+    /// let mut item = Item::new("an-id");
+    /// item.set_geometry_wkb(&wkb).unwrap();
+    /// assert_eq!(item.bbox.unwrap(), vec![1.0, 2.0, 1.0, 2.0].try_into().unwrap());
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn set_geometry_wkb(&mut self, wkb: &[u8]) -> Result<()> {
+        use geozero::{wkb::Wkb, ToGeo};
+
+        let geometry = Wkb(wkb).to_geo()?;
+        self.set_geometry(Geometry::new((&geometry).into()))
+    }
+
+    /// Rounds this item's geometry and bbox coordinates to a number of decimal places.
     ///
-    /// This is the first link with a rel="collection".
+    /// Footprints are often far more precise than their data (eight decimal
+    /// places of a degree is about a millimeter), and those extra digits can
+    /// make up most of the size of serialized items. Rounding is applied to the
+    /// geometry and the bbox alike, so a bbox that contained its geometry still
+    /// does.
     ///
     /// # Examples
     ///
     /// ```
     /// use stac::Item;
-    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
-    /// let link = item.collection_link().unwrap();
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.geometry = Some(Geometry::new(Value::Point(vec![-105.123456789, 41.987654321])));
+    /// item.round_coordinates(3);
+    /// assert_eq!(item.geometry.unwrap().value, Value::Point(vec![-105.123, 41.988]));
     /// ```
-    pub fn collection_link(&self) -> Option<&Link> {
-        self.links.iter().find(|link| link.is_collection())
+    pub fn round_coordinates(&mut self, precision: u8) {
+        let factor = 10f64.powi(precision.into());
+        let round = |x: &mut f64| *x = (*x * factor).round() / factor;
+        if let Some(geometry) = self.geometry.as_mut() {
+            for_each_position(&mut geometry.value, &mut |position| {
+                position.iter_mut().for_each(round)
+            });
+            if let Some(bbox) = geometry.bbox.as_mut() {
+                bbox.iter_mut().for_each(round);
+            }
+        }
+        match self.bbox.as_mut() {
+            Some(Bbox::TwoDimensional(bbox)) => bbox.iter_mut().for_each(round),
+            Some(Bbox::ThreeDimensional(bbox)) => bbox.iter_mut().for_each(round),
+            None => {}
+        }
     }
 
-    /// Sets this item's geometry.
+    /// Simplifies this item's geometry with the Ramer–Douglas–Peucker algorithm.
     ///
-    /// Also sets this item's bounding box.
+    /// `epsilon` is in the units of the geometry's coordinates, usually
+    /// degrees. Lines and polygons are simplified, including those inside
+    /// geometry collections. The geometry's own bbox and foreign members are
+    /// kept. Because simplification only removes vertices, the bboxes still
+    /// contain the geometry, but you may want to call [Item::sync_bbox]
+    /// afterwards to tighten the item's.
     ///
     /// # Examples
     ///
@@ -402,20 +1052,23 @@ impl Item {
     /// use geojson::{Geometry, Value};
     ///
     /// let mut item = Item::new("an-id");
-    /// item.set_geometry(Some(Geometry::new(Value::Point(vec![-105.1, 41.1]))));
-    /// assert_eq!(item.bbox.unwrap(), vec![-105.1, 41.1, -105.1, 41.1].try_into().unwrap());
+    /// item.set_geometry(Geometry::new(Value::LineString(vec![
+    ///     vec![0.0, 0.0],
+    ///     vec![1.0, 0.001],
+    ///     vec![2.0, 0.0],
+    /// ]))).unwrap();
+    /// item.simplify(0.01).unwrap();
+    /// assert_eq!(
+    ///     item.geometry.unwrap().value,
+    ///     Value::LineString(vec![vec![0.0, 0.0], vec![2.0, 0.0]])
+    /// );
     /// ```
     #[cfg(feature = "geo")]
-    pub fn set_geometry(&mut self, geometry: impl Into<Option<Geometry>>) -> Result<()> {
-        use geo::BoundingRect;
-
-        let geometry = geometry.into();
-        self.bbox = geometry
-            .as_ref()
-            .and_then(|geometry| geo::Geometry::try_from(geometry).ok())
-            .and_then(|geometry| geometry.bounding_rect())
-            .map(Bbox::from);
-        self.geometry = serde_json::from_value(serde_json::to_value(geometry)?)?;
+    pub fn simplify(&mut self, epsilon: f64) -> Result<()> {
+        if let Some(geometry) = self.geometry.as_mut() {
+            let value: geo::Geometry = geometry.value.clone().try_into().map_err(Box::new)?;
+            geometry.value = (&simplify(value, epsilon)).into();
+        }
         Ok(())
     }
 
@@ -543,6 +1196,9 @@ impl Item {
     /// `False`, and error will be raised. The same is true for any top-level
     /// fields that are not part of the spec.
     ///
+    /// Use [Item::into_flat_item_opts] to keep those fields, or to flatten
+    /// nested properties.
+    ///
     /// # Examples
     ///
     /// ```
@@ -552,28 +1208,45 @@ impl Item {
     /// let flat_item = item.into_flat_item(true).unwrap();
     /// ```
     pub fn into_flat_item(self, drop_invalid_attributes: bool) -> Result<FlatItem> {
+        let collision = if drop_invalid_attributes {
+            Collision::Drop
+        } else {
+            Collision::Error
+        };
+        self.into_flat_item_opts(FlattenOptions {
+            collision,
+            separator: None,
+        })
+    }
+
+    /// Converts this item into a [FlatItem] with options.
+    ///
+    /// [FlatItem::into_item] is the inverse, given the same options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{item::{Collision, FlattenOptions}, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let _ = item.properties.additional_fields.insert("id".into(), "a property".into());
+    /// let options = FlattenOptions {
+    ///     collision: Collision::Prefix,
+    ///     separator: Some('.'),
+    /// };
+    /// let flat_item = item.clone().into_flat_item_opts(options).unwrap();
+    /// assert_eq!(flat_item.properties["properties.id"], "a property");
+    /// assert_eq!(flat_item.into_item(options).unwrap(), item);
+    /// ```
+    pub fn into_flat_item_opts(self, options: FlattenOptions) -> Result<FlatItem> {
         let properties = if let Value::Object(object) = serde_json::to_value(self.properties)? {
             object
         } else {
             panic!("properties should always serialize to an object")
         };
-        for (key, _) in properties.iter() {
-            if TOP_LEVEL_ATTRIBUTES.contains(&key.as_str()) {
-                if drop_invalid_attributes {
-                    log::warn!("dropping invalid property: {}", key);
-                } else {
-                    return Err(Error::InvalidAttribute(key.to_string()));
-                }
-            }
-        }
-        for (key, _) in self.additional_fields {
-            if drop_invalid_attributes {
-                log::warn!("dropping out-of-spec top-level attribute: {}", key);
-            } else {
-                return Err(Error::InvalidAttribute(key));
-            }
-        }
+        let properties = options.flatten(properties, self.additional_fields)?;
         Ok(FlatItem {
+            r#type: self.r#type,
             version: STAC_VERSION,
             extensions: self.extensions,
             id: self.id,
@@ -585,6 +1258,152 @@ impl Item {
             properties,
         })
     }
+
+    /// Gets an additional field by [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+    ///
+    /// Pointers that start with `/properties` look in the item's additional
+    /// properties, and any other pointer looks in the item's top-level foreign
+    /// members. The value is deserialized straight from the item's JSON, so
+    /// borrowed types like `&str` work too. Returns `None` if there's nothing
+    /// at the pointer.
+    ///
+    /// Typed fields, like `/id` or `/properties/datetime`, can't be reached
+    /// this way — use the struct's fields instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use serde_json::json;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_field_at("/properties/eo:bands", json!([{"name": "red"}])).unwrap();
+    /// let name: Option<&str> = item.field_at("/properties/eo:bands/0/name").unwrap();
+    /// assert_eq!(name, Some("red"));
+    /// ```
+    pub fn field_at<'a, T: Deserialize<'a>>(&'a self, pointer: &str) -> Result<Option<T>> {
+        let (properties, key, rest) = split_pointer(pointer)?;
+        let fields = if properties {
+            &self.properties.additional_fields
+        } else {
+            &self.additional_fields
+        };
+        let Some(value) = fields.get(&key).and_then(|value| value.pointer(rest)) else {
+            return Ok(None);
+        };
+        T::deserialize(value)
+            .map(Some)
+            .map_err(|source| Error::InvalidField {
+                key: pointer.to_string(),
+                source,
+            })
+    }
+
+    /// Sets an additional field by [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// returning the previous value, if any.
+    ///
+    /// Pointers are resolved like [Item::field_at]. Missing objects along the
+    /// way are created, and `-` appends to an array. It's an error to go
+    /// through a value that isn't an object or an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_field_at("/properties/foo/bar", 42).unwrap();
+    /// assert_eq!(item.properties.additional_fields["foo"]["bar"], 42);
+    /// item.set_field_at("/foo", "a top-level foreign member").unwrap();
+    /// assert_eq!(item.additional_fields["foo"], "a top-level foreign member");
+    /// ```
+    pub fn set_field_at<S: Serialize>(&mut self, pointer: &str, value: S) -> Result<Option<Value>> {
+        let value = serde_json::to_value(value)?;
+        let (properties, key, rest) = split_pointer(pointer)?;
+        let fields = if properties {
+            &mut self.properties.additional_fields
+        } else {
+            &mut self.additional_fields
+        };
+        if rest.is_empty() {
+            return Ok(fields.insert(key, value));
+        }
+        let mut current = fields
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()));
+        let tokens: Vec<String> = rest[1..].split('/').map(unescape_token).collect();
+        let (last, parents) = tokens.split_last().expect("rest is not empty");
+        for token in parents {
+            current = match current {
+                Value::Object(object) => object
+                    .entry(token.as_str())
+                    .or_insert_with(|| Value::Object(Map::new())),
+                Value::Array(array) => array_index(array, token)
+                    .and_then(|index| array.get_mut(index))
+                    .ok_or_else(|| Error::InvalidPointer(pointer.to_string()))?,
+                _ => return Err(Error::InvalidPointer(pointer.to_string())),
+            };
+        }
+        match current {
+            Value::Object(object) => Ok(object.insert(last.clone(), value)),
+            Value::Array(array) if last == "-" => {
+                array.push(value);
+                Ok(None)
+            }
+            Value::Array(array) => {
+                let element = array_index(array, last)
+                    .and_then(|index| array.get_mut(index))
+                    .ok_or_else(|| Error::InvalidPointer(pointer.to_string()))?;
+                Ok(Some(std::mem::replace(element, value)))
+            }
+            _ => Err(Error::InvalidPointer(pointer.to_string())),
+        }
+    }
+}
+
+/// Splits a pointer into whether it's in the properties, the additional
+/// field's key, and the rest of the pointer.
+fn split_pointer(pointer: &str) -> Result<(bool, String, &str)> {
+    let invalid = || Error::InvalidPointer(pointer.to_string());
+    let rest = pointer.strip_prefix('/').ok_or_else(invalid)?;
+    let (first, rest) = split_token(rest);
+    let (properties, key, rest) = if first == "properties" {
+        if rest.is_empty() {
+            return Err(invalid());
+        }
+        let (key, rest) = split_token(&rest[1..]);
+        (true, unescape_token(key), rest)
+    } else {
+        (false, unescape_token(first), rest)
+    };
+    let typed = if properties {
+        TYPED_PROPERTIES.contains(&key.as_str())
+    } else {
+        key == "stac_version" || TOP_LEVEL_ATTRIBUTES.contains(&key.as_str())
+    };
+    if typed {
+        Err(invalid())
+    } else {
+        Ok((properties, key, rest))
+    }
+}
+
+fn split_token(s: &str) -> (&str, &str) {
+    match s.find('/') {
+        Some(index) => (&s[..index], &s[index..]),
+        None => (s, ""),
+    }
+}
+
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn array_index(array: &[Value], token: &str) -> Option<usize> {
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|&index| index < array.len())
 }
 
 impl Assets for Item {
@@ -605,6 +1424,15 @@ impl Fields for Item {
     }
 }
 
+impl Fields for Builder {
+    fn fields(&self) -> &Map<String, Value> {
+        &self.properties
+    }
+    fn fields_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.properties
+    }
+}
+
 impl TryFrom<Item> for Map<String, Value> {
     type Error = Error;
     fn try_from(item: Item) -> Result<Self> {
@@ -671,12 +1499,53 @@ fn default_stac_version() -> Version {
     STAC_VERSION
 }
 
+#[cfg(feature = "geo")]
+fn simplify(geometry: geo::Geometry, epsilon: f64) -> geo::Geometry {
+    use geo::{Geometry, GeometryCollection, Simplify};
+
+    match geometry {
+        Geometry::LineString(line_string) => Geometry::LineString(line_string.simplify(&epsilon)),
+        Geometry::MultiLineString(multi_line_string) => {
+            Geometry::MultiLineString(multi_line_string.simplify(&epsilon))
+        }
+        Geometry::Polygon(polygon) => Geometry::Polygon(polygon.simplify(&epsilon)),
+        Geometry::MultiPolygon(multi_polygon) => {
+            Geometry::MultiPolygon(multi_polygon.simplify(&epsilon))
+        }
+        Geometry::GeometryCollection(geometry_collection) => {
+            Geometry::GeometryCollection(GeometryCollection(
+                geometry_collection
+                    .into_iter()
+                    .map(|geometry| simplify(geometry, epsilon))
+                    .collect(),
+            ))
+        }
+        geometry => geometry,
+    }
+}
+
+fn for_each_position(value: &mut geojson::Value, f: &mut impl FnMut(&mut geojson::Position)) {
+    use geojson::Value::*;
+
+    match value {
+        Point(position) => f(position),
+        MultiPoint(positions) | LineString(positions) => positions.iter_mut().for_each(f),
+        MultiLineString(lines) | Polygon(lines) => lines.iter_mut().flatten().for_each(f),
+        MultiPolygon(polygons) => polygons.iter_mut().flatten().flatten().for_each(f),
+        GeometryCollection(geometries) => {
+            for geometry in geometries {
+                for_each_position(&mut geometry.value, f);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Builder, FlatItem, Item};
-    use crate::{Asset, STAC_VERSION};
+    use super::{Builder, Collision, FlatItem, FlattenOptions, Item};
+    use crate::{Asset, Error, STAC_VERSION};
     use geojson::{feature::Id, Feature};
-    use serde_json::json;
+    use serde_json::{json, Value};
 
     #[test]
     fn new() {
@@ -728,6 +1597,144 @@ mod tests {
         assert_eq!(item.bbox, None);
     }
 
+    #[test]
+    fn round_coordinates() {
+        use crate::Bbox;
+        use geojson::{Geometry, Value};
+
+        let mut item = Item::new("an-id");
+        item.geometry = Some(Geometry::new(Value::Polygon(vec![vec![
+            vec![0.123456, 0.123456],
+            vec![1.987654, 0.123456],
+            vec![1.987654, 1.987654],
+            vec![0.123456, 0.123456],
+        ]])));
+        item.bbox = Some(Bbox::new(0.123456, 0.123456, 1.987654, 1.987654));
+        item.round_coordinates(2);
+        assert_eq!(
+            item.geometry.unwrap().value,
+            Value::Polygon(vec![vec![
+                vec![0.12, 0.12],
+                vec![1.99, 0.12],
+                vec![1.99, 1.99],
+                vec![0.12, 0.12],
+            ]])
+        );
+        assert_eq!(item.bbox.unwrap(), Bbox::new(0.12, 0.12, 1.99, 1.99));
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn simplify() {
+        use geojson::{Geometry, Value};
+
+        let mut item = Item::new("an-id");
+        item.simplify(1.0).unwrap();
+        assert!(item.geometry.is_none());
+        item.set_geometry(Geometry::new(Value::Point(vec![-105.1, 41.1])))
+            .unwrap();
+        item.simplify(1.0).unwrap();
+        assert_eq!(
+            item.geometry.unwrap().value,
+            Value::Point(vec![-105.1, 41.1])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn simplify_keeps_bbox_and_foreign_members() {
+        use geojson::{Geometry, Value};
+
+        let mut item = Item::new("an-id");
+        let mut geometry = Geometry::new(Value::LineString(vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.001],
+            vec![2.0, 0.0],
+        ]));
+        geometry.bbox = Some(vec![0.0, 0.0, 2.0, 0.001]);
+        let mut foreign_members = serde_json::Map::new();
+        let _ = foreign_members.insert("foo".to_string(), "bar".into());
+        geometry.foreign_members = Some(foreign_members.clone());
+        item.geometry = Some(geometry);
+        item.simplify(0.01).unwrap();
+        let geometry = item.geometry.unwrap();
+        assert_eq!(
+            geometry.value,
+            Value::LineString(vec![vec![0.0, 0.0], vec![2.0, 0.0]])
+        );
+        assert_eq!(geometry.bbox.unwrap(), vec![0.0, 0.0, 2.0, 0.001]);
+        assert_eq!(geometry.foreign_members.unwrap(), foreign_members);
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn simplify_geometry_collection() {
+        use geojson::{Geometry, Value};
+
+        let mut item = Item::new("an-id");
+        item.geometry = Some(Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![-105.1, 41.1])),
+            Geometry::new(Value::LineString(vec![
+                vec![0.0, 0.0],
+                vec![1.0, 0.001],
+                vec![2.0, 0.0],
+            ])),
+        ])));
+        item.simplify(0.01).unwrap();
+        assert_eq!(
+            item.geometry.unwrap().value,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![-105.1, 41.1])),
+                Geometry::new(Value::LineString(vec![vec![0.0, 0.0], vec![2.0, 0.0]])),
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn validate_bbox() {
+        use crate::Bbox;
+        use geojson::Geometry;
+
+        let mut item = Item::new("an-id");
+        item.validate_bbox().unwrap();
+        item.geometry = Some(Geometry::new(geojson::Value::Point(vec![-105.1, 41.1])));
+        let _ = item.validate_bbox().unwrap_err();
+        item.sync_bbox();
+        item.validate_bbox().unwrap();
+        item.bbox = Some(Bbox::ThreeDimensional([
+            -105.1, 41.1, 0.0, -105.1, 41.1, 10.0,
+        ]));
+        item.validate_bbox().unwrap();
+        item.bbox = Some(Bbox::new(-105.1, 41.1, -105.1 + 1e-12, 41.1));
+        item.validate_bbox().unwrap();
+        item.geometry = None;
+        let _ = item.validate_bbox().unwrap_err();
+        item.sync_bbox();
+        assert!(item.bbox.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn set_geometry_wkt() {
+        let mut item = Item::new("an-id");
+        item.set_geometry_wkt("POLYGON((0 0, 1 0, 1 2, 0 2, 0 0))")
+            .unwrap();
+        assert_eq!(
+            item.bbox,
+            Some(vec![0.0, 0.0, 1.0, 2.0].try_into().unwrap())
+        );
+        assert_eq!(item.geometry.as_ref().unwrap().value.type_name(), "Polygon");
+        let _ = item.set_geometry_wkt("not wkt").unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn set_geometry_wkb() {
+        let mut item = Item::new("an-id");
+        let _ = item.set_geometry_wkb(&[1, 2, 3]).unwrap_err();
+    }
+
     #[test]
     #[cfg(feature = "geo")]
     fn insersects() {
@@ -821,6 +1828,71 @@ mod tests {
         assert_eq!(asset.roles, vec!["data"]);
     }
 
+    #[test]
+    fn builder_datetime_range() {
+        let start = "2024-01-01T00:00:00Z".parse().unwrap();
+        let end = "2024-01-02T00:00:00Z".parse().unwrap();
+        let item = Builder::new("an-id")
+            .datetime_range(start, end)
+            .build()
+            .unwrap();
+        assert!(item.properties.datetime.is_none());
+        assert_eq!(item.properties.start_datetime.unwrap(), start);
+        let _ = Builder::new("an-id")
+            .datetime_range(end, start)
+            .build()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn builder_invalid_property() {
+        let _ = Builder::new("an-id")
+            .property("geometry", "not allowed")
+            .build()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn builder_typed_property() {
+        let error = Builder::new("an-id")
+            .property("datetime", "2024-01-01T00:00:00Z")
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, Error::InvalidAttribute(key) if key == "datetime"));
+        let item = Builder::new("an-id").build().unwrap();
+        let value = serde_json::to_string(&item).unwrap();
+        assert_eq!(value.matches("\"datetime\"").count(), 1);
+    }
+
+    #[test]
+    fn builder_extension() {
+        let item = Builder::new("an-id")
+            .extension("https://stac-extensions.github.io/eo/v1.1.0/schema.json")
+            .extension("https://stac-extensions.github.io/eo/v1.1.0/schema.json")
+            .build()
+            .unwrap();
+        assert_eq!(item.extensions.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn builder_geometry() {
+        use crate::Bbox;
+        use geojson::{Geometry, Value};
+
+        let geometry = Geometry::new(Value::Point(vec![-105.1, 41.1]));
+        let item = Builder::new("an-id")
+            .geometry(geometry.clone())
+            .build()
+            .unwrap();
+        assert_eq!(item.bbox.unwrap(), Bbox::new(-105.1, 41.1, -105.1, 41.1));
+        let _ = Builder::new("an-id")
+            .geometry(geometry)
+            .bbox(Bbox::new(0., 0., 1., 1.))
+            .build()
+            .unwrap_err();
+    }
+
     #[test]
     fn try_from_geojson_feature() {
         let mut feature = Feature {
@@ -862,6 +1934,77 @@ mod tests {
         let _ = item.clone().into_flat_item(false).unwrap_err();
     }
 
+    #[test]
+    fn flat_item_drops_collisions() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("id".to_string(), "a property".into());
+        let flat_item = item.into_flat_item(true).unwrap();
+        let value = serde_json::to_value(flat_item).unwrap();
+        assert_eq!(value["id"], "an-id");
+    }
+
+    #[test]
+    fn flat_item_prefix_roundtrip() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("id".to_string(), "a property".into());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("item.foo".to_string(), "escaped".into());
+        let _ = item
+            .additional_fields
+            .insert("foo".to_string(), "a foreign member".into());
+        let options = FlattenOptions {
+            collision: Collision::Prefix,
+            separator: None,
+        };
+        let flat_item = item.clone().into_flat_item_opts(options).unwrap();
+        assert_eq!(flat_item.properties["properties.id"], "a property");
+        assert_eq!(flat_item.properties["item.foo"], "a foreign member");
+        assert_eq!(flat_item.properties["properties.item.foo"], "escaped");
+        assert_eq!(flat_item.into_item(options).unwrap(), item);
+    }
+
+    #[test]
+    fn flat_item_separator_roundtrip() {
+        let mut item = Item::new("an-id");
+        let _ = item.properties.additional_fields.insert(
+            "a".to_string(),
+            json!({"b": {"c": 1}, "d": [1, 2], "e": {}}),
+        );
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("f".to_string(), json!({"g.h": 1}));
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("i.j".to_string(), json!(1));
+        let options = FlattenOptions {
+            collision: Collision::Prefix,
+            separator: Some('.'),
+        };
+        let flat_item = item.clone().into_flat_item_opts(options).unwrap();
+        assert_eq!(flat_item.properties["a.b.c"], 1);
+        assert_eq!(flat_item.properties["a.d"], json!([1, 2]));
+        assert_eq!(flat_item.properties["a.e"], json!({}));
+        assert_eq!(flat_item.properties["f"], json!({"g.h": 1}));
+        assert_eq!(flat_item.properties["properties.i.j"], 1);
+        assert_eq!(flat_item.into_item(options).unwrap(), item);
+
+        let options = FlattenOptions {
+            collision: Collision::Error,
+            separator: Some('.'),
+        };
+        let _ = item.into_flat_item_opts(options).unwrap_err();
+    }
+
     #[test]
     fn flat_item_without_geometry() {
         let mut item = Item::new("an-item");
@@ -877,9 +2020,71 @@ mod tests {
         let _: Item = serde_json::from_value(json!({})).unwrap();
     }
 
+    #[test]
+    fn add_derived_from() {
+        use crate::{Link, Links};
+
+        let mut item = Item::new("an-id");
+        let _ = item.add_derived_from(&Item::new("no-href")).unwrap_err();
+        let mut source = Item::new("source");
+        source.set_link(Link::self_("http://stac-rs.test/source.json"));
+        item.add_derived_from(&source).unwrap();
+        item.add_derived_from(&source).unwrap();
+        let links: Vec<_> = item.derived_from_links().collect();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].href, "http://stac-rs.test/source.json");
+    }
+
     #[test]
     fn has_type() {
-        let value: serde_json::Value = serde_json::to_value(Item::new("an-id")).unwrap();
+        let value: Value = serde_json::to_value(Item::new("an-id")).unwrap();
         assert_eq!(value.as_object().unwrap()["type"], "Feature");
     }
+
+    #[test]
+    fn field_at() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .set_field_at("/properties/eo:bands", json!([{"name": "red"}]))
+            .unwrap();
+        let _ = item.set_field_at("/a~1b", json!({"c": 1})).unwrap();
+        assert_eq!(
+            item.field_at::<&str>("/properties/eo:bands/0/name")
+                .unwrap(),
+            Some("red")
+        );
+        assert_eq!(item.field_at::<u8>("/a~1b/c").unwrap(), Some(1));
+        assert_eq!(item.field_at::<u8>("/properties/missing").unwrap(), None);
+        assert_eq!(item.field_at::<u8>("/a~1b/missing").unwrap(), None);
+        let _ = item.field_at::<u8>("/properties/eo:bands").unwrap_err();
+        let _ = item.field_at::<Value>("/properties/datetime").unwrap_err();
+        let _ = item.field_at::<Value>("/id").unwrap_err();
+        let _ = item.field_at::<Value>("/properties").unwrap_err();
+        let _ = item.field_at::<Value>("properties/foo").unwrap_err();
+    }
+
+    #[test]
+    fn set_field_at() {
+        let mut item = Item::new("an-id");
+        assert_eq!(item.set_field_at("/properties/foo/bar", 1).unwrap(), None);
+        assert_eq!(
+            item.set_field_at("/properties/foo/bar", 2).unwrap(),
+            Some(json!(1))
+        );
+        let _ = item.set_field_at("/properties/list", json!([1])).unwrap();
+        let _ = item.set_field_at("/properties/list/-", 2).unwrap();
+        assert_eq!(
+            item.set_field_at("/properties/list/0", 0).unwrap(),
+            Some(json!(1))
+        );
+        let _ = item.set_field_at("/properties/list/2", 3).unwrap_err();
+        let _ = item.set_field_at("/properties/foo/bar/baz", 3).unwrap_err();
+        assert_eq!(
+            item.properties.additional_fields,
+            *json!({"foo": {"bar": 2}, "list": [0, 2]})
+                .as_object()
+                .unwrap()
+        );
+        assert!(item.additional_fields.is_empty());
+    }
 }
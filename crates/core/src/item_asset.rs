@@ -1,3 +1,4 @@
+use crate::{Asset, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
@@ -52,3 +53,72 @@ pub struct ItemAsset {
     #[serde(flatten)]
     pub additional_fields: Map<String, Value>,
 }
+
+impl ItemAsset {
+    /// Creates an item asset definition from an asset, dropping its href.
+    ///
+    /// All other fields, including extension fields, are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, ItemAsset};
+    ///
+    /// let mut asset = Asset::new("./data.tif");
+    /// asset.title = Some("Data".to_string());
+    /// let item_asset = ItemAsset::from_asset(asset).unwrap();
+    /// assert_eq!(item_asset.title.unwrap(), "Data");
+    /// assert!(!item_asset.additional_fields.contains_key("href"));
+    /// ```
+    pub fn from_asset(asset: Asset) -> Result<ItemAsset> {
+        let mut value = serde_json::to_value(asset)?;
+        if let Some(object) = value.as_object_mut() {
+            let _ = object.remove("href");
+        }
+        serde_json::from_value(value).map_err(Into::into)
+    }
+
+    /// Creates an asset with the given href from this item asset definition.
+    ///
+    /// Any href that was (incorrectly) set on this item asset is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::ItemAsset;
+    ///
+    /// let item_asset: ItemAsset = serde_json::from_value(serde_json::json!({
+    ///     "title": "Data",
+    ///     "type": "image/tiff",
+    /// })).unwrap();
+    /// let asset = item_asset.into_asset("./data.tif").unwrap();
+    /// assert_eq!(asset.href, "./data.tif");
+    /// assert_eq!(asset.title.unwrap(), "Data");
+    /// ```
+    pub fn into_asset(self, href: impl ToString) -> Result<Asset> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(object) = value.as_object_mut() {
+            let _ = object.insert("href".to_string(), href.to_string().into());
+        }
+        serde_json::from_value(value).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemAsset;
+    use crate::Asset;
+
+    #[test]
+    fn roundtrip_asset() {
+        let mut asset = Asset::new("./data.tif").role("data");
+        asset.nodata = Some(0.);
+        let _ = asset
+            .additional_fields
+            .insert("eo:cloud_cover".to_string(), 42.into());
+        let item_asset = ItemAsset::from_asset(asset.clone()).unwrap();
+        assert_eq!(item_asset.roles, vec!["data"]);
+        assert_eq!(item_asset.additional_fields["nodata"], 0.);
+        assert_eq!(item_asset.into_asset("./data.tif").unwrap(), asset);
+    }
+}
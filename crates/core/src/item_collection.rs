@@ -1,4 +1,5 @@
-use crate::{Error, Href, Item, Link, Migrate, Result, Version};
+use crate::{schema::Schema, Error, Href, Item, Link, Migrate, Result, Version};
+use geojson::{Feature, FeatureCollection};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Links, SelfHref};
@@ -30,6 +31,11 @@ where
 /// A [GeoJSON FeatureCollection](https://www.rfc-editor.org/rfc/rfc7946#page-12) of items.
 ///
 /// While not part of the STAC specification, ItemCollections are often used to store many items in a single file.
+///
+/// Each item owns its strings, so values that repeat across items (collection
+/// ids, extension URLs, asset keys and media types) are stored once per item.
+/// Use an [InternedItemCollection](crate::intern::InternedItemCollection) to
+/// share them instead.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SelfHref, Links)]
 pub struct ItemCollection {
     #[serde(
@@ -56,6 +62,211 @@ pub struct ItemCollection {
     self_href: Option<Href>,
 }
 
+impl ItemCollection {
+    /// Rounds the geometry and bbox coordinates of every item to a number of decimal places.
+    ///
+    /// See [Item::round_coordinates].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection};
+    ///
+    /// let mut item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+    /// item_collection.round_coordinates(6);
+    /// ```
+    pub fn round_coordinates(&mut self, precision: u8) {
+        for item in &mut self.items {
+            item.round_coordinates(precision);
+        }
+    }
+
+    /// Infers the schema of these items' properties.
+    ///
+    /// See [Schema](crate::schema::Schema) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection};
+    ///
+    /// let item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+    /// let schema = item_collection.infer_schema();
+    /// assert_eq!(schema.items, 2);
+    /// ```
+    pub fn infer_schema(&self) -> Schema {
+        Schema::infer(&self.items)
+    }
+
+    /// Simplifies the geometry of every item.
+    ///
+    /// See [Item::simplify].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection};
+    ///
+    /// let mut item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+    /// item_collection.simplify(0.0001).unwrap();
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn simplify(&mut self, epsilon: f64) -> Result<()> {
+        for item in &mut self.items {
+            item.simplify(epsilon)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the relative asset hrefs of every item absolute, using the given base.
+    ///
+    /// Absolute hrefs (urls and paths that start with `/`) are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, ItemCollection};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let _ = item.assets.insert("data".to_string(), Asset::new("./data.tif"));
+    /// let mut item_collection = ItemCollection::from(vec![item]);
+    /// item_collection
+    ///     .make_asset_hrefs_absolute(&"s3://bucket/items.parquet".into())
+    ///     .unwrap();
+    /// assert_eq!(item_collection.items[0].assets["data"].href, "s3://bucket/data.tif");
+    /// ```
+    pub fn make_asset_hrefs_absolute(&mut self, base: &Href) -> Result<()> {
+        for item in &mut self.items {
+            for asset in item.assets.values_mut() {
+                let href = Href::from(asset.href.as_str());
+                if !href.is_absolute() {
+                    asset.href = href.absolute(base)?.to_string();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates an item collection from a GeoJSON feature collection.
+    ///
+    /// Each feature is converted to an [Item]. If `skip_invalid` is true,
+    /// features that can't be converted (e.g. because they don't have an id)
+    /// are skipped with a warning, otherwise the first invalid feature is
+    /// returned as an error. Each feature's STAC foreign members (e.g. `assets`
+    /// and `links`) are deserialized into the item's fields. The feature
+    /// collection's foreign members are kept as additional fields, except for
+    /// `links`, which are parsed into [Link] objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    /// use stac::ItemCollection;
+    ///
+    /// let feature_collection = FeatureCollection {
+    ///     bbox: None,
+    ///     features: vec![Feature::default()],
+    ///     foreign_members: None,
+    /// };
+    /// ItemCollection::from_geojson(feature_collection.clone(), false).unwrap_err();
+    /// let item_collection = ItemCollection::from_geojson(feature_collection, true).unwrap();
+    /// assert!(item_collection.items.is_empty());
+    /// ```
+    pub fn from_geojson(
+        feature_collection: FeatureCollection,
+        skip_invalid: bool,
+    ) -> Result<ItemCollection> {
+        let mut items = Vec::with_capacity(feature_collection.features.len());
+        for feature in feature_collection.features {
+            match item_from_feature(feature) {
+                Ok(item) => items.push(item),
+                Err(err) => {
+                    if skip_invalid {
+                        tracing::warn!("skipping feature that is not a STAC item: {err}");
+                    } else {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+        let mut item_collection = ItemCollection::from(items);
+        let mut foreign_members = feature_collection.foreign_members.unwrap_or_default();
+        if let Some(links) = foreign_members.remove("links") {
+            item_collection.links = serde_json::from_value(links)?;
+        }
+        if let Some(bbox) = feature_collection.bbox {
+            let _ = foreign_members.insert("bbox".to_string(), bbox.into());
+        }
+        item_collection.additional_fields = foreign_members;
+        Ok(item_collection)
+    }
+
+    /// Converts this item collection into a GeoJSON feature collection.
+    ///
+    /// Links and additional fields are written as foreign members, except for
+    /// a `bbox` additional field, which is used as the feature collection's bbox.
+    /// Each item's STAC fields (e.g. `assets` and `links`) are kept as the
+    /// feature's foreign members.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection};
+    ///
+    /// let item_collection = ItemCollection::from(vec![Item::new("an-id")]);
+    /// let feature_collection = item_collection.to_geojson().unwrap();
+    /// assert_eq!(feature_collection.features.len(), 1);
+    /// ```
+    pub fn to_geojson(self) -> Result<FeatureCollection> {
+        let features = self
+            .items
+            .into_iter()
+            .map(|item| {
+                serde_json::to_value(item)
+                    .and_then(serde_json::from_value::<Feature>)
+                    .map_err(Error::from)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut foreign_members = self.additional_fields;
+        let bbox = foreign_members
+            .remove("bbox")
+            .map(serde_json::from_value)
+            .transpose()?;
+        if !self.links.is_empty() {
+            let _ = foreign_members.insert("links".to_string(), serde_json::to_value(self.links)?);
+        }
+        Ok(FeatureCollection {
+            bbox,
+            features,
+            foreign_members: if foreign_members.is_empty() {
+                None
+            } else {
+                Some(foreign_members)
+            },
+        })
+    }
+}
+
+/// Deserializes a feature as a full STAC item, so `assets`, `links`,
+/// `stac_version`, `stac_extensions`, and `collection` become item fields
+/// instead of additional fields.
+fn item_from_feature(feature: Feature) -> Result<Item> {
+    let mut value = serde_json::to_value(feature)?;
+    if let Some(object) = value.as_object_mut() {
+        if let Some(id) = object.get_mut("id") {
+            if let Value::Number(number) = id {
+                *id = Value::String(number.to_string());
+            }
+        } else {
+            return Err(Error::MissingField("id"));
+        }
+        if object.get("properties").is_some_and(Value::is_null) {
+            let _ = object.insert("properties".to_string(), Value::Object(Map::new()));
+        }
+    }
+    serde_json::from_value(value).map_err(Error::from)
+}
+
 impl From<Vec<Item>> for ItemCollection {
     fn from(items: Vec<Item>) -> Self {
         ItemCollection {
@@ -100,6 +311,22 @@ impl Migrate for ItemCollection {
     }
 }
 
+impl TryFrom<FeatureCollection> for ItemCollection {
+    type Error = Error;
+
+    fn try_from(feature_collection: FeatureCollection) -> Result<ItemCollection> {
+        ItemCollection::from_geojson(feature_collection, false)
+    }
+}
+
+impl TryFrom<ItemCollection> for FeatureCollection {
+    type Error = Error;
+
+    fn try_from(item_collection: ItemCollection) -> Result<FeatureCollection> {
+        item_collection.to_geojson()
+    }
+}
+
 impl TryFrom<Value> for ItemCollection {
     type Error = Error;
 
@@ -124,7 +351,8 @@ impl TryFrom<Value> for ItemCollection {
 #[cfg(test)]
 mod tests {
     use super::ItemCollection;
-    use crate::Item;
+    use crate::{Asset, Item, Link};
+    use geojson::{feature::Id, Feature, FeatureCollection};
     use serde_json::json;
 
     #[test]
@@ -139,8 +367,117 @@ mod tests {
         let _ = ItemCollection::from_iter(items);
     }
 
+    #[test]
+    fn from_geojson() {
+        let feature = Feature {
+            id: Some(Id::String("an-id".to_string())),
+            ..Default::default()
+        };
+        let feature_collection = FeatureCollection {
+            bbox: Some(vec![-180.0, -90.0, 180.0, 90.0]),
+            features: vec![feature, Feature::default()],
+            foreign_members: json!({
+                "links": [{"href": "./catalog.json", "rel": "root"}],
+                "foo": "bar"
+            })
+            .as_object()
+            .cloned(),
+        };
+        let _ = ItemCollection::from_geojson(feature_collection.clone(), false).unwrap_err();
+        let item_collection = ItemCollection::from_geojson(feature_collection, true).unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert_eq!(item_collection.items[0].id, "an-id");
+        assert_eq!(item_collection.links.len(), 1);
+        assert!(item_collection.links[0].is_root());
+        assert_eq!(item_collection.additional_fields["foo"], "bar");
+        assert_eq!(
+            item_collection.additional_fields["bbox"],
+            json!([-180.0, -90.0, 180.0, 90.0])
+        );
+    }
+
+    #[test]
+    fn geojson_roundtrip() {
+        let mut item_collection = ItemCollection::from(vec![Item::new("an-id")]);
+        item_collection.links.push(Link::root("./catalog.json"));
+        let _ = item_collection
+            .additional_fields
+            .insert("bbox".to_string(), json!([-180.0, -90.0, 180.0, 90.0]));
+        let feature_collection = item_collection.clone().to_geojson().unwrap();
+        assert_eq!(
+            feature_collection.bbox,
+            Some(vec![-180.0, -90.0, 180.0, 90.0])
+        );
+        assert!(feature_collection
+            .foreign_members
+            .as_ref()
+            .unwrap()
+            .contains_key("links"));
+        let roundtripped = ItemCollection::try_from(feature_collection).unwrap();
+        assert_eq!(roundtripped.links, item_collection.links);
+        assert_eq!(roundtripped.items[0].id, "an-id");
+    }
+
+    #[test]
+    fn from_geojson_keeps_stac_fields() {
+        let feature: Feature = serde_json::from_value(json!({
+            "type": "Feature",
+            "stac_version": "1.1.0",
+            "stac_extensions": ["https://stac-extensions.github.io/eo/v2.0.0/schema.json"],
+            "id": "an-id",
+            "geometry": null,
+            "properties": {"datetime": "2023-01-01T00:00:00Z"},
+            "links": [{"href": "./collection.json", "rel": "collection"}],
+            "assets": {"data": {"href": "./data.tif"}},
+            "collection": "a-collection",
+            "foo": "bar"
+        }))
+        .unwrap();
+        let feature_collection = FeatureCollection {
+            bbox: None,
+            features: vec![feature],
+            foreign_members: None,
+        };
+        let item_collection = ItemCollection::from_geojson(feature_collection, false).unwrap();
+        let item = &item_collection.items[0];
+        assert_eq!(item.assets["data"].href, "./data.tif");
+        assert_eq!(item.links.len(), 1);
+        assert_eq!(item.collection.as_deref(), Some("a-collection"));
+        assert_eq!(item.extensions.len(), 1);
+        assert_eq!(item.additional_fields.len(), 1);
+        assert_eq!(item.additional_fields["foo"], "bar");
+
+        let json = serde_json::to_string(item).unwrap();
+        assert_eq!(json.matches("\"assets\"").count(), 1);
+        assert_eq!(json.matches("\"links\"").count(), 1);
+
+        let feature_collection = item_collection.to_geojson().unwrap();
+        let roundtripped = ItemCollection::from_geojson(feature_collection, false).unwrap();
+        assert_eq!(roundtripped.items[0].assets["data"].href, "./data.tif");
+        assert_eq!(roundtripped.items[0].links.len(), 1);
+    }
+
     #[test]
     fn permissive_deserialization() {
         let _: ItemCollection = serde_json::from_value(json!({})).unwrap();
     }
+
+    #[test]
+    fn make_asset_hrefs_absolute() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .assets
+            .insert("relative".to_string(), Asset::new("../data.tif"));
+        let _ = item.assets.insert(
+            "url".to_string(),
+            Asset::new("http://stac-rs.test/data.tif"),
+        );
+        let mut item_collection = ItemCollection::from(vec![item]);
+        item_collection
+            .make_asset_hrefs_absolute(&"/a/b/items.parquet".into())
+            .unwrap();
+        let assets = &item_collection.items[0].assets;
+        assert_eq!(assets["relative"].href, "/a/data.tif");
+        assert_eq!(assets["url"].href, "http://stac-rs.test/data.tif");
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Error, Result, SelfHref};
+use crate::{Error, JsonError, Result, SelfHref};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     fs::File,
@@ -21,7 +21,7 @@ pub trait FromJson: DeserializeOwned + SelfHref {
         let path = path.as_ref();
         let mut buf = Vec::new();
         let _ = File::open(path)?.read_to_end(&mut buf)?;
-        let mut value = Self::from_json_slice(&buf)?;
+        let mut value = Self::from_json_slice(&buf).map_err(|err| err.with_href(path.display()))?;
         *value.self_href_mut() = Some(path.into());
         Ok(value)
     }
@@ -39,7 +39,7 @@ pub trait FromJson: DeserializeOwned + SelfHref {
     /// let item = Item::from_json_slice(&buf).unwrap();
     /// ```
     fn from_json_slice(slice: &[u8]) -> Result<Self> {
-        serde_json::from_slice(slice).map_err(Error::from)
+        from_slice(slice)
     }
 }
 
@@ -98,10 +98,86 @@ pub trait ToJson: Serialize {
 impl<T: DeserializeOwned + SelfHref> FromJson for T {}
 impl<T: Serialize> ToJson for T {}
 
+/// Deserializes JSON bytes, using **simd-json** if the `simd-json` feature is enabled.
+///
+/// Errors are [JsonError]s with the position of the problem. Tracking the
+/// JSON pointer slows parsing down, so it's only done when parsing again
+/// after an error.
+pub(crate) fn from_slice<T: DeserializeOwned>(slice: &[u8]) -> Result<T> {
+    #[cfg(feature = "simd-json")]
+    {
+        // simd-json parses in place, so it needs its own mutable copy
+        let mut buf = slice.to_vec();
+        simd_json::serde::from_slice(&mut buf).or_else(|err| {
+            // simd-json doesn't say where the problem is, so parse again to find out
+            from_slice_with_position::<T>(slice).and(Err(Error::from(err)))
+        })
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        serde_json::from_slice(slice)
+            .or_else(|err| from_slice_with_position::<T>(slice).and(Err(Error::from(err))))
+    }
+}
+
+fn from_slice_with_position<T: DeserializeOwned>(slice: &[u8]) -> Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(slice);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(JsonError::from)?;
+    deserializer
+        .end()
+        .map_err(|err| JsonError::new(err, String::new()))?;
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::FromJson;
-    use crate::{Item, SelfHref};
+    use crate::{Error, Item, SelfHref};
+
+    #[test]
+    fn from_slice() {
+        let buf = std::fs::read("data/item-collection.json").unwrap();
+        let value: serde_json::Value = super::from_slice(&buf).unwrap();
+        assert_eq!(
+            value,
+            serde_json::from_slice::<serde_json::Value>(&buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn error_position() {
+        let buf = br#"{
+    "type": "Feature",
+    "stac_version": "1.1.0",
+    "id": "an-id",
+    "geometry": null,
+    "properties": {
+        "datetime": "not-a-datetime"
+    },
+    "links": [],
+    "assets": {}
+}"#;
+        let Error::Json(err) = Item::from_json_slice(buf).unwrap_err() else {
+            panic!("should be a json error");
+        };
+        assert_eq!(err.pointer(), "/properties/datetime");
+        assert_eq!(err.line(), 7);
+        assert!(err.href().is_none());
+
+        let Error::Json(err) = super::from_slice::<serde_json::Value>(b"{} {}").unwrap_err() else {
+            panic!("should be a json error");
+        };
+        assert_eq!(err.pointer(), "");
+    }
+
+    #[test]
+    fn error_href() {
+        let tempdir = tempfile::TempDir::new().unwrap();
+        let path = tempdir.path().join("item.json");
+        std::fs::write(&path, "{\"type\": \"Feature\",").unwrap();
+        let err = Item::from_json_path(&path).unwrap_err();
+        assert!(err.to_string().starts_with(&path.display().to_string()));
+    }
 
     #[test]
     fn set_href() {
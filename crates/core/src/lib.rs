@@ -104,17 +104,23 @@
 //!
 //! # Features
 //!
+//! - `cbor`: read and write [CBOR](https://cbor.io/), see [codec]
 //! - `geo`: add some geo-enabled methods, see [geo]
 //! - `geoarrow`: read and write [geoarrow](https://geoarrow.org/), see [geoarrow]
 //! - `geoparquet`: read and write [geoparquet](https://geoparquet.org/), see [geoparquet]
 //!     - `geoparquet-compression`: enable parquet compression
+//! - `gzip`: read and write gzip-compressed JSON and NDJSON, see [codec]
 //! - `object-store`: get and put from object stores. Sub-features enable specific protocols:
 //!     - `object-store-aws`
 //!     - `object-store-azure`
 //!     - `object-store-gcp`
 //!     - `object-store-http`
 //!     - `object-store-all` (enable them all)
+//! - `rayon`: convert items to [geoarrow] tables in parallel
+//! - `regex`: regular expression rules when [rewriting](rewrite) hrefs
 //! - `reqwest`: get from `http` and `https` urls when using [read]
+//! - `simd-json`: use [simd-json](https://github.com/simd-lite/simd-json) to deserialize JSON and NDJSON
+//! - `zstd`: read and write zstd-compressed JSON and NDJSON, see [codec]
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![deny(
@@ -154,9 +160,13 @@ mod asset;
 mod band;
 mod bbox;
 mod catalog;
-mod collection;
+pub mod changes;
+pub mod codec;
+pub mod collection;
 mod data_type;
 pub mod datetime;
+#[cfg(feature = "object-store")]
+pub mod enrich;
 mod error;
 mod fields;
 mod format;
@@ -166,19 +176,28 @@ pub mod geo;
 pub mod geoarrow;
 pub mod geoparquet;
 mod href;
+pub mod intern;
 pub mod io;
 pub mod item;
 mod item_asset;
 mod item_collection;
 mod json;
+pub mod license;
 pub mod link;
+pub mod lint;
 mod migrate;
 pub mod mime;
-mod ndjson;
+pub mod ndjson;
 mod node;
+pub mod partial;
+mod raw_item;
 #[cfg(feature = "object-store")]
 mod resolver;
+pub mod rewrite;
+pub mod schema;
+pub mod split;
 mod statistics;
+pub mod testing;
 #[cfg(feature = "validate")]
 mod validate;
 mod value;
@@ -192,9 +211,9 @@ pub use bbox::Bbox;
 pub use catalog::Catalog;
 pub use collection::{Collection, Extent, Provider, SpatialExtent, TemporalExtent};
 pub use data_type::DataType;
-pub use error::Error;
+pub use error::{Error, JsonError};
 pub use fields::Fields;
-pub use format::Format;
+pub use format::{Format, WriteOptions};
 pub use geojson::Geometry;
 pub use geoparquet::{FromGeoparquet, IntoGeoparquet};
 pub use href::{Href, RealizedHref, SelfHref};
@@ -204,11 +223,14 @@ pub use item_asset::ItemAsset;
 pub use item_collection::ItemCollection;
 pub use json::{FromJson, ToJson};
 pub use link::{Link, Links};
+pub use lint::Lint;
 pub use migrate::Migrate;
 pub use ndjson::{FromNdjson, ToNdjson};
-pub use node::{Container, Node};
+pub use node::{Container, Node, TreeStatistics};
+pub use raw_item::RawItem;
 #[cfg(feature = "object-store")]
-pub use resolver::Resolver;
+pub use resolver::{Resolver, ResolverStats, DEFAULT_RESOLVER_CACHE_CAPACITY};
+pub use rewrite::Rewrite;
 pub use statistics::Statistics;
 #[cfg(feature = "validate")]
 pub use validate::{Validate, Validator};
@@ -338,6 +360,7 @@ pub fn user_agent() -> &'static str {
 
 #[cfg(test)]
 mod tests {
+    use criterion as _;
     use rstest as _;
     use tokio as _;
     use tokio_test as _;
@@ -428,3 +451,7 @@ mod readme {
 // For now, we only use tracing in the validate module.
 #[cfg(not(feature = "validate"))]
 use tracing as _;
+
+// Only used to test http reads and Google Cloud service account impersonation.
+#[cfg(all(test, not(any(feature = "reqwest", feature = "object-store-gcp"))))]
+use mockito as _;
@@ -0,0 +1,171 @@
+//! Check and link [SPDX](https://spdx.org/licenses/) `license` values.
+//!
+//! The values allowed in a collection's `license` field depend on the STAC
+//! version:
+//!
+//! - v1.0.0: a single SPDX license identifier, `various`, or `proprietary`
+//! - v1.1.0: an SPDX license identifier or expression, or `other`
+//!
+//! `various` and `proprietary` are deprecated as of v1.1.0, but they're still
+//! common, so they're accepted for every version. License identifiers are
+//! checked against the [SPDX license list](https://spdx.org/licenses/).
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{license, Version};
+//!
+//! assert!(license::is_valid("MIT OR Apache-2.0", &Version::v1_1_0));
+//! assert!(!license::is_valid("MIT OR Apache-2.0", &Version::v1_0_0));
+//! assert!(license::is_valid("proprietary", &Version::v1_0_0));
+//! assert!(license::is_valid("proprietary", &Version::v1_1_0));
+//! assert!(!license::is_valid("Not-A-License", &Version::v1_1_0));
+//! ```
+
+use crate::Version;
+
+/// The `license` value for licenses that aren't SPDX expressions (v1.1.0 and later).
+pub const OTHER: &str = "other";
+
+/// The `license` value for proprietary licenses (deprecated as of v1.1.0).
+pub const PROPRIETARY: &str = "proprietary";
+
+/// The `license` value for multiple licenses (deprecated as of v1.1.0).
+pub const VARIOUS: &str = "various";
+
+/// Returns true if `license` is a valid `license` value for the STAC version.
+///
+/// Unknown versions are checked against the rules of the latest version.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{license, Version};
+///
+/// assert!(license::is_valid("CC-BY-4.0", &Version::v1_1_0));
+/// assert!(license::is_valid("other", &Version::v1_1_0));
+/// assert!(license::is_valid("various", &Version::v1_1_0));
+/// assert!(!license::is_valid("CC BY 4.0", &Version::v1_1_0));
+/// ```
+pub fn is_valid(license: &str, version: &Version) -> bool {
+    match license {
+        PROPRIETARY | VARIOUS => true,
+        OTHER => !matches!(version, Version::v1_0_0),
+        _ if matches!(version, Version::v1_0_0) => is_identifier(license),
+        _ => is_expression(license),
+    }
+}
+
+/// Returns the url of an SPDX license identifier's page on the SPDX license list.
+///
+/// Returns `None` for expressions, identifiers that aren't on the SPDX
+/// license list (including `LicenseRef-` identifiers), and the non-SPDX
+/// values (`other`, `proprietary`, and `various`), since there's no single
+/// page for them.
+///
+/// # Examples
+///
+/// ```
+/// use stac::license;
+///
+/// assert_eq!(license::url("MIT").unwrap(), "https://spdx.org/licenses/MIT.html");
+/// assert!(license::url("MIT OR Apache-2.0").is_none());
+/// assert!(license::url("other").is_none());
+/// ```
+pub fn url(license: &str) -> Option<String> {
+    let identifier = license.strip_suffix('+').unwrap_or(license);
+    spdx::license_id(identifier).map(|id| format!("https://spdx.org/licenses/{}.html", id.name))
+}
+
+/// Returns true if `s` is a single license identifier, e.g. `MIT`,
+/// `GPL-2.0+`, or `LicenseRef-custom`.
+fn is_identifier(s: &str) -> bool {
+    !s.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') && is_expression(s)
+}
+
+/// Returns true if `s` is a valid SPDX license expression whose license and
+/// exception identifiers are all on the SPDX license list.
+///
+/// The deprecated `GPL-2.0+`-style identifiers are still on the list, so
+/// they're allowed.
+fn is_expression(s: &str) -> bool {
+    let mode = spdx::ParseMode {
+        allow_postfix_plus_on_gpl: true,
+        ..spdx::ParseMode::STRICT
+    };
+    spdx::Expression::parse_mode(s, mode).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Version;
+
+    #[test]
+    fn identifiers() {
+        for license in [
+            "MIT",
+            "GPL-2.0+",
+            "CC-BY-4.0",
+            "LicenseRef-custom",
+            "DocumentRef-spdx:LicenseRef-custom",
+        ] {
+            assert!(super::is_valid(license, &Version::v1_0_0), "{license}");
+            assert!(super::is_valid(license, &Version::v1_1_0), "{license}");
+        }
+        for license in [
+            "",
+            "CC BY",
+            "MIT/Apache",
+            "AND",
+            "Other:MIT",
+            "Not-A-License",
+        ] {
+            assert!(!super::is_valid(license, &Version::v1_1_0), "{license}");
+        }
+    }
+
+    #[test]
+    fn expressions() {
+        for license in [
+            "MIT OR Apache-2.0",
+            "(MIT OR Apache-2.0) AND CC-BY-4.0",
+            "GPL-2.0-or-later WITH Classpath-exception-2.0",
+            "((MIT))",
+        ] {
+            assert!(super::is_valid(license, &Version::v1_1_0), "{license}");
+            assert!(!super::is_valid(license, &Version::v1_0_0), "{license}");
+        }
+        for license in [
+            "MIT OR",
+            "(MIT",
+            "MIT)",
+            "MIT and Apache-2.0",
+            "MIT WITH",
+            "MIT WITH Not-An-Exception",
+        ] {
+            assert!(!super::is_valid(license, &Version::v1_1_0), "{license}");
+        }
+    }
+
+    #[test]
+    fn versions() {
+        assert!(super::is_valid("other", &Version::v1_1_0));
+        assert!(super::is_valid("other", &Version::Unknown("1.2.0".into())));
+        assert!(!super::is_valid("other", &Version::v1_0_0));
+        for license in ["various", "proprietary"] {
+            assert!(super::is_valid(license, &Version::v1_0_0), "{license}");
+            assert!(super::is_valid(license, &Version::v1_1_0), "{license}");
+        }
+    }
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            super::url("GPL-2.0+").unwrap(),
+            "https://spdx.org/licenses/GPL-2.0.html"
+        );
+        assert!(super::url("LicenseRef-custom").is_none());
+        assert!(super::url("Not-A-License").is_none());
+        assert!(super::url("proprietary").is_none());
+    }
+}
@@ -18,6 +18,10 @@ pub const ROOT_REL: &str = "root";
 pub const SELF_REL: &str = "self";
 /// Collection link.
 pub const COLLECTION_REL: &str = "collection";
+/// License link.
+pub const LICENSE_REL: &str = "license";
+/// Link to an object that this one was derived from.
+pub const DERIVED_FROM_REL: &str = "derived_from";
 
 /// This object describes a relationship with another entity.
 ///
@@ -475,6 +479,34 @@ impl Link {
         Link::new(href, COLLECTION_REL).json()
     }
 
+    /// Creates a new license link with HTML media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac::Link;
+    /// let link = Link::license("https://spdx.org/licenses/MIT.html");
+    /// assert!(link.is_license());
+    /// assert_eq!(link.r#type.as_ref().unwrap(), ::mime::TEXT_HTML.as_ref());
+    /// ```
+    pub fn license(href: impl Into<Href>) -> Link {
+        Link::new(href, LICENSE_REL).r#type(::mime::TEXT_HTML.to_string())
+    }
+
+    /// Creates a new derived from link with GeoJSON media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac::Link;
+    /// let link = Link::derived_from("./source-item.json");
+    /// assert!(link.is_derived_from());
+    /// assert_eq!(link.r#type.as_ref().unwrap(), stac::mime::APPLICATION_GEOJSON);
+    /// ```
+    pub fn derived_from(href: impl Into<Href>) -> Link {
+        Link::new(href, DERIVED_FROM_REL).geojson()
+    }
+
     /// Returns true if this link's rel is `"item"`.
     ///
     /// # Examples
@@ -565,6 +597,36 @@ impl Link {
         self.rel == COLLECTION_REL
     }
 
+    /// Returns true if this link's rel is `"license"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac::Link;
+    /// let link = Link::new("an-href", "license");
+    /// assert!(link.is_license());
+    /// let link = Link::new("an-href", "not-a-license");
+    /// assert!(!link.is_license());
+    /// ```
+    pub fn is_license(&self) -> bool {
+        self.rel == LICENSE_REL
+    }
+
+    /// Returns true if this link's rel is `"derived_from"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stac::Link;
+    /// let link = Link::new("an-href", "derived_from");
+    /// assert!(link.is_derived_from());
+    /// let link = Link::new("an-href", "not-derived-from");
+    /// assert!(!link.is_derived_from());
+    /// ```
+    pub fn is_derived_from(&self) -> bool {
+        self.rel == DERIVED_FROM_REL
+    }
+
     /// Returns true if this link is structural (i.e. not child, parent, item,
     /// root, or self).
     ///
@@ -0,0 +1,590 @@
+//! Find content that is valid STAC, but probably wrong.
+//!
+//! Linting complements [validation](crate::Validate): a value can pass
+//! json-schema validation and still have an epoch-default datetime or a bbox
+//! that doesn't contain its geometry.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{Item, Lint};
+//!
+//! let mut item = Item::new("an-id");
+//! item.properties.datetime = Some("1970-01-01T00:00:00Z".parse().unwrap());
+//! let problems = item.lint();
+//! assert_eq!(problems[0].rule, stac::lint::Rule::EpochDatetime);
+//! ```
+
+use crate::{Bbox, Catalog, Collection, Item, ItemCollection, Value};
+use chrono::{DateTime, Datelike, Utc};
+use geojson::{Geometry, PolygonType, Position};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Datetimes after the start of this year are flagged as being in the far future.
+pub const FAR_FUTURE_YEAR: i32 = 2100;
+
+/// A lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// A datetime is the Unix epoch, which is usually a default rather than a real time.
+    EpochDatetime,
+
+    /// A datetime is at or after the start of [FAR_FUTURE_YEAR].
+    FarFutureDatetime,
+
+    /// An end datetime is before its start datetime.
+    EndBeforeStart,
+
+    /// An item's datetime is outside of its collection's temporal extent.
+    OutsideTemporalExtent,
+
+    /// A geometry has no coordinates.
+    EmptyGeometry,
+
+    /// A polygon ring has fewer than four positions or isn't closed.
+    InvalidRing,
+
+    /// An item's bbox doesn't contain its geometry.
+    BboxDoesNotContainGeometry,
+
+    /// A collection's provider has a role that isn't a [provider
+    /// Role](crate::collection::Role).
+    UnknownProviderRole,
+
+    /// A collection's license isn't valid for its STAC version, see [crate::license].
+    InvalidLicense,
+
+    /// A collection's item asset definition has an href, which belongs on
+    /// the items' assets (or the collection's assets) instead.
+    ItemAssetWithHref,
+
+    /// A collection's item asset definition has fewer than two fields, so it
+    /// doesn't describe its assets.
+    ItemAssetTooFewFields,
+
+    /// A collection's asset has an empty href.
+    AssetWithoutHref,
+
+    /// An API response is missing a required link, e.g. a landing page without a `self` link.
+    MissingLink,
+
+    /// An API response has a link with a relative href.
+    RelativeLink,
+}
+
+/// A problem found by a lint [Rule].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Problem {
+    /// The rule that found this problem.
+    pub rule: Rule,
+
+    /// The id of the item or collection with the problem.
+    pub id: String,
+
+    /// A description of the problem.
+    pub message: String,
+}
+
+/// Lint a STAC value.
+pub trait Lint {
+    /// Returns any problems found in this value.
+    ///
+    /// An empty vector means that no problems were found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Lint};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// assert!(item.lint().is_empty());
+    /// ```
+    fn lint(&self) -> Vec<Problem>;
+}
+
+impl Rule {
+    /// Returns this rule's name, e.g. `epoch-datetime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::lint::Rule;
+    /// assert_eq!(Rule::EndBeforeStart.as_str(), "end-before-start");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rule::EpochDatetime => "epoch-datetime",
+            Rule::FarFutureDatetime => "far-future-datetime",
+            Rule::EndBeforeStart => "end-before-start",
+            Rule::OutsideTemporalExtent => "outside-temporal-extent",
+            Rule::EmptyGeometry => "empty-geometry",
+            Rule::InvalidRing => "invalid-ring",
+            Rule::BboxDoesNotContainGeometry => "bbox-does-not-contain-geometry",
+            Rule::UnknownProviderRole => "unknown-provider-role",
+            Rule::InvalidLicense => "invalid-license",
+            Rule::ItemAssetWithHref => "item-asset-with-href",
+            Rule::ItemAssetTooFewFields => "item-asset-too-few-fields",
+            Rule::AssetWithoutHref => "asset-without-href",
+            Rule::MissingLink => "missing-link",
+            Rule::RelativeLink => "relative-link",
+        }
+    }
+}
+
+impl Problem {
+    fn new(rule: Rule, id: impl ToString, message: impl ToString) -> Problem {
+        Problem {
+            rule,
+            id: id.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.id, self.message, self.rule)
+    }
+}
+
+impl Lint for Item {
+    fn lint(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let properties = &self.properties;
+        for (name, datetime) in [
+            ("datetime", properties.datetime),
+            ("start_datetime", properties.start_datetime),
+            ("end_datetime", properties.end_datetime),
+        ] {
+            if let Some(datetime) = datetime {
+                lint_datetime(&self.id, name, datetime, &mut problems);
+            }
+        }
+        if let (Some(start), Some(end)) = (properties.start_datetime, properties.end_datetime) {
+            if end < start {
+                problems.push(Problem::new(
+                    Rule::EndBeforeStart,
+                    &self.id,
+                    format!("end_datetime {} is before start_datetime {}", end, start),
+                ));
+            }
+        }
+        if let Some(geometry) = self.geometry.as_ref() {
+            lint_geometry(&self.id, geometry, &mut problems);
+            if let Some(bbox) = self.bbox {
+                if !positions(&geometry.value)
+                    .iter()
+                    .all(|position| contains(bbox, position))
+                {
+                    problems.push(Problem::new(
+                        Rule::BboxDoesNotContainGeometry,
+                        &self.id,
+                        format!("bbox {:?} does not contain the geometry", bbox),
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+impl Lint for Collection {
+    fn lint(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for [start, end] in &self.extent.temporal.interval {
+            for (name, datetime) in [("start", start), ("end", end)] {
+                if let Some(datetime) = datetime {
+                    lint_datetime(&self.id, name, *datetime, &mut problems);
+                }
+            }
+            if let (Some(start), Some(end)) = (start, end) {
+                if end < start {
+                    problems.push(Problem::new(
+                        Rule::EndBeforeStart,
+                        &self.id,
+                        format!(
+                            "temporal interval ends ({}) before it starts ({})",
+                            end, start
+                        ),
+                    ));
+                }
+            }
+        }
+        for provider in self.providers.iter().flatten() {
+            for role in provider.unknown_roles() {
+                problems.push(Problem::new(
+                    Rule::UnknownProviderRole,
+                    &self.id,
+                    format!(
+                        "provider '{}' has an unknown role '{}'",
+                        provider.name, role
+                    ),
+                ));
+            }
+        }
+        if !crate::license::is_valid(&self.license, &self.version) {
+            problems.push(Problem::new(
+                Rule::InvalidLicense,
+                &self.id,
+                format!(
+                    "license '{}' is not valid for STAC v{}",
+                    self.license, self.version
+                ),
+            ));
+        }
+        let mut keys: Vec<_> = self.item_assets.keys().collect();
+        keys.sort();
+        for key in keys {
+            let item_asset = &self.item_assets[key];
+            if item_asset.additional_fields.contains_key("href") {
+                problems.push(Problem::new(
+                    Rule::ItemAssetWithHref,
+                    &self.id,
+                    format!("item asset '{}' has an href", key),
+                ));
+            }
+            let fields = usize::from(item_asset.title.is_some())
+                + usize::from(item_asset.description.is_some())
+                + usize::from(item_asset.r#type.is_some())
+                + usize::from(!item_asset.roles.is_empty())
+                + item_asset.additional_fields.len();
+            if fields < 2 {
+                problems.push(Problem::new(
+                    Rule::ItemAssetTooFewFields,
+                    &self.id,
+                    format!(
+                        "item asset '{}' has {} field(s), but should have at least two",
+                        key, fields
+                    ),
+                ));
+            }
+        }
+        let mut keys: Vec<_> = self.assets.keys().collect();
+        keys.sort();
+        for key in keys {
+            if self.assets[key].href.trim().is_empty() {
+                problems.push(Problem::new(
+                    Rule::AssetWithoutHref,
+                    &self.id,
+                    format!("asset '{}' has no href", key),
+                ));
+            }
+        }
+        problems
+    }
+}
+
+impl Lint for ItemCollection {
+    fn lint(&self) -> Vec<Problem> {
+        self.items.iter().flat_map(Item::lint).collect()
+    }
+}
+
+impl Lint for Catalog {
+    fn lint(&self) -> Vec<Problem> {
+        Vec::new()
+    }
+}
+
+impl Lint for Value {
+    fn lint(&self) -> Vec<Problem> {
+        match self {
+            Value::Item(item) => item.lint(),
+            Value::Collection(collection) => collection.lint(),
+            Value::ItemCollection(item_collection) => item_collection.lint(),
+            Value::Catalog(catalog) => catalog.lint(),
+        }
+    }
+}
+
+/// Lints an item, including checks against the collection it belongs to.
+///
+/// In addition to [Item::lint], this checks that the item's datetimes are
+/// within the collection's temporal extent.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Collection, Item, lint::Rule};
+///
+/// let mut collection = Collection::new("collection-id", "a description");
+/// collection.extent.temporal.interval = vec![[
+///     Some("2023-01-01T00:00:00Z".parse().unwrap()),
+///     Some("2023-12-31T23:59:59Z".parse().unwrap()),
+/// ]];
+/// let mut item = Item::new("item-id");
+/// item.properties.datetime = Some("2024-06-01T00:00:00Z".parse().unwrap());
+/// let problems = stac::lint::item_in_collection(&item, &collection);
+/// assert_eq!(problems[0].rule, Rule::OutsideTemporalExtent);
+/// ```
+pub fn item_in_collection(item: &Item, collection: &Collection) -> Vec<Problem> {
+    let mut problems = item.lint();
+    let properties = &item.properties;
+    let intervals = &collection.extent.temporal.interval;
+    for (name, datetime) in [
+        ("datetime", properties.datetime),
+        ("start_datetime", properties.start_datetime),
+        ("end_datetime", properties.end_datetime),
+    ] {
+        let Some(datetime) = datetime else {
+            continue;
+        };
+        let inside = intervals.is_empty()
+            || intervals.iter().any(|[start, end]| {
+                start.map(|start| start <= datetime).unwrap_or(true)
+                    && end.map(|end| datetime <= end).unwrap_or(true)
+            });
+        if !inside {
+            problems.push(Problem::new(
+                Rule::OutsideTemporalExtent,
+                &item.id,
+                format!(
+                    "{} {} is outside of collection '{}' temporal extent",
+                    name, datetime, collection.id
+                ),
+            ));
+        }
+    }
+    problems
+}
+
+fn lint_datetime(id: &str, name: &str, datetime: DateTime<Utc>, problems: &mut Vec<Problem>) {
+    if datetime.timestamp() == 0 {
+        problems.push(Problem::new(
+            Rule::EpochDatetime,
+            id,
+            format!("{} is the Unix epoch ({})", name, datetime),
+        ));
+    } else if datetime.year() >= FAR_FUTURE_YEAR {
+        problems.push(Problem::new(
+            Rule::FarFutureDatetime,
+            id,
+            format!("{} is in the far future ({})", name, datetime),
+        ));
+    }
+}
+
+fn lint_geometry(id: &str, geometry: &Geometry, problems: &mut Vec<Problem>) {
+    use geojson::Value::*;
+
+    let empty = match &geometry.value {
+        Point(position) => position.is_empty(),
+        MultiPoint(positions) | LineString(positions) => positions.is_empty(),
+        MultiLineString(lines) => lines.is_empty(),
+        Polygon(polygon) => polygon.is_empty(),
+        MultiPolygon(polygons) => polygons.is_empty(),
+        GeometryCollection(geometries) => {
+            for geometry in geometries {
+                lint_geometry(id, geometry, problems);
+            }
+            geometries.is_empty()
+        }
+    };
+    if empty {
+        problems.push(Problem::new(
+            Rule::EmptyGeometry,
+            id,
+            "geometry has no coordinates",
+        ));
+    }
+    match &geometry.value {
+        Polygon(polygon) => lint_rings(id, polygon, problems),
+        MultiPolygon(polygons) => {
+            for polygon in polygons {
+                lint_rings(id, polygon, problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lint_rings(id: &str, polygon: &PolygonType, problems: &mut Vec<Problem>) {
+    for ring in polygon {
+        if ring.len() < 4 {
+            problems.push(Problem::new(
+                Rule::InvalidRing,
+                id,
+                format!(
+                    "ring has {} positions, at least four are required",
+                    ring.len()
+                ),
+            ));
+        } else if ring.first() != ring.last() {
+            problems.push(Problem::new(Rule::InvalidRing, id, "ring is not closed"));
+        }
+    }
+}
+
+fn positions(value: &geojson::Value) -> Vec<&Position> {
+    use geojson::Value::*;
+
+    match value {
+        Point(position) => vec![position],
+        MultiPoint(positions) | LineString(positions) => positions.iter().collect(),
+        MultiLineString(lines) | Polygon(lines) => lines.iter().flatten().collect(),
+        MultiPolygon(polygons) => polygons.iter().flatten().flatten().collect(),
+        GeometryCollection(geometries) => geometries
+            .iter()
+            .flat_map(|geometry| positions(&geometry.value))
+            .collect(),
+    }
+}
+
+fn contains(bbox: Bbox, position: &Position) -> bool {
+    let (Some(x), Some(y)) = (position.first(), position.get(1)) else {
+        return true;
+    };
+    bbox.contains_xy(*x, *y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lint, Rule};
+    use crate::{collection::Role, Asset, Bbox, Collection, Item, ItemAsset, Provider};
+    use geojson::{Geometry, Value};
+
+    fn rules(item: &Item) -> Vec<Rule> {
+        item.lint()
+            .into_iter()
+            .map(|problem| problem.rule)
+            .collect()
+    }
+
+    #[test]
+    fn simple_item() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        assert!(item.lint().is_empty());
+    }
+
+    #[test]
+    fn datetimes() {
+        let mut item = Item::new("an-id");
+        item.properties.datetime = Some("1970-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(rules(&item), [Rule::EpochDatetime]);
+        item.properties.datetime = Some("2999-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(rules(&item), [Rule::FarFutureDatetime]);
+        item.properties.datetime = None;
+        item.properties.start_datetime = Some("2023-02-01T00:00:00Z".parse().unwrap());
+        item.properties.end_datetime = Some("2023-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(rules(&item), [Rule::EndBeforeStart]);
+    }
+
+    #[test]
+    fn geometries() {
+        let mut item = Item::new("an-id");
+        item.geometry = Some(Geometry::new(Value::MultiPolygon(Vec::new())));
+        assert_eq!(rules(&item), [Rule::EmptyGeometry]);
+        item.geometry = Some(Geometry::new(Value::Polygon(vec![
+            vec![vec![0., 0.], vec![1., 0.], vec![1., 1.]],
+            vec![vec![0., 0.], vec![1., 0.], vec![1., 1.], vec![0., 1.]],
+        ])));
+        assert_eq!(rules(&item), [Rule::InvalidRing, Rule::InvalidRing]);
+        item.geometry = Some(Geometry::new(Value::Point(vec![-105.1, 41.1])));
+        item.bbox = Some(Bbox::new(-106., 41., -105., 42.));
+        assert!(rules(&item).is_empty());
+        item.bbox = Some(Bbox::new(0., 0., 1., 1.));
+        assert_eq!(rules(&item), [Rule::BboxDoesNotContainGeometry]);
+        item.bbox = Some(Bbox::new(170., 40., -100., 42.));
+        assert!(rules(&item).is_empty());
+        item.bbox = Some(Bbox::new(-105.1 + 1e-12, 41.1, -105.1 + 1e-12, 41.1));
+        assert!(rules(&item).is_empty());
+    }
+
+    #[test]
+    fn collection() {
+        let mut collection = Collection::new("an-id", "a description");
+        assert!(collection.lint().is_empty());
+        collection.extent.temporal.interval = vec![[
+            Some("2023-02-01T00:00:00Z".parse().unwrap()),
+            Some("2023-01-01T00:00:00Z".parse().unwrap()),
+        ]];
+        let problems = collection.lint();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::EndBeforeStart);
+    }
+
+    #[test]
+    fn unknown_provider_role() {
+        let mut provider = Provider::new("a-name").role(Role::Producer);
+        provider.roles.as_mut().unwrap().push("owner".to_string());
+        let mut collection = Collection::new("an-id", "a description");
+        collection.add_provider(provider);
+        let problems = collection.lint();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::UnknownProviderRole);
+    }
+
+    #[test]
+    fn invalid_license() {
+        let mut collection = Collection::new("an-id", "a description");
+        collection.license = "MIT OR Apache-2.0".to_string();
+        assert!(collection.lint().is_empty());
+        collection.license = "proprietary".to_string();
+        assert!(collection.lint().is_empty());
+        collection.license = "Not-A-License".to_string();
+        let problems = collection.lint();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::InvalidLicense);
+    }
+
+    #[test]
+    fn collection_assets() {
+        let mut collection = Collection::new("an-id", "a description");
+        let item_asset: ItemAsset = serde_json::from_value(serde_json::json!({
+            "title": "Data",
+            "type": "image/tiff",
+            "href": "./data.tif",
+        }))
+        .unwrap();
+        let _ = collection
+            .item_assets
+            .insert("data".to_string(), item_asset);
+        let item_asset: ItemAsset =
+            serde_json::from_value(serde_json::json!({"title": "Thumbnail"})).unwrap();
+        let _ = collection
+            .item_assets
+            .insert("thumbnail".to_string(), item_asset);
+        let _ = collection
+            .assets
+            .insert("metadata".to_string(), Asset::new(""));
+        let rules: Vec<_> = collection
+            .lint()
+            .into_iter()
+            .map(|problem| problem.rule)
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                Rule::ItemAssetWithHref,
+                Rule::ItemAssetTooFewFields,
+                Rule::AssetWithoutHref
+            ]
+        );
+    }
+
+    #[test]
+    fn item_in_collection() {
+        let mut collection = Collection::new("collection-id", "a description");
+        collection.extent.temporal.interval =
+            vec![[Some("2023-01-01T00:00:00Z".parse().unwrap()), None]];
+        let mut item = Item::new("item-id");
+        item.properties.datetime = Some("2023-06-01T00:00:00Z".parse().unwrap());
+        assert!(super::item_in_collection(&item, &collection).is_empty());
+        item.properties.datetime = Some("2022-06-01T00:00:00Z".parse().unwrap());
+        let problems = super::item_in_collection(&item, &collection);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::OutsideTemporalExtent);
+        assert_eq!(
+            problems[0].to_string(),
+            "item-id: datetime 2022-06-01 00:00:00 UTC is outside of collection 'collection-id' temporal extent (outside-temporal-extent)"
+        );
+    }
+}
@@ -204,6 +204,8 @@ fn migrate_links(object: &mut Map<String, Value>) {
                         if href.starts_with('/') {
                             let _ =
                                 link.insert("href".to_string(), format!("file://{}", href).into());
+                        } else if let Some(url) = crate::href::windows_path_to_url(href) {
+                            let _ = link.insert("href".to_string(), url.to_string().into());
                         }
                     }
                 }
@@ -1,4 +1,10 @@
-use crate::{Error, FromJson, Item, ItemCollection, Result, SelfHref, Value};
+//! Read and write [newline-delimited JSON](https://github.com/ndjson/ndjson-spec).
+
+use crate::{
+    codec::{Compression, Encoder},
+    partial::{ReadOptions, ReadReport},
+    Error, FromJson, Item, ItemCollection, RawItem, Result, SelfHref, Value,
+};
 use bytes::Bytes;
 use serde::Serialize;
 use std::{
@@ -6,6 +12,12 @@ use std::{
     io::{BufRead, BufReader, BufWriter, Write},
     path::Path,
 };
+#[cfg(feature = "object-store")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// The number of compressed bytes an [AsyncWriter] buffers before writing them.
+#[cfg(feature = "object-store")]
+const ASYNC_BUFFER_SIZE: usize = 64 * 1024;
 
 /// Create a STAC object from newline-delimited JSON.
 pub trait FromNdjson: FromJson {
@@ -85,6 +97,190 @@ pub trait ToNdjson: Serialize {
     fn to_ndjson_vec(&self) -> Result<Vec<u8>> {
         serde_json::to_vec(self).map_err(Error::from)
     }
+
+    /// Writes a value to a writer as newline-delimited JSON with the given
+    /// compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{codec::Compression, ToNdjson, ItemCollection, Item};
+    ///
+    /// let item_collection: ItemCollection = vec![Item::new("a"), Item::new("b")].into();
+    /// let mut buf = Vec::new();
+    /// item_collection.to_ndjson_writer_with_compression(&mut buf, Compression::None).unwrap();
+    /// assert_eq!(buf, item_collection.to_ndjson_vec().unwrap());
+    /// ```
+    fn to_ndjson_writer_with_compression(
+        &self,
+        writer: impl Write,
+        compression: Compression,
+    ) -> Result<()> {
+        let mut encoder = Encoder::new(writer, compression)?;
+        self.to_ndjson_writer(&mut encoder)?;
+        let _ = encoder.finish()?;
+        Ok(())
+    }
+}
+
+/// Writes values as newline-delimited JSON, one at a time.
+///
+/// Unlike [ToNdjson], the values don't have to all be in memory at once, so
+/// items can be written as they're fetched. Call [Writer::finish] when you're
+/// done to write any compression trailer.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{codec::Compression, ndjson::Writer, Item};
+///
+/// let mut writer = Writer::new(Vec::new(), Compression::None).unwrap().flush_every(100);
+/// writer.write(&Item::new("a")).unwrap();
+/// writer.write(&Item::new("b")).unwrap();
+/// assert_eq!(writer.count(), 2);
+/// let buf = writer.finish().unwrap();
+/// assert_eq!(buf.iter().filter(|&&b| b == b'\n').count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    encoder: Encoder<W>,
+    flush_every: Option<usize>,
+    count: usize,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new writer.
+    pub fn new(writer: W, compression: Compression) -> Result<Writer<W>> {
+        Ok(Writer {
+            encoder: Encoder::new(writer, compression)?,
+            flush_every: None,
+            count: 0,
+        })
+    }
+
+    /// Flushes the underlying writer after every `count` values.
+    ///
+    /// Useful when something is reading the output as it's written, e.g. a
+    /// pipe or a tailed file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{codec::Compression, ndjson::Writer};
+    /// let writer = Writer::new(std::io::sink(), Compression::None).unwrap().flush_every(1000);
+    /// ```
+    pub fn flush_every(mut self, count: usize) -> Writer<W> {
+        self.flush_every = Some(count.max(1));
+        self
+    }
+
+    /// Writes a value as a single line.
+    pub fn write(&mut self, value: &impl Serialize) -> Result<()> {
+        serde_json::to_writer(&mut self.encoder, value)?;
+        writeln!(&mut self.encoder)?;
+        self.count += 1;
+        if let Some(flush_every) = self.flush_every {
+            if self.count % flush_every == 0 {
+                self.encoder.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of values that have been written.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Finishes compression, flushes, and returns the underlying writer.
+    pub fn finish(self) -> Result<W> {
+        self.encoder.finish()
+    }
+}
+
+/// Writes values as newline-delimited JSON to an [AsyncWrite], one at a time.
+///
+/// Values are serialized and compressed like a [Writer] does, into a buffer
+/// that's written to the underlying writer when it's full or when values are
+/// flushed. Call [AsyncWriter::finish] when you're done to write any
+/// compression trailer.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{codec::Compression, ndjson::AsyncWriter, Item};
+///
+/// # tokio_test::block_on(async {
+/// let mut writer = AsyncWriter::new(Vec::new(), Compression::None).unwrap();
+/// writer.write(&Item::new("a")).await.unwrap();
+/// writer.write(&Item::new("b")).await.unwrap();
+/// let buf = writer.finish().await.unwrap();
+/// assert_eq!(buf.iter().filter(|&&b| b == b'\n').count(), 2);
+/// # })
+/// ```
+#[cfg(feature = "object-store")]
+#[derive(Debug)]
+pub struct AsyncWriter<W> {
+    writer: W,
+    inner: Writer<Vec<u8>>,
+}
+
+#[cfg(feature = "object-store")]
+impl<W: AsyncWrite + Unpin> AsyncWriter<W> {
+    /// Creates a new async writer.
+    pub fn new(writer: W, compression: Compression) -> Result<AsyncWriter<W>> {
+        Ok(AsyncWriter {
+            writer,
+            inner: Writer::new(Vec::new(), compression)?,
+        })
+    }
+
+    /// Writes and flushes the underlying writer after every `count` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{codec::Compression, ndjson::AsyncWriter};
+    /// let writer = AsyncWriter::new(tokio::io::sink(), Compression::None)
+    ///     .unwrap()
+    ///     .flush_every(1000);
+    /// ```
+    pub fn flush_every(mut self, count: usize) -> AsyncWriter<W> {
+        self.inner = self.inner.flush_every(count);
+        self
+    }
+
+    /// Writes a value as a single line.
+    pub async fn write(&mut self, value: &impl Serialize) -> Result<()> {
+        self.inner.write(value)?;
+        let flush = self
+            .inner
+            .flush_every
+            .is_some_and(|flush_every| self.inner.count % flush_every == 0);
+        let buf = self.inner.encoder.get_mut();
+        if flush || buf.len() >= ASYNC_BUFFER_SIZE {
+            self.writer.write_all(buf).await?;
+            buf.clear();
+            if flush {
+                self.writer.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of values that have been written.
+    pub fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    /// Finishes compression, writes and flushes everything that's buffered,
+    /// and returns the underlying writer.
+    pub async fn finish(mut self) -> Result<W> {
+        let buf = self.inner.finish()?;
+        self.writer.write_all(&buf).await?;
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
 }
 
 impl FromNdjson for Item {}
@@ -95,26 +291,21 @@ impl FromNdjson for ItemCollection {
         let path = path.as_ref();
         let reader = BufReader::new(File::open(path)?);
         let mut items = Vec::new();
-        for line in reader.lines() {
-            items.push(serde_json::from_str(&line?)?);
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                items.push(
+                    crate::json::from_slice(line.as_bytes())
+                        .map_err(|err| err.with_line(i + 1).with_href(path.display()))?,
+                );
+            }
         }
         let mut item_collection = ItemCollection::from(items);
         *item_collection.self_href_mut() = Some(path.into());
         Ok(item_collection)
     }
     fn from_ndjson_bytes(bytes: impl Into<Bytes>) -> Result<Self> {
-        bytes
-            .into()
-            .split(|b| *b == b'\n')
-            .filter_map(|line| {
-                if line.is_empty() {
-                    None
-                } else {
-                    Some(serde_json::from_slice::<Item>(line).map_err(Error::from))
-                }
-            })
-            .collect::<Result<Vec<_>>>()
-            .map(ItemCollection::from)
+        read_items(bytes.into(), ReadOptions::default()).map(|report| report.item_collection)
     }
 }
 impl FromNdjson for Value {
@@ -122,8 +313,14 @@ impl FromNdjson for Value {
         let path = path.as_ref();
         let reader = BufReader::new(File::open(path)?);
         let mut values: Vec<Value> = Vec::new();
-        for line in reader.lines() {
-            values.push(serde_json::from_str(&line?)?);
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.is_empty() {
+                values.push(
+                    crate::json::from_slice(line.as_bytes())
+                        .map_err(|err| err.with_line(i + 1).with_href(path.display()))?,
+                );
+            }
         }
         vec_into_value(values)
     }
@@ -131,11 +328,12 @@ impl FromNdjson for Value {
         let values = bytes
             .into()
             .split(|b| *b == b'\n')
-            .filter_map(|line| {
+            .enumerate()
+            .filter_map(|(i, line)| {
                 if line.is_empty() {
                     None
                 } else {
-                    Some(serde_json::from_slice::<Value>(line).map_err(Error::from))
+                    Some(crate::json::from_slice::<Value>(line).map_err(|err| err.with_line(i + 1)))
                 }
             })
             .collect::<Result<Vec<_>>>()?;
@@ -143,6 +341,59 @@ impl FromNdjson for Value {
     }
 }
 
+/// Reads items from newline-delimited JSON one line at a time.
+///
+/// Unlike [FromNdjson], items are parsed as they're read, so they don't all
+/// have to be in memory at once. Blank lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use std::{fs::File, io::BufReader};
+///
+/// let file = BufReader::new(File::open("data/items.ndjson").unwrap());
+/// let items = stac::ndjson::items_from_reader(file)
+///     .collect::<stac::Result<Vec<_>>>()
+///     .unwrap();
+/// ```
+pub fn items_from_reader<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Item>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(
+                crate::json::from_slice::<Item>(line.as_bytes())
+                    .map_err(|err| err.with_line(i + 1)),
+            ),
+            Err(err) => Some(Err(err.into())),
+        })
+}
+
+/// Reads items from newline-delimited JSON, skipping invalid lines if asked.
+pub(crate) fn read_items(bytes: Bytes, options: ReadOptions) -> Result<ReadReport> {
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for (i, line) in bytes.split(|b| *b == b'\n').enumerate() {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let result = crate::json::from_slice::<Item>(line).map_err(|err| err.with_line(i + 1));
+        let id = || {
+            RawItem::from_json_slice(line)
+                .ok()
+                .map(|raw_item| raw_item.id.into_owned())
+        };
+        if let Some(item) = options.handle(i + 1, id, result, &mut skipped)? {
+            items.push(item);
+        }
+    }
+    Ok(ReadReport {
+        item_collection: items.into(),
+        skipped,
+    })
+}
+
 fn vec_into_value(mut values: Vec<Value>) -> Result<Value> {
     if values.len() == 1 {
         Ok(values.pop().unwrap())
@@ -248,10 +499,62 @@ impl ToNdjson for serde_json::Value {
 
 #[cfg(test)]
 mod tests {
-    use super::FromNdjson;
-    use crate::{ItemCollection, SelfHref, Value};
+    use super::{FromNdjson, Writer};
+    use crate::{codec::Compression, partial::ReadOptions, Item, ItemCollection, SelfHref, Value};
     use std::{fs::File, io::Read};
 
+    #[test]
+    fn error_line() {
+        let mut buf = Vec::new();
+        let _ = File::open("data/items.ndjson")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        buf.extend_from_slice(b"{\"type\": \"Feature\", \"id\": 42}\n");
+        let crate::Error::Json(err) = ItemCollection::from_ndjson_bytes(buf).unwrap_err() else {
+            panic!("should be a json error");
+        };
+        assert_eq!(err.line(), 3);
+        assert_eq!(err.pointer(), "/id");
+    }
+
+    #[test]
+    fn skip_invalid() {
+        let mut buf = Vec::new();
+        let _ = File::open("data/items.ndjson")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        buf.extend_from_slice(b"not json\n");
+        buf.extend_from_slice(
+            b"{\"type\": \"Feature\", \"id\": \"an-id\", \"properties\": {\"datetime\": 42}}\n",
+        );
+        let report =
+            super::read_items(buf.clone().into(), ReadOptions { skip_invalid: true }).unwrap();
+        assert_eq!(report.item_collection.items.len(), 2);
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.skipped[0].record, 3);
+        assert!(report.skipped[0].id.is_none());
+        assert_eq!(report.skipped[1].id.as_deref(), Some("an-id"));
+        assert!(report.skipped[1]
+            .to_string()
+            .starts_with("record 4 (an-id): "));
+        let _ = super::read_items(buf.into(), ReadOptions::default()).unwrap_err();
+    }
+
+    #[test]
+    fn read_items_whitespace_lines() {
+        let mut buf = Vec::new();
+        let _ = File::open("data/items.ndjson")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        buf.extend_from_slice(b"  \r\n\t\n");
+        let report = super::read_items(buf.into(), ReadOptions::default()).unwrap();
+        assert_eq!(report.item_collection.items.len(), 2);
+        assert!(report.skipped.is_empty());
+    }
+
     #[test]
     fn item_collection_read() {
         let item_collection = ItemCollection::from_ndjson_path("data/items.ndjson").unwrap();
@@ -288,4 +591,69 @@ mod tests {
             .unwrap();
         let _ = Value::from_ndjson_bytes(buf).unwrap();
     }
+
+    #[test]
+    fn writer_flush_every() {
+        let mut writer = Writer::new(Vec::new(), Compression::None)
+            .unwrap()
+            .flush_every(1);
+        writer.write(&Item::new("a")).unwrap();
+        writer.write(&Item::new("b")).unwrap();
+        let item_collection = ItemCollection::from_ndjson_bytes(writer.finish().unwrap()).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn writer_gzip() {
+        use super::ToNdjson;
+
+        let item_collection = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+        let mut buf = Vec::new();
+        item_collection
+            .to_ndjson_writer_with_compression(&mut buf, Compression::Gzip)
+            .unwrap();
+        let format = crate::Format::Custom("ndjson.gz");
+        let item_collection: ItemCollection = format.from_bytes(buf).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "object-store")]
+    async fn async_writer_flush_every() {
+        let mut writer = super::AsyncWriter::new(Vec::new(), Compression::None)
+            .unwrap()
+            .flush_every(1);
+        writer.write(&Item::new("a")).await.unwrap();
+        assert!(!writer.writer.is_empty());
+        writer.write(&Item::new("b")).await.unwrap();
+        let buf = writer.finish().await.unwrap();
+        let item_collection = ItemCollection::from_ndjson_bytes(buf).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "object-store", feature = "gzip"))]
+    async fn async_writer_gzip() {
+        let mut writer = super::AsyncWriter::new(Vec::new(), Compression::Gzip).unwrap();
+        writer.write(&Item::new("a")).await.unwrap();
+        writer.write(&Item::new("b")).await.unwrap();
+        assert_eq!(writer.count(), 2);
+        let buf = writer.finish().await.unwrap();
+        let format = crate::Format::Custom("ndjson.gz");
+        let item_collection: ItemCollection = format.from_bytes(buf).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn writer_zstd() {
+        let mut writer =
+            Writer::new(Vec::new(), Compression::infer_from_href("items.ndjson.zst")).unwrap();
+        writer.write(&Item::new("a")).unwrap();
+        let buf = writer.finish().unwrap();
+        let format = crate::Format::Custom("ndjson.zst");
+        let item_collection: ItemCollection = format.from_bytes(buf).unwrap();
+        assert_eq!(item_collection.items[0].id, "a");
+    }
 }
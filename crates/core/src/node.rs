@@ -1,5 +1,6 @@
 use crate::{Catalog, Collection, Error, Href, Item, Link, Links, Result, SelfHref, Value};
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 
 /// A node in a STAC tree.
 #[derive(Debug)]
@@ -15,7 +16,8 @@ pub struct Node {
 }
 
 /// A STAC container, i.e. a [Catalog] or a [Collection].
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum Container {
     /// A [Collection].
     Collection(Box<Collection>), // To avoid large enum variant
@@ -24,6 +26,44 @@ pub enum Container {
     Catalog(Box<Catalog>),
 }
 
+/// Statistics about a (usually resolved) STAC tree, from [Node::statistics].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeStatistics {
+    /// The number of catalogs, including the root if it's a catalog.
+    pub catalogs: usize,
+
+    /// The number of collections, including the root if it's a collection.
+    pub collections: usize,
+
+    /// The number of items.
+    pub items: usize,
+
+    /// The number of levels of catalogs and collections below the root.
+    ///
+    /// A root without any children has a depth of zero.
+    pub depth: usize,
+
+    /// The number of objects that use each extension, by extension schema url.
+    pub extensions: BTreeMap<String, usize>,
+
+    /// The number of child and item links that are still in the tree, i.e.
+    /// that weren't resolved.
+    ///
+    /// After resolving, these include links that are broken, that would create
+    /// a cycle, and that are deeper than the resolver's maximum depth. Use
+    /// [Node::statistics_with_resolver_stats] to count those separately.
+    pub unresolved_links: usize,
+
+    /// The number of unresolved links that couldn't be fetched.
+    pub broken_links: usize,
+
+    /// The number of unresolved child links that would have created a cycle.
+    pub cyclic_links: usize,
+
+    /// The number of unresolved links that are deeper than the resolver's maximum depth.
+    pub too_deep_links: usize,
+}
+
 /// An iterator over a node and all of its descendants.
 #[derive(Debug)]
 pub struct IntoValues {
@@ -77,6 +117,91 @@ impl Node {
             items: VecDeque::new(),
         }
     }
+
+    /// Computes statistics about this node and all of its descendants.
+    ///
+    /// Only what's in the tree is counted, so [resolve](Node::resolve) the
+    /// node first to include the objects behind its links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Collection, Item, Node};
+    ///
+    /// let mut node: Node = Catalog::new("root", "a description").into();
+    /// let mut child: Node = Collection::new("child", "a description").into();
+    /// child.items.push_back(Item::new("an-item"));
+    /// node.children.push_back(child);
+    /// let statistics = node.statistics();
+    /// assert_eq!(statistics.catalogs, 1);
+    /// assert_eq!(statistics.collections, 1);
+    /// assert_eq!(statistics.items, 1);
+    /// assert_eq!(statistics.depth, 1);
+    /// ```
+    pub fn statistics(&self) -> TreeStatistics {
+        let mut statistics = TreeStatistics::default();
+        self.add_statistics(&mut statistics, 0);
+        statistics
+    }
+
+    /// Computes statistics about this node and all of its descendants, using
+    /// the stats of the [Resolver](crate::Resolver) that resolved it to say
+    /// why links are unresolved.
+    ///
+    /// The resolver's stats are cumulative, so use a fresh resolver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Node, Resolver};
+    ///
+    /// let node: Node = stac::read::<Catalog>("examples/catalog.json").unwrap().into();
+    /// # tokio_test::block_on(async {
+    /// let resolver = Resolver::new().recursive(true).max_depth(1);
+    /// let node = resolver.resolve(node).await.unwrap();
+    /// let statistics = node.statistics_with_resolver_stats(resolver.stats());
+    /// assert_eq!(statistics.broken_links, 0);
+    /// assert_eq!(statistics.too_deep_links, 1);
+    /// # });
+    /// ```
+    #[cfg(feature = "object-store")]
+    pub fn statistics_with_resolver_stats(&self, stats: crate::ResolverStats) -> TreeStatistics {
+        let mut statistics = self.statistics();
+        statistics.broken_links = stats.broken;
+        statistics.cyclic_links = stats.cycles;
+        statistics.too_deep_links = stats.too_deep;
+        statistics
+    }
+
+    fn add_statistics(&self, statistics: &mut TreeStatistics, depth: usize) {
+        statistics.depth = statistics.depth.max(depth);
+        let extensions = match &self.value {
+            Container::Catalog(catalog) => {
+                statistics.catalogs += 1;
+                &catalog.extensions
+            }
+            Container::Collection(collection) => {
+                statistics.collections += 1;
+                &collection.extensions
+            }
+        };
+        statistics.unresolved_links += self
+            .value
+            .links()
+            .iter()
+            .filter(|link| link.is_child() || link.is_item())
+            .count();
+        statistics.items += self.items.len();
+        for extension in extensions
+            .iter()
+            .chain(self.items.iter().flat_map(|item| &item.extensions))
+        {
+            *statistics.extensions.entry(extension.clone()).or_default() += 1;
+        }
+        for child in &self.children {
+            child.add_statistics(statistics, depth + 1);
+        }
+    }
 }
 
 impl Iterator for IntoValues {
@@ -188,8 +313,47 @@ impl SelfHref for Container {
 
 #[cfg(test)]
 mod tests {
-    use super::Node;
-    use crate::{Catalog, Collection};
+    use super::{Container, Node};
+    use crate::{Catalog, Collection, Item, Link, Links};
+
+    #[test]
+    fn container_roundtrip() {
+        let catalog = Container::from(Catalog::new("a-catalog", "a description"));
+        let collection = Container::from(Collection::new("a-collection", "a description"));
+        let value = serde_json::to_value(vec![catalog.clone(), collection.clone()]).unwrap();
+        assert_eq!(value[0]["type"], "Catalog");
+        assert_eq!(value[1]["type"], "Collection");
+        let containers: Vec<Container> = serde_json::from_value(value).unwrap();
+        assert_eq!(containers, vec![catalog, collection]);
+    }
+
+    #[test]
+    fn statistics() {
+        let mut node: Node = Catalog::new("root", "a description").into();
+        node.value
+            .links_mut()
+            .push(Link::child("./broken/catalog.json"));
+        let mut collection = Collection::new("collection", "a description");
+        collection
+            .extensions
+            .push("https://stac.test/a.json".to_string());
+        let mut child: Node = collection.into();
+        let mut item = Item::new("item");
+        item.extensions.push("https://stac.test/a.json".to_string());
+        child.items.push_back(item);
+        let mut grandchild: Node = Catalog::new("grandchild", "a description").into();
+        grandchild.items.push_back(Item::new("another-item"));
+        child.children.push_back(grandchild);
+        node.children.push_back(child);
+
+        let statistics = node.statistics();
+        assert_eq!(statistics.catalogs, 2);
+        assert_eq!(statistics.collections, 1);
+        assert_eq!(statistics.items, 2);
+        assert_eq!(statistics.depth, 2);
+        assert_eq!(statistics.extensions["https://stac.test/a.json"], 2);
+        assert_eq!(statistics.unresolved_links, 1);
+    }
 
     #[test]
     fn into_node() {
@@ -200,8 +364,6 @@ mod tests {
     #[tokio::test]
     #[cfg(feature = "object-store")]
     async fn resolve() {
-        use crate::Links;
-
         let node: Node = crate::read::<Catalog>("examples/catalog.json")
             .unwrap()
             .into();
@@ -211,6 +373,33 @@ mod tests {
         assert_eq!(node.value.links().len(), 2);
     }
 
+    #[tokio::test]
+    #[cfg(feature = "object-store")]
+    async fn statistics_with_resolver_stats() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut a = Catalog::new("a", "Catalog a");
+        a.links.push(Link::child("./b/catalog.json"));
+        a.links.push(Link::child("./missing/catalog.json"));
+        let mut b = Catalog::new("b", "Catalog b");
+        b.links.push(Link::child("../catalog.json"));
+        std::fs::create_dir(temp_dir.path().join("b")).unwrap();
+        let path = temp_dir.path().join("catalog.json");
+        crate::write(&path, a).unwrap();
+        crate::write(temp_dir.path().join("b").join("catalog.json"), b).unwrap();
+        let node: Node = crate::read::<Catalog>(path.to_str().unwrap())
+            .unwrap()
+            .into();
+        let resolver = crate::Resolver::new()
+            .recursive(true)
+            .skip_broken_links(true);
+        let node = resolver.resolve(node).await.unwrap();
+        let statistics = node.statistics_with_resolver_stats(resolver.stats());
+        assert_eq!(statistics.unresolved_links, 2);
+        assert_eq!(statistics.broken_links, 1);
+        assert_eq!(statistics.cyclic_links, 1);
+        assert_eq!(statistics.too_deep_links, 0);
+    }
+
     #[test]
     fn into_values() {
         let mut node: Node = Catalog::new("an-id", "a description").into();
@@ -0,0 +1,89 @@
+//! Read newline-delimited JSON and stac-geoparquet even if some records are invalid.
+//!
+//! By default, one malformed item stops a read. With
+//! [ReadOptions::skip_invalid], invalid records are skipped and reported
+//! instead, so a multi-million item file can still be read:
+//!
+//! ```
+//! use stac::{partial::ReadOptions, Format};
+//!
+//! let bytes = "{\"type\": \"Feature\", \"id\": 42}\n";
+//! let report = Format::ndjson()
+//!     .from_bytes_opts(bytes, ReadOptions { skip_invalid: true })
+//!     .unwrap();
+//! assert!(report.item_collection.items.is_empty());
+//! assert_eq!(report.skipped[0].record, 1);
+//! ```
+
+use crate::{Error, ItemCollection, Result};
+use std::fmt::{Display, Formatter};
+
+/// Options for reading many items at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Skip records that can't be read as items, instead of returning an error.
+    pub skip_invalid: bool,
+}
+
+/// The items that were read, and the records that were skipped.
+#[derive(Debug)]
+pub struct ReadReport {
+    /// The items that were read.
+    pub item_collection: ItemCollection,
+
+    /// The records that were skipped, in order.
+    pub skipped: Vec<Skipped>,
+}
+
+/// A record that was skipped because it couldn't be read as an item.
+#[derive(Debug)]
+pub struct Skipped {
+    /// The one-based line (for newline-delimited JSON) or row (for
+    /// stac-geoparquet) of the record.
+    pub record: usize,
+
+    /// The id of the record, if it could be read.
+    ///
+    /// Ids are only read from newline-delimited JSON.
+    pub id: Option<String>,
+
+    /// Why the record couldn't be read.
+    pub error: Error,
+}
+
+impl ReadOptions {
+    /// Handles the result of reading one record.
+    ///
+    /// Returns the error if invalid records aren't skipped, otherwise records
+    /// it in `report`. `id` is only called for skipped records.
+    pub(crate) fn handle<T>(
+        &self,
+        record: usize,
+        id: impl FnOnce() -> Option<String>,
+        result: Result<T>,
+        report: &mut Vec<Skipped>,
+    ) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if self.skip_invalid => {
+                tracing::debug!("skipping record {record}: {error}");
+                report.push(Skipped {
+                    record,
+                    id: id(),
+                    error,
+                });
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "record {} ({}): {}", self.record, id, self.error),
+            None => write!(f, "record {}: {}", self.record, self.error),
+        }
+    }
+}
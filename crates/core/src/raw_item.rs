@@ -0,0 +1,213 @@
+use crate::{Bbox, Error, Item, Result};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+};
+
+/// An [Item] that borrows from the JSON it was read from.
+///
+/// Only the id, collection, bbox, geometry, and properties are parsed, and
+/// strings and nested objects borrow from the input buffer where possible.
+/// This is useful for high-throughput pipelines that only need to look at a
+/// few fields of each item. Use [RawItem::to_item] to get an owned [Item].
+#[derive(Debug, Clone)]
+pub struct RawItem<'a> {
+    /// The item id.
+    pub id: Cow<'a, str>,
+
+    /// The id of the STAC Collection this item references to.
+    pub collection: Option<Cow<'a, str>>,
+
+    /// The bounding box of the item.
+    pub bbox: Option<Bbox>,
+
+    /// The unparsed geometry of the item.
+    pub geometry: Option<&'a RawValue>,
+
+    /// The unparsed properties of the item.
+    pub properties: &'a RawValue,
+
+    property_values: HashMap<Key<'a>, &'a RawValue>,
+    json: &'a [u8],
+}
+
+/// A property key, which borrows from the input buffer unless it has escapes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+struct Key<'a>(#[serde(borrow)] Cow<'a, str>);
+
+#[derive(Deserialize)]
+struct Borrowed<'a> {
+    #[serde(borrow)]
+    id: Cow<'a, str>,
+    #[serde(borrow, default)]
+    collection: Option<Cow<'a, str>>,
+    #[serde(default)]
+    bbox: Option<Bbox>,
+    #[serde(borrow, default)]
+    geometry: Option<&'a RawValue>,
+    #[serde(borrow)]
+    properties: &'a RawValue,
+}
+
+#[derive(Deserialize)]
+struct BorrowedItemCollection<'a> {
+    #[serde(borrow)]
+    features: Vec<&'a RawValue>,
+}
+
+impl<'a> RawItem<'a> {
+    /// Creates a raw item from JSON bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::RawItem;
+    ///
+    /// let buf = std::fs::read("examples/simple-item.json").unwrap();
+    /// let raw_item = RawItem::from_json_slice(&buf).unwrap();
+    /// assert_eq!(raw_item.id, "20201211_223832_CS2");
+    /// ```
+    pub fn from_json_slice(json: &'a [u8]) -> Result<RawItem<'a>> {
+        let borrowed: Borrowed<'a> = serde_json::from_slice(json)?;
+        let property_values = serde_json::from_str(borrowed.properties.get())?;
+        Ok(RawItem {
+            id: borrowed.id,
+            collection: borrowed.collection,
+            bbox: borrowed.bbox,
+            geometry: borrowed.geometry,
+            properties: borrowed.properties,
+            property_values,
+            json,
+        })
+    }
+
+    /// Creates raw items from the bytes of a JSON item collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::RawItem;
+    ///
+    /// let buf = std::fs::read("data/two-sentinel-2-items.json").unwrap();
+    /// let raw_items = RawItem::from_item_collection_slice(&buf).unwrap();
+    /// assert_eq!(raw_items.len(), 2);
+    /// ```
+    pub fn from_item_collection_slice(json: &'a [u8]) -> Result<Vec<RawItem<'a>>> {
+        let item_collection: BorrowedItemCollection<'a> = serde_json::from_slice(json)?;
+        item_collection
+            .features
+            .into_iter()
+            .map(|feature| RawItem::from_json_slice(feature.get().as_bytes()))
+            .collect()
+    }
+
+    /// Returns the JSON bytes of this item.
+    pub fn json(&self) -> &'a [u8] {
+        self.json
+    }
+
+    /// Deserializes a single property, borrowing from the input buffer if possible.
+    ///
+    /// The properties object is only split into its values once, when the
+    /// raw item is created. Returns `None` if the property isn't set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::RawItem;
+    ///
+    /// let buf = std::fs::read("examples/simple-item.json").unwrap();
+    /// let raw_item = RawItem::from_json_slice(&buf).unwrap();
+    /// let datetime: &str = raw_item.property("datetime").unwrap().unwrap();
+    /// assert_eq!(datetime, "2020-12-11T22:38:32.125000Z");
+    /// ```
+    pub fn property<T: Deserialize<'a>>(&self, key: &str) -> Result<Option<T>> {
+        self.property_values
+            .get(key)
+            .map(|value| serde_json::from_str(value.get()).map_err(Error::from))
+            .transpose()
+    }
+
+    /// Converts this raw item into an owned [Item].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::RawItem;
+    ///
+    /// let buf = std::fs::read("examples/simple-item.json").unwrap();
+    /// let item = RawItem::from_json_slice(&buf).unwrap().to_item().unwrap();
+    /// assert_eq!(item.id, "20201211_223832_CS2");
+    /// ```
+    pub fn to_item(&self) -> Result<Item> {
+        crate::json::from_slice(self.json)
+    }
+}
+
+impl Borrow<str> for Key<'_> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<RawItem<'_>> for Item {
+    type Error = Error;
+
+    fn try_from(raw_item: RawItem<'_>) -> Result<Item> {
+        raw_item.to_item()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RawItem;
+    use crate::Item;
+    use std::borrow::Cow;
+
+    #[test]
+    fn borrows() {
+        let buf = std::fs::read("examples/simple-item.json").unwrap();
+        let raw_item = RawItem::from_json_slice(&buf).unwrap();
+        assert!(matches!(raw_item.id, Cow::Borrowed(_)));
+        assert!(raw_item.geometry.is_some());
+        assert!(raw_item.bbox.is_some());
+        assert!(raw_item
+            .property::<f64>("not-a-property")
+            .unwrap()
+            .is_none());
+        assert!(raw_item
+            .property_values
+            .keys()
+            .all(|key| matches!(key.0, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn escaped_property() {
+        let raw_item =
+            RawItem::from_json_slice(br#"{"id": "an-id", "properties": {"a\"b": 42, "c": "d"}}"#)
+                .unwrap();
+        assert_eq!(raw_item.property::<i64>("a\"b").unwrap().unwrap(), 42);
+        assert_eq!(raw_item.property::<&str>("c").unwrap().unwrap(), "d");
+    }
+
+    #[test]
+    fn to_item() {
+        let buf = std::fs::read("examples/simple-item.json").unwrap();
+        let item = RawItem::from_json_slice(&buf).unwrap().to_item().unwrap();
+        let expected: Item = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(item, expected);
+    }
+
+    #[test]
+    fn item_collection() {
+        let buf = std::fs::read("data/item-collection.json").unwrap();
+        let raw_items = RawItem::from_item_collection_slice(&buf).unwrap();
+        let item_collection: crate::ItemCollection = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(raw_items.len(), item_collection.items.len());
+        for (raw_item, item) in raw_items.into_iter().zip(item_collection.items) {
+            assert_eq!(Item::try_from(raw_item).unwrap(), item);
+        }
+    }
+}
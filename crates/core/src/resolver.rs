@@ -1,35 +1,302 @@
-use crate::{Container, Links, Node, Result, SelfHref, Value};
-use std::{future::Future, pin::Pin};
-use tokio::task::JoinSet;
+use crate::{Container, Href, Item, Links, Node, Result, SelfHref, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::Semaphore, task::JoinSet, time::Instant};
 use url::Url;
 
+/// The default number of fetched objects kept by a [Resolver].
+pub const DEFAULT_RESOLVER_CACHE_CAPACITY: usize = 10_000;
+
 /// An object that uses object store to resolve links.
+///
+/// A resolver remembers the hrefs it has fetched, up to
+/// [Resolver::cache_capacity] of the most recently used, so the same object
+/// is usually not fetched twice, and child links that point back to one of their
+/// ancestors (a cycle) are left unresolved.
+///
+/// Links are fetched concurrently. Use [Resolver::concurrency] and
+/// [Resolver::rate_limit] to be gentle with remote servers.
 #[derive(Debug, Default)]
 #[cfg(feature = "object-store")]
 pub struct Resolver {
     recursive: bool,
     use_items_endpoint: bool,
+    max_depth: Option<usize>,
+    skip_broken_links: bool,
+    limiter: Limiter,
+    state: Arc<Mutex<State>>,
+}
+
+/// Statistics about the objects fetched by a [Resolver].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResolverStats {
+    /// The number of objects that were fetched.
+    pub fetched: usize,
+
+    /// The number of objects that were taken from the cache instead of being fetched.
+    pub cached: usize,
+
+    /// The number of child links that were not resolved because they would create a cycle.
+    pub cycles: usize,
+
+    /// The number of child and item links that were not resolved because
+    /// they're deeper than [Resolver::max_depth].
+    pub too_deep: usize,
+
+    /// The number of links that were not resolved because they couldn't be
+    /// fetched. Always zero unless [Resolver::skip_broken_links] is set.
+    pub broken: usize,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Limiter {
+    semaphore: Option<Arc<Semaphore>>,
+    interval: Option<Duration>,
+    hosts: Arc<Mutex<HashMap<String, Instant>>>,
+    options: Vec<(String, String)>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    cache: Cache,
+    stats: ResolverStats,
+}
+
+/// A least-recently-used cache of fetched values.
+#[derive(Debug)]
+struct Cache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (u64, Arc<Value>)>,
+    order: BTreeMap<u64, String>,
 }
 
 impl Resolver {
+    /// Creates a new resolver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new();
+    /// ```
+    pub fn new() -> Resolver {
+        Resolver::default()
+    }
+
+    /// Sets whether the resolver should resolve the links of child nodes as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().recursive(true);
+    /// ```
+    pub fn recursive(mut self, recursive: bool) -> Resolver {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Sets whether the resolver should use the `items` endpoint instead of `item` links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().use_items_endpoint(true);
+    /// ```
+    pub fn use_items_endpoint(mut self, use_items_endpoint: bool) -> Resolver {
+        self.use_items_endpoint = use_items_endpoint;
+        self
+    }
+
+    /// Sets the maximum number of levels of links to follow.
+    ///
+    /// A depth of zero resolves nothing, and a depth of one only resolves the
+    /// links of the node passed to [Resolver::resolve]. This is only useful
+    /// for recursive resolvers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().recursive(true).max_depth(2);
+    /// ```
+    pub fn max_depth(mut self, max_depth: usize) -> Resolver {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets whether links that can't be fetched are left unresolved instead of
+    /// failing the whole resolution.
+    ///
+    /// Broken links are kept in their node's links and counted in
+    /// [ResolverStats::broken].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().skip_broken_links(true);
+    /// ```
+    pub fn skip_broken_links(mut self, skip_broken_links: bool) -> Resolver {
+        self.skip_broken_links = skip_broken_links;
+        self
+    }
+
+    /// Sets the maximum number of objects that can be fetched at the same time.
+    ///
+    /// By default, there is no limit. A concurrency of zero is treated as one,
+    /// since nothing could ever be fetched otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().concurrency(8);
+    /// ```
+    pub fn concurrency(mut self, concurrency: usize) -> Resolver {
+        self.limiter.semaphore = Some(Arc::new(Semaphore::new(concurrency.max(1))));
+        self
+    }
+
+    /// Sets the minimum amount of time between two requests to the same host.
+    ///
+    /// Only urls with a host are rate limited, local files are not. By default,
+    /// there is no rate limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().rate_limit(Duration::from_millis(100));
+    /// ```
+    pub fn rate_limit(mut self, interval: Duration) -> Resolver {
+        self.limiter.interval = Some(interval);
+        self
+    }
+
+    /// Sets the maximum number of fetched objects to remember.
+    ///
+    /// Once the cache is full, the least recently used object is forgotten. A
+    /// capacity of zero disables the cache. Defaults to
+    /// [DEFAULT_RESOLVER_CACHE_CAPACITY].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Resolver;
+    ///
+    /// let resolver = Resolver::new().cache_capacity(100);
+    /// ```
+    pub fn cache_capacity(self, capacity: usize) -> Resolver {
+        self.state.lock().unwrap().cache.capacity = capacity;
+        self
+    }
+
+    /// Sets the options used to get objects, see [crate::io::get_opts].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{io::CACHE_DIRECTORY_OPTION, Resolver};
+    ///
+    /// let resolver = Resolver::new().options([(CACHE_DIRECTORY_OPTION, "/tmp/stac-cache")]);
+    /// ```
+    pub fn options<I, K, V>(mut self, options: I) -> Resolver
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        self.limiter.options = options
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value.into()))
+            .collect();
+        self
+    }
+
+    /// Returns statistics about the objects this resolver has fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Node, Resolver};
+    ///
+    /// let node: Node = stac::read::<Catalog>("examples/catalog.json").unwrap().into();
+    /// let resolver = Resolver::new();
+    /// # tokio_test::block_on(async {
+    /// let _ = resolver.resolve(node).await.unwrap();
+    /// # });
+    /// assert!(resolver.stats().fetched > 0);
+    /// ```
+    pub fn stats(&self) -> ResolverStats {
+        self.state.lock().unwrap().stats
+    }
+
     /// Resolves the links of a node.
-    pub fn resolve(&self, mut node: Node) -> Pin<Box<impl Future<Output = Result<Node>> + '_>> {
-        Box::pin(async {
-            let links = std::mem::take(node.value.links_mut());
+    pub fn resolve(&self, node: Node) -> Pin<Box<impl Future<Output = Result<Node>> + '_>> {
+        Box::pin(self.resolve_at(node, 0, Vec::new()))
+    }
+
+    fn resolve_at(
+        &self,
+        mut node: Node,
+        depth: usize,
+        mut ancestors: Vec<String>,
+    ) -> Pin<Box<impl Future<Output = Result<Node>> + '_>> {
+        Box::pin(async move {
+            if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                let too_deep = node
+                    .value
+                    .links()
+                    .iter()
+                    .filter(|link| link.is_child() || link.is_item())
+                    .count();
+                self.state.lock().unwrap().stats.too_deep += too_deep;
+                return Ok(node);
+            }
             let href = node.value.self_href().cloned();
+            if let Some(href) = &href {
+                let key = key(href);
+                if !ancestors.contains(&key) {
+                    ancestors.push(key);
+                }
+            }
+            let links = std::mem::take(node.value.links_mut());
             let mut join_set = JoinSet::new();
             for mut link in links {
-                if link.is_child() {
+                if link.is_child() || (!self.use_items_endpoint && link.is_item()) {
                     if let Some(href) = &href {
                         link.make_absolute(href)?;
                     }
-                    let _ = join_set
-                        .spawn(async move { (crate::io::get::<Value>(link.href).await, true) });
-                } else if !self.use_items_endpoint && link.is_item() {
-                    if let Some(href) = &href {
-                        link.make_absolute(href)?;
+                    let is_child = link.is_child();
+                    let key = key(&link.href);
+                    if is_child && ancestors.contains(&key) {
+                        self.state.lock().unwrap().stats.cycles += 1;
+                        node.value.links_mut().push(link);
+                    } else if let Some(value) = self.cached(&key) {
+                        add(&mut node, Arc::unwrap_or_clone(value), is_child)?;
+                    } else {
+                        let limiter = self.limiter.clone();
+                        let _ = join_set.spawn(async move {
+                            let result = limiter.get(link.href.clone()).await;
+                            (result, is_child, Some(key), Some(link))
+                        });
                     }
-                    let _ = join_set.spawn(async move { (crate::io::get(link.href).await, false) });
                 } else if self.use_items_endpoint && link.rel == "items" {
                     let mut url: Url = link.href.try_into()?;
                     // TODO make this configurable
@@ -37,30 +304,352 @@ impl Resolver {
                         .query_pairs_mut()
                         .append_pair("limit", "1")
                         .append_pair("sortby", "-properties.datetime");
-                    let _ = join_set.spawn(async move { (crate::io::get(url).await, false) });
+                    let limiter = self.limiter.clone();
+                    let _ = join_set
+                        .spawn(async move { (limiter.get(url.into()).await, false, None, None) });
                 } else {
                     node.value.links_mut().push(link);
                 }
             }
             while let Some(result) = join_set.join_next().await {
-                let (result, is_child) = result?;
-                let value = result?;
-                if is_child {
-                    let child = Container::try_from(value)?.into();
-                    node.children.push_back(child);
-                } else if let Value::ItemCollection(item_collection) = value {
-                    node.items.extend(item_collection.into_iter());
-                } else {
-                    node.items.push_back(value.try_into()?);
+                let (result, is_child, key, link) = result?;
+                let value = match result {
+                    Ok(value) => value,
+                    Err(err) if self.skip_broken_links => {
+                        tracing::warn!("skipping broken link: {err}");
+                        self.state.lock().unwrap().stats.broken += 1;
+                        if let Some(link) = link {
+                            node.value.links_mut().push(link);
+                        }
+                        continue;
+                    }
+                    Err(err) => return Err(err),
+                };
+                let value = Arc::new(value);
+                {
+                    let mut state = self.state.lock().unwrap();
+                    state.stats.fetched += 1;
+                    if let Some(key) = key {
+                        state.cache.insert(key, value.clone());
+                    }
                 }
+                add(&mut node, Arc::unwrap_or_clone(value), is_child)?;
             }
             if self.recursive {
                 let children = std::mem::take(&mut node.children);
-                for child in children {
-                    node.children.push_back(self.resolve(child).await?);
-                }
+                let children = futures::future::try_join_all(
+                    children
+                        .into_iter()
+                        .map(|child| self.resolve_at(child, depth + 1, ancestors.clone())),
+                )
+                .await?;
+                node.children.extend(children);
             }
             Ok(node)
         })
     }
+
+    fn cached(&self, key: &str) -> Option<Arc<Value>> {
+        let mut state = self.state.lock().unwrap();
+        let value = state.cache.get(key)?;
+        state.stats.cached += 1;
+        Some(value)
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Cache {
+        Cache {
+            capacity: DEFAULT_RESOLVER_CACHE_CAPACITY,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+}
+
+impl Cache {
+    fn get(&mut self, key: &str) -> Option<Arc<Value>> {
+        let tick = self.next_tick();
+        let (used, value) = self.entries.get_mut(key)?;
+        let _ = self.order.remove(used);
+        *used = tick;
+        let _ = self.order.insert(tick, key.to_string());
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: String, value: Arc<Value>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((used, _)) = self.entries.remove(&key) {
+            let _ = self.order.remove(&used);
+        }
+        while self.entries.len() >= self.capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => {
+                    let _ = self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        let tick = self.next_tick();
+        let _ = self.order.insert(tick, key.clone());
+        let _ = self.entries.insert(key, (tick, value));
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+impl Limiter {
+    async fn get(&self, href: Href) -> Result<Value> {
+        // Wait for the host's slot before taking a permit, so requests that
+        // are waiting on a rate limit don't block requests to other hosts.
+        if let Some(instant) = self.reserve(&href) {
+            tokio::time::sleep_until(instant).await;
+        }
+        let _permit = if let Some(semaphore) = &self.semaphore {
+            semaphore.acquire().await.ok()
+        } else {
+            None
+        };
+        crate::io::get_opts(href, self.options.iter().cloned()).await
+    }
+
+    /// Reserves the next slot for this href's host, returning when the request can be made.
+    fn reserve(&self, href: &Href) -> Option<Instant> {
+        let interval = self.interval?;
+        let host = match href {
+            Href::Url(url) => url.host_str()?,
+            Href::String(_) => return None,
+        };
+        let now = Instant::now();
+        let mut hosts = self.hosts.lock().unwrap();
+        let instant = hosts
+            .get(host)
+            .copied()
+            .filter(|instant| *instant > now)
+            .unwrap_or(now);
+        let _ = hosts.insert(host.to_string(), instant + interval);
+        Some(instant)
+    }
+}
+
+fn key(href: &Href) -> String {
+    href.normalize().to_string()
+}
+
+fn add(node: &mut Node, value: Value, is_child: bool) -> Result<()> {
+    if is_child {
+        let child = Container::try_from(value)?.into();
+        node.children.push_back(child);
+    } else if let Value::ItemCollection(item_collection) = value {
+        node.items.extend(item_collection);
+    } else {
+        node.items.push_back(Item::try_from(value)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, Limiter, Resolver, ResolverStats};
+    use crate::{Catalog, Link, Links, Node, SelfHref};
+    use std::{sync::Arc, time::Duration};
+    use tempfile::TempDir;
+
+    fn write_cycle() -> (TempDir, Node) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut a = Catalog::new("a", "Catalog a");
+        a.links.push(Link::child("./b/catalog.json"));
+        let mut b = Catalog::new("b", "Catalog b");
+        b.links.push(Link::child("../catalog.json"));
+        std::fs::create_dir(temp_dir.path().join("b")).unwrap();
+        let a_path = temp_dir.path().join("catalog.json");
+        crate::write(&a_path, a).unwrap();
+        crate::write(temp_dir.path().join("b").join("catalog.json"), b).unwrap();
+        let node = crate::read::<Catalog>(a_path.to_str().unwrap())
+            .unwrap()
+            .into();
+        (temp_dir, node)
+    }
+
+    #[tokio::test]
+    async fn resolve() {
+        let node: Node = crate::read::<Catalog>("examples/catalog.json")
+            .unwrap()
+            .into();
+        let resolver = Resolver::new();
+        let node = resolver.resolve(node).await.unwrap();
+        assert_eq!(node.children.len(), 3);
+        assert_eq!(node.items.len(), 1);
+        assert_eq!(resolver.stats().fetched, 4);
+    }
+
+    #[tokio::test]
+    async fn cycle() {
+        let (_temp_dir, node) = write_cycle();
+        let resolver = Resolver::new().recursive(true);
+        let node = resolver.resolve(node).await.unwrap();
+        assert_eq!(node.children.len(), 1);
+        let b = &node.children[0];
+        assert!(b.children.is_empty());
+        assert_eq!(b.value.links().len(), 1, "the cyclic link should be kept");
+        assert_eq!(
+            resolver.stats(),
+            ResolverStats {
+                fetched: 1,
+                cached: 0,
+                cycles: 1,
+                too_deep: 0,
+                broken: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_broken_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut catalog = Catalog::new("a", "Catalog a");
+        catalog.links.push(Link::child("./missing/catalog.json"));
+        let path = temp_dir.path().join("catalog.json");
+        crate::write(&path, catalog).unwrap();
+        let node: Node = crate::read::<Catalog>(path.to_str().unwrap())
+            .unwrap()
+            .into();
+
+        let _ = Resolver::new().resolve(node).await.unwrap_err();
+
+        let node: Node = crate::read::<Catalog>(path.to_str().unwrap())
+            .unwrap()
+            .into();
+        let resolver = Resolver::new().skip_broken_links(true);
+        let node = resolver.resolve(node).await.unwrap();
+        assert!(node.children.is_empty());
+        assert_eq!(node.value.links().len(), 1);
+        assert_eq!(resolver.stats().broken, 1);
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let node: Node = crate::read::<Catalog>("examples/catalog.json")
+            .unwrap()
+            .into();
+        let resolver = Resolver::new();
+        let _ = resolver.resolve(node).await.unwrap();
+        let fetched = resolver.stats().fetched;
+        let node: Node = crate::read::<Catalog>("examples/catalog.json")
+            .unwrap()
+            .into();
+        let _ = resolver.resolve(node).await.unwrap();
+        assert_eq!(resolver.stats().fetched, fetched);
+        assert_eq!(resolver.stats().cached, fetched);
+    }
+
+    #[test]
+    fn least_recently_used() {
+        let value = |id: &str| Arc::new(Catalog::new(id, id).into());
+        let mut cache = Cache {
+            capacity: 2,
+            ..Default::default()
+        };
+        cache.insert("a".to_string(), value("a"));
+        cache.insert("b".to_string(), value("b"));
+        assert!(cache.get("a").is_some());
+        cache.insert("c".to_string(), value("c"));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+        assert_eq!(cache.entries.len(), cache.order.len());
+    }
+
+    #[tokio::test]
+    async fn cache_disabled() {
+        let resolver = Resolver::new().cache_capacity(0);
+        for _ in 0..2 {
+            let node: Node = crate::read::<Catalog>("examples/catalog.json")
+                .unwrap()
+                .into();
+            let _ = resolver.resolve(node).await.unwrap();
+        }
+        assert_eq!(resolver.stats().cached, 0);
+        assert_eq!(resolver.stats().fetched, 8);
+    }
+
+    #[tokio::test]
+    async fn max_depth() {
+        let (_temp_dir, node) = write_cycle();
+        let resolver = Resolver::new().recursive(true).max_depth(0);
+        let node = resolver.resolve(node).await.unwrap();
+        assert!(node.children.is_empty());
+        assert!(node.value.self_href().is_some());
+        assert_eq!(resolver.stats().fetched, 0);
+        assert_eq!(resolver.stats().too_deep, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrency() {
+        let node: Node = crate::read::<Catalog>("examples/catalog.json")
+            .unwrap()
+            .into();
+        let resolver = Resolver::new().concurrency(1);
+        let node = resolver.resolve(node).await.unwrap();
+        assert_eq!(node.children.len(), 3);
+        assert_eq!(node.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn zero_concurrency() {
+        let node: Node = crate::read::<Catalog>("examples/catalog.json")
+            .unwrap()
+            .into();
+        let resolver = Resolver::new().concurrency(0);
+        let node = tokio::time::timeout(Duration::from_secs(10), resolver.resolve(node))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.children.len(), 3);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let limiter = Limiter {
+            interval: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let first = limiter
+            .reserve(&"http://stac-rs.test/a.json".into())
+            .unwrap();
+        let second = limiter
+            .reserve(&"http://stac-rs.test/b.json".into())
+            .unwrap();
+        assert!(second >= first + Duration::from_secs(1));
+        let other = limiter.reserve(&"http://other.test/a.json".into()).unwrap();
+        assert!(other < second);
+        assert!(limiter.reserve(&"examples/catalog.json".into()).is_none());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_releases_permit() {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(1));
+        let limiter = Limiter {
+            semaphore: Some(semaphore.clone()),
+            interval: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let href = "http://stac-rs.test/a.json";
+        let _ = limiter.reserve(&href.into());
+        let (result, available_permits) = tokio::join!(
+            tokio::time::timeout(Duration::from_millis(100), limiter.get(href.into())),
+            async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                semaphore.available_permits()
+            }
+        );
+        let _ = result.unwrap_err();
+        assert_eq!(available_permits, 1);
+    }
 }
@@ -0,0 +1,333 @@
+//! Rewrite asset and link hrefs, e.g. when relocating a catalog.
+//!
+//! A [Rewriter] holds an ordered list of [Rule]s. Each href is rewritten by
+//! the first rule that matches it, and hrefs that don't match any rule are left
+//! alone. Scheme changes, like `s3://` to `https://`, are prefix swaps:
+//!
+//! ```
+//! use stac::{Asset, Item, Rewrite, rewrite::Rewriter};
+//!
+//! let mut item = Item::new("an-id");
+//! let _ = item.assets.insert("data".to_string(), Asset::new("s3://bucket/data.tif"));
+//! let rewriter = Rewriter::new().prefix("s3://bucket/", "https://bucket.s3.amazonaws.com/");
+//! assert_eq!(item.rewrite(&rewriter), 1);
+//! assert_eq!(item.assets["data"].href, "https://bucket.s3.amazonaws.com/data.tif");
+//! ```
+
+use crate::{Asset, Catalog, Collection, Error, Item, ItemCollection, Link, Result, Value};
+use std::{borrow::BorrowMut, str::FromStr};
+
+/// A single href rewriting rule.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    /// Replaces a leading `from` with `to`.
+    Prefix {
+        /// The prefix to match.
+        from: String,
+
+        /// The replacement prefix.
+        to: String,
+    },
+
+    /// Replaces the first match of a regular expression.
+    ///
+    /// The replacement can refer to capture groups, e.g. `$1` or `${name}`.
+    #[cfg(feature = "regex")]
+    Regex {
+        /// The pattern to match.
+        regex: regex::Regex,
+
+        /// The replacement.
+        replacement: String,
+    },
+}
+
+/// Rewrites asset and, optionally, link hrefs.
+#[derive(Debug, Clone, Default)]
+pub struct Rewriter {
+    rules: Vec<Rule>,
+    links: bool,
+}
+
+/// Implemented by values whose hrefs can be rewritten.
+pub trait Rewrite {
+    /// Rewrites this value's hrefs, including those of any items it contains.
+    ///
+    /// Returns the number of hrefs that were changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Rewrite, rewrite::Rewriter};
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let rewriter = Rewriter::new().prefix("https://storage.googleapis.com/", "gs://");
+    /// assert_eq!(item.rewrite(&rewriter), 2);
+    /// ```
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize;
+}
+
+impl Rule {
+    /// Creates a new prefix rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rule;
+    /// let rule = Rule::prefix("s3://old/", "https://new.test/");
+    /// assert_eq!(rule.apply("s3://old/a.tif").unwrap(), "https://new.test/a.tif");
+    /// assert!(rule.apply("s3://other/a.tif").is_none());
+    /// ```
+    pub fn prefix(from: impl ToString, to: impl ToString) -> Rule {
+        Rule::Prefix {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    /// Creates a new regular expression rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rule;
+    /// let rule = Rule::regex(r"^s3://([^/]+)/", "https://$1.s3.amazonaws.com/").unwrap();
+    /// assert_eq!(
+    ///     rule.apply("s3://bucket/a.tif").unwrap(),
+    ///     "https://bucket.s3.amazonaws.com/a.tif"
+    /// );
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str, replacement: impl ToString) -> Result<Rule> {
+        Ok(Rule::Regex {
+            regex: regex::Regex::new(pattern)?,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// Applies this rule to an href, returning `None` if the rule doesn't match.
+    pub fn apply(&self, href: &str) -> Option<String> {
+        match self {
+            Rule::Prefix { from, to } => href
+                .strip_prefix(from.as_str())
+                .map(|rest| format!("{}{}", to, rest)),
+            #[cfg(feature = "regex")]
+            Rule::Regex { regex, replacement } => {
+                if regex.is_match(href) {
+                    Some(regex.replacen(href, 1, replacement.as_str()).into_owned())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    /// Parses a prefix rule from `from=to`.
+    fn from_str(s: &str) -> Result<Rule> {
+        s.split_once('=')
+            .filter(|(from, _)| !from.is_empty())
+            .map(|(from, to)| Rule::prefix(from, to))
+            .ok_or_else(|| Error::InvalidRewrite(s.to_string()))
+    }
+}
+
+impl Rewriter {
+    /// Creates a new rewriter with no rules that only rewrites asset hrefs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rewriter;
+    /// let rewriter = Rewriter::new();
+    /// ```
+    pub fn new() -> Rewriter {
+        Rewriter::default()
+    }
+
+    /// Adds a rule.
+    ///
+    /// Rules are tried in the order they're added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::{Rewriter, Rule};
+    /// let rewriter = Rewriter::new().rule("s3://old/=s3://new/".parse::<Rule>().unwrap());
+    /// assert_eq!(rewriter.rewrite_href("s3://old/a.tif").unwrap(), "s3://new/a.tif");
+    /// ```
+    pub fn rule(mut self, rule: Rule) -> Rewriter {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Adds a prefix rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rewriter;
+    /// let rewriter = Rewriter::new().prefix("s3://old/", "s3://new/");
+    /// ```
+    pub fn prefix(self, from: impl ToString, to: impl ToString) -> Rewriter {
+        self.rule(Rule::prefix(from, to))
+    }
+
+    /// Adds a regular expression rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rewriter;
+    /// let rewriter = Rewriter::new().regex(r"\.TIF$", ".tif").unwrap();
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex(self, pattern: &str, replacement: impl ToString) -> Result<Rewriter> {
+        Ok(self.rule(Rule::regex(pattern, replacement)?))
+    }
+
+    /// Sets whether link hrefs are rewritten as well as asset hrefs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::rewrite::Rewriter;
+    /// let rewriter = Rewriter::new().links(true);
+    /// ```
+    pub fn links(mut self, links: bool) -> Rewriter {
+        self.links = links;
+        self
+    }
+
+    /// Rewrites a single href with the first matching rule.
+    ///
+    /// Returns `None` if no rule matches.
+    pub fn rewrite_href(&self, href: &str) -> Option<String> {
+        self.rules.iter().find_map(|rule| rule.apply(href))
+    }
+
+    fn rewrite_assets(&self, assets: impl IntoIterator<Item = impl BorrowMut<Asset>>) -> usize {
+        let mut count = 0;
+        for mut asset in assets {
+            let asset = asset.borrow_mut();
+            if let Some(href) = self.rewrite_href(&asset.href) {
+                asset.href = href;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn rewrite_links(&self, links: &mut [Link]) -> usize {
+        if !self.links {
+            return 0;
+        }
+        let mut count = 0;
+        for link in links {
+            if let Some(href) = self.rewrite_href(link.href.as_str()) {
+                link.href = href.into();
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl Rewrite for Item {
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize {
+        rewriter.rewrite_assets(self.assets.values_mut()) + rewriter.rewrite_links(&mut self.links)
+    }
+}
+
+impl Rewrite for Collection {
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize {
+        rewriter.rewrite_assets(self.assets.values_mut()) + rewriter.rewrite_links(&mut self.links)
+    }
+}
+
+impl Rewrite for Catalog {
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize {
+        rewriter.rewrite_links(&mut self.links)
+    }
+}
+
+impl Rewrite for ItemCollection {
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize {
+        self.items
+            .iter_mut()
+            .map(|item| item.rewrite(rewriter))
+            .sum::<usize>()
+            + rewriter.rewrite_links(&mut self.links)
+    }
+}
+
+impl Rewrite for Value {
+    fn rewrite(&mut self, rewriter: &Rewriter) -> usize {
+        match self {
+            Value::Item(item) => item.rewrite(rewriter),
+            Value::Collection(collection) => collection.rewrite(rewriter),
+            Value::ItemCollection(item_collection) => item_collection.rewrite(rewriter),
+            Value::Catalog(catalog) => catalog.rewrite(rewriter),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rewrite, Rewriter, Rule};
+    use crate::{Asset, Item, Link};
+
+    #[test]
+    fn first_rule_wins() {
+        let rewriter = Rewriter::new()
+            .prefix("s3://bucket/a/", "https://a.test/")
+            .prefix("s3://bucket/", "https://bucket.test/");
+        assert_eq!(
+            rewriter.rewrite_href("s3://bucket/a/b.tif").unwrap(),
+            "https://a.test/b.tif"
+        );
+        assert_eq!(
+            rewriter.rewrite_href("s3://bucket/b.tif").unwrap(),
+            "https://bucket.test/b.tif"
+        );
+        assert!(rewriter.rewrite_href("./b.tif").is_none());
+    }
+
+    #[test]
+    fn links() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .assets
+            .insert("data".to_string(), Asset::new("s3://old/data.tif"));
+        item.links.push(Link::new("s3://old/item.json", "self"));
+        let rewriter = Rewriter::new().prefix("s3://old/", "s3://new/");
+        assert_eq!(item.clone().rewrite(&rewriter), 1);
+        assert_eq!(item.rewrite(&rewriter.links(true)), 2);
+        assert_eq!(item.links[0].href, "s3://new/item.json");
+    }
+
+    #[test]
+    fn parse() {
+        let rule: Rule = "s3://old=https://new".parse().unwrap();
+        assert_eq!(rule.apply("s3://old/a").unwrap(), "https://new/a");
+        let _ = "no-equals".parse::<Rule>().unwrap_err();
+        let _ = "=https://new".parse::<Rule>().unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn regex() {
+        let rewriter = Rewriter::new()
+            .regex(r"^https://([^.]+)\.s3\.amazonaws\.com/", "s3://$1/")
+            .unwrap();
+        assert_eq!(
+            rewriter
+                .rewrite_href("https://bucket.s3.amazonaws.com/a.tif")
+                .unwrap(),
+            "s3://bucket/a.tif"
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Infer the schema of items' properties.
+//!
+//! A [Schema] describes the properties of many items: each property's
+//! types, whether it's ever null or missing, how many items have it, and how
+//! many distinct values it has. It can be turned into
+//! [queryables](https://github.com/stac-api-extensions/filter#queryables):
+//!
+//! ```
+//! use stac::{Item, ItemCollection};
+//!
+//! let mut item = Item::new("a");
+//! item.properties.additional_fields.insert("eo:cloud_cover".into(), 42.into());
+//! let item_collection = ItemCollection::from(vec![item, Item::new("b")]);
+//! let schema = item_collection.infer_schema();
+//! let property = &schema.properties["eo:cloud_cover"];
+//! assert!(property.nullable);
+//! assert_eq!(property.count, 1);
+//! let queryables = schema.to_queryables();
+//! assert_eq!(queryables["properties"]["eo:cloud_cover"]["type"], "integer");
+//! ```
+
+use crate::Item;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+};
+
+/// The maximum number of distinct values counted for each property.
+///
+/// Once a property has this many distinct values, the rest aren't counted
+/// and its [Property::cardinality] is a lower bound.
+pub const MAX_DISTINCT_VALUES: usize = 1_000;
+
+/// The type of a property's value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PropertyType {
+    /// `true` or `false`.
+    Boolean,
+
+    /// A number without a fractional part.
+    Integer,
+
+    /// Any other number.
+    Number,
+
+    /// A string that's an RFC 3339 datetime.
+    Datetime,
+
+    /// Any other string.
+    String,
+
+    /// An array.
+    Array,
+
+    /// An object.
+    Object,
+}
+
+/// The inferred schema of items' properties.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    /// The number of items the schema was inferred from.
+    pub items: usize,
+
+    /// The properties, by name.
+    pub properties: BTreeMap<String, Property>,
+}
+
+/// The inferred schema of a single property.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Property {
+    /// The types of this property's (non-null) values.
+    pub types: BTreeSet<PropertyType>,
+
+    /// Whether this property is null or missing in at least one item.
+    pub nullable: bool,
+
+    /// The number of items with a non-null value for this property.
+    pub count: usize,
+
+    /// The number of distinct non-null values of this property.
+    ///
+    /// At most [MAX_DISTINCT_VALUES] values are counted, so if
+    /// [Property::cardinality_is_lower_bound] is true there may be more.
+    pub cardinality: usize,
+
+    /// Whether this property had more distinct values than were counted.
+    #[serde(default)]
+    pub cardinality_is_lower_bound: bool,
+}
+
+impl Schema {
+    /// Infers a schema from items' properties.
+    ///
+    /// Distinct values are only kept until a property has
+    /// [MAX_DISTINCT_VALUES] of them, so memory use doesn't grow with the
+    /// number of items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{schema::Schema, Item};
+    ///
+    /// let schema = Schema::infer(&[Item::new("an-id")]);
+    /// assert_eq!(schema.items, 1);
+    /// assert!(schema.properties.contains_key("datetime"));
+    /// ```
+    pub fn infer(items: impl IntoIterator<Item = impl Borrow<Item>>) -> Schema {
+        let mut count = 0;
+        let mut properties: BTreeMap<String, Property> = BTreeMap::new();
+        let mut values: HashMap<String, HashSet<String>> = HashMap::new();
+        for item in items {
+            count += 1;
+            let Ok(Value::Object(map)) = serde_json::to_value(&item.borrow().properties) else {
+                continue;
+            };
+            for (key, value) in map {
+                let property = properties.entry(key.clone()).or_default();
+                if let Some(r#type) = PropertyType::of(&value) {
+                    let _ = property.types.insert(r#type);
+                    property.count += 1;
+                    let values = values.entry(key).or_default();
+                    if values.len() < MAX_DISTINCT_VALUES {
+                        let _ = values.insert(value.to_string());
+                    } else if !property.cardinality_is_lower_bound
+                        && !values.contains(&value.to_string())
+                    {
+                        property.cardinality_is_lower_bound = true;
+                    }
+                } else {
+                    property.nullable = true;
+                }
+            }
+        }
+        for (key, property) in &mut properties {
+            // Properties that are missing from some items are nullable, too
+            property.nullable |= property.count < count;
+            property.cardinality = values.get(key).map(HashSet::len).unwrap_or_default();
+        }
+        Schema {
+            items: count,
+            properties,
+        }
+    }
+
+    /// Returns a queryables JSON schema for these properties.
+    ///
+    /// Datetime properties are strings with a `date-time` format, and
+    /// properties with both integer and non-integer values are numbers. The
+    /// item `id`, `collection`, and `geometry` are always queryable, and
+    /// well-known properties, e.g. `datetime` and `eo:cloud_cover`, also get a
+    /// title and their constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{schema::Schema, Item};
+    ///
+    /// let schema = Schema::infer(&[Item::new("an-id")]);
+    /// let queryables = schema.to_queryables();
+    /// assert_eq!(queryables["properties"]["datetime"]["format"], "date-time");
+    /// ```
+    pub fn to_queryables(&self) -> Value {
+        let mut properties = Map::new();
+        for key in ["id", "collection", "geometry"] {
+            let _ = properties.insert(key.to_string(), well_known(key));
+        }
+        for (key, property) in &self.properties {
+            let mut schema = well_known(key);
+            if let (Value::Object(schema), Value::Object(types)) =
+                (&mut schema, property.to_json_schema())
+            {
+                schema.extend(types);
+            }
+            let _ = properties.insert(key.clone(), schema);
+        }
+        json!({
+            "$schema": "https://json-schema.org/draft/2019-09/schema",
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": true,
+        })
+    }
+}
+
+impl Property {
+    fn to_json_schema(&self) -> Value {
+        let mut types = BTreeSet::new();
+        let mut is_datetime = false;
+        for r#type in &self.types {
+            let _ = types.insert(match r#type {
+                PropertyType::Boolean => "boolean",
+                PropertyType::Integer if self.types.contains(&PropertyType::Number) => "number",
+                PropertyType::Integer => "integer",
+                PropertyType::Number => "number",
+                PropertyType::Datetime => {
+                    is_datetime = true;
+                    "string"
+                }
+                PropertyType::String => "string",
+                PropertyType::Array => "array",
+                PropertyType::Object => "object",
+            });
+        }
+        let mut schema = Map::new();
+        match types.len() {
+            0 => {}
+            1 => {
+                let _ = schema.insert("type".into(), types.pop_first().unwrap_or_default().into());
+            }
+            _ => {
+                let _ = schema.insert("type".into(), types.into_iter().collect::<Vec<_>>().into());
+            }
+        }
+        if is_datetime && self.types.len() == 1 {
+            let _ = schema.insert("format".into(), "date-time".into());
+        }
+        Value::Object(schema)
+    }
+}
+
+/// Returns the title and constraints of a well-known queryable.
+fn well_known(name: &str) -> Value {
+    match name {
+        "id" => json!({"title": "Item ID", "description": "Item identifier", "type": "string"}),
+        "collection" => json!({
+            "title": "Collection ID",
+            "description": "ID of the collection the item belongs to",
+            "type": "string",
+        }),
+        "geometry" => json!({
+            "title": "Item Geometry",
+            "description": "Geometry of the item",
+            "$ref": "https://geojson.org/schema/Geometry.json",
+        }),
+        "datetime" => json!({"title": "Acquired", "description": "Datetime"}),
+        "start_datetime" => json!({"title": "Start datetime"}),
+        "end_datetime" => json!({"title": "End datetime"}),
+        "created" => json!({"title": "Created"}),
+        "updated" => json!({"title": "Updated"}),
+        "platform" => json!({"title": "Platform"}),
+        "constellation" => json!({"title": "Constellation"}),
+        "instruments" => json!({"title": "Instruments"}),
+        "gsd" => json!({"title": "Ground sample distance", "exclusiveMinimum": 0}),
+        "eo:cloud_cover" => json!({"title": "Cloud cover", "minimum": 0, "maximum": 100}),
+        "eo:snow_cover" => json!({"title": "Snow cover", "minimum": 0, "maximum": 100}),
+        "view:off_nadir" => json!({"title": "Off-nadir angle", "minimum": 0, "maximum": 90}),
+        "view:sun_elevation" => json!({"title": "Sun elevation", "minimum": -90, "maximum": 90}),
+        "view:sun_azimuth" => json!({"title": "Sun azimuth", "minimum": 0, "maximum": 360}),
+        _ => json!({}),
+    }
+}
+
+impl PropertyType {
+    /// Returns the type of a value, or `None` if it's null.
+    fn of(value: &Value) -> Option<PropertyType> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(PropertyType::Boolean),
+            Value::Number(number) if number.is_f64() => Some(PropertyType::Number),
+            Value::Number(_) => Some(PropertyType::Integer),
+            Value::String(s) if DateTime::parse_from_rfc3339(s).is_ok() => {
+                Some(PropertyType::Datetime)
+            }
+            Value::String(_) => Some(PropertyType::String),
+            Value::Array(_) => Some(PropertyType::Array),
+            Value::Object(_) => Some(PropertyType::Object),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropertyType, Schema};
+    use crate::Item;
+    use serde_json::json;
+
+    fn item(id: &str, properties: serde_json::Value) -> Item {
+        let mut item = Item::new(id);
+        let serde_json::Value::Object(properties) = properties else {
+            panic!("properties should be an object");
+        };
+        item.properties.additional_fields = properties;
+        item
+    }
+
+    #[test]
+    fn infer() {
+        let items = [
+            item("a", json!({"platform": "landsat-8", "eo:cloud_cover": 1})),
+            item("b", json!({"platform": "landsat-8", "eo:cloud_cover": 2.5})),
+            item("c", json!({"platform": null, "gsd": 30})),
+        ];
+        let schema = Schema::infer(&items);
+        assert_eq!(schema.items, 3);
+
+        let platform = &schema.properties["platform"];
+        assert_eq!(
+            platform.types.iter().collect::<Vec<_>>(),
+            [&PropertyType::String]
+        );
+        assert!(platform.nullable);
+        assert_eq!(platform.count, 2);
+        assert_eq!(platform.cardinality, 1);
+
+        let cloud_cover = &schema.properties["eo:cloud_cover"];
+        assert_eq!(cloud_cover.types.len(), 2);
+        assert!(cloud_cover.nullable);
+        assert_eq!(cloud_cover.cardinality, 2);
+
+        let gsd = &schema.properties["gsd"];
+        assert!(gsd.nullable);
+        assert_eq!(gsd.count, 1);
+
+        let datetime = &schema.properties["datetime"];
+        assert_eq!(
+            datetime.types.iter().collect::<Vec<_>>(),
+            [&PropertyType::Datetime]
+        );
+        assert!(!datetime.nullable);
+        assert_eq!(datetime.count, 3);
+    }
+
+    #[test]
+    fn infer_max_distinct_values() {
+        let items: Vec<_> = (0..=super::MAX_DISTINCT_VALUES)
+            .map(|i| item(&i.to_string(), json!({"index": i, "constant": 1})))
+            .collect();
+        let schema = Schema::infer(&items);
+        let index = &schema.properties["index"];
+        assert_eq!(index.cardinality, super::MAX_DISTINCT_VALUES);
+        assert!(index.cardinality_is_lower_bound);
+        let constant = &schema.properties["constant"];
+        assert_eq!(constant.cardinality, 1);
+        assert!(!constant.cardinality_is_lower_bound);
+    }
+
+    #[test]
+    fn queryables() {
+        let items = [
+            item("a", json!({"eo:cloud_cover": 1, "mixed": "a"})),
+            item("b", json!({"eo:cloud_cover": 2.5, "mixed": true})),
+        ];
+        let queryables = Schema::infer(&items).to_queryables();
+        assert_eq!(
+            queryables["properties"]["eo:cloud_cover"],
+            json!({"title": "Cloud cover", "minimum": 0, "maximum": 100, "type": "number"})
+        );
+        assert_eq!(
+            queryables["properties"]["mixed"],
+            json!({"type": ["boolean", "string"]})
+        );
+        assert_eq!(queryables["properties"]["id"]["type"], "string");
+        assert_eq!(
+            queryables["properties"]["geometry"]["$ref"],
+            "https://geojson.org/schema/Geometry.json"
+        );
+    }
+}
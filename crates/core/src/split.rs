@@ -0,0 +1,300 @@
+//! Split items into multiple item collections.
+//!
+//! Some downstream systems limit how many items can be in a single file, or
+//! want one file per collection or per time period. A [Splitter] partitions
+//! items as they arrive, so count-based splits of a stream only hold one chunk
+//! in memory at a time.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{Item, split::{self, SplitBy}};
+//!
+//! let items: Vec<_> = (0..5).map(|i| Item::new(format!("item-{}", i))).collect();
+//! let chunks = split::split(items, SplitBy::Count(2)).unwrap();
+//! let keys: Vec<_> = chunks.iter().map(|(key, _)| key.as_str()).collect();
+//! assert_eq!(keys, ["0", "1", "2"]);
+//! assert_eq!(chunks[2].1.items.len(), 1);
+//! ```
+
+use crate::{Error, Item, ItemCollection, Result};
+use chrono::{DateTime, Utc};
+use std::{collections::HashMap, str::FromStr};
+
+/// The key used for items that don't have the value being split on, e.g. an
+/// item without a collection when splitting by collection.
+///
+/// It's empty so it can't collide with a real key: collection ids must be
+/// non-empty, and the other keys are numbers or dates.
+pub const NONE_KEY: &str = "";
+
+/// How to split items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitBy {
+    /// Split into chunks of at most this many items, keyed by chunk number.
+    ///
+    /// The count must be at least one.
+    Count(usize),
+
+    /// Split by the item's `collection`.
+    Collection,
+
+    /// Split by the year of the item's datetime, e.g. `2023`.
+    Year,
+
+    /// Split by the month of the item's datetime, e.g. `2023-01`.
+    Month,
+
+    /// Split by the day of the item's datetime, e.g. `2023-01-15`.
+    Day,
+}
+
+/// Splits items as they arrive.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, split::{SplitBy, Splitter}};
+///
+/// let mut splitter = Splitter::new(SplitBy::Count(2)).unwrap();
+/// assert!(splitter.push(Item::new("a")).is_none());
+/// let (key, item_collection) = splitter.push(Item::new("b")).unwrap();
+/// assert_eq!(key, "0");
+/// assert_eq!(item_collection.items.len(), 2);
+/// assert!(splitter.push(Item::new("c")).is_none());
+/// let rest = splitter.finish();
+/// assert_eq!(rest[0].0, "1");
+/// ```
+#[derive(Debug)]
+pub struct Splitter {
+    by: SplitBy,
+    count: usize,
+    groups: Vec<(String, Vec<Item>)>,
+    indices: HashMap<String, usize>,
+}
+
+impl SplitBy {
+    /// Returns the key for an item.
+    ///
+    /// `index` is the position of the item in the input, and is only used for
+    /// [SplitBy::Count].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, split::SplitBy};
+    ///
+    /// let item = Item::new("an-id").collection("a-collection");
+    /// assert_eq!(SplitBy::Collection.key(0, &item), "a-collection");
+    /// assert_eq!(SplitBy::Count(10).key(25, &item), "2");
+    /// ```
+    pub fn key(&self, index: usize, item: &Item) -> String {
+        match self {
+            SplitBy::Count(count) => (index / (*count).max(1)).to_string(),
+            SplitBy::Collection => item
+                .collection
+                .clone()
+                .unwrap_or_else(|| NONE_KEY.to_string()),
+            SplitBy::Year => format_datetime(item, "%Y"),
+            SplitBy::Month => format_datetime(item, "%Y-%m"),
+            SplitBy::Day => format_datetime(item, "%Y-%m-%d"),
+        }
+    }
+}
+
+impl Splitter {
+    /// Creates a new splitter.
+    ///
+    /// Returns an error for [SplitBy::Count] with a count of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::split::{SplitBy, Splitter};
+    /// let splitter = Splitter::new(SplitBy::Collection).unwrap();
+    /// let _ = Splitter::new(SplitBy::Count(0)).unwrap_err();
+    /// ```
+    pub fn new(by: SplitBy) -> Result<Splitter> {
+        if by == SplitBy::Count(0) {
+            return Err(Error::InvalidSplit("0".to_string()));
+        }
+        Ok(Splitter {
+            by,
+            count: 0,
+            groups: Vec::new(),
+            indices: HashMap::new(),
+        })
+    }
+
+    /// Adds an item.
+    ///
+    /// For [SplitBy::Count], returns the chunk once it is full. Other splits
+    /// can't know when a group is complete, so they always return `None` and
+    /// all groups are returned by [Splitter::finish].
+    pub fn push(&mut self, item: Item) -> Option<(String, ItemCollection)> {
+        let key = self.by.key(self.count, &item);
+        self.count += 1;
+        let index = *self
+            .indices
+            .entry(key.clone())
+            .or_insert_with(|| self.groups.len());
+        if index == self.groups.len() {
+            self.groups.push((key, Vec::new()));
+        }
+        self.groups[index].1.push(item);
+        match self.by {
+            SplitBy::Count(count) if self.groups[index].1.len() >= count => {
+                let (key, items) = self.groups.remove(index);
+                let _ = self.indices.remove(&key);
+                Some((key, items.into()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns all remaining groups, in the order their keys were first seen.
+    pub fn finish(self) -> Vec<(String, ItemCollection)> {
+        self.groups
+            .into_iter()
+            .map(|(key, items)| (key, items.into()))
+            .collect()
+    }
+}
+
+impl FromStr for SplitBy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<SplitBy> {
+        match s {
+            "collection" => Ok(SplitBy::Collection),
+            "year" => Ok(SplitBy::Year),
+            "month" => Ok(SplitBy::Month),
+            "day" => Ok(SplitBy::Day),
+            _ => s
+                .parse()
+                .ok()
+                .filter(|&count| count > 0)
+                .map(SplitBy::Count)
+                .ok_or_else(|| Error::InvalidSplit(s.to_string())),
+        }
+    }
+}
+
+/// Splits items into item collections, in the order their keys were first seen.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, split::{self, SplitBy}};
+///
+/// let items = vec![
+///     Item::new("a").collection("one"),
+///     Item::new("b").collection("two"),
+///     Item::new("c").collection("one"),
+/// ];
+/// let chunks = split::split(items, SplitBy::Collection).unwrap();
+/// assert_eq!(chunks[0].0, "one");
+/// assert_eq!(chunks[0].1.items.len(), 2);
+/// ```
+pub fn split(
+    items: impl IntoIterator<Item = Item>,
+    by: SplitBy,
+) -> Result<Vec<(String, ItemCollection)>> {
+    let mut splitter = Splitter::new(by)?;
+    let mut chunks: Vec<_> = items
+        .into_iter()
+        .filter_map(|item| splitter.push(item))
+        .collect();
+    chunks.extend(splitter.finish());
+    Ok(chunks)
+}
+
+fn format_datetime(item: &Item, format: &str) -> String {
+    item.properties
+        .datetime
+        .or(item.properties.start_datetime)
+        .map(|datetime: DateTime<Utc>| datetime.format(format).to_string())
+        .unwrap_or_else(|| NONE_KEY.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SplitBy, Splitter, NONE_KEY};
+    use crate::Item;
+
+    #[test]
+    fn count() {
+        let items: Vec<_> = (0..5).map(|i| Item::new(i.to_string())).collect();
+        let chunks = super::split(items, SplitBy::Count(2)).unwrap();
+        let sizes: Vec<_> = chunks
+            .iter()
+            .map(|(key, item_collection)| (key.as_str(), item_collection.items.len()))
+            .collect();
+        assert_eq!(sizes, [("0", 2), ("1", 2), ("2", 1)]);
+    }
+
+    #[test]
+    fn period() {
+        let mut a = Item::new("a");
+        a.properties.datetime = Some("2023-01-15T00:00:00Z".parse().unwrap());
+        let mut b = Item::new("b");
+        b.properties.datetime = Some("2023-02-15T00:00:00Z".parse().unwrap());
+        let mut c = Item::new("c");
+        c.properties.datetime = None;
+        c.properties.start_datetime = Some("2023-01-01T00:00:00Z".parse().unwrap());
+        let mut d = Item::new("d");
+        d.properties.datetime = None;
+
+        let mut splitter = Splitter::new(SplitBy::Month).unwrap();
+        for item in [a.clone(), b.clone(), c.clone(), d.clone()] {
+            assert!(splitter.push(item).is_none());
+        }
+        let keys: Vec<_> = splitter
+            .finish()
+            .into_iter()
+            .map(|(key, item_collection)| (key, item_collection.items.len()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("2023-01".to_string(), 2),
+                ("2023-02".to_string(), 1),
+                (NONE_KEY.to_string(), 1)
+            ]
+        );
+        assert_eq!(SplitBy::Year.key(0, &a), "2023");
+        assert_eq!(SplitBy::Day.key(0, &a), "2023-01-15");
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "collection".parse::<SplitBy>().unwrap(),
+            SplitBy::Collection
+        );
+        assert_eq!("10".parse::<SplitBy>().unwrap(), SplitBy::Count(10));
+        let _ = "0".parse::<SplitBy>().unwrap_err();
+        let _ = "fortnight".parse::<SplitBy>().unwrap_err();
+    }
+
+    #[test]
+    fn count_zero() {
+        let _ = Splitter::new(SplitBy::Count(0)).unwrap_err();
+        let _ = super::split(vec![Item::new("a")], SplitBy::Count(0)).unwrap_err();
+    }
+
+    #[test]
+    fn collection_named_none() {
+        let items = vec![
+            Item::new("a").collection("none"),
+            Item::new("b"),
+            Item::new("c").collection("none"),
+        ];
+        let chunks = super::split(items, SplitBy::Collection).unwrap();
+        let keys: Vec<_> = chunks
+            .iter()
+            .map(|(key, item_collection)| (key.as_str(), item_collection.items.len()))
+            .collect();
+        assert_eq!(keys, [("none", 2), (NONE_KEY, 1)]);
+    }
+}
@@ -0,0 +1,420 @@
+//! Deterministic generation of synthetic STAC data.
+//!
+//! Synthetic data is useful for benchmarks, fuzzing, and load testing servers.
+//! The same seed and configuration always produce the same items.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::testing::{Distribution, Generator, GeometryType};
+//!
+//! let generator = Generator::new(42)
+//!     .geometry_type(GeometryType::Polygon)
+//!     .property("eo:cloud_cover", Distribution::Uniform { min: 0.0, max: 100.0 });
+//! let items: Vec<_> = generator.items(10).collect();
+//! assert_eq!(items.len(), 10);
+//! assert_eq!(items[3], generator.item(3));
+//! ```
+
+use crate::{Bbox, Collection, Geometry, Item, ItemCollection};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+const DEFAULT_COLLECTION: &str = "synthetic";
+
+/// A deterministic generator of synthetic items and collections.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    seed: u64,
+    collection: String,
+    geometry_type: GeometryType,
+    extensions: Vec<String>,
+    properties: Vec<(String, Distribution)>,
+    bbox: Bbox,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+/// The type of geometry to generate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GeometryType {
+    /// A single point.
+    #[default]
+    Point,
+
+    /// A rectangular polygon up to one degree on a side.
+    Polygon,
+}
+
+/// How to generate values for a property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// A floating point number uniformly distributed in `[min, max)`.
+    Uniform {
+        /// The minimum value, inclusive.
+        min: f64,
+
+        /// The maximum value, exclusive.
+        max: f64,
+    },
+
+    /// An integer uniformly distributed in `[min, max]`.
+    Integer {
+        /// The minimum value, inclusive.
+        min: i64,
+
+        /// The maximum value, inclusive.
+        max: i64,
+    },
+
+    /// One of the values, chosen with equal probability.
+    Choice(Vec<Value>),
+
+    /// Always this value.
+    Constant(Value),
+}
+
+/// A small, fast, seedable pseudo-random number generator (splitmix64).
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Generator {
+    /// Creates a new generator with the given seed.
+    ///
+    /// By default, items are points anywhere in the world with datetimes from
+    /// 2020 through 2024, and belong to a collection named `synthetic`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let generator = Generator::new(42);
+    /// ```
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            seed,
+            collection: DEFAULT_COLLECTION.to_string(),
+            geometry_type: GeometryType::Point,
+            extensions: Vec::new(),
+            properties: Vec::new(),
+            bbox: Bbox::new(-180.0, -90.0, 180.0, 90.0),
+            start: DateTime::from_timestamp(1_577_836_800, 0).expect("valid timestamp"),
+            end: DateTime::from_timestamp(1_735_689_600, 0).expect("valid timestamp"),
+        }
+    }
+
+    /// Sets the id of the collection that generated items belong to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let item = Generator::new(42).collection_id("a-collection").item(0);
+    /// assert_eq!(item.collection.unwrap(), "a-collection");
+    /// ```
+    pub fn collection_id(mut self, id: impl ToString) -> Generator {
+        self.collection = id.to_string();
+        self
+    }
+
+    /// Sets the type of geometry to generate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::{Generator, GeometryType};
+    /// let generator = Generator::new(42).geometry_type(GeometryType::Polygon);
+    /// ```
+    pub fn geometry_type(mut self, geometry_type: GeometryType) -> Generator {
+        self.geometry_type = geometry_type;
+        self
+    }
+
+    /// Adds an extension schema url to every generated item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let item = Generator::new(42)
+    ///     .extension("https://stac-extensions.github.io/eo/v1.1.0/schema.json")
+    ///     .item(0);
+    /// assert_eq!(item.extensions.len(), 1);
+    /// ```
+    pub fn extension(mut self, extension: impl ToString) -> Generator {
+        self.extensions.push(extension.to_string());
+        self
+    }
+
+    /// Adds a property, with values drawn from the distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::{Distribution, Generator};
+    /// let item = Generator::new(42)
+    ///     .property("eo:cloud_cover", Distribution::Uniform { min: 0.0, max: 100.0 })
+    ///     .item(0);
+    /// assert!(item.properties.additional_fields.contains_key("eo:cloud_cover"));
+    /// ```
+    pub fn property(mut self, name: impl ToString, distribution: Distribution) -> Generator {
+        self.properties.push((name.to_string(), distribution));
+        self
+    }
+
+    /// Sets the region that generated geometries fall within.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{testing::Generator, Bbox};
+    /// let item = Generator::new(42)
+    ///     .bbox(Bbox::new(-106.0, 40.0, -105.0, 41.0))
+    ///     .item(0);
+    /// let bbox = item.bbox.unwrap();
+    /// assert!(bbox.xmin() >= -106.0 && bbox.xmax() <= -105.0);
+    /// ```
+    pub fn bbox(mut self, bbox: Bbox) -> Generator {
+        self.bbox = bbox;
+        self
+    }
+
+    /// Sets the interval that generated datetimes fall within.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let start = "2024-01-01T00:00:00Z".parse().unwrap();
+    /// let end = "2024-02-01T00:00:00Z".parse().unwrap();
+    /// let item = Generator::new(42).interval(start, end).item(0);
+    /// let datetime = item.properties.datetime.unwrap();
+    /// assert!(datetime >= start && datetime < end);
+    /// ```
+    pub fn interval(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Generator {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Generates the item at the given index.
+    ///
+    /// Items only depend on the generator's configuration and the index, so
+    /// any item can be regenerated without generating the ones before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let generator = Generator::new(42);
+    /// assert_eq!(generator.item(7), generator.item(7));
+    /// assert_ne!(generator.item(7), generator.item(8));
+    /// ```
+    pub fn item(&self, index: usize) -> Item {
+        let mut rng = Rng::new(self.seed, index as u64);
+        let mut item =
+            Item::new(format!("{}-{}", self.collection, index)).collection(&self.collection);
+        item.extensions = self.extensions.clone();
+        let (geometry, bbox) = self.geometry(&mut rng);
+        item.geometry = Some(geometry);
+        item.bbox = Some(bbox);
+        let span = (self.end - self.start).num_milliseconds().max(0) as f64;
+        let offset = Duration::milliseconds((rng.next_f64() * span) as i64);
+        item.properties.datetime = Some(self.start + offset);
+        for (name, distribution) in &self.properties {
+            let _ = item
+                .properties
+                .additional_fields
+                .insert(name.clone(), distribution.sample(&mut rng));
+        }
+        item
+    }
+
+    /// Returns an iterator over the first `count` items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let generator = Generator::new(42);
+    /// assert_eq!(generator.items(100).count(), 100);
+    /// ```
+    pub fn items(&self, count: usize) -> impl Iterator<Item = Item> + '_ {
+        (0..count).map(|index| self.item(index))
+    }
+
+    /// Generates an item collection with `count` items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let item_collection = Generator::new(42).item_collection(10);
+    /// assert_eq!(item_collection.items.len(), 10);
+    /// ```
+    pub fn item_collection(&self, count: usize) -> ItemCollection {
+        self.items(count).collect()
+    }
+
+    /// Generates a collection and its `count` items.
+    ///
+    /// The collection's extents cover all of its items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    /// let (collection, items) = Generator::new(42).collection_and_items(10);
+    /// assert_eq!(collection.id, "synthetic");
+    /// assert_eq!(items.len(), 10);
+    /// ```
+    pub fn collection_and_items(&self, count: usize) -> (Collection, Vec<Item>) {
+        let items: Vec<_> = self.items(count).collect();
+        let collection = Collection::from_id_and_items(&self.collection, &items);
+        (collection, items)
+    }
+
+    fn geometry(&self, rng: &mut Rng) -> (Geometry, Bbox) {
+        let (xmin, ymin) = (self.bbox.xmin(), self.bbox.ymin());
+        let (xmax, ymax) = (self.bbox.xmax(), self.bbox.ymax());
+        match self.geometry_type {
+            GeometryType::Point => {
+                let x = rng.range(xmin, xmax);
+                let y = rng.range(ymin, ymax);
+                let geometry = Geometry::new(geojson::Value::Point(vec![x, y]));
+                (geometry, Bbox::new(x, y, x, y))
+            }
+            GeometryType::Polygon => {
+                let width = rng.range(0.0, 1.0).min(xmax - xmin);
+                let height = rng.range(0.0, 1.0).min(ymax - ymin);
+                let x0 = rng.range(xmin, xmax - width);
+                let y0 = rng.range(ymin, ymax - height);
+                let (x1, y1) = (x0 + width, y0 + height);
+                let geometry = Geometry::new(geojson::Value::Polygon(vec![vec![
+                    vec![x0, y0],
+                    vec![x1, y0],
+                    vec![x1, y1],
+                    vec![x0, y1],
+                    vec![x0, y0],
+                ]]));
+                (geometry, Bbox::new(x0, y0, x1, y1))
+            }
+        }
+    }
+}
+
+impl Default for Generator {
+    fn default() -> Generator {
+        Generator::new(0)
+    }
+}
+
+impl Distribution {
+    fn sample(&self, rng: &mut Rng) -> Value {
+        match self {
+            Distribution::Uniform { min, max } => rng.range(*min, *max).into(),
+            Distribution::Integer { min, max } => {
+                let span = max.abs_diff(*min).wrapping_add(1);
+                let offset = if span == 0 {
+                    rng.next_u64()
+                } else {
+                    rng.next_u64() % span
+                };
+                min.wrapping_add_unsigned(offset).into()
+            }
+            Distribution::Choice(values) => {
+                if values.is_empty() {
+                    Value::Null
+                } else {
+                    values[(rng.next_u64() % values.len() as u64) as usize].clone()
+                }
+            }
+            Distribution::Constant(value) => value.clone(),
+        }
+    }
+}
+
+impl Rng {
+    fn new(seed: u64, index: u64) -> Rng {
+        let mut rng = Rng(seed);
+        Rng(rng.next_u64() ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + self.next_f64() * (max - min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Distribution, Generator, GeometryType};
+    use serde_json::json;
+
+    #[test]
+    fn deterministic() {
+        let a: Vec<_> = Generator::new(1).items(10).collect();
+        let b: Vec<_> = Generator::new(1).items(10).collect();
+        let c: Vec<_> = Generator::new(2).items(10).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn polygon() {
+        let item = Generator::new(1)
+            .geometry_type(GeometryType::Polygon)
+            .item(0);
+        let bbox = item.bbox.unwrap();
+        assert!(bbox.xmin() < bbox.xmax());
+        assert!(bbox.ymin() < bbox.ymax());
+        assert!(matches!(
+            item.geometry.unwrap().value,
+            geojson::Value::Polygon(_)
+        ));
+    }
+
+    #[test]
+    fn distributions() {
+        let generator = Generator::new(1)
+            .property("cloud_cover", Distribution::Uniform { min: 0.0, max: 1.0 })
+            .property("count", Distribution::Integer { min: -2, max: 2 })
+            .property(
+                "platform",
+                Distribution::Choice(vec![json!("a"), json!("b")]),
+            )
+            .property("constellation", Distribution::Constant(json!("c")));
+        for item in generator.items(100) {
+            let properties = &item.properties.additional_fields;
+            let cloud_cover = properties["cloud_cover"].as_f64().unwrap();
+            assert!((0.0..1.0).contains(&cloud_cover));
+            let count = properties["count"].as_i64().unwrap();
+            assert!((-2..=2).contains(&count));
+            assert!(properties["platform"] == "a" || properties["platform"] == "b");
+            assert_eq!(properties["constellation"], "c");
+        }
+    }
+
+    #[test]
+    fn collection_and_items() {
+        let (collection, items) = Generator::new(1).collection_and_items(100);
+        assert_eq!(items.len(), 100);
+        let bbox = collection.extent.spatial.bbox[0];
+        for item in items {
+            let item_bbox = item.bbox.unwrap();
+            assert!(bbox.xmin() <= item_bbox.xmin());
+            assert!(bbox.xmax() >= item_bbox.xmax());
+        }
+    }
+}
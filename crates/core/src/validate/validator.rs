@@ -199,7 +199,10 @@ impl Validator {
     fn ensure_validator(&mut self, uri: &Uri<String>) -> Result<()> {
         if !self.validators.contains_key(uri) {
             let response = reqwest::blocking::get(uri.as_str())?.error_for_status()?;
-            let validator = self.validation_options.build(&response.json()?)?;
+            let validator = self
+                .validation_options
+                .build(&response.json()?)
+                .map_err(Box::new)?;
             let _ = self.validators.insert(uri.clone(), validator);
         }
         Ok(())
@@ -185,6 +185,44 @@ impl Value {
             ItemCollection(_) => "ItemCollection",
         }
     }
+
+    /// Rounds the geometry and bbox coordinates of an item, or of every item
+    /// in an item collection, to a number of decimal places.
+    ///
+    /// Catalogs and collections are left as they are. See [Item::round_coordinates].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut value: stac::Value = stac::read("examples/simple-item.json").unwrap();
+    /// value.round_coordinates(3);
+    /// ```
+    pub fn round_coordinates(&mut self, precision: u8) {
+        match self {
+            Value::Item(item) => item.round_coordinates(precision),
+            Value::ItemCollection(item_collection) => item_collection.round_coordinates(precision),
+            Value::Catalog(_) | Value::Collection(_) => {}
+        }
+    }
+
+    /// Simplifies the geometry of an item, or of every item in an item collection.
+    ///
+    /// Catalogs and collections are left as they are. See [Item::simplify].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut value: stac::Value = stac::read("examples/simple-item.json").unwrap();
+    /// value.simplify(0.001).unwrap();
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn simplify(&mut self, epsilon: f64) -> Result<()> {
+        match self {
+            Value::Item(item) => item.simplify(epsilon),
+            Value::ItemCollection(item_collection) => item_collection.simplify(epsilon),
+            Value::Catalog(_) | Value::Collection(_) => Ok(()),
+        }
+    }
 }
 
 impl SelfHref for Value {
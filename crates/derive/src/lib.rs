@@ -1,6 +1,8 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Expr, Field, LitStr, Token,
+};
 
 #[proc_macro_derive(SelfHref)]
 pub fn self_href_derive(input: TokenStream) -> TokenStream {
@@ -62,3 +64,139 @@ pub fn fields_derive(input: TokenStream) -> TokenStream {
     };
     TokenStream::from(expanded)
 }
+
+/// Derives `stac_extensions::Extension` for an extension's fields struct.
+///
+/// The `stac_extension` attribute sets the field prefix, and either the full
+/// schema `identifier` or the `version` (and optionally the `name`, which
+/// defaults to the prefix) of an extension hosted at
+/// <https://stac-extensions.github.io>. The struct also gets a `FIELDS`
+/// constant with its prefixed field names, honoring `#[serde(rename)]`.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, StacExtension)]
+/// #[stac_extension(prefix = "proj", name = "projection", version = "v2.0.0")]
+/// pub struct Projection { .. }
+/// ```
+#[proc_macro_derive(StacExtension, attributes(stac_extension))]
+pub fn stac_extension_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_stac_extension(input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn expand_stac_extension(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut identifier: Option<LitStr> = None;
+    let mut prefix: Option<LitStr> = None;
+    let mut name: Option<LitStr> = None;
+    let mut version: Option<LitStr> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("stac_extension"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let value = Some(meta.value()?.parse()?);
+            if meta.path.is_ident("identifier") {
+                identifier = value;
+            } else if meta.path.is_ident("prefix") {
+                prefix = value;
+            } else if meta.path.is_ident("name") {
+                name = value;
+            } else if meta.path.is_ident("version") {
+                version = value;
+            } else {
+                return Err(meta.error("expected identifier, prefix, name, or version"));
+            }
+            Ok(())
+        })?;
+    }
+    let span = input.ident.span();
+    let prefix = prefix
+        .ok_or_else(|| syn::Error::new(span, "missing #[stac_extension(prefix = \"...\")]"))?
+        .value();
+    let identifier = match (identifier, version) {
+        (Some(identifier), None) => identifier.value(),
+        (None, Some(version)) => format!(
+            "https://stac-extensions.github.io/{}/{}/schema.json",
+            name.map(|name| name.value()).unwrap_or_else(|| prefix.clone()),
+            version.value()
+        ),
+        (Some(identifier), Some(_)) => {
+            return Err(syn::Error::new(
+                identifier.span(),
+                "set either identifier or version, not both",
+            ))
+        }
+        (None, None) => {
+            return Err(syn::Error::new(
+                span,
+                "missing #[stac_extension(identifier = \"...\")] or #[stac_extension(version = \"...\")]",
+            ))
+        }
+    };
+    let fields = match &input.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .filter_map(|field| field_name(field).transpose())
+            .map(|name| name.map(|name| format!("{}:{}", prefix, name)))
+            .collect::<syn::Result<Vec<_>>>()?,
+        _ => return Err(syn::Error::new(span, "StacExtension requires a struct")),
+    };
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::stac_extensions::Extension for #ident #ty_generics #where_clause {
+            const IDENTIFIER: &'static str = #identifier;
+            const PREFIX: &'static str = #prefix;
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// This extension's field names, with their prefix.
+            pub const FIELDS: &'static [&'static str] = &[#(#fields),*];
+        }
+    })
+}
+
+/// Returns a field's serialized name, or `None` if it's flattened or skipped.
+fn field_name(field: &Field) -> syn::Result<Option<String>> {
+    let Some(ident) = &field.ident else {
+        return Ok(None);
+    };
+    let mut name = ident.to_string().trim_start_matches("r#").to_string();
+    let mut include = true;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") && meta.input.peek(Token![=]) {
+                let value: LitStr = meta.value()?.parse()?;
+                name = value.value();
+                Ok(())
+            } else {
+                if meta.path.is_ident("flatten") || meta.path.is_ident("skip") {
+                    include = false;
+                }
+                skip_meta(meta)
+            }
+        })?;
+    }
+    Ok(include.then_some(name))
+}
+
+/// Consumes a serde attribute argument that we don't care about.
+fn skip_meta(meta: ParseNestedMeta<'_>) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        let _: Expr = meta.value()?.parse()?;
+        Ok(())
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(skip_meta)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+//! Benchmarks searching stac-geoparquet with DuckDB.
+//!
+//! The `hilbert` group compares bbox searches of the same synthetic items from
+//! [stac::testing::Generator], written in datetime order and in Hilbert order.
+//! Set `STAC_BENCH_SYNTHETIC_ITEMS` to change the number of items (default
+//! 1,000,000).
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use stac::{
+    geoparquet::{Compactor, Order},
+    testing::Generator,
+    Bbox, Item,
+};
+use stac_api::Search;
+use stac_duckdb::Client;
+use std::{hint::black_box, path::Path};
+
+const SENTINEL_2_ITEMS: &str = "data/100-sentinel-2-items.parquet";
+
+fn search(c: &mut Criterion) {
+    let client = Client::new().unwrap();
+    let mut group = c.benchmark_group("search");
+    let _ = group.bench_function("everything", |b| {
+        b.iter(|| {
+            client
+                .search(SENTINEL_2_ITEMS, black_box(Search::default()))
+                .unwrap()
+        })
+    });
+    let _ = group.bench_function("bbox", |b| {
+        b.iter(|| {
+            client
+                .search(
+                    SENTINEL_2_ITEMS,
+                    black_box(Search::default().bbox(Bbox::new(-106.1, 40.5, -106.0, 40.6))),
+                )
+                .unwrap()
+        })
+    });
+    let _ = group.bench_function("to_json", |b| {
+        b.iter(|| {
+            client
+                .search_to_json(SENTINEL_2_ITEMS, black_box(Search::default()))
+                .unwrap()
+        })
+    });
+    let _ = group.bench_function("to_arrow", |b| {
+        b.iter(|| {
+            client
+                .search_to_arrow(SENTINEL_2_ITEMS, black_box(Search::default()))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+/// Scatters points over the globe, with datetimes that are unrelated to location.
+fn synthetic_items() -> Vec<Item> {
+    let count = std::env::var("STAC_BENCH_SYNTHETIC_ITEMS")
+        .ok()
+        .map(|count| count.parse().unwrap())
+        .unwrap_or(1_000_000);
+    Generator::new(42).items(count).collect()
+}
+
+fn write(items: Vec<Item>, order: Order, path: &Path) {
+    let mut buf = Vec::new();
+    stac::geoparquet::into_writer(&mut buf, items).unwrap();
+    let mut compactor = Compactor::new().row_group_size(1_000).order(order);
+    compactor.add_bytes(buf).unwrap();
+    let _ = compactor
+        .write(|_| std::fs::File::create(path).map_err(stac::Error::from))
+        .unwrap();
+}
+
+fn hilbert(c: &mut Criterion) {
+    let tempdir = tempfile::tempdir().unwrap();
+    let items = synthetic_items();
+    let datetime = tempdir.path().join("datetime.parquet");
+    let hilbert = tempdir.path().join("hilbert.parquet");
+    write(items.clone(), Order::Datetime, &datetime);
+    write(items, Order::Hilbert, &hilbert);
+    let client = Client::new().unwrap();
+    let search = Search::default().bbox(Bbox::new(-106.0, 39.0, -104.0, 41.0));
+    let mut group = c.benchmark_group("hilbert");
+    for (name, path) in [("datetime", &datetime), ("hilbert", &hilbert)] {
+        let path = path.to_str().unwrap();
+        let _ = group.bench_function(name, |b| {
+            b.iter(|| client.search(path, black_box(search.clone())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search, hilbert);
+criterion_main!(benches);
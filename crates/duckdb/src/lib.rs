@@ -15,9 +15,19 @@ use geoarrow::{
     table::Table,
 };
 use geojson::Geometry;
-use stac::{Collection, SpatialExtent, TemporalExtent};
+use stac::{
+    geoparquet::{Metadata, METADATA_KEY},
+    schema::{PropertyType, Schema},
+    Bbox, Collection, Extent, SpatialExtent, TemporalExtent,
+};
 use stac_api::{Direction, Search};
-use std::fmt::Debug;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display, Formatter},
+    ops::Deref,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
 use thiserror::Error;
 
 const DEFAULT_COLLECTION_DESCRIPTION: &str =
@@ -77,15 +87,92 @@ pub enum Error {
     /// [std::num::TryFromIntError]
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
+
+    /// This functionality is not yet implemented.
+    #[error("this functionality is not yet implemented: {0}")]
+    Unimplemented(&'static str),
+
+    /// The column does not exist in the stac-geoparquet file.
+    #[error("unknown column: {0}")]
+    UnknownColumn(String),
+
+    /// A sample percentage isn't between 1 and 100.
+    #[error("invalid sample percentage, expected 1 to 100: {0}")]
+    InvalidSample(u8),
+
+    /// There are no items in this collection.
+    #[error("no items in collection: {0}")]
+    UnknownCollection(String),
+
+    /// The page token could not be parsed.
+    #[error("invalid page token: {0}")]
+    InvalidPageToken(String),
+}
+
+impl Error {
+    /// Returns true if this error came from DuckDB reading or writing files,
+    /// e.g. because a file doesn't exist.
+    ///
+    /// DuckDB's own error type isn't exposed by the **duckdb** crate, so
+    /// failures without a specific error code are classified by the type that
+    /// DuckDB puts at the start of their message, e.g. `IO Error: ...`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = stac_duckdb::Error::UnknownColumn("foo".to_string());
+    /// assert!(!error.is_io());
+    /// ```
+    pub fn is_io(&self) -> bool {
+        use duckdb::ffi::ErrorCode;
+
+        match self {
+            Error::DuckDB(duckdb::Error::DuckDBFailure(error, message)) => match error.code {
+                ErrorCode::CannotOpen
+                | ErrorCode::DiskFull
+                | ErrorCode::NotFound
+                | ErrorCode::PermissionDenied
+                | ErrorCode::SystemIoFailure => true,
+                _ => message.as_deref().is_some_and(|message| {
+                    message.starts_with("IO Error:") || message.starts_with("HTTP Error:")
+                }),
+            },
+            Error::DuckDB(duckdb::Error::InvalidPath(_)) => true,
+            _ => false,
+        }
+    }
 }
 
 /// A crate-specific result type.
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// A client for making DuckDB requests for STAC objects.
+///
+/// A client keeps a pool of connections to its database, so it can be shared
+/// between threads and used for concurrent searches.
 #[derive(Debug)]
 pub struct Client {
-    connection: Connection,
+    connection: Mutex<Connection>,
+    pool: Mutex<Vec<Connection>>,
+}
+
+/// A connection checked out from a client's pool.
+///
+/// The connection is returned to the pool when this is dropped.
+#[derive(Debug)]
+struct PooledConnection<'a> {
+    connection: Option<Connection>,
+    pool: &'a Mutex<Vec<Connection>>,
+}
+
+/// Configuration for a [Client].
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// The path to a persistent DuckDB database file, e.g. `stac.duckdb`.
+    ///
+    /// The file is created if it doesn't exist. If `None`, the client uses an
+    /// in-memory database.
+    pub path: Option<PathBuf>,
 }
 
 /// A SQL query.
@@ -98,6 +185,38 @@ pub struct Query {
     pub params: Vec<Value>,
 }
 
+/// A filter on items' assets, for [Client::search_assets].
+///
+/// # Examples
+///
+/// ```
+/// use stac_duckdb::AssetFilter;
+///
+/// let filter = AssetFilter::HrefLike("s3://an-old-bucket/%".to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetFilter {
+    /// Items that have an asset with this key.
+    Key(String),
+
+    /// Items that have at least one asset whose href matches this SQL `LIKE`
+    /// pattern, e.g. `s3://an-old-bucket/%`.
+    HrefLike(String),
+}
+
+/// The SQL for a search and DuckDB's plan for it, from [Client::explain].
+///
+/// The [Display] implementation prints the SQL, its parameters, and the
+/// plan, for debugging slow or surprising searches.
+#[derive(Debug)]
+pub struct Explanation {
+    /// The query that would be run for the search.
+    pub query: Query,
+
+    /// The output of DuckDB's `EXPLAIN`.
+    pub plan: String,
+}
+
 impl Client {
     /// Creates a new client with no data sources.
     ///
@@ -109,81 +228,169 @@ impl Client {
     /// let client = Client::new().unwrap();
     /// ```
     pub fn new() -> Result<Client> {
-        let connection = Connection::open_in_memory()?;
+        Client::with_config(Config::default())
+    }
+
+    /// Creates a new client with the given configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_duckdb::{Client, Config};
+    ///
+    /// let config = Config {
+    ///     path: Some("stac.duckdb".into()),
+    /// };
+    /// let client = Client::with_config(config).unwrap();
+    /// ```
+    pub fn with_config(config: Config) -> Result<Client> {
+        let connection = if let Some(path) = config.path {
+            Connection::open(path)?
+        } else {
+            Connection::open_in_memory()?
+        };
         connection.execute("INSTALL spatial", [])?;
         connection.execute("LOAD spatial", [])?;
         connection.execute("INSTALL icu", [])?;
         connection.execute("LOAD icu", [])?;
-        Ok(Client { connection })
+        Ok(Client {
+            connection: Mutex::new(connection),
+            pool: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Creates a view of a stac-geoparquet file.
+    ///
+    /// The view can be searched by passing its name instead of an href. The
+    /// view still reads the file on every search, but it persists in the
+    /// client's database (see [Config::path]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// client.create_view("items", "data/100-sentinel-2-items.parquet").unwrap();
+    /// let item_collection = client.search("items", Search::default()).unwrap();
+    /// assert_eq!(item_collection.items.len(), 100);
+    /// ```
+    pub fn create_view(&self, name: &str, href: &str) -> Result<()> {
+        let connection = self.connection()?;
+        let _ = connection.execute(
+            &format!(
+                "CREATE OR REPLACE VIEW {} AS SELECT * FROM {}",
+                quote_identifier(name),
+                read_parquet(href)
+            ),
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Copies a stac-geoparquet file into a table.
+    ///
+    /// The table can be searched by passing its name instead of an href.
+    /// Searches on the table don't re-read the file, which is useful if the
+    /// file is remote and the client's database is persistent (see
+    /// [Config::path]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// client
+    ///     .create_table_from_href("items", "data/100-sentinel-2-items.parquet")
+    ///     .unwrap();
+    /// let item_collection = client.search("items", Search::default()).unwrap();
+    /// assert_eq!(item_collection.items.len(), 100);
+    /// ```
+    pub fn create_table_from_href(&self, name: &str, href: &str) -> Result<()> {
+        let connection = self.connection()?;
+        let _ = connection.execute(
+            &format!(
+                "CREATE OR REPLACE TABLE {} AS SELECT * FROM {}",
+                quote_identifier(name),
+                read_parquet(href)
+            ),
+            [],
+        )?;
+        Ok(())
     }
 
     /// Returns one or more [stac::Collection] from the items in the stac-geoparquet file.
+    ///
+    /// If the file has collections embedded in its stac-geoparquet metadata,
+    /// those are returned. Otherwise, collections are generated from the
+    /// items' extents.
+    ///
+    /// The href can also be the name of a view or table (see
+    /// [Client::create_view] and [Client::create_table_from_href]).
     pub fn collections(&self, href: &str) -> Result<Vec<Collection>> {
-        let start_datetime= if self.connection.prepare(&format!(
-            "SELECT column_name FROM (DESCRIBE SELECT * from read_parquet('{}')) where column_name = 'start_datetime'",
-            href
-        ))?.query([])?.next()?.is_some() {
-            "strftime(min(coalesce(start_datetime, datetime)), '%xT%X%z')"
-        } else {
-            "strftime(min(datetime), '%xT%X%z')"
-        };
-        let end_datetime= if self.connection.prepare(&format!(
-            "SELECT column_name FROM (DESCRIBE SELECT * from read_parquet('{}')) where column_name = 'end_datetime'",
-            href
-        ))?.query([])?.next()?.is_some() {
-            "strftime(max(coalesce(end_datetime, datetime)), '%xT%X%z')"
-        } else {
-            "strftime(max(datetime), '%xT%X%z')"
-        };
-        let mut statement = self.connection.prepare(&format!(
-            "SELECT DISTINCT collection FROM read_parquet('{}')",
-            href
-        ))?;
+        let connection = self.connection()?;
+        let source = source(&connection, href)?;
+        if source == read_parquet(href) {
+            if let Some(collections) = embedded_collections(&connection, href)? {
+                return Ok(collections);
+            }
+        }
+        let mut statement =
+            connection.prepare(&format!("SELECT DISTINCT collection FROM {}", source))?;
         let mut collections = Vec::new();
         for row in statement.query_map([], |row| row.get::<_, String>(0))? {
             let collection_id = row?;
-            let mut statement = self.connection.prepare(&
-                format!("SELECT ST_AsGeoJSON(ST_Extent_Agg(geometry)), {}, {} FROM read_parquet('{}') WHERE collection = $1", start_datetime, end_datetime,
-                href
-            ))?;
-            let row = statement.query_row([&collection_id], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                ))
-            })?;
-            let mut collection = Collection::new(collection_id, DEFAULT_COLLECTION_DESCRIPTION);
-            let geometry: geo::Geometry = Geometry::from_json_value(serde_json::from_str(&row.0)?)
-                .map_err(Box::new)?
-                .try_into()
-                .map_err(Box::new)?;
-            if let Some(bbox) = geometry.bounding_rect() {
-                collection.extent.spatial = SpatialExtent {
-                    bbox: vec![bbox.into()],
-                };
-            }
-            collection.extent.temporal = TemporalExtent {
-                interval: vec![[
-                    Some(DateTime::parse_from_str(&row.1, "%FT%T%#z")?.into()),
-                    Some(DateTime::parse_from_str(&row.2, "%FT%T%#z")?.into()),
-                ]],
-            };
+            let mut collection = Collection::new(&collection_id, DEFAULT_COLLECTION_DESCRIPTION);
+            collection.extent = extent(&connection, &source, &collection_id, None)?;
             collections.push(collection);
         }
         Ok(collections)
     }
 
+    /// Computes the extent of one collection's items.
+    ///
+    /// Use this to refresh a collection's metadata after adding items to a
+    /// file, without computing every collection's extent like
+    /// [Client::collections] does.
+    ///
+    /// For very large files, pass a `sample` percentage (1 to 100) to only
+    /// look at some of the collection's rows. A sampled extent is quicker to
+    /// compute, but might be smaller than the true extent. If the sample is
+    /// empty, e.g. for a small collection, every row is used.
+    ///
+    /// The href can also be the name of a view or table (see
+    /// [Client::create_view] and [Client::create_table_from_href]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let extent = client
+    ///     .extent("data/100-sentinel-2-items.parquet", "sentinel-2-l2a", None)
+    ///     .unwrap();
+    /// assert_eq!(extent.spatial.bbox.len(), 1);
+    /// ```
+    pub fn extent(&self, href: &str, collection_id: &str, sample: Option<u8>) -> Result<Extent> {
+        if let Some(sample) = sample.filter(|sample| !(1..=100).contains(sample)) {
+            return Err(Error::InvalidSample(sample));
+        }
+        let connection = self.connection()?;
+        let source = source(&connection, href)?;
+        extent(&connection, &source, collection_id, sample)
+    }
+
     /// Searches this client, returning a [stac::ItemCollection].
+    ///
+    /// The href can be a stac-geoparquet file or the name of a view or table
+    /// (see [Client::create_view] and [Client::create_table_from_href]).
     pub fn search(&self, href: &str, search: impl Into<Search>) -> Result<stac::ItemCollection> {
         let record_batches = self.search_to_arrow(href, search)?;
-        if record_batches.is_empty() {
-            return Ok(Vec::new().into());
-        }
-        let schema = record_batches[0].schema();
-        let table = Table::try_new(record_batches, schema)?;
-        let items = stac::geoarrow::from_table(table)?;
-        Ok(items)
+        to_item_collection(record_batches)
     }
 
     /// Searches this client, returning a [stac_api::ItemCollection].
@@ -195,172 +402,840 @@ impl Client {
         href: &str,
         search: impl Into<Search>,
     ) -> Result<stac_api::ItemCollection> {
+        let search = search.into();
+        let number_matched = self.count(href, search.clone())?;
         let record_batches = self.search_to_arrow(href, search)?;
-        if record_batches.is_empty() {
-            return Ok(Vec::new().into());
+        let mut item_collection = stac_api::ItemCollection::new(to_json(record_batches)?)?;
+        item_collection.number_matched = Some(number_matched);
+        Ok(item_collection)
+    }
+
+    /// Searches this client for one page of items.
+    ///
+    /// Pages are ordered by the search's `sortby`, then by collection and id,
+    /// and are `limit` items long. Pass `None` as the token to get the first
+    /// page. If there are more items, the returned item collection's `next`
+    /// has a `token` for the next page.
+    ///
+    /// Paging uses the sort keys of the previous page's last item (keyset
+    /// pagination) instead of an offset, so deep pages are as cheap as the
+    /// first one. The token holds those sort key values, so the next page
+    /// doesn't have to look the item up again. Items with null sort keys might
+    /// be skipped. The `id` and `collection` fields, and the `sortby` fields,
+    /// are always included in the returned items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let href = "data/100-sentinel-2-items.parquet";
+    /// let search = Search::default().limit(60);
+    /// let first = client.search_page(href, search.clone(), None).unwrap();
+    /// assert_eq!(first.items.len(), 60);
+    /// let token = first.next.unwrap()["token"].as_str().unwrap().to_string();
+    /// let second = client.search_page(href, search, Some(&token)).unwrap();
+    /// assert_eq!(second.items.len(), 40);
+    /// assert!(second.next.is_none());
+    /// ```
+    pub fn search_page(
+        &self,
+        href: &str,
+        search: impl Into<Search>,
+        token: Option<&str>,
+    ) -> Result<stac_api::ItemCollection> {
+        let mut search = search.into();
+        let token = token.map(PageToken::parse).transpose()?;
+        let limit = search.items.limit;
+        let sort_fields: Vec<String> = search
+            .items
+            .sortby
+            .iter()
+            .map(|sortby| sortby.field.clone())
+            .collect();
+        if let Some(fields) = search.items.fields.as_mut() {
+            for field in ["id", "collection"]
+                .into_iter()
+                .chain(sort_fields.iter().map(String::as_str))
+            {
+                fields.exclude.retain(|exclude| exclude != field);
+                if !fields.include.is_empty() && !fields.include.iter().any(|i| i == field) {
+                    fields.include.push(field.to_string());
+                }
+            }
+        }
+        let number_matched = self.count(href, search.clone())?;
+        let connection = self.connection()?;
+        let query = query(&connection, search, href, QueryKind::Page(token), None)?;
+        let mut items = to_json(execute(&connection, query)?)?;
+        let mut next = None;
+        if let Some(limit) = limit.and_then(|limit| usize::try_from(limit).ok()) {
+            if items.len() > limit {
+                items.truncate(limit);
+                next = items
+                    .last()
+                    .map(|item| PageToken::from_item(item, &sort_fields));
+            }
+        }
+        let mut item_collection = stac_api::ItemCollection::new(items)?;
+        item_collection.number_matched = Some(number_matched);
+        if let Some(next) = next {
+            let mut map = serde_json::Map::new();
+            let _ = map.insert("token".into(), next.to_string().into());
+            item_collection.next = Some(map);
         }
-        let schema = record_batches[0].schema();
-        let table = Table::try_new(record_batches, schema)?;
-        let items = stac::geoarrow::json::from_table(table)?;
-        let item_collection = stac_api::ItemCollection::new(items)?;
         Ok(item_collection)
     }
 
-    /// Searches this client, returning a vector of all matched record batches.
-    pub fn search_to_arrow(
-        &self,
-        href: &str,
-        search: impl Into<Search>,
-    ) -> Result<Vec<RecordBatch>> {
-        let query = self.query(search, href)?;
-        let mut statement = self.connection.prepare(&query.sql)?;
-        log::debug!("DuckDB SQL: {}", query.sql);
-        statement
-            .query_arrow(duckdb::params_from_iter(query.params))?
-            .map(to_geoarrow_record_batch)
-            .collect::<Result<_>>()
-    }
+    /// Returns the number of items that match the search.
+    ///
+    /// The search's `limit`, `sortby`, and `fields` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let count = client
+    ///     .count("data/100-sentinel-2-items.parquet", Search::default().limit(1))
+    ///     .unwrap();
+    /// assert_eq!(count, 100);
+    /// ```
+    pub fn count(&self, href: &str, search: impl Into<Search>) -> Result<u64> {
+        let connection = self.connection()?;
+        let query = query(&connection, search, href, QueryKind::Count, None)?;
+        let mut statement = connection.prepare_cached(&query.sql)?;
+        log::debug!("DuckDB SQL: {}", query.sql);
+        let count = statement.query_row(duckdb::params_from_iter(query.params), |row| {
+            row.get::<_, i64>(0)
+        })?;
+        count.try_into().map_err(Error::from)
+    }
+
+    /// Returns a queryables JSON schema derived from the columns of a stac-geoparquet file.
+    ///
+    /// Each property column becomes a queryable whose type comes from the
+    /// column's type, and the schema is built by [Schema::to_queryables].
+    /// Columns that can't be filtered on, e.g. `assets` and `links`, are left
+    /// out.
+    ///
+    /// The href can also be the name of a view or table (see
+    /// [Client::create_view] and [Client::create_table_from_href]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let queryables = client.queryables("data/100-sentinel-2-items.parquet").unwrap();
+    /// assert_eq!(queryables["properties"]["eo:cloud_cover"]["type"], "number");
+    /// ```
+    pub fn queryables(&self, href: &str) -> Result<serde_json::Value> {
+        let connection = self.connection()?;
+        let source = source(&connection, href)?;
+        let mut statement = connection.prepare(&format!(
+            "SELECT column_name, column_type FROM (DESCRIBE SELECT * FROM {})",
+            source
+        ))?;
+        let mut schema = Schema::default();
+        for row in statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (name, column_type) = row?;
+            if NOT_QUERYABLE_COLUMNS.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(property_type) = property_type(&column_type) {
+                let property = schema.properties.entry(name).or_default();
+                let _ = property.types.insert(property_type);
+                property.nullable = true;
+            }
+        }
+        Ok(schema.to_queryables())
+    }
+
+    /// Explains a search without running it.
+    ///
+    /// Returns the generated SQL and its parameters, along with DuckDB's
+    /// `EXPLAIN` output for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let explanation = client
+    ///     .explain("data/100-sentinel-2-items.parquet", Search::default().limit(1))
+    ///     .unwrap();
+    /// assert!(explanation.query.sql.starts_with("SELECT"));
+    /// println!("{explanation}");
+    /// ```
+    pub fn explain(&self, href: &str, search: impl Into<Search>) -> Result<Explanation> {
+        let connection = self.connection()?;
+        let query = query(&connection, search, href, QueryKind::Select, None)?;
+        let mut statement = connection.prepare(&format!("EXPLAIN {}", query.sql))?;
+        let plan = statement
+            .query_map(duckdb::params_from_iter(query.params.clone()), |row| {
+                row.get::<_, String>(1)
+            })?
+            .collect::<duckdb::Result<Vec<_>>>()?
+            .join("\n");
+        Ok(Explanation { query, plan })
+    }
+
+    /// Searches this client for items whose assets match a filter.
+    ///
+    /// Assets are stored as a struct with one field per asset key, so the
+    /// filter is expanded into a condition on each of the source's asset
+    /// keys. This is handy for audits, e.g. finding every item that still
+    /// points at an old bucket.
+    ///
+    /// The href can also be the name of a view or table (see
+    /// [Client::create_view] and [Client::create_table_from_href]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_duckdb::{AssetFilter, Client};
+    ///
+    /// let client = Client::new().unwrap();
+    /// let item_collection = client
+    ///     .search_assets(
+    ///         "data/100-sentinel-2-items.parquet",
+    ///         AssetFilter::Key("visual".to_string()),
+    ///         Search::default(),
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(item_collection.items.len(), 100);
+    /// ```
+    pub fn search_assets(
+        &self,
+        href: &str,
+        filter: AssetFilter,
+        search: impl Into<Search>,
+    ) -> Result<stac::ItemCollection> {
+        let connection = self.connection()?;
+        let query = query(&connection, search, href, QueryKind::Select, Some(&filter))?;
+        let record_batches = execute(&connection, query)?;
+        to_item_collection(record_batches)
+    }
+
+    /// Searches this client, returning a vector of all matched record batches.
+    pub fn search_to_arrow(
+        &self,
+        href: &str,
+        search: impl Into<Search>,
+    ) -> Result<Vec<RecordBatch>> {
+        let connection = self.connection()?;
+        let query = query(&connection, search, href, QueryKind::Select, None)?;
+        execute(&connection, query)
+    }
+
+    /// Runs arbitrary SQL, returning all result record batches.
+    ///
+    /// This is an escape hatch for queries that [Client::search] can't
+    /// express. Use `?` placeholders for the parameters, and
+    /// `read_parquet('...')` or a view or table name (see
+    /// [Client::create_view]) as the source. If the result has a `geometry`
+    /// column of WKB, e.g. from `ST_AsWKB(geometry) geometry`, it's converted
+    /// to a geoarrow geometry column, so results with the standard columns can
+    /// be turned back into items with [to_item_collection].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use duckdb::types::Value;
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let record_batches = client
+    ///     .query_sql(
+    ///         "SELECT * EXCLUDE geometry, ST_AsWKB(geometry) geometry \
+    ///          FROM read_parquet('data/100-sentinel-2-items.parquet') \
+    ///          WHERE \"eo:cloud_cover\" >= ? ORDER BY \"eo:cloud_cover\" LIMIT 5",
+    ///         vec![Value::Double(0.0)],
+    ///     )
+    ///     .unwrap();
+    /// let item_collection = stac_duckdb::to_item_collection(record_batches).unwrap();
+    /// assert_eq!(item_collection.items.len(), 5);
+    /// ```
+    pub fn query_sql(&self, sql: &str, params: Vec<Value>) -> Result<Vec<RecordBatch>> {
+        let connection = self.connection()?;
+        execute(
+            &connection,
+            Query {
+                sql: sql.to_string(),
+                params,
+            },
+        )
+    }
+
+    /// Checks out a connection from the pool, opening a new one if none are
+    /// available.
+    fn connection(&self) -> Result<PooledConnection<'_>> {
+        let connection = self
+            .pool
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        let connection = match connection {
+            Some(connection) => connection,
+            None => self
+                .connection
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .try_clone()?,
+        };
+        Ok(PooledConnection {
+            connection: Some(connection),
+            pool: &self.pool,
+        })
+    }
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.connection
+            .as_ref()
+            .expect("the connection is only taken on drop")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            self.pool
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(connection);
+        }
+    }
+}
+
+/// The kind of SQL query to build for a search.
+#[derive(Debug)]
+enum QueryKind {
+    /// Selects the matching items.
+    Select,
+
+    /// Counts the matching items.
+    Count,
+
+    /// Selects one more than a page of matching items, starting after the
+    /// token's item.
+    Page(Option<PageToken>),
+}
+
+/// The sort key values of the last item of a page: one for each `sortby`
+/// field, then the collection and the id.
+#[derive(Debug)]
+struct PageToken(Vec<serde_json::Value>);
+
+impl PageToken {
+    fn parse(token: &str) -> Result<PageToken> {
+        match serde_json::from_str::<Vec<serde_json::Value>>(token) {
+            Ok(values) if values.len() >= 2 => Ok(PageToken(values)),
+            _ => Err(Error::InvalidPageToken(token.to_string())),
+        }
+    }
+
+    fn from_item(item: &stac_api::Item, sort_fields: &[String]) -> PageToken {
+        let get = |field: &str| {
+            item.get(field)
+                .or_else(|| item.get("properties").and_then(|p| p.get(field)))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let mut values: Vec<_> = sort_fields.iter().map(|field| get(field)).collect();
+        values.push(get("collection"));
+        values.push(get("id"));
+        PageToken(values)
+    }
+
+    /// Returns the sort key values as query parameters.
+    ///
+    /// The collection is left out if the source doesn't have a collection column.
+    fn params(&self, has_collection: bool) -> Vec<Value> {
+        let len = self.0.len();
+        self.0
+            .iter()
+            .cloned()
+            .enumerate()
+            .filter(|(i, _)| has_collection || *i != len - 2)
+            .map(|(i, value)| match value {
+                // The collection is sorted as an empty string when it's null
+                serde_json::Value::Null if i == len - 2 => Value::Text(String::new()),
+                serde_json::Value::Null => Value::Null,
+                serde_json::Value::Bool(b) => Value::Boolean(b),
+                serde_json::Value::Number(n) => n
+                    .as_i64()
+                    .map(Value::BigInt)
+                    .or_else(|| n.as_f64().map(Value::Double))
+                    .unwrap_or(Value::Null),
+                serde_json::Value::String(s) => Value::Text(s),
+                value => Value::Text(value.to_string()),
+            })
+            .collect()
+    }
+}
+
+impl Display for PageToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::Value::Array(self.0.clone()))
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.query.sql)?;
+        if !self.query.params.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Parameters:")?;
+            for (i, param) in self.query.params.iter().enumerate() {
+                writeln!(f, "  {}: {:?}", i + 1, param)?;
+            }
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.plan)
+    }
+}
+
+fn execute(connection: &Connection, query: Query) -> Result<Vec<RecordBatch>> {
+    let mut statement = connection.prepare_cached(&query.sql)?;
+    log::debug!("DuckDB SQL: {}", query.sql);
+    statement
+        .query_arrow(duckdb::params_from_iter(query.params))?
+        .map(to_geoarrow_record_batch)
+        .collect::<Result<_>>()
+}
+
+/// Converts record batches with the standard stac-geoparquet columns to items.
+///
+/// The batches' `geometry` column must be geoarrow, as returned by
+/// [Client::search_to_arrow] and [Client::query_sql].
+///
+/// # Examples
+///
+/// ```
+/// use stac_api::Search;
+/// use stac_duckdb::Client;
+///
+/// let client = Client::new().unwrap();
+/// let record_batches = client
+///     .search_to_arrow("data/100-sentinel-2-items.parquet", Search::default())
+///     .unwrap();
+/// let item_collection = stac_duckdb::to_item_collection(record_batches).unwrap();
+/// assert_eq!(item_collection.items.len(), 100);
+/// ```
+pub fn to_item_collection(record_batches: Vec<RecordBatch>) -> Result<stac::ItemCollection> {
+    if record_batches.is_empty() {
+        return Ok(Vec::new().into());
+    }
+    let schema = record_batches[0].schema();
+    let table = Table::try_new(record_batches, schema)?;
+    let items = stac::geoarrow::from_table(table)?;
+    Ok(items)
+}
+
+fn to_json(record_batches: Vec<RecordBatch>) -> Result<Vec<stac_api::Item>> {
+    if record_batches.is_empty() {
+        return Ok(Vec::new());
+    }
+    let schema = record_batches[0].schema();
+    let table = Table::try_new(record_batches, schema)?;
+    stac::geoarrow::json::from_table(table).map_err(Error::from)
+}
+
+/// Returns the collections embedded in a file's stac-geoparquet metadata, if any.
+fn embedded_collections(connection: &Connection, href: &str) -> Result<Option<Vec<Collection>>> {
+    let mut statement = connection.prepare(&format!(
+        "SELECT decode(value) FROM parquet_kv_metadata({}) WHERE decode(key) = ?",
+        quote_literal(href)
+    ))?;
+    let mut collections = BTreeMap::new();
+    for row in statement.query_map([METADATA_KEY], |row| row.get::<_, String>(0))? {
+        let metadata: Metadata = serde_json::from_str(&row?)?;
+        collections.extend(metadata.collections);
+    }
+    if collections.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(collections.into_values().collect()))
+    }
+}
+
+/// Returns the SQL to select from for the href.
+///
+/// Views and tables in the database take precedence over files.
+fn source(connection: &Connection, href: &str) -> Result<String> {
+    let mut statement = connection
+        .prepare_cached("SELECT 1 FROM information_schema.tables WHERE table_name = ?")?;
+    if statement.exists([href])? {
+        Ok(quote_identifier(href))
+    } else {
+        Ok(read_parquet(href))
+    }
+}
+
+fn columns(connection: &Connection, source: &str) -> Result<Vec<String>> {
+    // Not cached, since the file's schema might change between searches.
+    let mut statement = connection.prepare(&format!(
+        "SELECT column_name FROM (DESCRIBE SELECT * FROM {})",
+        source
+    ))?;
+    let columns = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<duckdb::Result<_>>()?;
+    Ok(columns)
+}
+
+/// Computes the extent of a collection's items, optionally from a percentage of the rows.
+fn extent(
+    connection: &Connection,
+    source: &str,
+    collection_id: &str,
+    sample: Option<u8>,
+) -> Result<Extent> {
+    let columns = columns(connection, source)?;
+    let start_datetime = if columns.iter().any(|column| column == "start_datetime") {
+        "strftime(min(coalesce(start_datetime, datetime)), '%xT%X%z')"
+    } else {
+        "strftime(min(datetime), '%xT%X%z')"
+    };
+    let end_datetime = if columns.iter().any(|column| column == "end_datetime") {
+        "strftime(max(coalesce(end_datetime, datetime)), '%xT%X%z')"
+    } else {
+        "strftime(max(datetime), '%xT%X%z')"
+    };
+    // The sample is taken from the collection's rows, so other collections
+    // in the same file don't shrink it.
+    let from = match sample {
+        Some(sample) => format!(
+            "(SELECT * FROM {} WHERE collection = $1) TABLESAMPLE {} PERCENT (bernoulli)",
+            source, sample
+        ),
+        None => format!("{} WHERE collection = $1", source),
+    };
+    let mut statement = connection.prepare_cached(&format!(
+        "SELECT ST_AsGeoJSON(ST_Extent_Agg(geometry)), {}, {}, COUNT(*) FROM {}",
+        start_datetime, end_datetime, from
+    ))?;
+    let row = statement.query_row([collection_id], |row| {
+        Ok((
+            row.get::<_, Option<String>>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+    if row.3 == 0 {
+        if sample.is_some() {
+            // A small collection might not have any sampled rows.
+            return extent(connection, source, collection_id, None);
+        }
+        return Err(Error::UnknownCollection(collection_id.to_string()));
+    }
+    let mut extent = Extent::default();
+    if let Some(geometry) = row.0 {
+        let geometry: geo::Geometry = Geometry::from_json_value(serde_json::from_str(&geometry)?)
+            .map_err(Box::new)?
+            .try_into()
+            .map_err(Box::new)?;
+        if let Some(bbox) = geometry.bounding_rect() {
+            extent.spatial = SpatialExtent {
+                bbox: vec![bbox.into()],
+            };
+        }
+    }
+    let parse = |datetime: Option<String>| -> Result<_> {
+        datetime
+            .map(|datetime| DateTime::parse_from_str(&datetime, "%FT%T%#z").map(Into::into))
+            .transpose()
+            .map_err(Error::from)
+    };
+    extent.temporal = TemporalExtent {
+        interval: vec![[parse(row.1)?, parse(row.2)?]],
+    };
+    Ok(extent)
+}
+
+/// Returns the asset keys of a source's `assets` struct column.
+fn asset_keys(connection: &Connection, source: &str) -> Result<Vec<String>> {
+    // Every row has the same struct fields, so one row is enough.
+    let mut statement = connection.prepare(&format!(
+        "SELECT unnest(json_keys(to_json(assets))) FROM (SELECT assets FROM {} WHERE assets IS NOT NULL LIMIT 1)",
+        source
+    ))?;
+    let keys = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<duckdb::Result<_>>()?;
+    Ok(keys)
+}
 
-    fn query(&self, search: impl Into<Search>, href: &str) -> Result<Query> {
-        let mut search: Search = search.into();
-        // Get suffix information early so we can take ownership of other parts of search as we go along.
-        let limit = search.items.limit.take();
-        let offset = search
-            .items
-            .additional_fields
-            .get("offset")
-            .and_then(|v| v.as_i64());
-        let sortby = std::mem::take(&mut search.items.sortby);
-        let fields = std::mem::take(&mut search.items.fields);
-
-        let mut statement = self.connection.prepare(&format!(
-            "SELECT column_name FROM (DESCRIBE SELECT * from read_parquet('{}'))",
-            href
-        ))?;
-        let mut columns = Vec::new();
-        // Can we use SQL magic to make our query not depend on which columns are present?
-        let mut has_start_datetime = false;
-        let mut has_end_datetime: bool = false;
-        for row in statement.query_map([], |row| row.get::<_, String>(0))? {
-            let column = row?;
-            if column == "start_datetime" {
-                has_start_datetime = true;
+/// Returns the condition for an asset filter, given the source's asset keys.
+fn asset_filter(filter: &AssetFilter, keys: &[String], params: &mut Vec<Value>) -> String {
+    match filter {
+        AssetFilter::Key(key) => {
+            if keys.contains(key) {
+                format!("assets.{} IS NOT NULL", quote_identifier(key))
+            } else {
+                "FALSE".to_string()
             }
-            if column == "end_datetime" {
-                has_end_datetime = true;
+        }
+        AssetFilter::HrefLike(pattern) => {
+            if keys.is_empty() {
+                return "FALSE".to_string();
             }
+            let conditions: Vec<_> = keys
+                .iter()
+                .map(|key| {
+                    params.push(Value::Text(pattern.clone()));
+                    format!("assets.{}.href LIKE ?", quote_identifier(key))
+                })
+                .collect();
+            format!("({})", conditions.join(" OR "))
+        }
+    }
+}
 
-            if let Some(fields) = fields.as_ref() {
-                if fields.exclude.contains(&column)
-                    || !(fields.include.is_empty() || fields.include.contains(&column))
-                {
-                    continue;
-                }
-            }
+/// Builds the SQL for a search.
+fn query(
+    connection: &Connection,
+    search: impl Into<Search>,
+    href: &str,
+    kind: QueryKind,
+    assets: Option<&AssetFilter>,
+) -> Result<Query> {
+    let mut search: Search = search.into();
+    search.items = std::mem::take(&mut search.items).into_crs84_filter()?;
+    // Get suffix information early so we can take ownership of other parts of search as we go along.
+    let limit = search.items.limit.take();
+    let offset = search
+        .items
+        .additional_fields
+        .get("offset")
+        .and_then(|v| v.as_i64());
+    let sortby = std::mem::take(&mut search.items.sortby);
+    let fields = std::mem::take(&mut search.items.fields);
 
-            if column == "geometry" {
-                columns.push("ST_AsWKB(geometry) geometry".to_string());
-            } else {
-                columns.push(format!("\"{}\"", column));
+    let source = source(connection, href)?;
+    let all_columns = columns(connection, &source)?;
+    let mut columns = Vec::new();
+    // Can we use SQL magic to make our query not depend on which columns are present?
+    let mut has_start_datetime = false;
+    let mut has_end_datetime: bool = false;
+    let mut has_bbox = false;
+    for column in &all_columns {
+        if column == "bbox" {
+            has_bbox = true;
+        }
+        if column == "start_datetime" {
+            has_start_datetime = true;
+        }
+        if column == "end_datetime" {
+            has_end_datetime = true;
+        }
+
+        if let Some(fields) = fields.as_ref() {
+            if fields.exclude.contains(column)
+                || !(fields.include.is_empty() || fields.include.contains(column))
+            {
+                continue;
             }
         }
 
-        let mut wheres = Vec::new();
-        let mut params = Vec::new();
-        if !search.ids.is_empty() {
+        if column == "geometry" {
+            columns.push("ST_AsWKB(geometry) geometry".to_string());
+        } else {
+            columns.push(quote_identifier(column));
+        }
+    }
+
+    let mut wheres = Vec::new();
+    let mut params = Vec::new();
+    if !search.ids.is_empty() {
+        wheres.push(format!(
+            "id IN ({})",
+            (0..search.ids.len())
+                .map(|_| "?")
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+        params.extend(search.ids.into_iter().map(Value::Text));
+    }
+    if let Some(intersects) = search.intersects {
+        if has_bbox {
+            let geometry: Option<geo::Geometry> = intersects.clone().try_into().ok();
+            if let Some(rect) = geometry.and_then(|geometry| geometry.bounding_rect()) {
+                bbox_prefilter(rect.into(), &mut wheres, &mut params);
+            }
+        }
+        wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
+        params.push(Value::Text(intersects.to_string()));
+    }
+    if !search.collections.is_empty() {
+        wheres.push(format!(
+            "collection IN ({})",
+            (0..search.collections.len())
+                .map(|_| "?")
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+        params.extend(search.collections.into_iter().map(Value::Text));
+    }
+    if let Some(filter) = assets {
+        let keys = if all_columns.iter().any(|column| column == "assets") {
+            asset_keys(connection, &source)?
+        } else {
+            Vec::new()
+        };
+        wheres.push(asset_filter(filter, &keys, &mut params));
+    }
+    if let Some(bbox) = search.items.bbox {
+        if has_bbox {
+            bbox_prefilter(bbox, &mut wheres, &mut params);
+        }
+        wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
+        params.push(Value::Text(bbox.to_geometry().to_string()));
+    }
+    if let Some(datetime) = search.items.datetime {
+        let interval = stac::datetime::parse(&datetime)?;
+        if let Some(start) = interval.0 {
             wheres.push(format!(
-                "id IN ({})",
-                (0..search.ids.len())
-                    .map(|_| "?")
-                    .collect::<Vec<_>>()
-                    .join(",")
+                "?::TIMESTAMPTZ <= {}",
+                if has_start_datetime {
+                    "start_datetime"
+                } else {
+                    "datetime"
+                }
             ));
-            params.extend(search.ids.into_iter().map(Value::Text));
+            params.push(Value::Text(start.to_rfc3339()));
         }
-        if let Some(intersects) = search.intersects {
-            wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
-            params.push(Value::Text(intersects.to_string()));
-        }
-        if !search.collections.is_empty() {
+        if let Some(end) = interval.1 {
             wheres.push(format!(
-                "collection IN ({})",
-                (0..search.collections.len())
-                    .map(|_| "?")
-                    .collect::<Vec<_>>()
-                    .join(",")
+                "?::TIMESTAMPTZ >= {}", // Inclusive, https://github.com/radiantearth/stac-spec/pull/1280
+                if has_end_datetime {
+                    "end_datetime"
+                } else {
+                    "datetime"
+                }
             ));
-            params.extend(search.collections.into_iter().map(Value::Text));
+            params.push(Value::Text(end.to_rfc3339()));
+        }
+    }
+    if search.items.filter.is_some() {
+        return Err(Error::Unimplemented("filter"));
+    }
+    if search.items.query.is_some() {
+        return Err(Error::Unimplemented("query"));
+    }
+
+    let mut order_by = Vec::with_capacity(sortby.len());
+    for sortby in sortby {
+        if !all_columns.contains(&sortby.field) {
+            return Err(Error::UnknownColumn(sortby.field));
         }
-        if let Some(bbox) = search.items.bbox {
-            wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
-            params.push(Value::Text(bbox.to_geometry().to_string()));
+        order_by.push((quote_identifier(&sortby.field), sortby.direction));
+    }
+    let mut limit = limit;
+    if let QueryKind::Page(token) = &kind {
+        let has_collection = all_columns.iter().any(|column| column == "collection");
+        if has_collection {
+            order_by.push(("COALESCE(collection, '')".to_string(), Direction::Ascending));
         }
-        if let Some(datetime) = search.items.datetime {
-            let interval = stac::datetime::parse(&datetime)?;
-            if let Some(start) = interval.0 {
-                wheres.push(format!(
-                    "?::TIMESTAMPTZ <= {}",
-                    if has_start_datetime {
-                        "start_datetime"
-                    } else {
-                        "datetime"
-                    }
-                ));
-                params.push(Value::Text(start.to_rfc3339()));
+        order_by.push(("id".to_string(), Direction::Ascending));
+        if let Some(token) = token {
+            let values = token.params(has_collection);
+            if values.len() != order_by.len() {
+                return Err(Error::InvalidPageToken(token.to_string()));
             }
-            if let Some(end) = interval.1 {
-                wheres.push(format!(
-                    "?::TIMESTAMPTZ >= {}", // Inclusive, https://github.com/radiantearth/stac-spec/pull/1280
-                    if has_end_datetime {
-                        "end_datetime"
-                    } else {
-                        "datetime"
-                    }
+            // (a > ?) OR (a = ? AND b > ?) OR ..., with the comparisons flipped
+            // for descending keys.
+            let mut terms = Vec::with_capacity(order_by.len());
+            for (i, (expression, direction)) in order_by.iter().enumerate() {
+                let mut term = Vec::with_capacity(i + 1);
+                for ((expression, _), value) in order_by[..i].iter().zip(&values) {
+                    term.push(format!("{} = ?", expression));
+                    params.push(value.clone());
+                }
+                term.push(format!(
+                    "{} {} ?",
+                    expression,
+                    match direction {
+                        Direction::Ascending => ">",
+                        Direction::Descending => "<",
+                    },
                 ));
-                params.push(Value::Text(end.to_rfc3339()));
+                params.push(values[i].clone());
+                terms.push(format!("({})", term.join(" AND ")));
             }
+            wheres.push(format!("({})", terms.join(" OR ")));
         }
-        if search.items.filter.is_some() {
-            todo!("Implement the filter extension");
-        }
-        if search.items.query.is_some() {
-            todo!("Implement the query extension");
-        }
+        limit = limit.map(|limit| limit + 1);
+    }
 
-        let mut suffix = String::new();
-        if !wheres.is_empty() {
-            suffix.push_str(&format!(" WHERE {}", wheres.join(" AND ")));
-        }
-        if !sortby.is_empty() {
-            let mut order_by = Vec::with_capacity(sortby.len());
-            for sortby in sortby {
-                order_by.push(format!(
+    let mut suffix = String::new();
+    if !wheres.is_empty() {
+        suffix.push_str(&format!(" WHERE {}", wheres.join(" AND ")));
+    }
+    if let QueryKind::Count = kind {
+        return Ok(Query {
+            sql: format!("SELECT COUNT(*) FROM {}{}", source, suffix),
+            params,
+        });
+    }
+    if !order_by.is_empty() {
+        let order_by = order_by
+            .into_iter()
+            .map(|(expression, direction)| {
+                format!(
                     "{} {}",
-                    sortby.field,
-                    match sortby.direction {
+                    expression,
+                    match direction {
                         Direction::Ascending => "ASC",
                         Direction::Descending => "DESC",
                     }
-                ));
-            }
-            suffix.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
-        }
-        if let Some(limit) = limit {
-            suffix.push_str(&format!(" LIMIT {}", limit));
-        }
-        if let Some(offset) = offset {
-            suffix.push_str(&format!(" OFFSET {}", offset));
-        }
-        Ok(Query {
-            sql: format!(
-                "SELECT {} FROM read_parquet('{}'){}",
-                columns.join(","),
-                href,
-                suffix,
-            ),
-            params,
-        })
+                )
+            })
+            .collect::<Vec<_>>();
+        suffix.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
     }
+    if let Some(limit) = limit {
+        suffix.push_str(&format!(" LIMIT {}", limit));
+    }
+    if let Some(offset) = offset {
+        suffix.push_str(&format!(" OFFSET {}", offset));
+    }
+    Ok(Query {
+        sql: format!("SELECT {} FROM {}{}", columns.join(","), source, suffix,),
+        params,
+    })
+}
+
+/// Adds comparisons against the `bbox` column's y fields for a search bbox.
+///
+/// stac-geoparquet writers store each of the `bbox` struct's fields as its own
+/// parquet column with min/max statistics, so DuckDB can push these
+/// comparisons down to skip row groups before evaluating the much more
+/// expensive `ST_Intersects`. There's no x comparison: an item whose bbox
+/// crosses the antimeridian can match a search on either side of it, which
+/// can only be written as an `OR` across columns, and DuckDB can't push that
+/// down.
+fn bbox_prefilter(bbox: Bbox, wheres: &mut Vec<String>, params: &mut Vec<Value>) {
+    wheres.push("bbox.ymax >= ?".to_string());
+    params.push(Value::Double(bbox.ymin()));
+    wheres.push("bbox.ymin <= ?".to_string());
+    params.push(Value::Double(bbox.ymax()));
 }
 
 /// Return this crate's version.
@@ -374,6 +1249,60 @@ pub fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// Columns that aren't item properties, that can't be filtered on, or that
+/// are always queryable.
+const NOT_QUERYABLE_COLUMNS: [&str; 9] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "links",
+    "assets",
+    "bbox",
+    "id",
+    "collection",
+    "geometry",
+];
+
+/// Returns the property type of a column type, or `None` if it isn't queryable.
+fn property_type(column_type: &str) -> Option<PropertyType> {
+    let column_type = column_type.to_uppercase();
+    if column_type.ends_with("[]") || column_type.starts_with("LIST") {
+        Some(PropertyType::Array)
+    } else if column_type.starts_with("STRUCT") || column_type.starts_with("MAP") {
+        Some(PropertyType::Object)
+    } else if column_type.starts_with("TIMESTAMP") {
+        Some(PropertyType::Datetime)
+    } else if column_type == "BOOLEAN" {
+        Some(PropertyType::Boolean)
+    } else if column_type.ends_with("INT") || column_type.ends_with("INTEGER") {
+        Some(PropertyType::Integer)
+    } else if matches!(column_type.as_str(), "FLOAT" | "DOUBLE" | "REAL")
+        || column_type.starts_with("DECIMAL")
+    {
+        Some(PropertyType::Number)
+    } else if column_type == "VARCHAR" || column_type == "DATE" {
+        Some(PropertyType::String)
+    } else {
+        None
+    }
+}
+
+/// Returns a `read_parquet` table function call for the href.
+///
+/// DuckDB can't bind parameters to table function arguments in every context,
+/// so the href is escaped as a string literal instead.
+fn read_parquet(href: &str) -> String {
+    format!("read_parquet({})", quote_literal(href))
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn quote_identifier(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
 fn to_geoarrow_record_batch(mut record_batch: RecordBatch) -> Result<RecordBatch> {
     if let Some((index, _)) = record_batch.schema().column_with_name("geometry") {
         let geometry_column = record_batch.remove_column(index);
@@ -397,7 +1326,8 @@ fn to_geoarrow_record_batch(mut record_batch: RecordBatch) -> Result<RecordBatch
 
 #[cfg(test)]
 mod tests {
-    use super::Client;
+    use super::{AssetFilter, Client, Config, Error};
+    use criterion as _;
     use geo::Geometry;
     use rstest::{fixture, rstest};
     use stac::{Bbox, Validate};
@@ -479,6 +1409,28 @@ mod tests {
         assert_eq!(item_collection.items.len(), 50);
     }
 
+    #[rstest]
+    fn search_bbox_prefilter(client: Client) {
+        let connection = client.connection().unwrap();
+        let query = super::query(
+            &connection,
+            Search::default().bbox(Bbox::new(-106.1, 40.5, -106.0, 40.6)),
+            "data/100-sentinel-2-items.parquet",
+            super::QueryKind::Count,
+            None,
+        )
+        .unwrap();
+        assert!(query.sql.contains("bbox.ymax >= ?"));
+        assert!(!query.sql.contains(" OR "));
+        let item_collection = client
+            .search(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().bbox(Bbox::new(-100., 40.5, -99., 40.6)),
+            )
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+    }
+
     #[rstest]
     fn search_datetime(client: Client) {
         let item_collection = client
@@ -553,6 +1505,107 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn count(client: Client) {
+        let count = client
+            .count(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().ids(vec![
+                    "S2A_MSIL2A_20240326T174951_R141_T13TDE_20240329T224429".to_string(),
+                ]),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[rstest]
+    fn search_to_json_number_matched(client: Client) {
+        let item_collection = client
+            .search_to_json(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().limit(1),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert_eq!(item_collection.number_matched, Some(100));
+    }
+
+    #[rstest]
+    fn search_page(client: Client) {
+        let href = "data/100-sentinel-2-items.parquet";
+        let search = Search::default()
+            .sortby(vec![Sortby::desc("datetime")])
+            .limit(30);
+        let mut ids = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = client
+                .search_page(href, search.clone(), token.as_deref())
+                .unwrap();
+            assert_eq!(page.number_matched, Some(100));
+            ids.extend(page.items.into_iter().map(|item| item["id"].clone()));
+            if let Some(next) = page.next {
+                token = Some(next["token"].as_str().unwrap().to_string());
+            } else {
+                break;
+            }
+        }
+        assert_eq!(ids.len(), 100);
+        let mut deduplicated = ids.clone();
+        deduplicated.sort_by_key(|id| id.to_string());
+        deduplicated.dedup();
+        assert_eq!(deduplicated.len(), 100);
+        assert_eq!(
+            ids[0],
+            "S2B_MSIL2A_20241203T174629_R098_T13TDE_20241203T211406"
+        );
+    }
+
+    #[rstest]
+    fn search_page_fields(client: Client) {
+        let page = client
+            .search_page(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().fields("-id".into()).limit(1),
+                None,
+            )
+            .unwrap();
+        assert!(page.items[0].contains_key("id"));
+        assert!(page.next.is_some());
+    }
+
+    #[rstest]
+    fn search_page_invalid_token(client: Client) {
+        let result = client.search_page(
+            "data/100-sentinel-2-items.parquet",
+            Search::default(),
+            Some("not a token"),
+        );
+        assert!(matches!(
+            result.unwrap_err(),
+            super::Error::InvalidPageToken(_)
+        ));
+    }
+
+    #[rstest]
+    fn search_page_token_values(client: Client) {
+        let href = "data/100-sentinel-2-items.parquet";
+        let search = Search::default()
+            .sortby(vec![Sortby::desc("datetime")])
+            .limit(1);
+        let page = client.search_page(href, search.clone(), None).unwrap();
+        let token = page.next.unwrap()["token"].as_str().unwrap().to_string();
+        let values: Vec<serde_json::Value> = serde_json::from_str(&token).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[2], page.items[0]["id"]);
+
+        let result = client.search_page(href, search, Some(r#"["a", "b"]"#));
+        assert!(matches!(
+            result.unwrap_err(),
+            super::Error::InvalidPageToken(_)
+        ));
+    }
+
     #[rstest]
     fn search_fields(client: Client) {
         let item_collection = client
@@ -564,6 +1617,221 @@ mod tests {
         assert_eq!(item_collection.items[0].len(), 1);
     }
 
+    #[rstest]
+    fn search_fields_from_str(client: Client) {
+        let item_collection = client
+            .search_to_json(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().fields("+id".into()).limit(1),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items[0].len(), 1);
+    }
+
+    #[rstest]
+    fn search_filter_unimplemented(client: Client) {
+        let result = client.search(
+            "data/100-sentinel-2-items.parquet",
+            Search::default().filter("eo:cloud_cover < 10"),
+        );
+        assert!(matches!(
+            result.unwrap_err(),
+            super::Error::Unimplemented("filter")
+        ));
+    }
+
+    #[rstest]
+    fn search_unsupported_filter_crs(client: Client) {
+        let mut search = Search::default();
+        search.items.filter_crs = Some("not-a-crs".to_string());
+        let result = client.search("data/100-sentinel-2-items.parquet", search);
+        assert!(matches!(
+            result.unwrap_err(),
+            super::Error::StacApi(stac_api::Error::UnsupportedFilterCrs(_))
+        ));
+        let mut search = Search::default();
+        search.items.filter_crs = Some("EPSG:4326".to_string());
+        let _ = client
+            .search("data/100-sentinel-2-items.parquet", search)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn explain(client: Client) {
+        let explanation = client
+            .explain(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().ids(vec!["an-id".to_string()]),
+            )
+            .unwrap();
+        assert_eq!(explanation.query.params.len(), 1);
+        assert!(!explanation.plan.is_empty());
+        assert!(explanation.to_string().contains("Parameters:"));
+    }
+
+    #[rstest]
+    fn search_assets(client: Client) {
+        let href = "data/100-sentinel-2-items.parquet";
+        let search = |filter| {
+            client
+                .search_assets(href, filter, Search::default())
+                .unwrap()
+                .items
+                .len()
+        };
+        assert_eq!(search(AssetFilter::Key("visual".to_string())), 100);
+        assert_eq!(search(AssetFilter::Key("not-an-asset".to_string())), 0);
+        assert_eq!(
+            search(AssetFilter::HrefLike(
+                "https://sentinel2l2a01.blob.core.windows.net/%".to_string()
+            )),
+            100
+        );
+        assert_eq!(search(AssetFilter::HrefLike("s3://%".to_string())), 0);
+        let item_collection = client
+            .search_assets(
+                href,
+                AssetFilter::Key("visual".to_string()),
+                Search::default().limit(1),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+    }
+
+    #[rstest]
+    fn search_assets_hostile_key(client: Client) {
+        let item_collection = client
+            .search_assets(
+                "data/100-sentinel-2-items.parquet",
+                AssetFilter::Key("visual\" IS NOT NULL OR TRUE --".to_string()),
+                Search::default(),
+            )
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+    }
+
+    #[rstest]
+    fn query_sql(client: Client) {
+        let record_batches = client
+            .query_sql(
+                "SELECT * EXCLUDE geometry, ST_AsWKB(geometry) geometry FROM read_parquet('data/100-sentinel-2-items.parquet') WHERE id = ?",
+                vec![duckdb::types::Value::Text(
+                    "S2A_MSIL2A_20240326T174951_R141_T13TDE_20240329T224429".to_string(),
+                )],
+            )
+            .unwrap();
+        let item_collection = super::to_item_collection(record_batches).unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert!(item_collection.items[0].geometry.is_some());
+        item_collection.items[0].validate().unwrap();
+
+        let record_batches = client
+            .query_sql(
+                "SELECT collection, COUNT(*) count FROM read_parquet('data/100-sentinel-2-items.parquet') GROUP BY collection",
+                Vec::new(),
+            )
+            .unwrap();
+        assert_eq!(record_batches[0].num_rows(), 1);
+    }
+
+    #[rstest]
+    fn search_hostile_href(client: Client) {
+        let result = client.search(
+            "data/100-sentinel-2-items.parquet') UNION SELECT * FROM read_parquet('data/100-landsat-items.parquet",
+            Search::default(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn search_hostile_sortby(client: Client) {
+        let result = client.search(
+            "data/100-sentinel-2-items.parquet",
+            Search::default().sortby(vec![Sortby::asc("datetime; DROP TABLE items; --")]),
+        );
+        assert!(matches!(
+            result.unwrap_err(),
+            super::Error::UnknownColumn(_)
+        ));
+    }
+
+    #[rstest]
+    fn search_hostile_fields(client: Client) {
+        let item_collection = client
+            .search_to_json(
+                "data/100-sentinel-2-items.parquet",
+                Search::default()
+                    .fields("+id,\"id\" FROM x; --".into())
+                    .limit(1),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items[0].len(), 1);
+    }
+
+    #[test]
+    fn quote() {
+        assert_eq!(super::quote_literal("it's"), "'it''s'");
+        assert_eq!(super::quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[rstest]
+    fn search_concurrently(client: Client) {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        client
+                            .search("data/100-sentinel-2-items.parquet", Search::default())
+                            .unwrap()
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap().items.len(), 100);
+            }
+        });
+    }
+
+    #[rstest]
+    fn create_view(client: Client) {
+        client
+            .create_view("sentinel-2", "data/100-sentinel-2-items.parquet")
+            .unwrap();
+        let item_collection = client
+            .search("sentinel-2", Search::default().limit(1))
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert_eq!(client.collections("sentinel-2").unwrap().len(), 1);
+    }
+
+    #[rstest]
+    fn create_table_from_href(client: Client) {
+        client
+            .create_table_from_href("sentinel-2", "data/100-sentinel-2-items.parquet")
+            .unwrap();
+        let item_collection = client.search("sentinel-2", Search::default()).unwrap();
+        assert_eq!(item_collection.items.len(), 100);
+    }
+
+    #[test]
+    fn persistent() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let config = Config {
+            path: Some(tempdir.path().join("stac.duckdb")),
+        };
+        {
+            let _mutex = MUTEX.lock().unwrap();
+            let client = Client::with_config(config.clone()).unwrap();
+            client
+                .create_table_from_href("sentinel-2", "data/100-sentinel-2-items.parquet")
+                .unwrap();
+        }
+        let _mutex = MUTEX.lock().unwrap();
+        let client = Client::with_config(config).unwrap();
+        let item_collection = client.search("sentinel-2", Search::default()).unwrap();
+        assert_eq!(item_collection.items.len(), 100);
+    }
+
     #[rstest]
     fn collections(client: Client) {
         let collections = client
@@ -571,4 +1839,105 @@ mod tests {
             .unwrap();
         assert_eq!(collections.len(), 1);
     }
+
+    #[rstest]
+    fn extent(client: Client) {
+        let href = "data/100-sentinel-2-items.parquet";
+        let extent = client.extent(href, "sentinel-2-l2a", None).unwrap();
+        let collections = client.collections(href).unwrap();
+        assert_eq!(extent, collections[0].extent);
+        let sampled = client.extent(href, "sentinel-2-l2a", Some(100)).unwrap();
+        assert_eq!(sampled, extent);
+        assert!(matches!(
+            client
+                .extent(href, "not-a-collection", Some(1))
+                .unwrap_err(),
+            super::Error::UnknownCollection(_)
+        ));
+        assert!(matches!(
+            client.extent(href, "not-a-collection", None).unwrap_err(),
+            super::Error::UnknownCollection(_)
+        ));
+        assert!(matches!(
+            client.extent(href, "sentinel-2-l2a", Some(0)).unwrap_err(),
+            super::Error::InvalidSample(0)
+        ));
+    }
+
+    #[rstest]
+    fn embedded_collections(client: Client) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("items.parquet");
+        let mut item = stac::Item::new("an-id").collection("a-collection");
+        item.geometry = Some(geojson::Geometry::new(geojson::Value::Point(vec![
+            -105.1, 41.1,
+        ])));
+        let collection = stac::Collection::new("a-collection", "An embedded collection");
+        stac::geoparquet::into_writer_with_collections(
+            std::fs::File::create(&path).unwrap(),
+            vec![item],
+            [collection],
+        )
+        .unwrap();
+        let collections = client.collections(path.to_str().unwrap()).unwrap();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].description, "An embedded collection");
+    }
+
+    #[rstest]
+    fn queryables(client: Client) {
+        let queryables = client
+            .queryables("data/100-sentinel-2-items.parquet")
+            .unwrap();
+        let properties = queryables["properties"].as_object().unwrap();
+        assert_eq!(properties["datetime"]["format"], "date-time");
+        assert_eq!(properties["eo:cloud_cover"]["maximum"], 100);
+        assert_eq!(
+            properties["geometry"]["$ref"],
+            "https://geojson.org/schema/Geometry.json"
+        );
+        assert!(!properties.contains_key("assets"));
+        assert!(!properties.contains_key("links"));
+    }
+
+    #[test]
+    fn property_type() {
+        use stac::schema::PropertyType;
+
+        assert_eq!(super::property_type("DOUBLE"), Some(PropertyType::Number));
+        assert_eq!(super::property_type("VARCHAR[]"), Some(PropertyType::Array));
+        assert_eq!(super::property_type("UBIGINT"), Some(PropertyType::Integer));
+        assert_eq!(
+            super::property_type("TIMESTAMP WITH TIME ZONE"),
+            Some(PropertyType::Datetime)
+        );
+        assert!(super::property_type("BLOB").is_none());
+    }
+
+    #[test]
+    fn is_io() {
+        use duckdb::ffi::{Error as FfiError, ErrorCode};
+
+        let failure = |code, message: Option<&str>| {
+            Error::from(duckdb::Error::DuckDBFailure(
+                FfiError {
+                    code,
+                    extended_code: 1,
+                },
+                message.map(String::from),
+            ))
+        };
+        assert!(failure(ErrorCode::CannotOpen, None).is_io());
+        assert!(failure(
+            ErrorCode::Unknown,
+            Some("IO Error: No files found that match the pattern \"foo.parquet\"")
+        )
+        .is_io());
+        assert!(!failure(
+            ErrorCode::Unknown,
+            Some("Binder Error: Referenced column \"foo\" not found")
+        )
+        .is_io());
+        assert!(!Error::InvalidPageToken("a-token".to_string()).is_io());
+    }
 }
@@ -3,13 +3,18 @@
 //! [Assets](stac::Asset) and [Links](stac::Link) that align with the [OpenAPI
 //! security spec](https://swagger.io/docs/specification/authentication/).
 
-use crate::Extension;
+use crate::StacExtension;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use stac::{Error, Fields, Result};
 use std::collections::HashMap;
 
+const REFS: &str = "auth:refs";
+
 /// The authentication extension fields.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, StacExtension)]
+#[stac_extension(prefix = "auth", name = "authentication", version = "v1.1.0")]
+#[non_exhaustive]
 pub struct Authentication {
     /// A property that contains all of the [scheme definitions](Scheme) used by
     /// [Assets](stac::Asset) and [Links](stac::Link) in the STAC [Item](crate::Item) or [Collection](crate::Collection).
@@ -18,60 +23,112 @@ pub struct Authentication {
 
     /// A property that specifies which schemes may be used to access an [Asset](stac::Asset)
     /// or [Link](stac::Link).
+    ///
+    /// Usually set on the asset or link itself, see [refs].
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub refs: Vec<String>,
+
+    /// Additional fields not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 /// The Authentication Scheme extends the [OpenAPI security
 /// spec](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#security-scheme-object)
 /// for support of OAuth2.0, API Key, and OpenID Connect authentication.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Scheme {
-    /// The authentication scheme type used to access the data (`http` | `s3` |
-    /// `signedUrl` | `oauth2` | `apiKey` | `openIdConnect` | a custom scheme type ).
-    pub r#type: String,
+///
+/// Schemes are tagged by their `type`. Any scheme type that isn't defined by
+/// the extension is kept as a [Scheme::Custom].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Scheme {
+    /// HTTP authentication, e.g. basic or bearer.
+    #[serde(rename = "http")]
+    Http {
+        /// The name of the HTTP Authorization scheme to be used in the Authorization header as defined in RFC7235.
+        ///
+        /// The values used SHOULD be registered in the IANA Authentication Scheme registry.
+        /// (`basic` | `bearer` | `digest` | `dpop` | `hoba` | `mutual` |
+        /// `negotiate` | `oauth` (1.0) | `privatetoken` | `scram-sha-1` |
+        /// `scram-sha-256` | `vapid`)
+        scheme: String,
 
-    /// Additional instructions for authentication.
-    ///
-    /// [CommonMark 0.29](https://commonmark.org/) syntax MAY be used for rich text representation.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
 
-    /// The name of the header, query, or cookie parameter to be used.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
+    /// Simple S3 authentication.
+    #[serde(rename = "s3")]
+    S3 {
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
 
-    /// The location of the API key (`query` | `header` | `cookie`).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub r#in: Option<In>,
+    /// A signed URL API.
+    #[serde(rename = "signedUrl")]
+    SignedUrl {
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
 
-    /// The name of the HTTP Authorization scheme to be used in the Authorization header as defined in RFC7235.
-    ///
-    /// The values used SHOULD be registered in the IANA Authentication Scheme registry.
-    /// (`basic` | `bearer` | `digest` | `dpop` | `hoba` | `mutual` |
-    /// `negotiate` | `oauth` (1.0) | `privatetoken` | `scram-sha-1` |
-    /// `scram-sha-256` | `vapid`)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub scheme: Option<String>,
+        /// The signed URL flows, by name.
+        #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+        flows: HashMap<String, Flow>,
+    },
 
-    /// Scenarios an API client performs to get an access token from the authorization server.
-    ///
-    /// For oauth2 the following keys are pre-defined for the corresponding
-    /// OAuth flows: `authorizationCode` | `implicit` | `password` |
-    /// `clientCredentials`.  The OAuth2 Flow Object applies for oauth2, the
-    /// Signed URL Object applies to signedUrl.
-    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
-    pub flows: HashMap<String, Flow>,
-
-    /// OpenID Connect URL to discover OpenID configuration values.
-    ///
-    /// This MUST be in the form of a URL.
-    #[serde(skip_serializing_if = "Option::is_none", rename = "openIdConnectUrl")]
-    pub open_id_connect_url: Option<String>,
+    /// OAuth 2.0.
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+
+        /// Scenarios an API client performs to get an access token from the authorization server.
+        ///
+        /// The following keys are pre-defined for the corresponding OAuth
+        /// flows: `authorizationCode` | `implicit` | `password` |
+        /// `clientCredentials`.
+        #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+        flows: HashMap<String, Flow>,
+    },
+
+    /// An API key.
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+
+        /// The name of the header, query, or cookie parameter to be used.
+        name: String,
+
+        /// The location of the API key.
+        r#in: In,
+    },
+
+    /// OpenID Connect.
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect {
+        /// Additional instructions for authentication.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+
+        /// OpenID Connect URL to discover OpenID configuration values.
+        ///
+        /// This MUST be in the form of a URL.
+        #[serde(rename = "openIdConnectUrl")]
+        open_id_connect_url: String,
+    },
+
+    /// A custom scheme type, including its `type` field.
+    #[serde(untagged)]
+    Custom(Map<String, Value>),
 }
 
 /// The OAuth2 Flow Object applies for oauth2, the Signed URL Object applies to signedUrl.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Flow {
     /// Based on the [OpenAPI OAuth Flow
@@ -125,7 +182,7 @@ pub enum Flow {
 }
 
 /// Definition for a request parameter.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     /// The location of the parameter (`query` | `header` | `body`).
     pub r#in: String,
@@ -142,7 +199,7 @@ pub struct Parameter {
 }
 
 /// Query, header, or cookie.
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
 pub enum In {
     /// In the GET query string.
     #[serde(rename = "query")]
@@ -158,24 +215,159 @@ pub enum In {
     Cookie,
 }
 
-impl Extension for Authentication {
-    const IDENTIFIER: &'static str =
-        "https://stac-extensions.github.io/authentication/v1.1.0/schema.json";
-    const PREFIX: &'static str = "auth";
+impl Authentication {
+    /// Returns the scheme with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::{authentication::Authentication, Extensions};
+    ///
+    /// let item: Item = stac::read("data/auth/item.json").unwrap();
+    /// let authentication: Authentication = item.extension().unwrap();
+    /// let scheme = authentication.scheme("oauth").unwrap();
+    /// assert_eq!(scheme.r#type(), "oauth2");
+    /// ```
+    pub fn scheme(&self, name: &str) -> Option<&Scheme> {
+        self.schemes.get(name)
+    }
+
+    /// Returns the named schemes referenced by an asset's or link's `auth:refs`.
+    ///
+    /// Clients can use this to pick credentials for an asset automatically.
+    /// References to schemes that aren't defined here are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::{authentication::{Authentication, Scheme}, Extensions};
+    ///
+    /// let item: Item = stac::read("data/auth/item.json").unwrap();
+    /// let authentication: Authentication = item.extension().unwrap();
+    /// let schemes = authentication.schemes_for(&item.assets["data"]).unwrap();
+    /// assert_eq!(schemes[0].0, "oauth");
+    /// assert!(matches!(schemes[0].1, Scheme::OAuth2 { .. }));
+    /// ```
+    pub fn schemes_for(&self, object: &impl Fields) -> Result<Vec<(&str, &Scheme)>> {
+        Ok(refs(object)?
+            .iter()
+            .filter_map(|name| self.schemes.get_key_value(name))
+            .map(|(name, scheme)| (name.as_str(), scheme))
+            .collect())
+    }
+}
+
+impl Scheme {
+    /// Returns this scheme's `type`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::authentication::Scheme;
+    ///
+    /// let scheme = Scheme::S3 { description: None };
+    /// assert_eq!(scheme.r#type(), "s3");
+    /// ```
+    pub fn r#type(&self) -> &str {
+        match self {
+            Scheme::Http { .. } => "http",
+            Scheme::S3 { .. } => "s3",
+            Scheme::SignedUrl { .. } => "signedUrl",
+            Scheme::OAuth2 { .. } => "oauth2",
+            Scheme::ApiKey { .. } => "apiKey",
+            Scheme::OpenIdConnect { .. } => "openIdConnect",
+            Scheme::Custom(map) => map.get("type").and_then(|v| v.as_str()).unwrap_or_default(),
+        }
+    }
+
+    /// Returns this scheme's description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::authentication::Scheme;
+    ///
+    /// let scheme = Scheme::S3 { description: Some("Requester pays".to_string()) };
+    /// assert_eq!(scheme.description().unwrap(), "Requester pays");
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Scheme::Http { description, .. }
+            | Scheme::S3 { description }
+            | Scheme::SignedUrl { description, .. }
+            | Scheme::OAuth2 { description, .. }
+            | Scheme::ApiKey { description, .. }
+            | Scheme::OpenIdConnect { description, .. } => description.as_deref(),
+            Scheme::Custom(map) => map.get("description").and_then(|v| v.as_str()),
+        }
+    }
+}
+
+/// Returns the `auth:refs` of an asset or link.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Item;
+///
+/// let item: Item = stac::read("data/auth/item.json").unwrap();
+/// let refs = stac_extensions::authentication::refs(&item.assets["data"]).unwrap();
+/// assert_eq!(refs, ["oauth"]);
+/// ```
+pub fn refs(object: &impl Fields) -> Result<Vec<String>> {
+    object
+        .field(REFS)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|source| Error::InvalidField {
+            key: REFS.to_string(),
+            source,
+        })
+}
+
+/// Sets the `auth:refs` of an asset or link, removing the field if `refs` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Asset;
+///
+/// let mut asset = Asset::new("https://example.com/data.tif");
+/// stac_extensions::authentication::set_refs(&mut asset, ["oauth"]);
+/// assert_eq!(asset.additional_fields["auth:refs"][0], "oauth");
+/// ```
+pub fn set_refs(object: &mut impl Fields, refs: impl IntoIterator<Item = impl ToString>) {
+    let refs: Vec<Value> = refs
+        .into_iter()
+        .map(|name| Value::String(name.to_string()))
+        .collect();
+    if refs.is_empty() {
+        let _ = object.fields_mut().remove(REFS);
+    } else {
+        let _ = object
+            .fields_mut()
+            .insert(REFS.to_string(), Value::Array(refs));
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Authentication, In, Scheme};
+    use super::{Authentication, Flow, In, Scheme};
     use crate::{Collection, Extensions, Item};
     use serde_json::json;
+    use stac::Links;
 
     #[test]
     fn collection() {
         let collection: Collection = stac::read("data/auth/collection.json").unwrap();
         let authentication: Authentication = collection.extension().unwrap();
-        let oauth = authentication.schemes.get("oauth").unwrap();
-        let _ = oauth.flows.get("authorizationCode").unwrap();
+        let Scheme::OAuth2 { flows, .. } = authentication.schemes.get("oauth").unwrap() else {
+            panic!("should be an oauth2 scheme");
+        };
+        let _ = flows.get("authorizationCode").unwrap();
     }
 
     #[test]
@@ -193,6 +385,54 @@ mod tests {
           "name": "API_KEY"
         }))
         .unwrap();
-        assert_eq!(scheme.r#in.unwrap(), In::Query);
+        let Scheme::ApiKey { r#in, name, .. } = scheme else {
+            panic!("not an api key scheme: {:?}", scheme);
+        };
+        assert_eq!(r#in, In::Query);
+        assert_eq!(name, "API_KEY");
+    }
+
+    #[test]
+    fn custom() {
+        let value = json!({
+          "type": "my-custom-scheme",
+          "description": "a custom scheme"
+        });
+        let scheme: Scheme = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(scheme, Scheme::Custom(_)));
+        assert_eq!(scheme.r#type(), "my-custom-scheme");
+        assert_eq!(scheme.description().unwrap(), "a custom scheme");
+        assert_eq!(serde_json::to_value(scheme).unwrap(), value);
+    }
+
+    #[test]
+    fn signed_url() {
+        let collection: Collection = stac::read("data/auth/collection.json").unwrap();
+        let authentication: Authentication = collection.extension().unwrap();
+        let schemes = authentication
+            .schemes_for(&collection.assets["example"])
+            .unwrap();
+        assert_eq!(schemes.len(), 1);
+        let (name, Scheme::SignedUrl { flows, .. }) = schemes[0] else {
+            panic!("not a signed url scheme: {:?}", schemes[0]);
+        };
+        assert_eq!(name, "signed_url_auth");
+        assert!(matches!(flows["auth"], Flow::SignedUrl { .. }));
+    }
+
+    #[test]
+    fn link_refs() {
+        let collection: Collection = stac::read("data/auth/collection.json").unwrap();
+        let link = collection.link("item").unwrap();
+        assert_eq!(super::refs(link).unwrap(), ["oauth"]);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let collection: Collection = stac::read("data/auth/collection.json").unwrap();
+        let authentication: Authentication = collection.extension().unwrap();
+        let value = serde_json::to_value(&authentication).unwrap();
+        let other: Authentication = serde_json::from_value(value).unwrap();
+        assert_eq!(authentication, other);
     }
 }
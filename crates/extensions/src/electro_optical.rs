@@ -1,7 +1,8 @@
 //! The [electro-optical](https://github.com/stac-extensions/eo) extension.
 
-use crate::Extension;
+use crate::StacExtension;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// EO data is considered to be data that represents a snapshot of the Earth for
 /// a single date and time.
@@ -10,7 +11,9 @@ use serde::{Deserialize, Serialize};
 /// electromagnetic spectrum. Examples of EO data include sensors with visible,
 /// short-wave and mid-wave IR bands (e.g., the OLI instrument on Landsat-8),
 /// long-wave IR bands (e.g. TIRS aboard Landsat-8).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, StacExtension)]
+#[stac_extension(prefix = "eo", version = "v1.1.0")]
+#[non_exhaustive]
 pub struct ElectroOptical {
     /// An array of available bands where each object is a [Band].
     ///
@@ -25,12 +28,17 @@ pub struct ElectroOptical {
     /// Estimate of snow and ice cover, in %.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snow_cover: Option<f64>,
+
+    /// Additional fields not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 /// [Spectral
 /// bands](https://www.sciencedirect.com/topics/earth-and-planetary-sciences/spectral-band)
 /// in an [Asset](stac::Asset).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct Band {
     /// The name of the band (e.g., "B01", "B8", "band2", "red").
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -62,11 +70,10 @@ pub struct Band {
     /// The solar illumination of the band, as measured at half the maximum transmission, in W/m2/micrometers.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub solar_illumination: Option<f64>,
-}
 
-impl Extension for ElectroOptical {
-    const IDENTIFIER: &'static str = "https://stac-extensions.github.io/eo/v1.1.0/schema.json";
-    const PREFIX: &'static str = "eo";
+    /// Additional fields on the band not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 #[cfg(test)]
@@ -14,9 +14,11 @@
 //! | [Electro-Optical](https://github.com/stac-extensions/eo) | Stable | v1.1.0 |
 //! | [File Info](https://github.com/stac-extensions/file) | Stable | n/a |
 //! | [Landsat](https://github.com/stac-extensions/landsat) | Stable | n/a |
+//! | [Processing](https://github.com/stac-extensions/processing) | Candidate | v1.2.0 |
 //! | [Projection](https://github.com/stac-extensions/projection) | Stable | v1.1.0 |
 //! | [Raster](https://github.com/stac-extensions/raster) | Candidate | v1.1.0 |
 //! | [Scientific Citation](https://github.com/stac-extensions/scientific) | Stable | n/a |
+//! | [Versioning Indicators](https://github.com/stac-extensions/version) | Stable | v1.2.0 |
 //! | [View Geometry](https://github.com/stac-extensions/view) | Stable | n/a |
 //!
 //! ## Usage
@@ -43,15 +45,42 @@
 //! assert!(!item.has_extension::<Projection>());
 //! ```
 
+extern crate self as stac_extensions;
+
 pub mod authentication;
 pub mod electro_optical;
+pub mod processing;
 pub mod projection;
 pub mod raster;
+pub mod version;
 
+pub use processing::Processing;
 pub use projection::Projection;
 pub use raster::Raster;
 use serde::{de::DeserializeOwned, Serialize};
-use stac::{Catalog, Collection, Error, Fields, Item, Result};
+use stac::{collection, item, Catalog, Collection, Fields, Item, Result};
+/// Derives [Extension] for an extension's fields struct.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use stac_extensions::{Extension, StacExtension};
+///
+/// #[derive(Serialize, Deserialize, StacExtension)]
+/// #[stac_extension(prefix = "sci", name = "scientific", version = "v1.0.0")]
+/// struct Scientific {
+///     doi: Option<String>,
+///     citation: Option<String>,
+/// }
+///
+/// assert_eq!(
+///     Scientific::IDENTIFIER,
+///     "https://stac-extensions.github.io/scientific/v1.0.0/schema.json"
+/// );
+/// assert_eq!(Scientific::FIELDS, ["sci:doi", "sci:citation"]);
+/// ```
+pub use stac_derive::StacExtension;
 
 /// A trait implemented by extensions.
 ///
@@ -119,7 +148,8 @@ pub trait Extensions: Fields {
     ///
     /// let mut item = Item::new("an-id");
     /// assert!(!item.has_extension::<Projection>());
-    /// let projection = Projection { code: Some("EPSG:4326".to_string()), ..Default::default() };
+    /// let mut projection = Projection::default();
+    /// projection.code = Some("EPSG:4326".to_string());
     /// item.set_extension(projection).unwrap();
     /// assert!(item.has_extension::<Projection>());
     /// ```
@@ -142,7 +172,7 @@ pub trait Extensions: Fields {
     /// assert_eq!(projection.code.unwrap(), "EPSG:32614");
     /// ```
     fn extension<E: Extension>(&self) -> Result<E> {
-        self.fields_with_prefix(E::PREFIX).map_err(Error::from)
+        self.fields_with_prefix(E::PREFIX)
     }
 
     /// Adds an extension's identifier to this object.
@@ -172,7 +202,8 @@ pub trait Extensions: Fields {
     /// use stac_extensions::{Projection, Extensions};
     ///
     /// let mut item = Item::new("an-id");
-    /// let projection = Projection { code: Some("EPSG:4326".to_string()), ..Default::default() };
+    /// let mut projection = Projection::default();
+    /// projection.code = Some("EPSG:4326".to_string());
     /// item.set_extension(projection).unwrap();
     /// ```
     fn set_extension<E: Extension>(&mut self, extension: E) -> Result<()> {
@@ -180,7 +211,6 @@ pub trait Extensions: Fields {
         self.extensions_mut().dedup();
         self.remove_fields_with_prefix(E::PREFIX);
         self.set_fields_with_prefix(E::PREFIX, extension)
-            .map_err(Error::from)
     }
 
     /// Removes this extension and all of its fields from this object.
@@ -203,6 +233,44 @@ pub trait Extensions: Fields {
     }
 }
 
+/// A trait for builders that can add typed STAC extensions.
+pub trait WithExtension: Fields + Sized {
+    /// Adds an extension's identifier to the builder and sets its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::{Extensions, Projection, WithExtension};
+    ///
+    /// let mut projection = Projection::default();
+    /// projection.code = Some("EPSG:4326".to_string());
+    /// let item = Item::builder("an-id")
+    ///     .with_extension(projection)
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(item.has_extension::<Projection>());
+    /// ```
+    fn with_extension<E: Extension>(self, extension: E) -> Result<Self>;
+}
+
+impl WithExtension for item::Builder {
+    fn with_extension<E: Extension>(self, extension: E) -> Result<Self> {
+        let mut builder = self.extension(E::IDENTIFIER);
+        builder.set_fields_with_prefix(E::PREFIX, extension)?;
+        Ok(builder)
+    }
+}
+
+impl WithExtension for collection::Builder {
+    fn with_extension<E: Extension>(self, extension: E) -> Result<Self> {
+        let mut builder = self.extension(E::IDENTIFIER);
+        builder.set_fields_with_prefix(E::PREFIX, extension)?;
+        Ok(builder)
+    }
+}
+
 macro_rules! impl_extensions {
     ($name:ident) => {
         impl Extensions for $name {
@@ -222,9 +290,71 @@ impl_extensions!(Collection);
 
 #[cfg(test)]
 mod tests {
-    use crate::{raster::Raster, Extension, Extensions, Projection};
+    use crate::{raster::Raster, Extension, Extensions, Projection, WithExtension};
     use serde_json::json;
-    use stac::Item;
+    use stac::{Collection, Fields, Item};
+
+    #[test]
+    fn collection_builder_with_extension() {
+        let projection = Projection {
+            code: Some("EPSG:4326".to_string()),
+            ..Default::default()
+        };
+        let collection = Collection::builder("an-id", "a description")
+            .with_extension(projection)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(collection.has_extension::<Projection>());
+        assert_eq!(collection.additional_fields["proj:code"], "EPSG:4326");
+    }
+
+    #[test]
+    fn derived() {
+        assert_eq!(
+            Projection::IDENTIFIER,
+            "https://stac-extensions.github.io/projection/v2.0.0/schema.json"
+        );
+        assert_eq!(Projection::PREFIX, "proj");
+        assert!(Projection::FIELDS.contains(&"proj:code"));
+        assert_eq!(
+            crate::electro_optical::ElectroOptical::IDENTIFIER,
+            "https://stac-extensions.github.io/eo/v1.1.0/schema.json"
+        );
+    }
+
+    #[test]
+    fn roundtrip_unknown_fields() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .set_field(
+                "raster:bands",
+                json!([{"nodata": 0, "not-a-raster-field": {"nested": true}}]),
+            )
+            .unwrap();
+        let _ = item.set_field("raster:also-unknown", 42).unwrap();
+        let raster: Raster = item.extension().unwrap();
+        item.set_extension(raster).unwrap();
+        assert_eq!(
+            item.properties.additional_fields["raster:bands"],
+            json!([{"nodata": 0.0, "not-a-raster-field": {"nested": true}}])
+        );
+        assert_eq!(item.properties.additional_fields["raster:also-unknown"], 42);
+    }
+
+    #[test]
+    fn invalid_field() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .set_field("raster:bands", json!([{"nodata": "not a number"}]))
+            .unwrap();
+        let err = item.extension::<Raster>().unwrap_err();
+        assert!(
+            matches!(err, stac::Error::InvalidField { ref key, .. } if key == "raster:bands[0].nodata"),
+            "{:?}",
+            err
+        );
+    }
 
     #[test]
     fn identifer_prefix() {
@@ -0,0 +1,154 @@
+//! The [processing extension](https://github.com/stac-extensions/processing)
+//! records how an [Item](stac::Item) or [Collection](stac::Collection) was
+//! produced.
+//!
+//! Together with [Item::add_derived_from](stac::Item::add_derived_from), this
+//! lets pipelines emit outputs that can be traced back to their inputs and
+//! the software that made them.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::Item;
+//! use stac_extensions::{Extensions, Processing};
+//!
+//! let source: Item = stac::read("examples/simple-item.json").unwrap();
+//! let mut item = Item::new("derived-item");
+//! item.add_derived_from(&source).unwrap();
+//! let processing = Processing::new()
+//!     .software("my-pipeline", "1.2.3")
+//!     .datetime(chrono::Utc::now());
+//! item.set_extension(processing).unwrap();
+//! assert!(item.has_extension::<Processing>());
+//! ```
+
+use crate::StacExtension;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The processing extension fields.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone, StacExtension)]
+#[stac_extension(prefix = "processing", name = "processing", version = "v1.2.0")]
+#[non_exhaustive]
+pub struct Processing {
+    /// An expression or processing chain that describes how the data has been processed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<Expression>,
+
+    /// Lineage information provided as free text about how observations were
+    /// processed or models that were used to create the resource being
+    /// described.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<String>,
+
+    /// The name commonly used to refer to the processing level, e.g. `L1C`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+
+    /// The name of the facility that produced the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facility: Option<String>,
+
+    /// The version of the primary processing software or processing chain
+    /// that produced the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Processing date and time of the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<DateTime<Utc>>,
+
+    /// The software that was used to produce the data, as a map of software
+    /// names to versions.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub software: HashMap<String, String>,
+
+    /// Additional fields not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
+}
+
+/// An expression or processing chain that describes how the data has been processed.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Expression {
+    /// The type of the expression, e.g. `gdal-calc`, `python`, or `docker`.
+    pub format: String,
+
+    /// The expression itself, usually a string but any JSON value is allowed.
+    pub expression: Value,
+}
+
+impl Processing {
+    /// Creates new, empty processing fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::Processing;
+    ///
+    /// let processing = Processing::new();
+    /// ```
+    pub fn new() -> Processing {
+        Processing::default()
+    }
+
+    /// Adds a piece of software and its version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::Processing;
+    ///
+    /// let processing = Processing::new().software("stac-rs", "0.11.0");
+    /// assert_eq!(processing.software["stac-rs"], "0.11.0");
+    /// ```
+    pub fn software(mut self, name: impl ToString, version: impl ToString) -> Processing {
+        let _ = self.software.insert(name.to_string(), version.to_string());
+        self
+    }
+
+    /// Sets the processing datetime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::Processing;
+    ///
+    /// let processing = Processing::new().datetime(chrono::Utc::now());
+    /// assert!(processing.datetime.is_some());
+    /// ```
+    pub fn datetime(mut self, datetime: DateTime<Utc>) -> Processing {
+        self.datetime = Some(datetime);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Processing;
+    use crate::Extensions;
+    use serde_json::json;
+    use stac::Item;
+
+    #[test]
+    fn roundtrip() {
+        let mut item = Item::new("an-id");
+        let processing = Processing::new()
+            .software("stac-rs", "0.11.0")
+            .datetime("2024-01-01T00:00:00Z".parse().unwrap());
+        item.set_extension(processing.clone()).unwrap();
+        let value = serde_json::to_value(&item).unwrap();
+        assert_eq!(
+            value["properties"]["processing:software"],
+            json!({"stac-rs": "0.11.0"})
+        );
+        assert_eq!(
+            value["properties"]["processing:datetime"],
+            "2024-01-01T00:00:00Z"
+        );
+        assert!(value["properties"].get("processing:level").is_none());
+        assert_eq!(item.extension::<Processing>().unwrap(), processing);
+    }
+}
@@ -1,12 +1,14 @@
 //! The Projection extension.
 
-use super::Extension;
+use crate::StacExtension;
 use geojson::Geometry;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 /// The projection extension fields.
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone, StacExtension)]
+#[stac_extension(prefix = "proj", name = "projection", version = "v2.0.0")]
+#[non_exhaustive]
 pub struct Projection {
     /// EPSG code of the datasource
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,6 +43,10 @@ pub struct Projection {
     /// The affine transformation coefficients for the default grid
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transform: Option<Vec<f64>>,
+
+    /// Additional fields not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 /// This object represents the centroid of the Item Geometry.
@@ -71,12 +77,6 @@ impl Projection {
     }
 }
 
-impl Extension for Projection {
-    const IDENTIFIER: &'static str =
-        "https://stac-extensions.github.io/projection/v2.0.0/schema.json";
-    const PREFIX: &'static str = "proj";
-}
-
 #[cfg(test)]
 mod tests {
     use super::Projection;
@@ -9,21 +9,29 @@
 //! many applications, it is interesting to have some metadata about the rasters
 //! in the asset (values statistics, value interpretation, transforms).
 
-use super::Extension;
+use crate::StacExtension;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 pub use stac::{DataType, Statistics};
 
 /// The raster extension.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, StacExtension)]
+#[stac_extension(prefix = "raster", version = "v1.1.0")]
+#[non_exhaustive]
 pub struct Raster {
     /// An array of available bands where each object is a [Band].
     ///
     /// If given, requires at least one band.
     pub bands: Vec<Band>,
+
+    /// Additional fields not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 /// The bands of a raster asset.
 #[derive(Debug, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct Band {
     /// Pixel values used to identify pixels that are nodata in the band either
     /// by the pixel value as a number or nan, inf or -inf (all strings).
@@ -79,6 +87,10 @@ pub struct Band {
     /// Histogram distribution information of the pixels values in the band.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub histogram: Option<Histogram>,
+
+    /// Additional fields on the band not modeled by this struct.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
 }
 
 /// Indicates whether a pixel value should be assumed
@@ -114,11 +126,6 @@ pub struct Histogram {
     pub buckets: Vec<u64>,
 }
 
-impl Extension for Raster {
-    const IDENTIFIER: &'static str = "https://stac-extensions.github.io/raster/v1.1.0/schema.json";
-    const PREFIX: &'static str = "raster";
-}
-
 impl Raster {
     /// Returns true if this raster structure is empty.
     ///
@@ -0,0 +1,398 @@
+//! The [versioning indicators
+//! extension](https://github.com/stac-extensions/version) describes the
+//! version of an [Item] or [Collection] and links it to its other versions.
+//!
+//! Unlike most extensions, the versioning fields (`version`, `deprecated`, and
+//! `experimental`) don't have a prefix, so they're accessed through the
+//! [Versioned] trait instead of [Extensions::extension].
+//!
+//! # Examples
+//!
+//! ```
+//! use stac::{Item, Link, Links};
+//! use stac_extensions::version::{self, Versioned};
+//!
+//! let mut old = Item::new("an-id");
+//! old.set_version("1").unwrap();
+//! old.set_link(Link::self_("https://stac.test/items/an-id-v1.json"));
+//! let mut new = Item::new("an-id");
+//! new.set_version("2").unwrap();
+//! new.set_link(Link::self_("https://stac.test/items/an-id-v2.json"));
+//!
+//! version::link_versions(&mut old, &mut new).unwrap();
+//! assert!(old.is_deprecated());
+//! assert_eq!(
+//!     old.latest_version_link().unwrap().href,
+//!     "https://stac.test/items/an-id-v2.json"
+//! );
+//! ```
+
+use crate::Extensions;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use stac::{Collection, Error, Fields, Href, Item, Link, Links, Result, SelfHref};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// The versioning indicators extension schema identifier.
+pub const IDENTIFIER: &str = "https://stac-extensions.github.io/version/v1.2.0/schema.json";
+
+/// Everything in an identifier before the version, used to find the extension.
+const IDENTIFIER_PREFIX: &str = "https://stac-extensions.github.io/version/";
+
+/// The rel type of a link to the latest version.
+pub const LATEST_VERSION_REL: &str = "latest-version";
+
+/// The rel type of a link to the previous version.
+pub const PREDECESSOR_VERSION_REL: &str = "predecessor-version";
+
+/// The rel type of a link to the next version.
+pub const SUCCESSOR_VERSION_REL: &str = "successor-version";
+
+/// The rel type of a link to a version history or changelog.
+pub const VERSION_HISTORY_REL: &str = "version-history";
+
+/// The versioning indicators fields.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Version {
+    /// The version of the item or collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Is this item or collection deprecated in favor of another one?
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+
+    /// Is this item or collection experimental, i.e. not yet fit for production use?
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub experimental: bool,
+}
+
+/// Implemented by objects that can carry versioning indicators.
+pub trait Versioned: Extensions + Links + SelfHref {
+    /// Returns this object's versioning fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::version::Versioned;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_version("1.0.0").unwrap();
+    /// assert_eq!(item.versioning().unwrap().version.unwrap(), "1.0.0");
+    /// ```
+    fn versioning(&self) -> Result<Version> {
+        let mut map = Map::new();
+        for key in FIELDS {
+            if let Some(value) = self.field(key) {
+                let _ = map.insert(key.to_string(), value.clone());
+            }
+        }
+        serde_json::from_value(Value::Object(map)).map_err(Error::from)
+    }
+
+    /// Sets this object's versioning fields and adds the extension.
+    ///
+    /// Fields that are `None` or `false` are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::version::{Version, Versioned};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_versioning(Version { experimental: true, ..Default::default() }).unwrap();
+    /// assert!(item.versioning().unwrap().experimental);
+    /// ```
+    fn set_versioning(&mut self, version: Version) -> Result<()> {
+        if !self
+            .extensions()
+            .iter()
+            .any(|extension| extension.starts_with(IDENTIFIER_PREFIX))
+        {
+            self.extensions_mut().push(IDENTIFIER.to_string());
+        }
+        for key in FIELDS {
+            let _ = self.fields_mut().remove(key);
+        }
+        if let Value::Object(object) = serde_json::to_value(version)? {
+            for (key, value) in object {
+                let _ = self.set_field(key, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets this object's version, keeping its other versioning fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::version::Versioned;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.set_version("2").unwrap();
+    /// ```
+    fn set_version(&mut self, version: impl ToString) -> Result<()> {
+        let mut versioning = self.versioning()?;
+        versioning.version = Some(version.to_string());
+        self.set_versioning(versioning)
+    }
+
+    /// Returns true if this object is marked as deprecated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::version::Versioned;
+    ///
+    /// let item = Item::new("an-id");
+    /// assert!(!item.is_deprecated());
+    /// ```
+    fn is_deprecated(&self) -> bool {
+        self.field("deprecated")
+            .and_then(Value::as_bool)
+            .unwrap_or_default()
+    }
+
+    /// Marks this object as deprecated, optionally linking to the latest version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::version::Versioned;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.deprecate(Some("./an-id-v2.json".into())).unwrap();
+    /// assert!(item.is_deprecated());
+    /// assert!(item.latest_version_link().is_some());
+    /// ```
+    fn deprecate(&mut self, latest: Option<Href>) -> Result<()> {
+        let mut versioning = self.versioning()?;
+        versioning.deprecated = true;
+        self.set_versioning(versioning)?;
+        if let Some(href) = latest {
+            self.set_link(Link::new(href, LATEST_VERSION_REL));
+        }
+        Ok(())
+    }
+
+    /// Returns the link to the latest version, if there is one.
+    fn latest_version_link(&self) -> Option<&Link> {
+        self.link(LATEST_VERSION_REL)
+    }
+
+    /// Returns the link to the previous version, if there is one.
+    fn predecessor_version_link(&self) -> Option<&Link> {
+        self.link(PREDECESSOR_VERSION_REL)
+    }
+
+    /// Returns the link to the next version, if there is one.
+    fn successor_version_link(&self) -> Option<&Link> {
+        self.link(SUCCESSOR_VERSION_REL)
+    }
+}
+
+impl Versioned for Item {}
+impl Versioned for Collection {}
+
+/// Links two versions of an object to each other and deprecates the older one.
+///
+/// The predecessor gets `successor-version` and `latest-version` links to the
+/// successor, and the successor gets a `predecessor-version` link. Each
+/// object's href is its [self href](SelfHref) or, if that isn't set, its
+/// `self` link.
+///
+/// Returns [Error::NoHref] if either object doesn't have an href.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, Link, Links};
+/// use stac_extensions::version::{self, Versioned};
+///
+/// let mut old = Item::new("an-id");
+/// old.set_link(Link::self_("./v1.json"));
+/// let mut new = Item::new("an-id");
+/// new.set_link(Link::self_("./v2.json"));
+/// version::link_versions(&mut old, &mut new).unwrap();
+/// assert_eq!(new.predecessor_version_link().unwrap().href, "./v1.json");
+/// ```
+pub fn link_versions<T: Versioned>(predecessor: &mut T, successor: &mut T) -> Result<()> {
+    let predecessor_href = href(predecessor).ok_or(Error::NoHref)?;
+    let successor_href = href(successor).ok_or(Error::NoHref)?;
+    predecessor.deprecate(Some(successor_href.clone()))?;
+    predecessor.set_link(Link::new(successor_href, SUCCESSOR_VERSION_REL));
+    successor.set_link(Link::new(predecessor_href, PREDECESSOR_VERSION_REL));
+    Ok(())
+}
+
+/// Keeps only the newest version of each item.
+///
+/// Items are keyed by their collection and id. Within a key, non-deprecated
+/// items are preferred over deprecated ones, then the greatest version (see
+/// [compare]), then the item that came last. The kept items are returned in
+/// the order their keys were first seen.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Item;
+/// use stac_extensions::version::{self, Versioned};
+///
+/// let mut a = Item::new("an-id");
+/// a.set_version("1.10.0").unwrap();
+/// let mut b = Item::new("an-id");
+/// b.set_version("1.9.0").unwrap();
+/// let items = version::dedup(vec![a, b, Item::new("another-id")]);
+/// assert_eq!(items.len(), 2);
+/// assert_eq!(items[0].versioning().unwrap().version.unwrap(), "1.10.0");
+/// ```
+pub fn dedup(items: impl IntoIterator<Item = Item>) -> Vec<Item> {
+    let mut indices: HashMap<(Option<String>, String), usize> = HashMap::new();
+    let mut kept: Vec<Item> = Vec::new();
+    for item in items {
+        let key = (item.collection.clone(), item.id.clone());
+        if let Some(&index) = indices.get(&key) {
+            if rank(&item) >= rank(&kept[index]) {
+                kept[index] = item;
+            }
+        } else {
+            let _ = indices.insert(key, kept.len());
+            kept.push(item);
+        }
+    }
+    kept
+}
+
+/// Compares two version strings.
+///
+/// Versions are split on `.`, `-`, and `+`, and the parts are compared in
+/// order, numerically if both parts are numbers and as strings otherwise. A
+/// version with more parts is greater than one it starts with.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use stac_extensions::version;
+///
+/// assert_eq!(version::compare("1.10.0", "1.9.0"), Ordering::Greater);
+/// assert_eq!(version::compare("2", "2.0"), Ordering::Less);
+/// assert_eq!(version::compare("2024-01-02", "2024-01-10"), Ordering::Less);
+/// ```
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let mut a = a.split(['.', '-', '+']);
+    let mut b = b.split(['.', '-', '+']);
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    _ => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+const FIELDS: [&str; 3] = ["version", "deprecated", "experimental"];
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+fn href<T: Versioned>(value: &T) -> Option<Href> {
+    value
+        .self_href()
+        .cloned()
+        .or_else(|| value.self_link().map(|link| link.href.clone()))
+}
+
+/// Non-deprecated items outrank deprecated ones, then greater versions win.
+fn rank(item: &Item) -> (bool, VersionKey<'_>) {
+    (
+        !item.is_deprecated(),
+        VersionKey(item.field("version").and_then(Value::as_str)),
+    )
+}
+
+#[derive(PartialEq, Eq)]
+struct VersionKey<'a>(Option<&'a str>);
+
+impl PartialOrd for VersionKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VersionKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0, other.0) {
+            (Some(a), Some(b)) => compare(a, b),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Version, Versioned, IDENTIFIER};
+    use crate::Extensions;
+    use stac::{Fields, Item, Links};
+
+    #[test]
+    fn roundtrip() {
+        let mut item = Item::new("an-id");
+        item.set_version("1").unwrap();
+        item.deprecate(None).unwrap();
+        assert_eq!(item.extensions, [IDENTIFIER]);
+        assert_eq!(item.field("version").unwrap().as_str(), Some("1"));
+        assert_eq!(item.field("deprecated").unwrap().as_bool(), Some(true));
+        assert!(item.field("experimental").is_none());
+        assert_eq!(
+            item.versioning().unwrap(),
+            Version {
+                version: Some("1".to_string()),
+                deprecated: true,
+                experimental: false,
+            }
+        );
+        item.set_version("2").unwrap();
+        assert_eq!(item.extensions().len(), 1);
+        assert!(item.is_deprecated());
+    }
+
+    #[test]
+    fn link_versions_requires_href() {
+        let mut old = Item::new("an-id");
+        let mut new = Item::new("an-id");
+        let _ = super::link_versions(&mut old, &mut new).unwrap_err();
+        assert!(old.links().is_empty());
+    }
+
+    #[test]
+    fn dedup_prefers_not_deprecated() {
+        let mut a = Item::new("an-id");
+        a.set_version("2").unwrap();
+        a.deprecate(None).unwrap();
+        let mut b = Item::new("an-id");
+        b.set_version("1").unwrap();
+        let mut c = Item::new("an-id").collection("another-collection");
+        c.set_version("3").unwrap();
+        let items = super::dedup(vec![a, b, c]);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].versioning().unwrap().version.unwrap(), "1");
+        assert_eq!(items[1].collection.as_deref(), Some("another-collection"));
+    }
+}
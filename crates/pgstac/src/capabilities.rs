@@ -0,0 +1,207 @@
+use crate::{Error, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// The version-dependent capabilities of a **pgstac** database.
+///
+/// Get these from a connected database with
+/// [Pgstac::capabilities](crate::Pgstac::capabilities).
+///
+/// # Examples
+///
+/// ```
+/// use pgstac::Capabilities;
+///
+/// let capabilities: Capabilities = "0.8.5".parse().unwrap();
+/// assert!(!capabilities.has_numbers());
+/// assert!(!capabilities.has_collection_search());
+///
+/// let capabilities: Capabilities = "0.9.1".parse().unwrap();
+/// assert!(capabilities.has_numbers());
+/// assert!(capabilities.has_collection_search());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    version: String,
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Capabilities {
+    /// Returns the **pgstac** version string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Capabilities;
+    ///
+    /// let capabilities: Capabilities = "0.9.1".parse().unwrap();
+    /// assert_eq!(capabilities.version(), "0.9.1");
+    /// ```
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns true if search results include `numberMatched` and `numberReturned`.
+    ///
+    /// These were added in **pgstac** v0.9.
+    pub fn has_numbers(&self) -> bool {
+        self.is_at_least(0, 9)
+    }
+
+    /// Returns true if the database provides a `collection_search` function.
+    ///
+    /// Collection search was added in **pgstac** v0.9.
+    pub fn has_collection_search(&self) -> bool {
+        self.is_at_least(0, 9)
+    }
+
+    /// Returns an error if the database doesn't provide collection search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Capabilities;
+    ///
+    /// let capabilities: Capabilities = "0.8.5".parse().unwrap();
+    /// assert!(capabilities.require_collection_search().is_err());
+    /// ```
+    pub fn require_collection_search(&self) -> Result<()> {
+        self.require(self.has_collection_search(), "collection search", "0.9.0")
+    }
+
+    /// Returns an error if a paging token doesn't have this database's format.
+    ///
+    /// Tokens start with `next:` or `prev:`. Before **pgstac** v0.9 the rest
+    /// of the token is an item id, while v0.9 and later use
+    /// `<collection>:<item id>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Capabilities;
+    ///
+    /// let capabilities: Capabilities = "0.8.5".parse().unwrap();
+    /// capabilities.check_token("next:an-id").unwrap();
+    ///
+    /// let capabilities: Capabilities = "0.9.1".parse().unwrap();
+    /// capabilities.check_token("next:a-collection:an-id").unwrap();
+    /// assert!(capabilities.check_token("next:an-id").is_err());
+    /// ```
+    pub fn check_token(&self, token: &str) -> Result<()> {
+        let rest = token
+            .strip_prefix("next:")
+            .or_else(|| token.strip_prefix("prev:"))
+            .unwrap_or_default();
+        let valid = if self.is_at_least(0, 9) {
+            rest.split_once(':')
+                .is_some_and(|(collection, id)| !collection.is_empty() && !id.is_empty())
+        } else {
+            !rest.is_empty()
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidToken {
+                token: token.to_string(),
+                version: self.version.clone(),
+            })
+        }
+    }
+
+    fn require(&self, supported: bool, feature: &'static str, minimum: &'static str) -> Result<()> {
+        if supported {
+            Ok(())
+        } else {
+            Err(Error::Unsupported {
+                feature,
+                version: self.version.clone(),
+                minimum,
+            })
+        }
+    }
+
+    fn is_at_least(&self, major: u64, minor: u64) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+impl FromStr for Capabilities {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Capabilities> {
+        let version = s.trim().trim_start_matches('v');
+        let numbers = version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidVersion(s.to_string()))?;
+        match numbers.as_slice() {
+            [major, minor] | [major, minor, _] => Ok(Capabilities {
+                version: version.to_string(),
+                major: *major,
+                minor: *minor,
+                patch: numbers.get(2).copied().unwrap_or_default(),
+            }),
+            _ => Err(Error::InvalidVersion(s.to_string())),
+        }
+    }
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pgstac v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capabilities;
+
+    #[test]
+    fn parse() {
+        let capabilities: Capabilities = "0.9.1".parse().unwrap();
+        assert_eq!(capabilities.to_string(), "pgstac v0.9.1");
+        let capabilities: Capabilities = "v0.8.5-dev".parse().unwrap();
+        assert_eq!(capabilities.version(), "0.8.5-dev");
+        let _ = "not-a-version".parse::<Capabilities>().unwrap_err();
+        let _ = "1".parse::<Capabilities>().unwrap_err();
+    }
+
+    #[test]
+    fn gating() {
+        let capabilities: Capabilities = "0.8.5".parse().unwrap();
+        assert!(!capabilities.has_numbers());
+        let error = capabilities.require_collection_search().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "collection search is not supported by pgstac 0.8.5, it requires at least 0.9.0"
+        );
+
+        let capabilities: Capabilities = "1.0.0".parse().unwrap();
+        assert!(capabilities.has_numbers());
+        capabilities.require_collection_search().unwrap();
+    }
+
+    #[test]
+    fn check_token() {
+        let capabilities: Capabilities = "0.8.5".parse().unwrap();
+        capabilities.check_token("next:an-id").unwrap();
+        capabilities.check_token("prev:a-collection:an-id").unwrap();
+        let _ = capabilities.check_token("next:").unwrap_err();
+        let _ = capabilities.check_token("an-id").unwrap_err();
+
+        let capabilities: Capabilities = "0.9.1".parse().unwrap();
+        capabilities.check_token("next:a-collection:an-id").unwrap();
+        capabilities.check_token("prev:a-collection:an-id").unwrap();
+        let error = capabilities.check_token("next:an-id").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid pagination token for pgstac 0.9.1: next:an-id"
+        );
+        let _ = capabilities.check_token("next::an-id").unwrap_err();
+    }
+}
@@ -24,6 +24,12 @@ pub struct Page {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<Context>,
 
+    /// The number of values matched.
+    ///
+    /// Added in pgstac v0.9, only present if the context setting is on.
+    #[serde(rename = "numberMatched", skip_serializing_if = "Option::is_none")]
+    pub number_matched: Option<usize>,
+
     /// The number of values returned.
     ///
     /// Added in pgstac v0.9
@@ -43,12 +49,102 @@ pub struct Page {
 
 impl Page {
     /// Returns this page's next token, if it has one.
+    ///
+    /// pgstac v0.8 returns the next id in `next`, while v0.9 puts the token in
+    /// the `next` link.
     pub fn next_token(&self) -> Option<String> {
-        self.next.as_ref().map(|next| format!("next:{}", next))
+        self.next
+            .as_ref()
+            .map(|next| format!("next:{}", next))
+            .or_else(|| self.link_token("next"))
     }
 
     /// Returns this page's prev token, if it has one.
+    ///
+    /// pgstac v0.8 returns the previous id in `prev`, while v0.9 puts the
+    /// token in the `prev` link.
     pub fn prev_token(&self) -> Option<String> {
-        self.prev.as_ref().map(|prev| format!("prev:{}", prev))
+        self.prev
+            .as_ref()
+            .map(|prev| format!("prev:{}", prev))
+            .or_else(|| self.link_token("prev"))
+    }
+
+    fn link_token(&self, rel: &str) -> Option<String> {
+        let link = self.links.iter().find(|link| link.rel == rel)?;
+        link.body
+            .as_ref()
+            .and_then(|body| body.get("token"))
+            .and_then(|token| token.as_str())
+            .map(String::from)
+            .or_else(|| {
+                let (_, query) = link.href.as_str().split_once('?')?;
+                url::form_urlencoded::parse(query.as_bytes())
+                    .find(|(key, _)| key == "token")
+                    .map(|(_, token)| token.into_owned())
+            })
+    }
+
+    /// Returns the number of items that matched the search, if known.
+    ///
+    /// Works with both the pgstac v0.8 `context` and the v0.9 `numberMatched`.
+    pub fn matched(&self) -> Option<u64> {
+        self.number_matched
+            .and_then(|n| n.try_into().ok())
+            .or_else(|| self.context.as_ref().and_then(|context| context.matched))
+    }
+
+    /// Returns the number of items returned in this page.
+    ///
+    /// Works with both the pgstac v0.8 `context` and the v0.9 `numberReturned`.
+    pub fn returned(&self) -> Option<u64> {
+        self.number_returned
+            .and_then(|n| n.try_into().ok())
+            .or_else(|| self.context.as_ref().map(|context| context.returned))
+    }
+
+    /// Returns the maximum number of items this page was limited to, if known.
+    ///
+    /// pgstac v0.9 doesn't report the limit, so this is only set by v0.8.
+    pub fn limit(&self) -> Option<u64> {
+        self.context.as_ref().and_then(|context| context.limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Page;
+    use serde_json::json;
+
+    #[test]
+    fn tokens_v0_8() {
+        let page: Page = serde_json::from_value(json!({
+            "features": [],
+            "next": "an-id",
+            "context": {"returned": 0, "limit": 10}
+        }))
+        .unwrap();
+        assert_eq!(page.next_token().unwrap(), "next:an-id");
+        assert!(page.prev_token().is_none());
+        assert_eq!(page.returned(), Some(0));
+        assert_eq!(page.limit(), Some(10));
+    }
+
+    #[test]
+    fn tokens_v0_9() {
+        let page: Page = serde_json::from_value(json!({
+            "features": [],
+            "links": [
+                {"rel": "next", "href": "./search?token=next%3Acollection%3Aan-id"},
+                {"rel": "prev", "href": "./search", "body": {"token": "prev:collection:another-id"}}
+            ],
+            "numberMatched": 42,
+            "numberReturned": 0
+        }))
+        .unwrap();
+        assert_eq!(page.next_token().unwrap(), "next:collection:an-id");
+        assert_eq!(page.prev_token().unwrap(), "prev:collection:another-id");
+        assert_eq!(page.matched(), Some(42));
+        assert_eq!(page.returned(), Some(0));
     }
 }
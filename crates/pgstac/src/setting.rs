@@ -0,0 +1,173 @@
+use crate::{Error, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// A **pgstac** setting.
+///
+/// Settings are stored in the `pgstac_settings` table, and can be read and
+/// written with [Pgstac::setting](crate::Pgstac::setting) and
+/// [Pgstac::set_setting](crate::Pgstac::set_setting). See [the **pgstac**
+/// docs](https://github.com/stac-utils/pgstac/blob/main/docs/src/pgstac.md#pgstac-settings)
+/// for more information on the settings and their meaning.
+///
+/// # Examples
+///
+/// ```
+/// use pgstac::Setting;
+///
+/// let setting: Setting = "default_filter_lang".parse().unwrap();
+/// assert_eq!(setting, Setting::DefaultFilterLang);
+/// assert_eq!(setting.to_string(), "default_filter_lang");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Setting {
+    /// Whether search results include a count of matched items, `on`, `off`, or `auto`.
+    Context,
+
+    /// The estimated row count above which `auto` context uses an estimate.
+    ContextEstimatedCount,
+
+    /// The estimated query cost above which `auto` context uses an estimate.
+    ContextEstimatedCost,
+
+    /// How long context counts are cached, as a Postgres interval.
+    ContextStatsTtl,
+
+    /// The filter language used when a search doesn't specify one.
+    DefaultFilterLang,
+
+    /// Whether fields that aren't in the queryables can be used in filters.
+    AdditionalProperties,
+
+    /// Whether expensive maintenance, like updating collection extents, is
+    /// queued instead of run immediately.
+    UseQueue,
+
+    /// How long queued maintenance may run, as a Postgres interval.
+    QueueTimeout,
+
+    /// Whether collection extents are updated when items are added.
+    UpdateCollectionExtent,
+
+    /// Whether formatted search results are cached.
+    FormatCache,
+
+    /// Whether the database is read-only.
+    Readonly,
+}
+
+impl Setting {
+    /// All of the settings.
+    pub const ALL: [Setting; 11] = [
+        Setting::Context,
+        Setting::ContextEstimatedCount,
+        Setting::ContextEstimatedCost,
+        Setting::ContextStatsTtl,
+        Setting::DefaultFilterLang,
+        Setting::AdditionalProperties,
+        Setting::UseQueue,
+        Setting::QueueTimeout,
+        Setting::UpdateCollectionExtent,
+        Setting::FormatCache,
+        Setting::Readonly,
+    ];
+
+    /// Returns this setting's name in the `pgstac_settings` table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Setting;
+    ///
+    /// assert_eq!(Setting::UseQueue.name(), "use_queue");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Setting::Context => "context",
+            Setting::ContextEstimatedCount => "context_estimated_count",
+            Setting::ContextEstimatedCost => "context_estimated_cost",
+            Setting::ContextStatsTtl => "context_stats_ttl",
+            Setting::DefaultFilterLang => "default_filter_lang",
+            Setting::AdditionalProperties => "additional_properties",
+            Setting::UseQueue => "use_queue",
+            Setting::QueueTimeout => "queue_timeout",
+            Setting::UpdateCollectionExtent => "update_collection_extent",
+            Setting::FormatCache => "format_cache",
+            Setting::Readonly => "readonly",
+        }
+    }
+
+    /// Returns true if this setting's value is a boolean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Setting;
+    ///
+    /// assert!(Setting::UseQueue.is_bool());
+    /// assert!(!Setting::Context.is_bool());
+    /// ```
+    pub fn is_bool(&self) -> bool {
+        matches!(
+            self,
+            Setting::AdditionalProperties
+                | Setting::UseQueue
+                | Setting::UpdateCollectionExtent
+                | Setting::FormatCache
+                | Setting::Readonly
+        )
+    }
+
+    /// Parses a boolean value of this setting.
+    ///
+    /// Accepts the same spellings as Postgres, e.g. `true`, `on`, `f`, or `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::Setting;
+    ///
+    /// assert!(Setting::UseQueue.parse_bool("on").unwrap());
+    /// assert!(!Setting::UseQueue.parse_bool("false").unwrap());
+    /// Setting::UseQueue.parse_bool("maybe").unwrap_err();
+    /// ```
+    pub fn parse_bool(&self, value: &str) -> Result<bool> {
+        match value.trim().to_lowercase().as_str() {
+            "true" | "t" | "on" | "yes" | "y" | "1" => Ok(true),
+            "false" | "f" | "off" | "no" | "n" | "0" => Ok(false),
+            _ => Err(Error::InvalidSetting {
+                setting: *self,
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Setting {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Setting> {
+        Setting::ALL
+            .into_iter()
+            .find(|setting| setting.name() == s)
+            .ok_or_else(|| Error::UnknownSetting(s.to_string()))
+    }
+}
+
+impl Display for Setting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Setting;
+
+    #[test]
+    fn roundtrip() {
+        for setting in Setting::ALL {
+            assert_eq!(setting.name().parse::<Setting>().unwrap(), setting);
+        }
+        let _ = "not-a-setting".parse::<Setting>().unwrap_err();
+    }
+}
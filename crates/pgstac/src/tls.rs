@@ -0,0 +1,270 @@
+//! TLS configuration for **pgstac** connections.
+
+use crate::Result;
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    ClientConfig, ConfigBuilder, DigitallySignedStruct, RootCertStore, SignatureScheme,
+    WantsVerifier,
+};
+use std::{path::PathBuf, sync::Arc};
+pub use tokio_postgres_rustls::MakeRustlsConnect;
+
+/// Configuration for a verified TLS connection to a **pgstac** database.
+///
+/// By default, server certificates are verified against the Mozilla root
+/// certificates, no client certificate is sent, and server name indication
+/// (SNI) is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pgstac::tls::TlsConfig;
+///
+/// let tls = TlsConfig::new()
+///     .ca_bundle("/etc/ssl/certs/rds-ca.pem")
+///     .client_cert("client.crt", "client.key")
+///     .make_tls_connect()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    ca_bundle: Option<PathBuf>,
+    client_cert: Option<(PathBuf, PathBuf)>,
+    sni: bool,
+}
+
+impl TlsConfig {
+    /// Creates a new, default TLS configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::tls::TlsConfig;
+    /// let tls = TlsConfig::new();
+    /// ```
+    pub fn new() -> TlsConfig {
+        TlsConfig {
+            ca_bundle: None,
+            client_cert: None,
+            sni: true,
+        }
+    }
+
+    /// Sets a PEM file of CA certificates to verify the server against.
+    ///
+    /// When set, these certificates are used instead of the Mozilla root
+    /// certificates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::tls::TlsConfig;
+    /// let tls = TlsConfig::new().ca_bundle("/etc/ssl/certs/rds-ca.pem");
+    /// ```
+    pub fn ca_bundle(mut self, path: impl Into<PathBuf>) -> TlsConfig {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Sets PEM files for a client certificate chain and its private key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::tls::TlsConfig;
+    /// let tls = TlsConfig::new().client_cert("client.crt", "client.key");
+    /// ```
+    pub fn client_cert(mut self, cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> TlsConfig {
+        self.client_cert = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Enables or disables server name indication (SNI).
+    ///
+    /// Some proxies and poolers don't handle SNI, so it can be useful to turn
+    /// it off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::tls::TlsConfig;
+    /// let tls = TlsConfig::new().sni(false);
+    /// ```
+    pub fn sni(mut self, sni: bool) -> TlsConfig {
+        self.sni = sni;
+        self
+    }
+
+    /// Builds a TLS connector for [tokio_postgres].
+    ///
+    /// Returns an error if any of the certificate or key files can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pgstac::tls::TlsConfig;
+    /// let tls = TlsConfig::new().make_tls_connect().unwrap();
+    /// ```
+    pub fn make_tls_connect(&self) -> Result<MakeRustlsConnect> {
+        let mut roots = RootCertStore::empty();
+        if let Some(ca_bundle) = self.ca_bundle.as_ref() {
+            for certificate in CertificateDer::pem_file_iter(ca_bundle)? {
+                roots.add(certificate?)?;
+            }
+        } else {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        let builder = config_builder().with_root_certificates(roots);
+        let mut config = if let Some((cert, key)) = self.client_cert.as_ref() {
+            let certificates =
+                CertificateDer::pem_file_iter(cert)?.collect::<std::result::Result<Vec<_>, _>>()?;
+            let key = PrivateKeyDer::from_pem_file(key)?;
+            builder.with_client_auth_cert(certificates, key)?
+        } else {
+            builder.with_no_client_auth()
+        };
+        config.enable_sni = self.sni;
+        Ok(MakeRustlsConnect::new(config))
+    }
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates a TLS connector that does **not** verify the server's certificate.
+///
+/// This is insecure: any certificate is accepted, so the connection is
+/// encrypted but not protected against man-in-the-middle attacks. It can be
+/// useful for databases with self-signed certificates that you can't get a CA
+/// bundle for (see <https://github.com/stac-utils/stac-rs/issues/375>). To
+/// verify the server, use [TlsConfig].
+///
+/// # Examples
+///
+/// ```
+/// let tls = pgstac::tls::make_unverified_tls();
+/// ```
+pub fn make_unverified_tls() -> MakeRustlsConnect {
+    let builder = config_builder();
+    let verifier = NoVerification(builder.crypto_provider().clone());
+    let config = builder
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    MakeRustlsConnect::new(config)
+}
+
+/// Returns a client config builder for the process-default crypto provider.
+///
+/// If no default has been installed, e.g. because more than one provider is
+/// enabled by crate features, **ring** is used.
+fn config_builder() -> ConfigBuilder<ClientConfig, WantsVerifier> {
+    let provider = CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+    ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("crypto providers should support the default protocol versions")
+}
+
+/// A certificate verifier that accepts any server certificate.
+///
+/// Handshake signatures are still checked, so the server must hold the private
+/// key for the certificate it presents.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _: &CertificateDer<'_>,
+        _: &[CertificateDer<'_>],
+        _: &ServerName<'_>,
+        _: &[u8],
+        _: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TlsConfig;
+
+    #[test]
+    fn default() {
+        let _ = TlsConfig::new().make_tls_connect().unwrap();
+    }
+
+    #[test]
+    fn unverified() {
+        let _ = super::make_unverified_tls();
+    }
+
+    #[test]
+    fn no_verification_accepts_any_certificate() {
+        use super::{NoVerification, ServerCertVerifier};
+        use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+
+        let verifier = NoVerification(super::config_builder().crypto_provider().clone());
+        let certificate = CertificateDer::from(b"not a certificate".to_vec());
+        let server_name = ServerName::try_from("pgstac.example.com").unwrap();
+        let _ = verifier
+            .verify_server_cert(&certificate, &[], &server_name, &[], UnixTime::now())
+            .unwrap();
+        assert!(!verifier.supported_verify_schemes().is_empty());
+    }
+
+    #[test]
+    fn missing_ca_bundle() {
+        assert!(TlsConfig::new()
+            .ca_bundle("does-not-exist.pem")
+            .make_tls_connect()
+            .is_err());
+    }
+
+    #[test]
+    fn missing_client_cert() {
+        assert!(TlsConfig::new()
+            .client_cert("does-not-exist.crt", "does-not-exist.key")
+            .make_tls_connect()
+            .is_err());
+    }
+}
@@ -1,10 +1,19 @@
-use crate::{Backend, Error, Result, DEFAULT_DESCRIPTION, DEFAULT_ID};
-use http::Method;
+use crate::{
+    Action, Auth, Authorizer, Backend, BulkIngest, BulkItems, Decision, Error, Identity, Limits,
+    Result, SearchCache, DEFAULT_DESCRIPTION, DEFAULT_ID,
+};
+use http::{request::Parts, HeaderMap, Method};
 use serde::Serialize;
 use serde_json::{json, Map, Value};
-use stac::{mime::APPLICATION_OPENAPI_3_0, Catalog, Collection, Fields, Item, Link, Links};
-use stac_api::{Collections, Conformance, ItemCollection, Items, Root, Search};
-use url::Url;
+use stac::{
+    mime::APPLICATION_OPENAPI_3_0, Catalog, Collection, Container, Fields, Item, Link, Links,
+};
+use stac_api::{
+    Children, Collections, Conformance, GetCollections, ItemCollection, Items, Root, Search,
+    UrlBuilder,
+};
+use std::sync::Arc;
+use url::{Position, Url};
 
 /// A STAC server API.
 #[derive(Clone, Debug)]
@@ -20,11 +29,30 @@ pub struct Api<B: Backend> {
 
     /// The root url of this API.
     pub root: Url,
+
+    /// The cache of search results, if any.
+    pub search_cache: Option<SearchCache>,
+
+    /// The limits on requests sent to the backend.
+    pub limits: Limits,
+
+    /// The authentication required for requests.
+    pub auth: Auth,
+
+    /// The per-collection authorization for requests, if any.
+    pub authorizer: Option<Arc<dyn Authorizer>>,
+
+    /// Whether to build links from `X-Forwarded-*` request headers.
+    pub trust_forwarded_headers: bool,
 }
 
 impl<B: Backend> Api<B> {
     /// Creates a new API with the given backend.
     ///
+    /// The root can have a path, e.g. `http://stac.test/stac/v1`, in which
+    /// case all links are under that path and the [routes](crate::routes) are
+    /// mounted there.
+    ///
     /// # Examples
     ///
     /// ```
@@ -32,13 +60,25 @@ impl<B: Backend> Api<B> {
     ///
     /// let backend = MemoryBackend::new();
     /// let api = Api::new(backend, "http://stac.test").unwrap();
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test/stac/v1/").unwrap();
+    /// assert_eq!(api.root.as_str(), "http://stac.test/stac/v1");
     /// ```
     pub fn new(backend: B, root: &str) -> Result<Api<B>> {
+        let mut root: Url = root.parse()?;
+        if root.path() != "/" {
+            let path = root.path().trim_end_matches('/').to_string();
+            root.set_path(&path);
+        }
         Ok(Api {
             backend,
             id: DEFAULT_ID.to_string(),
             description: DEFAULT_DESCRIPTION.to_string(),
-            root: root.parse()?,
+            root,
+            search_cache: None,
+            limits: Limits::default(),
+            auth: Auth::default(),
+            authorizer: None,
+            trust_forwarded_headers: false,
         })
     }
 
@@ -72,8 +112,210 @@ impl<B: Backend> Api<B> {
         self
     }
 
-    fn url(&self, path: &str) -> Result<Url> {
-        self.root.join(path).map_err(Error::from)
+    /// Sets this API's search cache.
+    ///
+    /// Search results are served from the cache until they expire. The cache
+    /// is cleared after [Api::bulk_items].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend, SearchCache};
+    /// use std::time::Duration;
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .search_cache(SearchCache::new(Duration::from_secs(30)));
+    /// ```
+    pub fn search_cache(mut self, search_cache: SearchCache) -> Api<B> {
+        self.search_cache = Some(search_cache);
+        self
+    }
+
+    /// Sets the limits on requests sent to the backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, Limits, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .limits(Limits::default().max_limit(1000));
+    /// ```
+    pub fn limits(mut self, limits: Limits) -> Api<B> {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the authentication required for requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, Auth, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .auth(Auth::default().api_key("x-api-key", "a-secret-key"));
+    /// ```
+    pub fn auth(mut self, auth: Auth) -> Api<B> {
+        self.auth = auth;
+        self
+    }
+
+    /// Sets the authorizer that decides which collections a request may read or write.
+    ///
+    /// Authorization is checked by the [routes](crate::routes) before
+    /// requests reach the backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::request::Parts;
+    /// use stac_server::{Action, Api, Decision, Identity, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .authorizer(|_: &Parts, _: &Identity, action: Action, _: Option<&str>| {
+    ///         if action == Action::Read {
+    ///             Decision::Allow
+    ///         } else {
+    ///             Decision::Deny
+    ///         }
+    ///     });
+    /// ```
+    pub fn authorizer(mut self, authorizer: impl Authorizer) -> Api<B> {
+        self.authorizer = Some(Arc::new(authorizer));
+        self
+    }
+
+    /// Sets whether to build links from `X-Forwarded-*` request headers.
+    ///
+    /// Only enable this behind a reverse proxy that sets (or strips) these
+    /// headers, since otherwise clients can choose the links in responses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://localhost:7822")
+    ///     .unwrap()
+    ///     .trust_forwarded_headers(true);
+    /// ```
+    pub fn trust_forwarded_headers(mut self, trust_forwarded_headers: bool) -> Api<B> {
+        self.trust_forwarded_headers = trust_forwarded_headers;
+        self
+    }
+
+    /// Returns this API with its root rewritten by a request's
+    /// `X-Forwarded-Proto`, `X-Forwarded-Host`, `X-Forwarded-Port`, and
+    /// `X-Forwarded-Prefix` headers.
+    ///
+    /// The root is unchanged unless [Api::trust_forwarded_headers] is set.
+    /// Invalid headers are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::HeaderMap;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://localhost:7822")
+    ///     .unwrap()
+    ///     .trust_forwarded_headers(true);
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-forwarded-proto", "https".parse().unwrap());
+    /// headers.insert("x-forwarded-host", "stac.test".parse().unwrap());
+    /// headers.insert("x-forwarded-prefix", "/stac/v1".parse().unwrap());
+    /// let api = api.forwarded(&headers);
+    /// assert_eq!(api.root.as_str(), "https://stac.test/stac/v1");
+    /// ```
+    pub fn forwarded(mut self, headers: &HeaderMap) -> Api<B> {
+        if !self.trust_forwarded_headers {
+            return self;
+        }
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let scheme = header("x-forwarded-proto").unwrap_or(self.root.scheme());
+        let host = header("x-forwarded-host")
+            .unwrap_or(&self.root[Position::BeforeHost..Position::AfterPort]);
+        let Ok(mut root) = Url::parse(&format!("{scheme}://{host}")) else {
+            tracing::warn!("ignoring invalid x-forwarded-* headers: {scheme}://{host}");
+            return self;
+        };
+        if let Some(port) = header("x-forwarded-port").and_then(|port| port.parse().ok()) {
+            if root.port().is_none() {
+                let _ = root.set_port(Some(port));
+            }
+        }
+        let prefix = header("x-forwarded-prefix")
+            .unwrap_or_default()
+            .trim_matches('/');
+        let mut path = String::new();
+        if !prefix.is_empty() {
+            path = format!("/{prefix}");
+        }
+        path.push_str(self.root.path().trim_end_matches('/'));
+        root.set_path(&path);
+        self.root = root;
+        self
+    }
+
+    /// Returns an [Error::Forbidden] if this API's authorizer denies the action.
+    ///
+    /// The request's [Identity] is taken from its extensions, where the
+    /// [routes](crate::routes) put it after validating its credentials, and is
+    /// [Identity::Anonymous] if it isn't there. Always succeeds if there's no
+    /// authorizer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::{request::Parts, Request};
+    /// use stac_server::{Action, Api, Decision, Identity, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .authorizer(|_: &Parts, _: &Identity, _: Action, collection_id: Option<&str>| {
+    ///         if collection_id == Some("secret") {
+    ///             Decision::Deny
+    ///         } else {
+    ///             Decision::Allow
+    ///         }
+    ///     });
+    /// let (parts, _) = Request::new(()).into_parts();
+    /// api.authorize(&parts, Action::Read, Some("public")).unwrap();
+    /// api.authorize(&parts, Action::Read, Some("secret")).unwrap_err();
+    /// ```
+    pub fn authorize(
+        &self,
+        parts: &Parts,
+        action: Action,
+        collection_id: Option<&str>,
+    ) -> Result<()> {
+        match self.authorizer.as_ref().map(|authorizer| {
+            let anonymous = Identity::Anonymous;
+            let identity = parts.extensions.get::<Identity>().unwrap_or(&anonymous);
+            authorizer.authorize(parts, identity, action, collection_id)
+        }) {
+            Some(Decision::Deny) => Err(Error::Forbidden(match collection_id {
+                Some(collection_id) => format!("{action:?} on collection '{collection_id}'"),
+                None => format!("{action:?} on all collections"),
+            })),
+            Some(Decision::Allow) | None => Ok(()),
+        }
+    }
+
+    fn url_builder(&self) -> Result<UrlBuilder> {
+        UrlBuilder::new(self.root.as_str()).map_err(Error::from)
     }
 
     /// Returns the root of the API.
@@ -89,36 +331,53 @@ impl<B: Backend> Api<B> {
     /// # })
     /// ```
     pub async fn root(&self) -> Result<Root> {
+        self.root_with_children(|_| true).await
+    }
+
+    /// Returns the root of the API, only linking to the collections that
+    /// `is_visible` returns true for.
+    pub(crate) async fn root_with_children(
+        &self,
+        is_visible: impl Fn(&str) -> bool,
+    ) -> Result<Root> {
+        let urls = self.url_builder()?;
         let mut catalog = Catalog::new(&self.id, &self.description);
         catalog.set_link(Link::root(self.root.clone()).json());
         catalog.set_link(Link::self_(self.root.clone()).json());
         catalog.set_link(
-            Link::new(self.url("/api")?, "service-desc")
+            Link::new(urls.service_desc().clone(), "service-desc")
                 .r#type(APPLICATION_OPENAPI_3_0.to_string()),
         );
-        catalog.set_link(
-            Link::new(self.url("/api.html")?, "service-doc").r#type("text/html".to_string()),
-        );
-        catalog.set_link(Link::new(self.url("/conformance")?, "conformance").json());
-        catalog.set_link(Link::new(self.url("/collections")?, "data").json());
+        catalog
+            .set_link(Link::new(urls.service_doc(), "service-doc").r#type("text/html".to_string()));
+        catalog.set_link(Link::new(urls.conformance().clone(), "conformance").json());
+        catalog.set_link(Link::new(urls.collections().clone(), "data").json());
         for collection in self.backend.collections().await? {
+            if is_visible(&collection.id) {
+                catalog
+                    .links
+                    .push(Link::child(urls.collection(&collection.id)?).json());
+            }
+        }
+        let capabilities = self.backend.capabilities();
+        if capabilities.children {
+            catalog.set_link(Link::new(urls.children(), "children").json());
+        }
+        if capabilities.item_search {
+            let search_url = urls.search().clone();
+            catalog.links.push(
+                Link::new(search_url.clone(), "search")
+                    .geojson()
+                    .method("GET"),
+            );
             catalog
                 .links
-                .push(Link::child(self.url(&format!("/collections/{}", collection.id))?).json());
+                .push(Link::new(search_url, "search").geojson().method("POST"));
         }
-        let search_url = self.url("/search")?;
-        catalog.links.push(
-            Link::new(search_url.clone(), "search")
-                .geojson()
-                .method("GET"),
-        );
-        catalog
-            .links
-            .push(Link::new(search_url, "search").geojson().method("POST"));
-        if self.backend.has_filter() {
+        if capabilities.filter {
             catalog.links.push(
                 Link::new(
-                    self.url("/queryables")?,
+                    urls.queryables(),
                     "http://www.opengis.net/def/rel/ogc/1.0/queryables",
                 )
                 .r#type("application/schema+json".to_string()),
@@ -132,6 +391,9 @@ impl<B: Backend> Api<B> {
 
     /// Returns the conformance classes.
     ///
+    /// The conformance classes are built from the backend's
+    /// [capabilities](Backend::capabilities).
+    ///
     /// # Examples
     ///
     /// ```
@@ -141,20 +403,58 @@ impl<B: Backend> Api<B> {
     /// let conformance = api.conformance();
     /// ```
     pub fn conformance(&self) -> Conformance {
+        let capabilities = self.backend.capabilities();
         let mut conformance = Conformance::new().ogcapi_features();
-        if self.backend.has_item_search() {
+        if capabilities.item_search {
             conformance = conformance.item_search();
+            if capabilities.filter {
+                conformance = conformance.filter();
+            }
+            if capabilities.fields {
+                conformance = conformance.fields();
+            }
+            if capabilities.sort {
+                conformance = conformance.sort();
+            }
+        }
+        if capabilities.transaction {
+            conformance = conformance.transaction();
         }
-        if self.backend.has_filter() {
-            conformance = conformance.filter();
+        if capabilities.collection_search {
+            conformance = conformance.collection_search();
+        }
+        if capabilities.children {
+            conformance = conformance.children();
         }
         conformance
     }
 
     /// Returns queryables.
-    pub fn queryables(&self) -> Value {
+    ///
+    /// If the backend can't generate queryables (see [Backend::queryables]),
+    /// the returned schema allows any property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
+    /// # tokio_test::block_on(async {
+    /// let queryables = api.queryables().await.unwrap();
+    /// assert_eq!(queryables["additionalProperties"], true);
+    /// # })
+    /// ```
+    pub async fn queryables(&self) -> Result<Value> {
+        let urls = self.url_builder()?;
+        if let Some(mut queryables) = self.backend.queryables().await? {
+            if let Some(object) = queryables.as_object_mut() {
+                let _ = object.insert("$id".into(), urls.queryables().to_string().into());
+            }
+            return Ok(queryables);
+        }
         // This is a pure punt from https://github.com/stac-api-extensions/filter?tab=readme-ov-file#queryables
-        json!({
+        Ok(json!({
           "$schema" : "https://json-schema.org/draft/2019-09/schema",
           "$id" : "https://stac-api.example.com/queryables",
           "type" : "object",
@@ -163,31 +463,85 @@ impl<B: Backend> Api<B> {
           "properties" : {
           },
           "additionalProperties": true
-        })
+        }))
     }
 
-    /// Returns the collections from the backend.
+    /// Returns a page of collections from the backend.
     ///
     /// # Examples
     ///
     /// ```
+    /// use stac_api::GetCollections;
     /// use stac_server::{Api, MemoryBackend};
     ///
     /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
     /// # tokio_test::block_on(async {
-    /// let collections = api.collections().await.unwrap();
+    /// let collections = api.collections(GetCollections::default()).await.unwrap();
     /// # })
     /// ```
-    pub async fn collections(&self) -> Result<Collections> {
-        let mut collections: Collections = self.backend.collections().await?.into();
+    pub async fn collections(&self, mut get_collections: GetCollections) -> Result<Collections> {
+        let urls = self.url_builder()?;
+        self.limits.apply_to_collections(&mut get_collections)?;
+        let mut collections = self
+            .backend
+            .collections_page(get_collections.clone())
+            .await?;
         collections.set_link(Link::root(self.root.clone()).json());
-        collections.set_link(Link::self_(self.url("/collections")?).json());
+        let url = urls.collections().clone();
+        collections.set_link(Link::self_(url.clone()).json());
+        if let Some(next) = collections.next.take() {
+            collections.set_link(self.collections_pagination_link(
+                url.clone(),
+                &get_collections,
+                next,
+                "next",
+            )?);
+        }
+        if let Some(prev) = collections.prev.take() {
+            collections.set_link(self.collections_pagination_link(
+                url,
+                &get_collections,
+                prev,
+                "prev",
+            )?);
+        }
         for collection in collections.collections.iter_mut() {
-            self.set_collection_links(collection)?;
+            self.set_collection_links(&urls, collection)?;
         }
         Ok(collections)
     }
 
+    /// Returns the children of the root catalog.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
+    /// # tokio_test::block_on(async {
+    /// let children = api.children().await.unwrap();
+    /// # })
+    /// ```
+    pub async fn children(&self) -> Result<Children> {
+        let urls = self.url_builder()?;
+        let mut children: Children = self.backend.children().await?.into();
+        children.set_link(Link::root(self.root.clone()).json());
+        children.set_link(Link::self_(urls.children()).json());
+        for child in children.children.iter_mut() {
+            match child {
+                Container::Collection(collection) => {
+                    self.set_collection_links(&urls, collection)?
+                }
+                Container::Catalog(catalog) => {
+                    catalog.set_link(Link::root(self.root.clone()).json());
+                    catalog.set_link(Link::parent(self.root.clone()).json());
+                }
+            }
+        }
+        Ok(children)
+    }
+
     /// Returns the collections from the backend.
     ///
     /// # Examples
@@ -205,7 +559,7 @@ impl<B: Backend> Api<B> {
     /// ```
     pub async fn collection(&self, id: &str) -> Result<Option<Collection>> {
         if let Some(mut collection) = self.backend.collection(id).await? {
-            self.set_collection_links(&mut collection)?;
+            self.set_collection_links(&self.url_builder()?, &mut collection)?;
             Ok(Some(collection))
         } else {
             Ok(None)
@@ -230,10 +584,16 @@ impl<B: Backend> Api<B> {
     /// assert_eq!(items.items.len(), 1);
     /// # })
     /// ```
-    pub async fn items(&self, collection_id: &str, items: Items) -> Result<Option<ItemCollection>> {
+    pub async fn items(
+        &self,
+        collection_id: &str,
+        mut items: Items,
+    ) -> Result<Option<ItemCollection>> {
+        let urls = self.url_builder()?;
+        self.limits.apply_to_items(&mut items)?;
         if let Some(mut item_collection) = self.backend.items(collection_id, items.clone()).await? {
-            let collection_url = self.url(&format!("/collections/{}", collection_id))?;
-            let items_url = self.url(&format!("/collections/{}/items", collection_id))?;
+            let collection_url = urls.collection(collection_id)?;
+            let items_url = urls.items(collection_id)?;
             item_collection.set_link(Link::root(self.root.clone()).json());
             item_collection.set_link(Link::self_(items_url.clone()).geojson());
             item_collection.set_link(Link::collection(collection_url).json());
@@ -256,7 +616,7 @@ impl<B: Backend> Api<B> {
                 )?);
             }
             for item in item_collection.items.iter_mut() {
-                self.set_item_links(item)?;
+                self.set_item_links(&urls, item)?;
             }
             Ok(Some(item_collection))
         } else {
@@ -282,15 +642,11 @@ impl<B: Backend> Api<B> {
     /// # })
     /// ```
     pub async fn item(&self, collection_id: &str, item_id: &str) -> Result<Option<Item>> {
+        let urls = self.url_builder()?;
         if let Some(mut item) = self.backend.item(collection_id, item_id).await? {
             item.set_link(Link::root(self.root.clone()).json());
-            item.set_link(
-                Link::self_(
-                    self.url(&format!("/collections/{}/items/{}", collection_id, item_id))?,
-                )
-                .geojson(),
-            );
-            let collection_url = self.url(&format!("/collections/{}", collection_id))?;
+            item.set_link(Link::self_(urls.item(collection_id, item_id)?).geojson());
+            let collection_url = urls.collection(collection_id)?;
             item.set_link(Link::collection(collection_url.clone()).json());
             item.set_link(Link::parent(collection_url).json());
             Ok(Some(item))
@@ -299,6 +655,87 @@ impl<B: Backend> Api<B> {
         }
     }
 
+    /// Adds many items to a collection, reporting success or failure per item.
+    ///
+    /// Each value is parsed as an item. Items without a `collection` are
+    /// assigned to this collection, and items that belong to another collection
+    /// are rejected. Valid items are sent to the backend in batches of
+    /// [BULK_ITEMS_BATCH_SIZE](crate::BULK_ITEMS_BATCH_SIZE).
+    ///
+    /// Returns `None` if the collection doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac::Collection;
+    /// use stac_server::{Api, MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+    /// let api = Api::new(backend, "http://stac.test").unwrap();
+    /// let item = serde_json::to_value(stac::Item::new("item-id")).unwrap();
+    /// let bulk_items = api
+    ///     .bulk_items("collection-id", vec![item, json!({"type": "Collection", "id": "not-an-item"})])
+    ///     .await
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(bulk_items.number_succeeded, 1);
+    /// assert_eq!(bulk_items.number_failed, 1);
+    /// # })
+    /// ```
+    pub async fn bulk_items(
+        &self,
+        collection_id: &str,
+        values: Vec<Value>,
+    ) -> Result<Option<BulkItems>> {
+        if let Some(mut ingest) = self.bulk_ingest(collection_id).await? {
+            for value in values {
+                ingest.push(value).await;
+            }
+            Ok(Some(ingest.finish().await))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Starts a bulk ingest into a collection.
+    ///
+    /// Use this instead of [Api::bulk_items] to add items as they arrive, e.g.
+    /// from a stream, without holding them all in memory.
+    ///
+    /// Returns `None` if the collection doesn't exist, and
+    /// [Error::ReadOnly](crate::Error::ReadOnly) if the backend is read-only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    /// use stac_server::{Api, MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+    /// let api = Api::new(backend, "http://stac.test").unwrap();
+    /// assert!(api.bulk_ingest("collection-id").await.unwrap().is_some());
+    /// assert!(api.bulk_ingest("does-not-exist").await.unwrap().is_none());
+    /// # })
+    /// ```
+    pub async fn bulk_ingest(&self, collection_id: &str) -> Result<Option<BulkIngest<B>>> {
+        if self.backend.is_readonly() {
+            Err(Error::ReadOnly)
+        } else if self.backend.collection(collection_id).await?.is_none() {
+            Ok(None)
+        } else {
+            Ok(Some(BulkIngest::new(
+                self.backend.clone(),
+                self.search_cache.clone(),
+                collection_id,
+            )))
+        }
+    }
+
     /// Searches the API.
     ///
     /// # Examples
@@ -314,14 +751,16 @@ impl<B: Backend> Api<B> {
     /// # })
     /// ```
     pub async fn search(&self, mut search: Search, method: Method) -> Result<ItemCollection> {
-        let mut item_collection = self.backend.search(search.clone()).await?;
+        let urls = self.url_builder()?;
+        self.limits.apply_to_search(&mut search)?;
+        let mut item_collection = self.backend_search(&search).await?;
         if method == Method::GET {
             if let Some(filter) = search.filter.take() {
                 search.filter = Some(filter.into_cql2_text()?);
             }
         }
         item_collection.set_link(Link::root(self.root.clone()).json());
-        let search_url = self.url("/search")?;
+        let search_url = urls.search().clone();
         if let Some(next) = item_collection.next.take() {
             tracing::debug!("adding next pagination link");
             item_collection.set_link(self.pagination_link(
@@ -338,23 +777,30 @@ impl<B: Backend> Api<B> {
                 .set_link(self.pagination_link(search_url, search, prev, "prev", &method)?);
         }
         for item in item_collection.items.iter_mut() {
-            self.set_item_links(item)?;
+            self.set_item_links(&urls, item)?;
         }
         Ok(item_collection)
     }
 
-    fn set_collection_links(&self, collection: &mut Collection) -> Result<()> {
+    async fn backend_search(&self, search: &Search) -> Result<ItemCollection> {
+        let Some(search_cache) = self.search_cache.as_ref() else {
+            return self.backend.search(search.clone()).await;
+        };
+        let key = crate::cache::key(search)?;
+        if let Some(item_collection) = search_cache.get(&key) {
+            tracing::debug!("using cached search results");
+            return Ok(item_collection);
+        }
+        let item_collection = self.backend.search(search.clone()).await?;
+        search_cache.put(key, item_collection.clone());
+        Ok(item_collection)
+    }
+
+    fn set_collection_links(&self, urls: &UrlBuilder, collection: &mut Collection) -> Result<()> {
         collection.set_link(Link::root(self.root.clone()).json());
-        collection
-            .set_link(Link::self_(self.url(&format!("/collections/{}", collection.id))?).json());
+        collection.set_link(Link::self_(urls.collection(&collection.id)?).json());
         collection.set_link(Link::parent(self.root.clone()).json());
-        collection.set_link(
-            Link::new(
-                self.url(&format!("/collections/{}/items", collection.id))?,
-                "items",
-            )
-            .geojson(),
-        );
+        collection.set_link(Link::new(urls.items(&collection.id)?, "items").geojson());
         Ok(())
     }
 
@@ -382,17 +828,36 @@ impl<B: Backend> Api<B> {
         }
     }
 
-    fn set_item_links(&self, item: &mut stac_api::Item) -> Result<()> {
+    fn collections_pagination_link(
+        &self,
+        mut url: Url,
+        get_collections: &GetCollections,
+        pagination: Map<String, Value>,
+        rel: &str,
+    ) -> Result<Link> {
+        let mut query = Map::new();
+        if let Some(limit) = get_collections.limit {
+            let _ = query.insert("limit".to_string(), limit.into());
+        }
+        if let Some(q) = get_collections.q.as_ref() {
+            let _ = query.insert("q".to_string(), q.clone().into());
+        }
+        if let Some(keywords) = get_collections.keywords.as_ref() {
+            let _ = query.insert("keywords".to_string(), keywords.clone().into());
+        }
+        query.extend(pagination);
+        url.set_query(Some(&serde_urlencoded::to_string(query)?));
+        Ok(Link::new(url, rel).json())
+    }
+
+    fn set_item_links(&self, urls: &UrlBuilder, item: &mut stac_api::Item) -> Result<()> {
         let mut collection_url = None;
         let mut item_link = None;
         if let Some(item_id) = item.get("id").and_then(|id| id.as_str()) {
             if let Some(collection_id) = item.get("collection").and_then(|id| id.as_str()) {
-                collection_url = Some(self.url(&format!("/collections/{}", collection_id))?);
+                collection_url = Some(urls.collection(collection_id)?);
                 item_link = Some(serde_json::to_value(
-                    Link::self_(
-                        self.url(&format!("/collections/{}/items/{}", collection_id, item_id))?,
-                    )
-                    .geojson(),
+                    Link::self_(urls.item(collection_id, item_id)?).geojson(),
                 )?);
             }
         }
@@ -421,11 +886,15 @@ impl<B: Backend> Api<B> {
 #[cfg(test)]
 mod tests {
     use super::Api;
-    use crate::{Backend, MemoryBackend};
+    use crate::{Backend, Capabilities, MemoryBackend, SearchCache};
     use http::Method;
-    use stac::{Catalog, Collection, Item, Links};
-    use stac_api::{Items, Search, ITEM_SEARCH_URI};
-    use std::collections::HashSet;
+    use serde_json::json;
+    use stac::{Catalog, Collection, Container, Item, Links, Lint};
+    use stac_api::{
+        GetCollections, Items, Search, Sortby, CHILDREN_URI, COLLECTION_SEARCH_URIS, FIELDS_URI,
+        FILTER_URIS, ITEM_SEARCH_URI, SORT_URI, TRANSACTION_URIS,
+    };
+    use std::{collections::HashSet, time::Duration};
 
     macro_rules! assert_link {
         ($link:expr, $href:expr, $media_type:expr) => {
@@ -452,6 +921,7 @@ mod tests {
         let api = test_api(backend);
         let root = api.root().await.unwrap();
         assert!(!root.conformance.conforms_to.is_empty());
+        assert_eq!(root.lint(), Vec::new());
         let catalog: Catalog = serde_json::from_value(serde_json::to_value(root).unwrap()).unwrap();
         // catalog.validate().await.unwrap();
         assert_eq!(catalog.id, "an-id");
@@ -533,7 +1003,8 @@ mod tests {
             .await
             .unwrap();
         let api = test_api(backend);
-        let collections = api.collections().await.unwrap();
+        let collections = api.collections(GetCollections::default()).await.unwrap();
+        assert_eq!(collections.lint(), Vec::new());
         assert_link!(
             collections.link("root"),
             "http://stac.test/",
@@ -569,6 +1040,123 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn children() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("a-collection", "A description"))
+            .await
+            .unwrap();
+        let api = test_api(backend);
+        let children = api.children().await.unwrap();
+        assert_link!(
+            children.link("root"),
+            "http://stac.test/",
+            "application/json"
+        );
+        assert_link!(
+            children.link("self"),
+            "http://stac.test/children",
+            "application/json"
+        );
+        assert_eq!(children.children.len(), 1);
+        let Container::Collection(collection) = &children.children[0] else {
+            panic!("child should be a collection");
+        };
+        assert_link!(
+            collection.link("self"),
+            "http://stac.test/collections/a-collection",
+            "application/json"
+        );
+        let root = api.root().await.unwrap();
+        assert_link!(
+            root.catalog.link("children"),
+            "http://stac.test/children",
+            "application/json"
+        );
+        assert!(root
+            .conformance
+            .conforms_to
+            .contains(&CHILDREN_URI.to_string()));
+    }
+
+    #[tokio::test]
+    async fn collections_pagination() {
+        let mut backend = MemoryBackend::new();
+        for id in ["a", "b", "c"] {
+            backend
+                .add_collection(Collection::new(id, "A description"))
+                .await
+                .unwrap();
+        }
+        let api = test_api(backend);
+        let collections = api
+            .collections(GetCollections {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(collections.collections.len(), 2);
+        assert_eq!(collections.number_matched, Some(3));
+        assert_eq!(collections.number_returned, Some(2));
+        assert_link!(
+            collections.link("next"),
+            "http://stac.test/collections?limit=2&token=2",
+            "application/json"
+        );
+        assert!(collections.link("prev").is_none());
+
+        let collections = api
+            .collections(GetCollections {
+                limit: Some(2),
+                token: Some("2".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(collections.collections.len(), 1);
+        assert_eq!(collections.collections[0].id, "c");
+        assert_eq!(collections.number_returned, Some(1));
+        assert!(collections.link("next").is_none());
+        assert_link!(
+            collections.link("prev"),
+            "http://stac.test/collections?limit=2&token=0",
+            "application/json"
+        );
+    }
+
+    #[tokio::test]
+    async fn collections_q_and_keywords() {
+        let mut backend = MemoryBackend::new();
+        for (id, keyword) in [
+            ("sentinel-a", "optical"),
+            ("sentinel-b", "optical"),
+            ("landsat", "optical"),
+        ] {
+            let mut collection = Collection::new(id, "A description");
+            collection.keywords = Some(vec![keyword.to_string()]);
+            backend.add_collection(collection).await.unwrap();
+        }
+        let api = test_api(backend);
+        let collections = api
+            .collections(GetCollections {
+                limit: Some(1),
+                q: Some("sentinel".to_string()),
+                keywords: Some("optical".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(collections.collections[0].id, "sentinel-a");
+        assert_eq!(collections.number_matched, Some(2));
+        assert_link!(
+            collections.link("next"),
+            "http://stac.test/collections?limit=1&q=sentinel&keywords=optical&token=1",
+            "application/json"
+        );
+    }
+
     #[tokio::test]
     async fn collection() {
         let mut backend = MemoryBackend::new();
@@ -748,6 +1336,93 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn search_cache() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        let api = test_api(backend.clone()).search_cache(SearchCache::new(Duration::from_secs(60)));
+        let item_collection = api.search(Search::default(), Method::GET).await.unwrap();
+        assert!(item_collection.items.is_empty());
+        backend
+            .add_item(Item::new("item-a").collection("collection-id"))
+            .await
+            .unwrap();
+        let item_collection = api.search(Search::default(), Method::POST).await.unwrap();
+        assert!(item_collection.items.is_empty());
+        let item = serde_json::to_value(Item::new("item-b")).unwrap();
+        let _ = api
+            .bulk_items("collection-id", vec![item])
+            .await
+            .unwrap()
+            .unwrap();
+        let item_collection = api.search(Search::default(), Method::GET).await.unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        let mut ingest = api.bulk_ingest("collection-id").await.unwrap().unwrap();
+        ingest
+            .push(serde_json::to_value(Item::new("item-c")).unwrap())
+            .await;
+        let _ = ingest.finish().await;
+        let item_collection = api.search(Search::default(), Method::GET).await.unwrap();
+        assert_eq!(item_collection.items.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn search_fields() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        backend
+            .add_item(Item::new("item-a").collection("collection-id"))
+            .await
+            .unwrap();
+        let api = test_api(backend);
+        let search = Search::default().fields("-geometry".into());
+        let item_collection = api.search(search, Method::GET).await.unwrap();
+        let item = &item_collection.items[0];
+        assert_eq!(item["id"], "item-a");
+        assert!(!item.contains_key("geometry"));
+        assert!(item.contains_key("properties"));
+    }
+
+    #[tokio::test]
+    async fn search_sortby() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        for (id, gsd, datetime) in [
+            ("item-a", 10, "2023-01-01T00:00:00Z"),
+            ("item-b", 10, "2023-01-02T00:00:00Z"),
+            ("item-c", 2, "2023-01-01T00:00:00Z"),
+        ] {
+            let mut item = Item::new(id).collection("collection-id");
+            item.properties.datetime = Some(datetime.parse().unwrap());
+            let _ = item
+                .properties
+                .additional_fields
+                .insert("gsd".to_string(), gsd.into());
+            backend.add_item(item).await.unwrap();
+        }
+        let api = test_api(backend);
+        let search = Search::default().sortby(vec![
+            Sortby::desc("properties.gsd"),
+            Sortby::desc("datetime"),
+        ]);
+        let item_collection = api.search(search, Method::GET).await.unwrap();
+        let ids: Vec<_> = item_collection
+            .items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["item-b", "item-a", "item-c"]);
+    }
+
     #[test]
     fn memory_item_search_conformance() {
         let api = test_api(MemoryBackend::new());
@@ -755,5 +1430,75 @@ mod tests {
         assert!(conformance
             .conforms_to
             .contains(&ITEM_SEARCH_URI.to_string()));
+        assert!(conformance.conforms_to.contains(&FIELDS_URI.to_string()));
+        assert!(conformance.conforms_to.contains(&SORT_URI.to_string()));
+    }
+
+    #[test]
+    fn memory_capabilities_conformance() {
+        let backend = MemoryBackend::new();
+        assert_eq!(
+            backend.capabilities(),
+            Capabilities {
+                item_search: true,
+                filter: false,
+                fields: true,
+                sort: true,
+                transaction: false,
+                collection_search: false,
+                children: true,
+            }
+        );
+        let conformance = test_api(backend).conformance();
+        for uri in FILTER_URIS
+            .iter()
+            .chain(TRANSACTION_URIS.iter())
+            .chain(COLLECTION_SEARCH_URIS.iter())
+        {
+            assert!(!conformance.conforms_to.contains(&uri.to_string()));
+        }
+    }
+
+    #[tokio::test]
+    async fn bulk_items() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        let api = test_api(backend);
+        assert!(api
+            .bulk_items("does-not-exist", Vec::new())
+            .await
+            .unwrap()
+            .is_none());
+        let values = vec![
+            serde_json::to_value(Item::new("a")).unwrap(),
+            serde_json::to_value(Item::new("b").collection("another-collection")).unwrap(),
+            json!({"type": "Collection", "id": "c"}),
+            serde_json::to_value(Item::new("d").collection("collection-id")).unwrap(),
+        ];
+        let bulk_items = api
+            .bulk_items("collection-id", values)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bulk_items.number_succeeded, 2);
+        assert_eq!(bulk_items.number_failed, 2);
+        let results: Vec<_> = bulk_items
+            .items
+            .iter()
+            .map(|result| (result.index, result.id.as_deref(), result.success))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                (0, Some("a"), true),
+                (1, Some("b"), false),
+                (2, Some("c"), false),
+                (3, Some("d"), true)
+            ]
+        );
+        assert!(api.item("collection-id", "a").await.unwrap().is_some());
     }
 }
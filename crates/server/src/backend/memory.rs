@@ -1,7 +1,7 @@
 use crate::{Backend, Error, Result, DEFAULT_LIMIT};
-use serde_json::Map;
-use stac::{Collection, Item};
-use stac_api::{ItemCollection, Items, Search};
+use serde_json::{Map, Value};
+use stac::{schema::Schema, Collection, Item};
+use stac_api::{ItemCollection, Items, Search, Sortby};
 use std::{
     collections::{BTreeMap, HashMap},
     sync::{Arc, RwLock},
@@ -42,6 +42,14 @@ impl Backend for MemoryBackend {
         false
     }
 
+    fn has_fields(&self) -> bool {
+        true
+    }
+
+    fn has_sort(&self) -> bool {
+        true
+    }
+
     async fn collections(&self) -> Result<Vec<Collection>> {
         let collections = self.collections.read().unwrap();
         Ok(collections.values().cloned().collect())
@@ -78,6 +86,12 @@ impl Backend for MemoryBackend {
         }
     }
 
+    async fn queryables(&self) -> Result<Option<Value>> {
+        let items = self.items.read().unwrap();
+        let schema = Schema::infer(items.values().flatten());
+        Ok(Some(schema.to_queryables()))
+    }
+
     async fn items(&self, collection_id: &str, items: Items) -> Result<Option<ItemCollection>> {
         {
             let collections = self.collections.read().unwrap();
@@ -96,7 +110,8 @@ impl Backend for MemoryBackend {
             .and_then(|items| items.iter().find(|item| item.id == item_id).cloned()))
     }
 
-    async fn search(&self, mut search: Search) -> Result<ItemCollection> {
+    async fn search(&self, search: Search) -> Result<ItemCollection> {
+        let mut search = search.valid()?.into_crs84_filter()?;
         let items = self.items.read().unwrap();
         if search.collections.is_empty() {
             search.collections = items.keys().cloned().collect();
@@ -104,27 +119,46 @@ impl Backend for MemoryBackend {
         let mut item_references = Vec::new();
         for collection in &search.collections {
             if let Some(items) = items.get(collection) {
-                item_references.extend(
-                    items
-                        .iter()
-                        .filter(|item| search.matches(item).unwrap_or_default()),
-                );
+                for item in items {
+                    if search.matches(item)? {
+                        item_references.push(item);
+                    }
+                }
             }
         }
         let limit = search.limit.unwrap_or(DEFAULT_LIMIT).try_into()?;
         let skip = search
             .additional_fields
             .get("skip")
-            .and_then(|skip| skip.as_str())
-            .and_then(|skip| skip.parse::<u64>().ok())
+            .and_then(|skip| match skip {
+                Value::Number(number) => number.as_u64(),
+                Value::String(s) => s.parse::<u64>().ok(),
+                _ => None,
+            })
             .unwrap_or_default()
             .try_into()?;
+        if !search.sortby.is_empty() {
+            // Sorting only needs the id, collection, and properties, so the
+            // rest of each item isn't converted.
+            let mut keyed = item_references
+                .into_iter()
+                .map(|item| Ok((sort_key(item)?, item)))
+                .collect::<Result<Vec<_>>>()?;
+            Sortby::sort_items_by(&search.sortby, &mut keyed, |(key, _)| key);
+            item_references = keyed.into_iter().map(|(_, item)| item).collect();
+        }
         let len = item_references.len();
         let items = item_references
             .into_iter()
             .skip(skip)
             .take(limit)
-            .map(|item| stac_api::Item::try_from(item.clone()).map_err(Error::from))
+            .map(|item| {
+                let item = stac_api::Item::try_from(item.clone())?;
+                Ok(match &search.fields {
+                    Some(fields) => fields.apply(item),
+                    None => item,
+                })
+            })
             .collect::<Result<Vec<_>>>()?;
         let mut item_collection = ItemCollection::new(items)?;
         if len > item_collection.items.len() + skip {
@@ -142,8 +176,256 @@ impl Backend for MemoryBackend {
     }
 }
 
+/// Returns the parts of an item that can be sorted on.
+fn sort_key(item: &Item) -> Result<stac_api::Item> {
+    let mut key = Map::new();
+    let _ = key.insert("id".to_string(), item.id.clone().into());
+    if let Some(collection) = &item.collection {
+        let _ = key.insert("collection".to_string(), collection.clone().into());
+    }
+    let _ = key.insert(
+        "properties".to_string(),
+        serde_json::to_value(&item.properties)?,
+    );
+    Ok(key)
+}
+
 impl Default for MemoryBackend {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryBackend;
+    use crate::Backend;
+    use geojson::{Geometry, Value};
+    use stac::{Collection, Item};
+    use stac_api::{GetCollections, Search};
+
+    async fn backend() -> MemoryBackend {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        let mut item = Item::new("point").collection("collection-id");
+        item.set_geometry(Geometry::new(Value::Point(vec![-105.1, 41.1])))
+            .unwrap();
+        backend.add_item(item).await.unwrap();
+        let mut item = Item::new("line").collection("collection-id");
+        item.set_geometry(Geometry::new(Value::LineString(vec![
+            vec![10.0, 10.0],
+            vec![20.0, 20.0],
+        ])))
+        .unwrap();
+        backend.add_item(item).await.unwrap();
+        backend
+            .add_item(Item::new("no-geometry").collection("collection-id"))
+            .await
+            .unwrap();
+        backend
+    }
+
+    fn polygon(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Vec<Vec<Vec<f64>>> {
+        vec![vec![
+            vec![xmin, ymin],
+            vec![xmax, ymin],
+            vec![xmax, ymax],
+            vec![xmin, ymax],
+            vec![xmin, ymin],
+        ]]
+    }
+
+    async fn ids(backend: &MemoryBackend, intersects: Value) -> Vec<String> {
+        let search = Search::new().intersects(Geometry::new(intersects));
+        backend
+            .search(search)
+            .await
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn intersects_polygon() {
+        let backend = backend().await;
+        assert_eq!(
+            ids(
+                &backend,
+                Value::Polygon(polygon(-106.0, 41.0, -105.0, 42.0))
+            )
+            .await,
+            ["point"]
+        );
+        assert_eq!(
+            ids(&backend, Value::Polygon(polygon(15.0, 0.0, 16.0, 30.0))).await,
+            ["line"]
+        );
+        assert!(ids(&backend, Value::Polygon(polygon(0.0, 0.0, 1.0, 1.0)))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn intersects_multi_polygon() {
+        let backend = backend().await;
+        assert_eq!(
+            ids(
+                &backend,
+                Value::MultiPolygon(vec![
+                    polygon(-106.0, 41.0, -105.0, 42.0),
+                    polygon(15.0, 0.0, 16.0, 30.0)
+                ])
+            )
+            .await,
+            ["point", "line"]
+        );
+    }
+
+    #[tokio::test]
+    async fn intersects_line_string() {
+        let backend = backend().await;
+        assert_eq!(
+            ids(
+                &backend,
+                Value::LineString(vec![vec![10.0, 20.0], vec![20.0, 10.0]])
+            )
+            .await,
+            ["line"]
+        );
+    }
+
+    #[tokio::test]
+    async fn intersects_point() {
+        let backend = backend().await;
+        assert_eq!(
+            ids(&backend, Value::Point(vec![-105.1, 41.1])).await,
+            ["point"]
+        );
+    }
+
+    #[tokio::test]
+    async fn intersects_geometry_collection() {
+        let backend = backend().await;
+        assert_eq!(
+            ids(
+                &backend,
+                Value::GeometryCollection(vec![
+                    Geometry::new(Value::Point(vec![-105.1, 41.1])),
+                    Geometry::new(Value::Point(vec![15.0, 15.0])),
+                ])
+            )
+            .await,
+            ["point", "line"]
+        );
+    }
+
+    #[tokio::test]
+    async fn collections_page_q_and_keywords() {
+        let mut backend = backend().await;
+        let mut collection = Collection::new("sentinel-2", "Optical imagery");
+        collection.keywords = Some(vec!["ESA".to_string()]);
+        backend.add_collection(collection).await.unwrap();
+        let ids = |collections: stac_api::Collections| {
+            collections
+                .collections
+                .into_iter()
+                .map(|collection| collection.id)
+                .collect::<Vec<_>>()
+        };
+        let get_collections = GetCollections {
+            q: Some("imagery".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(backend.collections_page(get_collections).await.unwrap()),
+            ["sentinel-2"]
+        );
+        let get_collections = GetCollections {
+            keywords: Some("esa".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(backend.collections_page(get_collections).await.unwrap()),
+            ["sentinel-2"]
+        );
+        let get_collections = GetCollections {
+            q: Some("description".to_string()),
+            keywords: Some("esa".to_string()),
+            ..Default::default()
+        };
+        assert!(ids(backend.collections_page(get_collections).await.unwrap()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn bbox_and_intersects() {
+        let backend = backend().await;
+        let search = Search::new()
+            .bbox(stac::Bbox::new(-106.0, 41.0, -105.0, 42.0))
+            .intersects(Geometry::new(Value::Point(vec![-105.1, 41.1])));
+        let _ = backend.search(search).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn filter_crs() {
+        let backend = backend().await;
+        let mut search = Search::new();
+        search.filter_crs = Some("EPSG:4326".to_string());
+        assert_eq!(backend.search(search).await.unwrap().items.len(), 3);
+        let mut search = Search::new();
+        search.filter_crs = Some("not-a-crs".to_string());
+        let _ = backend.search(search).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn skip() {
+        let backend = backend().await;
+        for skip in [serde_json::json!(2), serde_json::json!("2")] {
+            let mut search = Search::new();
+            let _ = search.additional_fields.insert("skip".to_string(), skip);
+            let item_collection = backend.search(search).await.unwrap();
+            assert_eq!(item_collection.items.len(), 1);
+            assert!(item_collection.next.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn sort_then_page() {
+        let backend = backend().await;
+        let mut search = Search::new();
+        search.limit = Some(2);
+        search.sortby = vec![stac_api::Sortby::asc("id")];
+        let item_collection = backend.search(search.clone()).await.unwrap();
+        let ids: Vec<_> = item_collection
+            .items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["line", "no-geometry"]);
+        let _ = search
+            .additional_fields
+            .insert("skip".to_string(), serde_json::json!(2));
+        let item_collection = backend.search(search).await.unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert_eq!(item_collection.items[0]["id"], "point");
+    }
+
+    #[tokio::test]
+    async fn queryables() {
+        let mut backend = backend().await;
+        let mut item = Item::new("cloudy").collection("collection-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("eo:cloud_cover".into(), 42.5.into());
+        backend.add_item(item).await.unwrap();
+        let queryables = backend.queryables().await.unwrap().unwrap();
+        assert_eq!(queryables["properties"]["eo:cloud_cover"]["type"], "number");
+        assert_eq!(queryables["properties"]["eo:cloud_cover"]["maximum"], 100);
+        assert_eq!(queryables["properties"]["datetime"]["format"], "date-time");
+    }
+}
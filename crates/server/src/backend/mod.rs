@@ -2,16 +2,71 @@ mod memory;
 #[cfg(feature = "pgstac")]
 mod pgstac;
 
-use crate::Result;
+use crate::{DeadLetter, Error, Result};
 pub use memory::MemoryBackend;
 #[cfg(feature = "pgstac")]
 pub use pgstac::PgstacBackend;
-use stac::{Collection, Item};
-use stac_api::{ItemCollection, Items, Search};
-use std::future::Future;
+use serde_json::{Map, Value};
+use stac::{Collection, Container, Item};
+use stac_api::{Collections, GetCollections, ItemCollection, Items, Search};
+use std::{future::Future, io::Write};
+
+/// The capabilities of a [Backend].
+///
+/// These are used by the [Api](crate::Api) to decide which conformance classes
+/// and links to advertise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Can the backend do [item search](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/item-search)?
+    pub item_search: bool,
+
+    /// Can the backend [filter](https://github.com/stac-api-extensions/filter) items?
+    pub filter: bool,
+
+    /// Can the backend include and exclude item [fields](https://github.com/stac-api-extensions/fields)?
+    pub fields: bool,
+
+    /// Can the backend [sort](https://github.com/stac-api-extensions/sort) items?
+    pub sort: bool,
+
+    /// Can the backend create, update, and delete items via [transactions](https://github.com/stac-api-extensions/transaction)?
+    pub transaction: bool,
+
+    /// Can the backend [search collections](https://github.com/stac-api-extensions/collection-search)?
+    pub collection_search: bool,
+
+    /// Can the backend list the root's [children](https://github.com/stac-api-extensions/children)?
+    pub children: bool,
+}
 
 /// Storage backend for a STAC API.
 pub trait Backend: Clone + Sync + Send + 'static {
+    /// Returns this backend's capabilities.
+    ///
+    /// The default implementation is built from the `has_*` methods, with
+    /// transactions and collection search disabled and children enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// let capabilities = MemoryBackend::new().capabilities();
+    /// assert!(capabilities.item_search);
+    /// assert!(!capabilities.transaction);
+    /// ```
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            item_search: self.has_item_search(),
+            filter: self.has_filter(),
+            fields: self.has_fields(),
+            sort: self.has_sort(),
+            transaction: false,
+            collection_search: false,
+            children: true,
+        }
+    }
+
     /// Returns true if this backend has item search capabilities.
     ///
     /// # Examples
@@ -34,6 +89,52 @@ pub trait Backend: Clone + Sync + Send + 'static {
     /// ```
     fn has_filter(&self) -> bool;
 
+    /// Returns true if this backend has [fields](https://github.com/stac-api-extensions/fields) capabilities.
+    ///
+    /// The default implementation returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(MemoryBackend::new().has_fields());
+    /// ```
+    fn has_fields(&self) -> bool {
+        false
+    }
+
+    /// Returns true if this backend has [sort](https://github.com/stac-api-extensions/sort) capabilities.
+    ///
+    /// The default implementation returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(MemoryBackend::new().has_sort());
+    /// ```
+    fn has_sort(&self) -> bool {
+        false
+    }
+
+    /// Returns true if this backend refuses writes.
+    ///
+    /// Write endpoints aren't served for read-only backends. The default
+    /// implementation returns false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(!MemoryBackend::new().is_readonly());
+    /// ```
+    fn is_readonly(&self) -> bool {
+        false
+    }
+
     /// Returns all collections.
     ///
     /// # Examples
@@ -48,6 +149,92 @@ pub trait Backend: Clone + Sync + Send + 'static {
     /// ```
     fn collections(&self) -> impl Future<Output = Result<Vec<Collection>>> + Send;
 
+    /// Returns a page of collections.
+    ///
+    /// The `token` is an opaque value previously set in the `next` or `prev`
+    /// pagination information of a page. If `limit` is `None`, all collections
+    /// after the token are returned.
+    ///
+    /// The default implementation filters [Backend::collections] with
+    /// [GetCollections::matches] and pages through the result, using the token
+    /// as an offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    /// use stac_api::GetCollections;
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("a", "a description")).await.unwrap();
+    /// backend.add_collection(Collection::new("b", "a description")).await.unwrap();
+    /// let get_collections = GetCollections {
+    ///     limit: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let collections = backend.collections_page(get_collections).await.unwrap();
+    /// assert_eq!(collections.collections.len(), 1);
+    /// assert_eq!(collections.number_matched, Some(2));
+    /// assert!(collections.next.is_some());
+    /// # })
+    /// ```
+    fn collections_page(
+        &self,
+        get_collections: GetCollections,
+    ) -> impl Future<Output = Result<Collections>> + Send {
+        async move { page_collections(self.collections().await?, &get_collections) }
+    }
+
+    /// Returns the children of the root catalog.
+    ///
+    /// The default implementation returns all collections. Backends that store
+    /// nested catalogs should override this to include them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Collection;
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// let children = backend.children().await.unwrap();
+    /// assert_eq!(children.len(), 1);
+    /// # })
+    /// ```
+    fn children(&self) -> impl Future<Output = Result<Vec<Container>>> + Send {
+        async move {
+            let collections = self.collections().await?;
+            Ok(collections.into_iter().map(Container::from).collect())
+        }
+    }
+
+    /// Returns a [queryables](https://github.com/stac-api-extensions/filter#queryables)
+    /// JSON schema for this backend's items, if it can generate one.
+    ///
+    /// The [MemoryBackend] infers the queryables from its items (see
+    /// [stac::schema::Schema::to_queryables]), and the
+    /// `PgstacBackend` uses **pgstac**'s. The default
+    /// implementation returns `None`, in which case the [Api](crate::Api)
+    /// serves a schema that allows any property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    /// let backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// let queryables = backend.queryables().await.unwrap().unwrap();
+    /// assert_eq!(queryables["properties"]["id"]["type"], "string");
+    /// # })
+    /// ```
+    fn queryables(&self) -> impl Future<Output = Result<Option<Value>>> + Send {
+        async { Ok(None) }
+    }
+
     /// Returns a single collection.
     ///
     /// # Examples
@@ -110,6 +297,103 @@ pub trait Backend: Clone + Sync + Send + 'static {
         }
     }
 
+    /// Adds a batch of items, returning the ones that failed because of
+    /// their own data.
+    ///
+    /// This is used for bulk ingests, so that one bad item doesn't fail the
+    /// whole batch. Each failed item is returned with its position in the
+    /// batch and its error, which is an [item
+    /// error](crate::Error::is_item_error). Any other error, e.g. a lost
+    /// connection, fails the whole batch so it can be retried. The default
+    /// implementation calls [Backend::add_item] for each item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+    /// let failed = backend
+    ///     .try_add_items(vec![
+    ///         Item::new("a").collection("collection-id"),
+    ///         Item::new("b").collection("does-not-exist"),
+    ///     ])
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(failed.len(), 1);
+    /// assert_eq!(failed[0].0, 1);
+    /// # })
+    /// ```
+    fn try_add_items(
+        &mut self,
+        items: Vec<Item>,
+    ) -> impl Future<Output = Result<Vec<(usize, Item, Error)>>> + Send {
+        async move {
+            let mut failed = Vec::new();
+            for (i, item) in items.into_iter().enumerate() {
+                if let Err(err) = self.add_item(item.clone()).await {
+                    if err.is_item_error() {
+                        failed.push((i, item, err));
+                    } else {
+                        return Err(err);
+                    }
+                }
+            }
+            Ok(failed)
+        }
+    }
+
+    /// Adds items, writing the ones that fail to a [DeadLetter] instead of
+    /// returning an error.
+    ///
+    /// Returns the number of items that were added. Items are added with
+    /// [Backend::try_add_items], so batches are only split up when they fail,
+    /// and only items that failed because of their own data are written to
+    /// the dead letter. Other errors, e.g. a lost connection, are returned so
+    /// the batch can be retried, as are errors writing the dead letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use stac_server::{Backend, DeadLetter, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// let mut dead_letter = DeadLetter::new(Vec::new());
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+    /// let count = backend
+    ///     .add_items_with_dead_letter(
+    ///         vec![
+    ///             Item::new("a").collection("collection-id"),
+    ///             Item::new("b").collection("does-not-exist"),
+    ///         ],
+    ///         &mut dead_letter,
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(count, 1);
+    /// assert_eq!(dead_letter.count(), 1);
+    /// # })
+    /// ```
+    fn add_items_with_dead_letter<W: Write + Send>(
+        &mut self,
+        items: Vec<Item>,
+        dead_letter: &mut DeadLetter<W>,
+    ) -> impl Future<Output = Result<usize>> + Send {
+        async move {
+            let count = items.len();
+            let failed = self.try_add_items(items).await?;
+            for (_, item, err) in &failed {
+                dead_letter.write(item, err)?;
+            }
+            Ok(count - failed.len())
+        }
+    }
+
     /// Retrieves items for a given collection.
     ///
     /// # Examples
@@ -168,3 +452,99 @@ pub trait Backend: Clone + Sync + Send + 'static {
     /// ```
     fn search(&self, search: Search) -> impl Future<Output = Result<ItemCollection>> + Send;
 }
+
+/// Filters collections with [GetCollections::matches] and returns the page
+/// described by the `limit` and `token`, which is an offset.
+pub(crate) fn page_collections(
+    collections: Vec<Collection>,
+    get_collections: &GetCollections,
+) -> Result<Collections> {
+    let skip = collections_offset(get_collections.token.as_deref())?;
+    let limit = get_collections.limit.map(usize::try_from).transpose()?;
+    let collections: Vec<_> = collections
+        .into_iter()
+        .filter(|collection| get_collections.matches(collection))
+        .collect();
+    let number_matched = collections.len();
+    let collections: Vec<_> = collections
+        .into_iter()
+        .skip(skip)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    let mut collections = Collections::from(collections);
+    paginate_collections(&mut collections, skip, limit, number_matched)?;
+    Ok(collections)
+}
+
+/// Returns the offset of a collections page token, or zero if there isn't one.
+pub(crate) fn collections_offset(token: Option<&str>) -> Result<usize> {
+    token
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| Error::InvalidToken(token.to_string()))
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Sets the counts and the `next` and `prev` tokens of a page of collections
+/// that starts at `skip`.
+///
+/// Tokens that would point back at this page, e.g. with a `limit` of zero,
+/// aren't set.
+pub(crate) fn paginate_collections(
+    collections: &mut Collections,
+    skip: usize,
+    limit: Option<usize>,
+    number_matched: usize,
+) -> Result<()> {
+    let number_returned = collections.collections.len();
+    collections.number_matched = Some(number_matched.try_into()?);
+    collections.number_returned = Some(number_returned.try_into()?);
+    if number_returned > 0 && skip + number_returned < number_matched {
+        let mut next = Map::new();
+        let _ = next.insert("token".into(), (skip + number_returned).to_string().into());
+        collections.next = Some(next);
+    }
+    let prev = skip.saturating_sub(limit.unwrap_or(usize::MAX));
+    if prev < skip {
+        let mut prev_map = Map::new();
+        let _ = prev_map.insert("token".into(), prev.to_string().into());
+        collections.prev = Some(prev_map);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::paginate_collections;
+    use stac::Collection;
+    use stac_api::Collections;
+
+    #[test]
+    fn paginate_collections_limit_zero() {
+        let mut collections = Collections::from(Vec::<Collection>::new());
+        paginate_collections(&mut collections, 2, Some(0), 5).unwrap();
+        assert!(collections.next.is_none());
+        assert!(collections.prev.is_none());
+    }
+
+    #[test]
+    fn paginate_collections_middle() {
+        let mut collections = Collections::from(vec![Collection::new("b", "a description")]);
+        paginate_collections(&mut collections, 1, Some(1), 3).unwrap();
+        assert_eq!(collections.next.unwrap()["token"], "2");
+        assert_eq!(collections.prev.unwrap()["token"], "0");
+    }
+
+    #[test]
+    fn collections_offset() {
+        assert_eq!(super::collections_offset(None).unwrap(), 0);
+        assert_eq!(super::collections_offset(Some("3")).unwrap(), 3);
+        assert!(matches!(
+            super::collections_offset(Some("not-a-number")).unwrap_err(),
+            crate::Error::InvalidToken(_)
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{Backend, SearchCache, BULK_ITEMS_BATCH_SIZE, MAX_BULK_ITEM_FAILURES};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use stac::Item;
+
+/// An in-progress bulk item ingest into a single collection.
+///
+/// Values are pushed one at a time and sent to the backend in batches of
+/// [BULK_ITEMS_BATCH_SIZE], so only one batch is held in memory at once. Create
+/// one with [Api::bulk_ingest](crate::Api::bulk_ingest).
+///
+/// The api's [SearchCache], if any, is cleared whenever a batch adds items.
+///
+/// By default, the report has one result per item. For ingests that are too
+/// large for that, use [BulkIngest::summarize].
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Collection, Item};
+/// use stac_server::{Api, MemoryBackend, Backend};
+///
+/// let mut backend = MemoryBackend::new();
+/// # tokio_test::block_on(async {
+/// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+/// let api = Api::new(backend, "http://stac.test").unwrap();
+/// let mut ingest = api.bulk_ingest("collection-id").await.unwrap().unwrap();
+/// ingest.push(serde_json::to_value(Item::new("item-id")).unwrap()).await;
+/// let bulk_items = ingest.finish().await;
+/// assert_eq!(bulk_items.number_succeeded, 1);
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct BulkIngest<B: Backend> {
+    backend: B,
+    search_cache: Option<SearchCache>,
+    collection_id: String,
+    index: usize,
+    batch: Vec<(usize, Option<String>, Item)>,
+    bulk_items: BulkItems,
+    summarize: bool,
+}
+
+/// A summary of a bulk item ingest.
+///
+/// Returned by [Api::bulk_items](crate::Api::bulk_items). A
+/// [summarized](BulkIngest::summarize) ingest only lists the first
+/// [MAX_BULK_ITEM_FAILURES] failed items, not every item.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItems {
+    /// The number of items that were added.
+    pub number_succeeded: u64,
+
+    /// The number of items that were not added.
+    pub number_failed: u64,
+
+    /// One result per item, or only some failures if the ingest was summarized, in request order.
+    pub items: Vec<BulkItemResult>,
+}
+
+/// The result of adding a single item in a bulk ingest.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BulkItemResult {
+    /// The position of the item in the request.
+    pub index: usize,
+
+    /// The item id, if one could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Was the item added?
+    pub success: bool,
+
+    /// Why the item wasn't added.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<B: Backend> BulkIngest<B> {
+    pub(crate) fn new(
+        backend: B,
+        search_cache: Option<SearchCache>,
+        collection_id: impl ToString,
+    ) -> BulkIngest<B> {
+        BulkIngest {
+            backend,
+            search_cache,
+            collection_id: collection_id.to_string(),
+            index: 0,
+            batch: Vec::new(),
+            bulk_items: BulkItems::default(),
+            summarize: false,
+        }
+    }
+
+    /// Sets whether to only count items, instead of reporting each one.
+    ///
+    /// A summarized report still counts every item, but only lists the first
+    /// [MAX_BULK_ITEM_FAILURES] failures, so its size doesn't grow with the
+    /// ingest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use stac_server::{Api, MemoryBackend, Backend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("collection-id", "a description")).await.unwrap();
+    /// let api = Api::new(backend, "http://stac.test").unwrap();
+    /// let mut ingest = api.bulk_ingest("collection-id").await.unwrap().unwrap().summarize(true);
+    /// ingest.push(serde_json::to_value(Item::new("item-id")).unwrap()).await;
+    /// let bulk_items = ingest.finish().await;
+    /// assert_eq!(bulk_items.number_succeeded, 1);
+    /// assert!(bulk_items.items.is_empty());
+    /// # })
+    /// ```
+    pub fn summarize(mut self, summarize: bool) -> BulkIngest<B> {
+        self.summarize = summarize;
+        self
+    }
+
+    /// Pushes a value to be added as an item.
+    ///
+    /// Items without a `collection` are assigned to this ingest's collection,
+    /// and items that belong to another collection are rejected.
+    pub async fn push(&mut self, value: Value) {
+        let index = self.next_index();
+        let id = value.get("id").and_then(|id| id.as_str()).map(String::from);
+        match serde_json::from_value::<Item>(value) {
+            Ok(mut item) => match item.collection.as_deref() {
+                Some(other) if other != self.collection_id => {
+                    let error = format!(
+                        "item collection '{}' does not match '{}'",
+                        other, self.collection_id
+                    );
+                    self.record(index, id, Some(error))
+                }
+                _ => {
+                    item.collection = Some(self.collection_id.clone());
+                    self.batch.push((index, id, item));
+                }
+            },
+            Err(err) => self.record(index, id, Some(err.to_string())),
+        }
+        if self.batch.len() >= BULK_ITEMS_BATCH_SIZE {
+            self.flush().await;
+        }
+    }
+
+    /// Records a failure for a value that couldn't be read, e.g. invalid JSON.
+    pub fn push_error(&mut self, error: impl ToString) {
+        let index = self.next_index();
+        self.record(index, None, Some(error.to_string()));
+    }
+
+    /// Adds any remaining items and returns the report.
+    pub async fn finish(mut self) -> BulkItems {
+        self.flush().await;
+        self.bulk_items.items.sort_by_key(|result| result.index);
+        self.bulk_items
+    }
+
+    fn record(&mut self, index: usize, id: Option<String>, error: Option<String>) {
+        let listed = !self.summarize
+            || (error.is_some() && self.bulk_items.items.len() < MAX_BULK_ITEM_FAILURES);
+        if listed {
+            self.bulk_items.push(index, id, error);
+        } else if error.is_some() {
+            self.bulk_items.number_failed += 1;
+        } else {
+            self.bulk_items.number_succeeded += 1;
+        }
+    }
+
+    fn next_index(&mut self) -> usize {
+        let index = self.index;
+        self.index += 1;
+        index
+    }
+
+    async fn flush(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+        let (keys, items): (Vec<_>, Vec<_>) = self
+            .batch
+            .drain(..)
+            .map(|(index, id, item)| ((index, id), item))
+            .unzip();
+        let errors = match self.backend.try_add_items(items).await {
+            Ok(failed) => {
+                let mut errors = vec![None; keys.len()];
+                for (i, _, err) in failed {
+                    errors[i] = Some(err.to_string());
+                }
+                errors
+            }
+            Err(err) => vec![Some(err.to_string()); keys.len()],
+        };
+        let number_succeeded = self.bulk_items.number_succeeded;
+        for ((index, id), error) in keys.into_iter().zip(errors) {
+            self.record(index, id, error);
+        }
+        if self.bulk_items.number_succeeded > number_succeeded {
+            if let Some(search_cache) = &self.search_cache {
+                search_cache.clear();
+            }
+        }
+    }
+}
+
+impl BulkItems {
+    pub(crate) fn push(&mut self, index: usize, id: Option<String>, error: Option<String>) {
+        if error.is_some() {
+            self.number_failed += 1;
+        } else {
+            self.number_succeeded += 1;
+        }
+        self.items.push(BulkItemResult {
+            index,
+            id,
+            success: error.is_none(),
+            error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BulkItems;
+    use serde_json::json;
+
+    #[test]
+    fn serialize() {
+        let mut bulk_items = BulkItems::default();
+        bulk_items.push(0, Some("a".to_string()), None);
+        bulk_items.push(1, None, Some("missing id".to_string()));
+        assert_eq!(
+            serde_json::to_value(bulk_items).unwrap(),
+            json!({
+                "numberSucceeded": 1,
+                "numberFailed": 1,
+                "items": [
+                    {"index": 0, "id": "a", "success": true},
+                    {"index": 1, "success": false, "error": "missing id"},
+                ]
+            })
+        );
+    }
+}
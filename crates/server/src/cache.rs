@@ -0,0 +1,296 @@
+use crate::Result;
+use serde_json::Value;
+use stac_api::{ItemCollection, Search};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The default size of a [SearchCache], in bytes.
+pub const DEFAULT_SEARCH_CACHE_CAPACITY: usize = 64 * 1024 * 1024;
+
+/// An in-memory, least-recently-used cache of search results.
+///
+/// Repeated searches, e.g. from dashboards that request the same tiles over
+/// and over, are answered from the cache instead of the backend. Searches are
+/// normalized before they're used as keys, so the same search sent with
+/// `GET` and `POST`, or with its collections in a different order, shares an
+/// entry.
+///
+/// Entries are dropped when they're older than the cache's time-to-live, or
+/// when the cache is full and they're the least recently used. The cache's
+/// size is the total size of its search results as JSON, so a few huge
+/// pages can't use much more memory than many small ones. The cache is
+/// cleared by bulk ingests through the [Api](crate::Api), but items added to
+/// the backend directly aren't visible through the cache until their
+/// searches expire, so keep the time-to-live short if the data changes
+/// often.
+///
+/// Clones of a cache share their entries.
+///
+/// # Examples
+///
+/// ```
+/// use stac_server::{Api, MemoryBackend, SearchCache};
+/// use std::time::Duration;
+///
+/// let cache = SearchCache::new(Duration::from_secs(30)).capacity(16 * 1024 * 1024);
+/// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+///     .unwrap()
+///     .search_cache(cache);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// The total size of the cached search results.
+    size: usize,
+
+    /// Incremented on every use, so entries can be ordered by their last use.
+    tick: u64,
+
+    map: HashMap<String, Entry>,
+
+    /// Keys by the tick of their last use, least recent first.
+    order: BTreeMap<u64, String>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    created: Instant,
+    tick: u64,
+    size: usize,
+    item_collection: ItemCollection,
+}
+
+impl SearchCache {
+    /// Creates a new search cache whose entries live for `ttl`.
+    ///
+    /// The cache holds [DEFAULT_SEARCH_CACHE_CAPACITY] bytes of search results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::SearchCache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = SearchCache::new(Duration::from_secs(30));
+    /// ```
+    pub fn new(ttl: Duration) -> SearchCache {
+        SearchCache {
+            ttl,
+            capacity: DEFAULT_SEARCH_CACHE_CAPACITY,
+            entries: Arc::new(Mutex::new(Entries::default())),
+        }
+    }
+
+    /// Sets the maximum size of this cache's search results, in bytes of JSON.
+    ///
+    /// Search results that are bigger than the whole cache aren't cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::SearchCache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = SearchCache::new(Duration::from_secs(30)).capacity(1024 * 1024);
+    /// ```
+    pub fn capacity(mut self, capacity: usize) -> SearchCache {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Removes all entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::SearchCache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = SearchCache::new(Duration::from_secs(30));
+    /// cache.clear();
+    /// ```
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.order.clear();
+        entries.size = 0;
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<ItemCollection> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.map.get(key)?;
+        if entry.created.elapsed() < self.ttl {
+            let item_collection = entry.item_collection.clone();
+            entries.touch(key);
+            Some(item_collection)
+        } else {
+            entries.remove(key);
+            None
+        }
+    }
+
+    pub(crate) fn put(&self, key: String, item_collection: ItemCollection) {
+        let Ok(size) = serde_json::to_vec(&item_collection).map(|bytes| key.len() + bytes.len())
+        else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(&key);
+        if size > self.capacity {
+            return;
+        }
+        while entries.size + size > self.capacity {
+            let Some((_, oldest)) = entries.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = entries.map.remove(&oldest) {
+                entries.size -= entry.size;
+            }
+        }
+        let tick = entries.next_tick();
+        let _ = entries.order.insert(tick, key.clone());
+        entries.size += size;
+        let _ = entries.map.insert(
+            key,
+            Entry {
+                created: Instant::now(),
+                tick,
+                size,
+                item_collection,
+            },
+        );
+    }
+}
+
+impl Entries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch(&mut self, key: &str) {
+        let tick = self.next_tick();
+        if let Some(entry) = self.map.get_mut(key) {
+            let _ = self.order.remove(&entry.tick);
+            entry.tick = tick;
+            let _ = self.order.insert(tick, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.map.remove(key) {
+            let _ = self.order.remove(&entry.tick);
+            self.size -= entry.size;
+        }
+    }
+}
+
+/// Returns the cache key for a search.
+///
+/// Filters are converted to cql2-json, ids and collections are sorted, and
+/// object keys are sorted, so equivalent searches have the same key.
+pub(crate) fn key(search: &Search) -> Result<String> {
+    let mut search = search.clone().into_cql2_json()?;
+    search.ids.sort();
+    search.ids.dedup();
+    search.collections.sort();
+    search.collections.dedup();
+    let value = sort_keys(serde_json::to_value(search)?);
+    serde_json::to_string(&value).map_err(Into::into)
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(array) => Value::Array(array.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchCache;
+    use serde_json::json;
+    use stac_api::{Filter, ItemCollection, Search};
+    use std::time::Duration;
+
+    #[test]
+    fn key_is_normalized() {
+        let a = Search::new()
+            .collections(vec!["b".to_string(), "a".to_string()])
+            .filter("id = 'an-id'");
+        let b = Search::new()
+            .collections(vec!["a".to_string(), "b".to_string()])
+            .filter(Filter::Cql2Json(
+                serde_json::from_value(json!({
+                    "op": "=",
+                    "args": [{"property": "id"}, "an-id"]
+                }))
+                .unwrap(),
+            ));
+        assert_eq!(super::key(&a).unwrap(), super::key(&b).unwrap());
+        assert_ne!(super::key(&a).unwrap(), super::key(&Search::new()).unwrap());
+    }
+
+    /// Returns the size of an empty item collection with a one-byte key.
+    fn entry_size() -> usize {
+        1 + serde_json::to_vec(&ItemCollection::default())
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn least_recently_used() {
+        let cache = SearchCache::new(Duration::from_secs(60)).capacity(2 * entry_size());
+        cache.put("a".to_string(), ItemCollection::default());
+        cache.put("b".to_string(), ItemCollection::default());
+        assert!(cache.get("a").is_some());
+        cache.put("c".to_string(), ItemCollection::default());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn capacity_is_bytes() {
+        let cache = SearchCache::new(Duration::from_secs(60)).capacity(3 * entry_size());
+        let big = ItemCollection::new(vec![stac_api::Item::default(); 100]).unwrap();
+        cache.put("a".to_string(), ItemCollection::default());
+        cache.put("b".to_string(), big);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        cache.put("c".to_string(), ItemCollection::default());
+        cache.put("d".to_string(), ItemCollection::default());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+        assert!(cache.get("d").is_some());
+        cache.put("e".to_string(), ItemCollection::default());
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("e").is_some());
+    }
+
+    #[test]
+    fn expired() {
+        let cache = SearchCache::new(Duration::ZERO);
+        cache.put("a".to_string(), ItemCollection::default());
+        assert!(cache.get("a").is_none());
+    }
+}
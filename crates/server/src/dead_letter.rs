@@ -0,0 +1,125 @@
+use crate::Result;
+use serde::Serialize;
+use std::io::Write;
+
+/// A newline-delimited JSON sink for items that couldn't be loaded.
+///
+/// Each line is an object with the `error` message and the `item` itself, so
+/// failed items can be fixed and loaded again. Use it with
+/// [Backend::add_items_with_dead_letter](crate::Backend::add_items_with_dead_letter)
+/// to keep loading when some items fail.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Item;
+/// use stac_server::DeadLetter;
+///
+/// let mut dead_letter = DeadLetter::new(Vec::new());
+/// dead_letter.write(&Item::new("an-id"), "invalid geometry").unwrap();
+/// assert_eq!(dead_letter.count(), 1);
+/// let bytes = dead_letter.finish().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct DeadLetter<W: Write> {
+    writer: W,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Record<'a, T> {
+    error: &'a str,
+    item: &'a T,
+}
+
+impl<W: Write> DeadLetter<W> {
+    /// Creates a new dead letter that writes to a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::DeadLetter;
+    ///
+    /// let dead_letter = DeadLetter::new(std::io::sink());
+    /// ```
+    pub fn new(writer: W) -> DeadLetter<W> {
+        DeadLetter { writer, count: 0 }
+    }
+
+    /// Writes an item and the reason it couldn't be loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_server::DeadLetter;
+    ///
+    /// let mut dead_letter = DeadLetter::new(Vec::new());
+    /// dead_letter.write(&Item::new("an-id"), "invalid geometry").unwrap();
+    /// ```
+    pub fn write<T: Serialize>(&mut self, item: &T, error: impl ToString) -> Result<()> {
+        let error = error.to_string();
+        serde_json::to_writer(
+            &mut self.writer,
+            &Record {
+                error: &error,
+                item,
+            },
+        )?;
+        self.writer.write_all(b"\n")?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Returns the number of items that have been written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::DeadLetter;
+    ///
+    /// let dead_letter = DeadLetter::new(Vec::new());
+    /// assert_eq!(dead_letter.count(), 0);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Flushes and returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::DeadLetter;
+    ///
+    /// let bytes = DeadLetter::new(Vec::new()).finish().unwrap();
+    /// assert!(bytes.is_empty());
+    /// ```
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeadLetter;
+    use stac::Item;
+
+    #[test]
+    fn write() {
+        let mut dead_letter = DeadLetter::new(Vec::new());
+        dead_letter.write(&Item::new("a"), "first").unwrap();
+        dead_letter.write(&Item::new("b"), "second").unwrap();
+        assert_eq!(dead_letter.count(), 2);
+        let bytes = dead_letter.finish().unwrap();
+        let lines: Vec<serde_json::Value> = bytes
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["error"], "first");
+        assert_eq!(lines[1]["item"]["id"], "b");
+    }
+}
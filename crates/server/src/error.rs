@@ -12,15 +12,45 @@ pub enum Error {
     #[error("backend error: {0}")]
     Backend(String),
 
+    /// An [Authorizer](crate::Authorizer) denied a request.
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+
+    /// [std::io::Error]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// A pagination token couldn't be parsed.
+    #[error("invalid pagination token: {0}")]
+    InvalidToken(String),
+
+    /// A request exceeded one of the server's [Limits](crate::Limits).
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(String),
+
     /// A memory backend error.
     #[error("memory backend error: {0}")]
     MemoryBackend(String),
 
+    /// An OpenID Connect configuration error.
+    #[cfg(feature = "oidc")]
+    #[error("oidc error: {0}")]
+    Oidc(String),
+
+    /// The backend is read-only.
+    #[error("backend is read-only")]
+    ReadOnly,
+
     /// [pgstac::Error]
     #[cfg(feature = "pgstac")]
     #[error(transparent)]
     Pgstac(#[from] pgstac::Error),
 
+    /// [reqwest::Error]
+    #[cfg(feature = "oidc")]
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
     /// [serde_json::Error]
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
@@ -46,7 +76,38 @@ pub enum Error {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// A request is missing valid credentials.
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
     /// [url::ParseError]
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
 }
+
+impl Error {
+    /// Returns true if this error was caused by a single item, e.g. because
+    /// its collection doesn't exist or it violates a database constraint.
+    ///
+    /// Other errors, like a lost database connection or a read-only backend,
+    /// aren't about any one item and would fail a retry of it the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Error;
+    ///
+    /// assert!(Error::MemoryBackend("no collection".to_string()).is_item_error());
+    /// assert!(!Error::ReadOnly.is_item_error());
+    /// ```
+    pub fn is_item_error(&self) -> bool {
+        match self {
+            Error::MemoryBackend(_) | Error::SerdeJson(_) | Error::Stac(_) | Error::StacApi(_) => {
+                true
+            }
+            #[cfg(feature = "pgstac")]
+            Error::Pgstac(err) => err.is_data_error(),
+            _ => false,
+        }
+    }
+}
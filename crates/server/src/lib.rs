@@ -31,16 +31,30 @@
 )]
 
 mod api;
+mod auth;
 mod backend;
+mod bulk;
+mod cache;
+mod dead_letter;
 mod error;
+mod limits;
 #[cfg(feature = "axum")]
 pub mod routes;
+#[cfg(feature = "test")]
+pub mod test;
 
 pub use api::Api;
+#[cfg(feature = "oidc")]
+pub use auth::Oidc;
+pub use auth::{Action, Auth, Authorizer, Decision, Identity, DEFAULT_API_KEY_HEADER};
 #[cfg(feature = "pgstac")]
 pub use backend::PgstacBackend;
-pub use backend::{Backend, MemoryBackend};
+pub use backend::{Backend, Capabilities, MemoryBackend};
+pub use bulk::{BulkIngest, BulkItemResult, BulkItems};
+pub use cache::{SearchCache, DEFAULT_SEARCH_CACHE_CAPACITY};
+pub use dead_letter::DeadLetter;
 pub use error::Error;
+pub use limits::Limits;
 
 /// A crate-specific result type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -54,8 +68,27 @@ pub const DEFAULT_DESCRIPTION: &str = "A STAC API server written in Rust";
 /// The default limit.
 pub const DEFAULT_LIMIT: u64 = 10;
 
+/// The number of items sent to the backend at a time during a bulk ingest.
+pub const BULK_ITEMS_BATCH_SIZE: usize = 1000;
+
+/// The default largest request body, in bytes, which matches axum's default.
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// The largest single item, in bytes, in a newline-delimited bulk items body.
+pub const MAX_BULK_ITEM_BYTES: usize = DEFAULT_BODY_LIMIT;
+
+/// The most failed items that a [summarized](BulkIngest::summarize) bulk
+/// ingest reports one by one.
+pub const MAX_BULK_ITEM_FAILURES: usize = 100;
+
 #[cfg(test)]
 use tokio_test as _;
 
+#[cfg(all(test, not(feature = "axum")))]
+use futures as _;
+
 #[cfg(all(test, not(feature = "axum")))]
 use tower as _;
+
+#[cfg(all(test, not(feature = "oidc")))]
+use mockito as _;
@@ -0,0 +1,317 @@
+use crate::{Error, Result, DEFAULT_BODY_LIMIT, DEFAULT_LIMIT};
+use geojson::{Geometry, Value};
+use stac::Bbox;
+use stac_api::{GetCollections, Items, Search};
+
+/// Limits on the requests that an [Api](crate::Api) will send to its backend.
+///
+/// These protect backends from pathological queries. A request that exceeds
+/// `max_limit`, `max_bbox_area`, or `max_intersects_vertices` is rejected
+/// with an [Error::LimitExceeded] that describes the violated limit, which
+/// the [routes](crate::routes) return as a `400 Bad Request`. Page sizes
+/// above `max_page_size` are reduced to it, as recommended by the STAC API
+/// specification.
+///
+/// Buffered request bodies are limited to `max_body_bytes`, which defaults to
+/// [DEFAULT_BODY_LIMIT]. Streamed NDJSON bulk items bodies are instead
+/// limited to `max_stream_bytes`, and each of their lines to
+/// [MAX_BULK_ITEM_BYTES](crate::MAX_BULK_ITEM_BYTES). Otherwise, by default,
+/// there are no limits.
+///
+/// # Examples
+///
+/// ```
+/// use stac_server::{Api, Limits, MemoryBackend};
+///
+/// let limits = Limits::default()
+///     .max_limit(10_000)
+///     .max_page_size(1000)
+///     .max_bbox_area(100.0)
+///     .max_intersects_vertices(10_000)
+///     .max_body_bytes(100 * 1024 * 1024)
+///     .max_stream_bytes(100 * 1024 * 1024 * 1024);
+/// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+///     .unwrap()
+///     .limits(limits);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// The largest `limit` a request may ask for.
+    pub max_limit: Option<u64>,
+
+    /// The largest page that will be requested from the backend.
+    pub max_page_size: Option<u64>,
+
+    /// The largest bbox area, in square degrees, that a request may use.
+    pub max_bbox_area: Option<f64>,
+
+    /// The largest number of vertices in an `intersects` geometry.
+    pub max_intersects_vertices: Option<usize>,
+
+    /// The largest buffered request body, in bytes.
+    pub max_body_bytes: Option<usize>,
+
+    /// The largest streamed NDJSON bulk items body, in bytes.
+    pub max_stream_bytes: Option<usize>,
+}
+
+impl Limits {
+    /// Sets the largest `limit` a request may ask for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_limit(10_000);
+    /// ```
+    pub fn max_limit(mut self, max_limit: u64) -> Limits {
+        self.max_limit = Some(max_limit);
+        self
+    }
+
+    /// Sets the largest page that will be requested from the backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_page_size(1000);
+    /// ```
+    pub fn max_page_size(mut self, max_page_size: u64) -> Limits {
+        self.max_page_size = Some(max_page_size);
+        self
+    }
+
+    /// Sets the largest bbox area, in square degrees, that a request may use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_bbox_area(100.0);
+    /// ```
+    pub fn max_bbox_area(mut self, max_bbox_area: f64) -> Limits {
+        self.max_bbox_area = Some(max_bbox_area);
+        self
+    }
+
+    /// Sets the largest number of vertices in an `intersects` geometry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_intersects_vertices(10_000);
+    /// ```
+    pub fn max_intersects_vertices(mut self, max_intersects_vertices: usize) -> Limits {
+        self.max_intersects_vertices = Some(max_intersects_vertices);
+        self
+    }
+
+    /// Sets the largest buffered request body, in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_body_bytes(100 * 1024 * 1024);
+    /// ```
+    pub fn max_body_bytes(mut self, max_body_bytes: usize) -> Limits {
+        self.max_body_bytes = Some(max_body_bytes);
+        self
+    }
+
+    /// Sets the largest streamed NDJSON bulk items body, in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_stream_bytes(100 * 1024 * 1024 * 1024);
+    /// ```
+    pub fn max_stream_bytes(mut self, max_stream_bytes: usize) -> Limits {
+        self.max_stream_bytes = Some(max_stream_bytes);
+        self
+    }
+
+    /// Returns the largest buffered request body, in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Limits, DEFAULT_BODY_LIMIT};
+    ///
+    /// assert_eq!(Limits::default().body_limit(), DEFAULT_BODY_LIMIT);
+    /// assert_eq!(Limits::default().max_body_bytes(42).body_limit(), 42);
+    /// ```
+    pub fn body_limit(&self) -> usize {
+        self.max_body_bytes.unwrap_or(DEFAULT_BODY_LIMIT)
+    }
+
+    /// Checks a search against these limits, reducing its page size if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Search;
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_limit(100).max_page_size(50);
+    /// let mut search = Search::new().limit(75);
+    /// limits.apply_to_search(&mut search).unwrap();
+    /// assert_eq!(search.limit, Some(50));
+    /// let mut search = Search::new().limit(101);
+    /// limits.apply_to_search(&mut search).unwrap_err();
+    /// ```
+    pub fn apply_to_search(&self, search: &mut Search) -> Result<()> {
+        self.apply_to_items(&mut search.items)?;
+        if let (Some(max), Some(intersects)) = (self.max_intersects_vertices, &search.intersects) {
+            let vertices = count_vertices(intersects);
+            if vertices > max {
+                return Err(Error::LimitExceeded(format!(
+                    "intersects has {vertices} vertices, but the maximum is {max}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks an items request against these limits, reducing its page size if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::Items;
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_bbox_area(1.0);
+    /// let mut items = Items {
+    ///     bbox: Some(stac::Bbox::new(-105.0, 40.0, -104.0, 41.0)),
+    ///     ..Default::default()
+    /// };
+    /// limits.apply_to_items(&mut items).unwrap();
+    /// items.bbox = Some(stac::Bbox::new(-106.0, 40.0, -104.0, 41.0));
+    /// limits.apply_to_items(&mut items).unwrap_err();
+    /// ```
+    pub fn apply_to_items(&self, items: &mut Items) -> Result<()> {
+        items.limit = self.limit(items.limit)?;
+        if let (Some(max), Some(bbox)) = (self.max_bbox_area, items.bbox) {
+            let area = area(bbox);
+            if area > max {
+                return Err(Error::LimitExceeded(format!(
+                    "bbox area is {area} square degrees, but the maximum is {max}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a collections request against these limits, reducing its page size if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_api::GetCollections;
+    /// use stac_server::Limits;
+    ///
+    /// let limits = Limits::default().max_limit(100);
+    /// let mut get_collections = GetCollections {
+    ///     limit: Some(1000),
+    ///     ..Default::default()
+    /// };
+    /// limits.apply_to_collections(&mut get_collections).unwrap_err();
+    /// ```
+    pub fn apply_to_collections(&self, get_collections: &mut GetCollections) -> Result<()> {
+        get_collections.limit = self.limit(get_collections.limit)?;
+        Ok(())
+    }
+
+    fn limit(&self, limit: Option<u64>) -> Result<Option<u64>> {
+        if let (Some(max), Some(limit)) = (self.max_limit, limit) {
+            if limit > max {
+                return Err(Error::LimitExceeded(format!(
+                    "limit is {limit}, but the maximum is {max}"
+                )));
+            }
+        }
+        match self.max_page_size {
+            Some(max) if limit.unwrap_or(DEFAULT_LIMIT) > max => Ok(Some(max)),
+            _ => Ok(limit),
+        }
+    }
+}
+
+/// Returns a bbox's area in square degrees, handling bboxes that cross the antimeridian.
+fn area(bbox: Bbox) -> f64 {
+    let mut width = bbox.xmax() - bbox.xmin();
+    if width < 0. {
+        width += 360.;
+    }
+    width * (bbox.ymax() - bbox.ymin()).abs()
+}
+
+fn count_vertices(geometry: &Geometry) -> usize {
+    match &geometry.value {
+        Value::Point(_) => 1,
+        Value::MultiPoint(points) | Value::LineString(points) => points.len(),
+        Value::MultiLineString(lines) | Value::Polygon(lines) => lines.iter().map(Vec::len).sum(),
+        Value::MultiPolygon(polygons) => polygons.iter().flatten().map(Vec::len).sum(),
+        Value::GeometryCollection(geometries) => geometries.iter().map(count_vertices).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Limits;
+    use geojson::{Geometry, Value};
+    use stac::Bbox;
+    use stac_api::Search;
+
+    #[test]
+    fn no_limits() {
+        let mut search = Search::new().limit(1_000_000);
+        Limits::default().apply_to_search(&mut search).unwrap();
+        assert_eq!(search.limit, Some(1_000_000));
+    }
+
+    #[test]
+    fn max_page_size_without_limit() {
+        let mut search = Search::new();
+        Limits::default()
+            .max_page_size(5)
+            .apply_to_search(&mut search)
+            .unwrap();
+        assert_eq!(search.limit, Some(5));
+    }
+
+    #[test]
+    fn antimeridian_bbox_area() {
+        assert_eq!(super::area(Bbox::new(179.0, 0.0, -179.0, 1.0)), 2.0);
+    }
+
+    #[test]
+    fn max_intersects_vertices() {
+        let limits = Limits::default().max_intersects_vertices(4);
+        let polygon = Geometry::new(Value::Polygon(vec![vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![0.0, 0.0],
+        ]]));
+        let mut search = Search::new().intersects(polygon.clone());
+        limits.apply_to_search(&mut search).unwrap();
+        let collection = Geometry::new(Value::GeometryCollection(vec![
+            polygon,
+            Geometry::new(Value::Point(vec![0.0, 0.0])),
+        ]));
+        let mut search = Search::new().intersects(collection);
+        let error = limits.apply_to_search(&mut search).unwrap_err();
+        assert!(error.to_string().contains("intersects"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Helpers for testing against an in-process STAC API server.
+
+use crate::{Api, Backend, MemoryBackend, Result};
+use stac::{Collection, Item, ItemCollection};
+use tokio::net::TcpListener;
+
+const FIXTURES: [(&str, &str); 2] = [
+    (
+        include_str!("../data/sentinel-2/collection.json"),
+        include_str!("../data/sentinel-2/item-collection.json"),
+    ),
+    (
+        include_str!("../data/joplin/collection.json"),
+        include_str!("../data/joplin/feature.geojson"),
+    ),
+];
+
+/// Starts a STAC API server with a memory backend loaded with fixture data,
+/// returning its root url.
+///
+/// The server listens on an ephemeral localhost port and is spawned onto the
+/// current tokio runtime, so it lives as long as that runtime. The backend
+/// holds two collections, `sentinel-2-c1-l2a` (with ten items) and
+/// `joplin` (with one item).
+///
+/// # Examples
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// let url = stac_server::test::mock_api().await.unwrap();
+/// assert!(url.starts_with("http://127.0.0.1:"));
+/// # })
+/// ```
+pub async fn mock_api() -> Result<String> {
+    serve(mock_backend().await?).await
+}
+
+/// Returns a memory backend loaded with the same fixture data as [mock_api].
+///
+/// # Examples
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// let backend = stac_server::test::mock_backend().await.unwrap();
+/// # })
+/// ```
+pub async fn mock_backend() -> Result<MemoryBackend> {
+    let mut backend = MemoryBackend::new();
+    for (collection, items) in FIXTURES {
+        let collection: Collection = serde_json::from_str(collection)?;
+        backend.add_collection(collection).await?;
+        let items = if let Ok(item) = serde_json::from_str::<Item>(items) {
+            vec![item]
+        } else {
+            serde_json::from_str::<ItemCollection>(items)?.items
+        };
+        backend.add_items(items).await?;
+    }
+    Ok(backend)
+}
+
+/// Starts a STAC API server for a backend, returning its root url.
+///
+/// # Examples
+///
+/// ```
+/// use stac_server::MemoryBackend;
+///
+/// # tokio_test::block_on(async {
+/// let url = stac_server::test::serve(MemoryBackend::new()).await.unwrap();
+/// # })
+/// ```
+pub async fn serve<B: Backend>(backend: B) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let api = Api::new(backend, &url)?;
+    let router = crate::routes::from_api(api);
+    drop(tokio::spawn(
+        async move { axum::serve(listener, router).await },
+    ));
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use stac_api::{Client, Search};
+
+    #[tokio::test]
+    async fn mock_api() {
+        let url = super::mock_api().await.unwrap();
+        let client = Client::new(&url).unwrap();
+        assert!(client.collection("joplin").await.unwrap().is_some());
+        let items: Vec<_> = client
+            .search(Search::default())
+            .await
+            .unwrap()
+            .map(|result| result.unwrap())
+            .collect()
+            .await;
+        assert_eq!(items.len(), 11);
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use stac::Collection;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(collection) = serde_json::from_slice::<Collection>(data) {
+        let json = serde_json::to_vec(&collection).unwrap();
+        let roundtrip: Collection = serde_json::from_slice(&json).unwrap();
+        assert_eq!(collection.id, roundtrip.id);
+    }
+});
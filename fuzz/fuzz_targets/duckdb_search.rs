@@ -0,0 +1,24 @@
+#![no_main]
+
+//! Translates arbitrary searches to SQL and runs them against stac-geoparquet.
+
+use libfuzzer_sys::fuzz_target;
+use stac_api::Search;
+use stac_duckdb::Client;
+
+const HREF: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../crates/duckdb/data/100-sentinel-2-items.parquet"
+);
+
+thread_local! {
+    static CLIENT: Client = Client::new().unwrap();
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(search) = serde_json::from_slice::<Search>(data) {
+        CLIENT.with(|client| {
+            let _ = client.search(HREF, search);
+        });
+    }
+});
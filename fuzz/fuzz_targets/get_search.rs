@@ -0,0 +1,14 @@
+#![no_main]
+
+//! Parses query strings the same way `stac-server` does for `GET /search`.
+
+use libfuzzer_sys::fuzz_target;
+use stac_api::{GetSearch, Search};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(get_search) = serde_urlencoded::from_bytes::<GetSearch>(data) {
+        if let Ok(search) = Search::try_from(get_search).and_then(Search::valid) {
+            let _ = GetSearch::try_from(search);
+        }
+    }
+});
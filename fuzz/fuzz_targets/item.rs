@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use stac::Item;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(item) = serde_json::from_slice::<Item>(data) {
+        let json = serde_json::to_vec(&item).unwrap();
+        let roundtrip: Item = serde_json::from_slice(&json).unwrap();
+        assert_eq!(item.id, roundtrip.id);
+    }
+});